@@ -1,4 +1,3 @@
-use office::docx::elements::{BodyContent, ParagraphContent, RunContent};
 use office::docx::Docx;
 use std::path::Path;
 
@@ -12,35 +11,7 @@ fn main() {
     println!("-- Reading docx file: {} --", docx_path);
 
     match Docx::open(Path::new(docx_path)) {
-        Ok(docx) => {
-            for content in docx.document.body.content.iter() {
-                match content {
-                    BodyContent::Paragraph(para) => {
-                        let para_text = get_paragraph_text(&para, &docx);
-                        println!("{}", para_text);
-                    }
-                    BodyContent::Table(table) => {
-                        println!("-- Table --");
-                        for row in &table.rows {
-                            for cell in &row.cells {
-                                let cell_text: String = cell
-                                    .content
-                                    .iter()
-                                    .map(|c| match c {
-                                        BodyContent::Paragraph(p) => get_paragraph_text(p, &docx),
-                                        BodyContent::Table(_) => "[Nested Table]".to_string(),
-                                    })
-                                    .collect::<Vec<_>>()
-                                    .join("\n");
-                                print!("[{}]\t", cell_text);
-                            }
-                            println!();
-                        }
-                        println!("-- End Table --");
-                    }
-                }
-            }
-        }
+        Ok(docx) => println!("{}", docx.to_markdown()),
         Err(e) => eprintln!("Error reading docx file: {}", e),
     }
 
@@ -48,44 +19,6 @@ fn main() {
     println!("\n-- Dummy file removed. --");
 }
 
-fn get_paragraph_text(para: &office::docx::elements::Paragraph, docx: &Docx) -> String {
-    let mut text = String::new();
-    for item in &para.content {
-        match item {
-            ParagraphContent::Run(run) => {
-                for rc in &run.content {
-                    match rc {
-                        RunContent::Text(t) => text.push_str(t),
-                        RunContent::Break => text.push('\n'),
-                        RunContent::Tab => text.push('\t'),
-                    }
-                }
-            }
-            ParagraphContent::Hyperlink(hyperlink) => {
-                let link_text: String = hyperlink
-                    .runs
-                    .iter()
-                    .flat_map(|r| &r.content)
-                    .map(|rc| match rc {
-                        RunContent::Text(t) => t.as_str(),
-                        _ => "",
-                    })
-                    .collect();
-
-                let url = docx
-                    .relationships
-                    .as_ref()
-                    .and_then(|rels| rels.get_target(&hyperlink.r_id))
-                    .map(|s| s.as_str())
-                    .unwrap_or("");
-
-                text.push_str(&format!("[{}]({})", link_text, url));
-            }
-        }
-    }
-    text
-}
-
 fn create_dummy_docx(path: &str) -> Result<(), Box<dyn std::error::Error>> {
     use std::fs::File;
     use std::io::Write;
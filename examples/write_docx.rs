@@ -1,68 +1,57 @@
-use office::docx::elements::{Paragraph, Run, RunContent};
-use office::docx::properties::{ParagraphProperties, RunProperties};
-use office::docx::Docx;
-use office::docx::document::{Document, Body};
-use office::docx::elements::BodyContent;
 use office::docprops::{AppProps, CoreProps};
-use office::common::relations::Relationships;
+use office::docx::builder::{Image, ParagraphBuilder};
+use office::docx::Docx;
 use std::path::Path;
-use std::collections::HashMap;
 
 fn main() {
-    let mut docx = Docx::default();
+    // 可选：命令行参数给出的图片路径
+    let image = std::env::args().nth(1).map(Image::open);
+
+    let mut builder = Docx::builder()
+        .heading("Generated Document", 1)
+        .paragraph(
+            ParagraphBuilder::new()
+                .text("Hello, ")
+                .bold("world")
+                .text("! See ")
+                .hyperlink("the Rust website", "https://www.rust-lang.org/")
+                .text("."),
+        )
+        .heading("Lists", 2)
+        .bullet_list(["First item", "Second item"])
+        .numbered_list(["Step one", "Step two"])
+        .heading("Table", 2)
+        .table_with_header([
+            ["Name", "Value"],
+            ["Alpha", "1"],
+            ["Beta", "2"],
+        ]);
 
-    // Initialize AppProps and CoreProps
+    match image {
+        Some(Ok(image)) => builder = builder.page_break().image(image),
+        Some(Err(e)) => eprintln!("Failed to read image: {}", e),
+        None => {}
+    }
+
+    let mut docx = builder.build();
     docx.app_props = Some(AppProps {
-        application: Some("Gemini CLI".to_string()),
+        application: Some("office-rs".to_string()),
         app_version: Some("1.0".to_string()),
     });
     docx.core_props = Some(CoreProps {
         title: Some("Generated Document".to_string()),
-        creator: Some("Gemini CLI".to_string()),
-        description: Some("A document generated by Gemini CLI".to_string()),
-        last_modified_by: Some("Gemini CLI".to_string()),
+        creator: Some("office-rs".to_string()),
+        description: Some("A document generated with the builder API".to_string()),
+        last_modified_by: Some("office-rs".to_string()),
         revision: Some("1".to_string()),
         created: None,
         modified: None,
     });
 
-    // Initialize Relationships for word/_rels/document.xml.rels
-    let mut doc_rels_map = HashMap::new();
-    doc_rels_map.insert(
-        "rId1".to_string(),
-        "styles.xml".to_string(),
-    );
-    doc_rels_map.insert(
-        "rId2".to_string(),
-        "settings.xml".to_string(),
-    );
-    doc_rels_map.insert(
-        "rId3".to_string(),
-        "theme/theme1.xml".to_string(),
-    );
-    doc_rels_map.insert(
-        "rId4".to_string(),
-        "fontTable.xml".to_string(),
-    );
-    docx.relationships = Some(Relationships::new(doc_rels_map));
-    
-    // Create document with proper content
-    let para = Paragraph {
-        properties: Some(ParagraphProperties::default()),
-        content: vec![Run {
-            properties: Some(RunProperties::default()),
-            content: vec![RunContent::Text("Hello, world!".to_string())],
-            ..Default::default()
-        }.into()],
-    };
-    
-    docx.document = Document::default();
-    docx.document.body.content.push(BodyContent::Paragraph(para));
-
     let path = Path::new("test_write.docx");
     if let Err(e) = docx.save(path) {
         eprintln!("Failed to save docx file: {}", e);
     } else {
         println!("Docx file saved to: {:?}", path);
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,4 @@
 pub mod package;
+pub mod xml_element;
 pub mod xml_utils;
 pub mod relations;
@@ -29,6 +29,24 @@ impl<R: Read + Seek> OfficePackage<R> {
         Ok(content)
     }
 
+    /// Reads a binary file (e.g. an image) from the package by its path.
+    pub fn read_binary_by_path(&mut self, file_path: &str) -> Result<Vec<u8>> {
+        let mut file = self
+            .archive
+            .by_name(file_path)
+            .map_err(|_| OfficeError::FileNotFoundInArchive(file_path.to_string()))?;
+
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)?;
+
+        Ok(content)
+    }
+
+    /// Lists the paths of all files in the package.
+    pub fn file_names(&self) -> Vec<String> {
+        self.archive.file_names().map(str::to_string).collect()
+    }
+
     /// Checks if a file exists in the package.
     pub fn has_file(&mut self, file_path: &str) -> bool {
         self.archive.by_name(file_path).is_ok()
@@ -4,14 +4,76 @@ use quick_xml::{Reader, Writer};
 use std::collections::HashMap;
 use std::io::Cursor;
 
-#[derive(Debug, Default)]
+/// Well-known relationship types.
+pub mod types {
+    pub const OFFICE_DOCUMENT: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument";
+    pub const STYLES: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles";
+    pub const NUMBERING: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering";
+    pub const SETTINGS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/settings";
+    pub const THEME: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme";
+    pub const FONT_TABLE: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/fontTable";
+    pub const WEB_SETTINGS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/webSettings";
+    pub const HYPERLINK: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink";
+    pub const IMAGE: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image";
+    pub const HEADER: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/header";
+    pub const FOOTER: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/footer";
+    pub const FOOTNOTES: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/footnotes";
+    pub const ENDNOTES: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/endnotes";
+    pub const COMMENTS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments";
+    pub const CUSTOM_XML: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXml";
+    pub const CUSTOM_XML_PROPS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXmlProps";
+}
+
+/// A single relationship from a part to another part or an external resource.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Relationship {
+    /// Relationship id, e.g. `rId1`.
+    pub id: String,
+    /// Relationship type URI, see [`types`].
+    pub rel_type: String,
+    /// Target part (relative to the source part) or external URI.
+    pub target: String,
+    /// Whether the target is outside the package (`TargetMode="External"`).
+    pub external: bool,
+}
+
+/// The relationships of one part, kept in document order.
+#[derive(Debug, Clone, Default)]
 pub struct Relationships {
-    map: HashMap<String, String>,
+    items: Vec<Relationship>,
 }
 
 impl Relationships {
+    /// Creates relationships from an id → target map. Types are inferred from
+    /// the target for the common document parts.
     pub fn new(map: HashMap<String, String>) -> Self {
-        Relationships { map }
+        let mut items: Vec<Relationship> = map
+            .into_iter()
+            .map(|(id, target)| Relationship {
+                rel_type: infer_type(&target).unwrap_or_default().to_string(),
+                id,
+                target,
+                external: false,
+            })
+            .collect();
+        items.sort_by(|a, b| a.id.cmp(&b.id));
+        Relationships { items }
     }
 
     pub fn from_xml(xml_content: &str) -> Result<Self> {
@@ -26,18 +88,26 @@ impl Relationships {
                 Event::Start(e) | Event::Empty(e) if e.name().as_ref() == b"Relationship" => {
                     let mut id = None;
                     let mut target = None;
+                    let mut rel_type = String::new();
+                    let mut external = false;
                     for attr in e.attributes() {
                         let attr = attr?;
+                        let value = attr.decode_and_unescape_value(decoder)?.into_owned();
                         match attr.key.as_ref() {
-                            b"Id" => id = Some(attr.decode_and_unescape_value(decoder)?.into_owned()),
-                            b"Target" => {
-                                target = Some(attr.decode_and_unescape_value(decoder)?.into_owned())
-                            }
+                            b"Id" => id = Some(value),
+                            b"Target" => target = Some(value),
+                            b"Type" => rel_type = value,
+                            b"TargetMode" => external = value == "External",
                             _ => {}
                         }
                     }
                     if let (Some(id), Some(target)) = (id, target) {
-                        rels.map.insert(id, target);
+                        rels.items.push(Relationship {
+                            id,
+                            rel_type,
+                            target,
+                            external,
+                        });
                     }
                 }
                 Event::Eof => break,
@@ -50,7 +120,64 @@ impl Relationships {
     }
 
     pub fn get_target(&self, id: &str) -> Option<&String> {
-        self.map.get(id)
+        self.get(id).map(|r| &r.target)
+    }
+
+    /// Looks up a relationship by id.
+    pub fn get(&self, id: &str) -> Option<&Relationship> {
+        self.items.iter().find(|r| r.id == id)
+    }
+
+    /// Finds the first relationship of the given type.
+    pub fn find_by_type(&self, rel_type: &str) -> Option<&Relationship> {
+        self.items.iter().find(|r| r.rel_type == rel_type)
+    }
+
+    /// Iterates over all relationships in document order.
+    pub fn iter(&self) -> impl Iterator<Item = &Relationship> {
+        self.items.iter()
+    }
+
+    /// Iterates mutably over all relationships in document order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Relationship> {
+        self.items.iter_mut()
+    }
+
+    /// Adds a relationship and returns its new id. An existing relationship with
+    /// the same type, target and mode is reused.
+    pub fn add(&mut self, rel_type: &str, target: &str, external: bool) -> String {
+        if let Some(existing) = self
+            .items
+            .iter()
+            .find(|r| r.rel_type == rel_type && r.target == target && r.external == external)
+        {
+            return existing.id.clone();
+        }
+        let id = self.next_id();
+        self.items.push(Relationship {
+            id: id.clone(),
+            rel_type: rel_type.to_string(),
+            target: target.to_string(),
+            external,
+        });
+        id
+    }
+
+    /// Removes a relationship by id and returns it.
+    pub fn remove(&mut self, id: &str) -> Option<Relationship> {
+        let index = self.items.iter().position(|r| r.id == id)?;
+        Some(self.items.remove(index))
+    }
+
+    /// Returns the first unused `rIdN` id.
+    pub fn next_id(&self) -> String {
+        let max = self
+            .items
+            .iter()
+            .filter_map(|r| r.id.strip_prefix("rId")?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0);
+        format!("rId{}", max + 1)
     }
 
     pub fn to_xml(&self) -> Result<String> {
@@ -62,10 +189,18 @@ impl Relationships {
         ));
         writer.write_event(Event::Start(root))?;
 
-        for (id, target) in &self.map {
+        for rel in &self.items {
             let mut element = BytesStart::new("Relationship");
-            element.push_attribute(("Id", id.as_str()));
-            element.push_attribute(("Target", target.as_str()));
+            element.push_attribute(("Id", rel.id.as_str()));
+            let rel_type = match rel.rel_type.as_str() {
+                "" => infer_type(&rel.target).unwrap_or_default(),
+                rel_type => rel_type,
+            };
+            element.push_attribute(("Type", rel_type));
+            element.push_attribute(("Target", rel.target.as_str()));
+            if rel.external {
+                element.push_attribute(("TargetMode", "External"));
+            }
             writer.write_event(Event::Empty(element))?;
         }
 
@@ -76,6 +211,27 @@ impl Relationships {
 
     #[cfg(test)]
     pub fn from_map(map: HashMap<String, String>) -> Self {
-        Relationships { map }
+        Relationships::new(map)
     }
 }
+
+/// Infers the relationship type of the common document parts from the target.
+fn infer_type(target: &str) -> Option<&'static str> {
+    let name = target.rsplit('/').next().unwrap_or(target);
+    let rel_type = match name {
+        "styles.xml" => types::STYLES,
+        "numbering.xml" => types::NUMBERING,
+        "settings.xml" => types::SETTINGS,
+        "fontTable.xml" => types::FONT_TABLE,
+        "webSettings.xml" => types::WEB_SETTINGS,
+        "footnotes.xml" => types::FOOTNOTES,
+        "endnotes.xml" => types::ENDNOTES,
+        "comments.xml" => types::COMMENTS,
+        _ if name.starts_with("theme") => types::THEME,
+        _ if name.starts_with("header") => types::HEADER,
+        _ if name.starts_with("footer") => types::FOOTER,
+        _ if target.starts_with("media/") => types::IMAGE,
+        _ => return None,
+    };
+    Some(rel_type)
+}
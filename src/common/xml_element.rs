@@ -0,0 +1,221 @@
+use crate::error::{OfficeError, Result};
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::de::DeserializeOwned;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use std::collections::BTreeSet;
+use std::sync::{Mutex, OnceLock};
+
+/// A generic XML element, used to keep elements that are not modelled so that
+/// they can be written back unchanged.
+///
+/// Element and attribute names keep their namespace prefixes. Text is only kept
+/// for elements without child elements; whitespace between child elements,
+/// comments and processing instructions are dropped.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct XmlElement {
+    /// Qualified element name, e.g. `w14:ligatures`
+    pub name: String,
+    /// Attributes in document order, with unescaped values
+    pub attributes: Vec<(String, String)>,
+    /// Child elements
+    pub children: Vec<XmlElement>,
+    /// Text content of an element without child elements
+    pub text: Option<String>,
+}
+
+impl XmlElement {
+    /// Creates an element without attributes or content.
+    pub fn new(name: impl Into<String>) -> Self {
+        XmlElement {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Parses a string holding a single element.
+    pub fn parse(xml: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(false);
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => return Self::from_reader(&mut reader, &e),
+                Event::Empty(e) => return Self::from_start(&e, reader.decoder()),
+                Event::Eof => {
+                    return Err(OfficeError::InvalidFormat(
+                        "XML contains no element".to_string(),
+                    ))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads an element whose start tag was just read, up to its end tag.
+    pub fn from_reader<R: std::io::BufRead>(
+        reader: &mut Reader<R>,
+        start_tag: &BytesStart,
+    ) -> Result<Self> {
+        let decoder = reader.decoder();
+        let mut element = Self::from_start(start_tag, decoder)?;
+        let mut text = String::new();
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(e) => element.children.push(Self::from_reader(reader, &e)?),
+                Event::Empty(e) => element.children.push(Self::from_start(&e, decoder)?),
+                Event::Text(t) => text.push_str(&t.decode()?),
+                Event::CData(t) => text.push_str(&t.decode()?),
+                Event::GeneralRef(r) => {
+                    if let Some(ch) = r.resolve_char_ref()? {
+                        text.push(ch);
+                    } else if let Some(entity) = resolve_predefined_entity(&r.decode()?) {
+                        text.push_str(entity);
+                    }
+                }
+                Event::End(e) if e.name() == start_tag.name() => break,
+                Event::Eof => {
+                    return Err(OfficeError::InvalidFormat(format!(
+                        "Unexpected EOF while reading element '{}'",
+                        element.name
+                    )))
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        if element.children.is_empty() && !text.is_empty() {
+            element.text = Some(text);
+        }
+        Ok(element)
+    }
+
+    /// Builds an element without content from a start or empty tag.
+    fn from_start(tag: &BytesStart, decoder: quick_xml::encoding::Decoder) -> Result<Self> {
+        let mut element = XmlElement::new(decoder.decode(tag.name().as_ref())?);
+        for attr in tag.attributes() {
+            let attr = attr?;
+            let key = decoder.decode(attr.key.as_ref())?.into_owned();
+            let value = attr.decode_and_unescape_value(decoder)?.into_owned();
+            element.attributes.push((key, value));
+        }
+        Ok(element)
+    }
+
+    /// Returns the value of an attribute.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the first child element with the given name.
+    pub fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Namespace prefix of the element name, empty if there is none.
+    pub fn prefix(&self) -> &str {
+        self.name.split_once(':').map_or("", |(prefix, _)| prefix)
+    }
+
+    /// Serializes the element to a string.
+    pub fn to_xml(&self) -> Result<String> {
+        Ok(quick_xml::se::to_string(self)?)
+    }
+
+    /// Deserializes the element as `T`, one child at a time, so that a child the
+    /// model cannot read does not discard the whole element.
+    ///
+    /// A child is kept in the returned list instead of being deserialized if:
+    /// - its namespace prefix differs from the element's own, since the deserializer
+    ///   ignores prefixes and would confuse e.g. `w14:shadow` with `w:shadow`;
+    /// - it fails to deserialize, e.g. because of an unknown enumeration value;
+    /// - it does not change the result, i.e. `T` does not model it.
+    ///
+    /// Children with the same name are read together, so that repeated elements
+    /// interleaved with others still form one list. Errors in the element's own
+    /// attributes are returned.
+    pub fn deserialize_lenient<T: DeserializeOwned + PartialEq>(
+        &self,
+    ) -> Result<(T, Vec<XmlElement>)> {
+        let mut accepted = XmlElement {
+            children: Vec::new(),
+            text: None,
+            ..self.clone()
+        };
+        let mut value: T = quick_xml::de::from_str(&accepted.to_xml()?)?;
+        let mut unknown = Vec::new();
+
+        let mut names: Vec<&str> = Vec::new();
+        for child in &self.children {
+            if !names.contains(&child.name.as_str()) {
+                names.push(&child.name);
+            }
+        }
+        let grouped = names
+            .into_iter()
+            .flat_map(|name| self.children.iter().filter(move |c| c.name == name));
+
+        for child in grouped {
+            if child.prefix() != self.prefix() {
+                unknown.push(child.clone());
+                continue;
+            }
+            accepted.children.push(child.clone());
+            match quick_xml::de::from_str::<T>(&accepted.to_xml()?) {
+                Ok(next) if next != value => value = next,
+                _ => unknown.push(accepted.children.pop().unwrap_or_default()),
+            }
+        }
+        Ok((value, unknown))
+    }
+}
+
+impl Serialize for XmlElement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        // 序列化器只接受静态的元素名，使用枚举变体的形式写出动态名称
+        serializer.serialize_newtype_variant("XmlElement", 0, intern(&self.name), &Content(self))
+    }
+}
+
+/// The attributes and content of an element, written as a map
+struct Content<'a>(&'a XmlElement);
+
+impl Serialize for Content<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let element = self.0;
+        let mut map = serializer.serialize_map(None)?;
+        for (key, value) in &element.attributes {
+            map.serialize_entry(&format!("@{key}"), value)?;
+        }
+        if !element.children.is_empty() {
+            map.serialize_entry("$value", &element.children)?;
+        } else if let Some(text) = &element.text {
+            map.serialize_entry("$text", text)?;
+        }
+        map.end()
+    }
+}
+
+/// Returns a static copy of an element name. Each distinct name is allocated once.
+fn intern(name: &str) -> &'static str {
+    static NAMES: OnceLock<Mutex<BTreeSet<&'static str>>> = OnceLock::new();
+    let mut names = NAMES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    match names.get(name) {
+        Some(interned) => interned,
+        None => {
+            let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+            names.insert(interned);
+            interned
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename = "Properties")]
 pub struct AppProps {
     #[serde(rename = "Application", skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,182 @@
+//! Defines structs for Custom Document Properties (`docProps/custom.xml`).
+
+use crate::error::Result;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::fmt;
+use std::io::Cursor;
+
+/// The format id Office uses for user-defined properties.
+const FMTID_USER_DEFINED: &str = "{D5CDD505-2E9C-101B-9397-08002B2CF9AE}";
+
+/// The value of a custom property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomValue {
+    /// `vt:lpwstr`
+    Text(String),
+    /// `vt:i4`
+    Integer(i32),
+    /// `vt:r8`
+    Number(f64),
+    /// `vt:bool`
+    Bool(bool),
+    /// `vt:filetime`, an ISO 8601 date such as `2024-01-31T00:00:00Z`.
+    Date(String),
+}
+
+impl fmt::Display for CustomValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CustomValue::Text(text) | CustomValue::Date(text) => f.write_str(text),
+            CustomValue::Integer(value) => write!(f, "{value}"),
+            CustomValue::Number(value) => write!(f, "{value}"),
+            CustomValue::Bool(true) => f.write_str("Yes"),
+            CustomValue::Bool(false) => f.write_str("No"),
+        }
+    }
+}
+
+/// A single named custom property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomProperty {
+    pub name: String,
+    pub value: CustomValue,
+}
+
+/// The user-defined document properties.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CustomProps {
+    pub properties: Vec<CustomProperty>,
+}
+
+impl CustomProps {
+    pub fn from_xml(xml_content: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(xml_content);
+        let decoder = reader.decoder();
+        let mut props = CustomProps::default();
+        let mut name: Option<String> = None;
+        let mut value_type: Option<Vec<u8>> = None;
+        let mut text = String::new();
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) if e.local_name().as_ref() == b"property" => {
+                    for attr in e.attributes() {
+                        let attr = attr?;
+                        if attr.key.as_ref() == b"name" {
+                            name = Some(attr.decode_and_unescape_value(decoder)?.into_owned());
+                        }
+                    }
+                }
+                Event::Start(e) if name.is_some() => {
+                    value_type = Some(e.local_name().as_ref().to_vec());
+                    text.clear();
+                }
+                Event::Text(t) if value_type.is_some() => text.push_str(&t.decode()?),
+                Event::GeneralRef(r) if value_type.is_some() => {
+                    if let Some(ch) = r.resolve_char_ref()? {
+                        text.push(ch);
+                    } else if let Some(entity) = resolve_predefined_entity(&r.decode()?) {
+                        text.push_str(entity);
+                    }
+                }
+                Event::End(e) if e.local_name().as_ref() == b"property" => name = None,
+                Event::End(_) => {
+                    if let (Some(name), Some(value_type)) = (&name, value_type.take()) {
+                        let value = match value_type.as_slice() {
+                            b"i1" | b"i2" | b"i4" | b"int" | b"ui1" | b"ui2" | b"ui4" => {
+                                text.trim().parse().map(CustomValue::Integer).ok()
+                            }
+                            b"r4" | b"r8" | b"decimal" => {
+                                text.trim().parse().map(CustomValue::Number).ok()
+                            }
+                            b"bool" => Some(CustomValue::Bool(matches!(text.trim(), "true" | "1"))),
+                            b"filetime" | b"date" => Some(CustomValue::Date(text.clone())),
+                            _ => None,
+                        };
+                        props.properties.push(CustomProperty {
+                            name: name.clone(),
+                            value: value.unwrap_or_else(|| CustomValue::Text(text.clone())),
+                        });
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(props)
+    }
+
+    /// Looks up a property by name, ignoring ASCII case like Word does.
+    pub fn get(&self, name: &str) -> Option<&CustomValue> {
+        self.properties
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .map(|p| &p.value)
+    }
+
+    /// Sets a property, replacing an existing one with the same name.
+    pub fn set(&mut self, name: &str, value: CustomValue) {
+        match self
+            .properties
+            .iter_mut()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+        {
+            Some(property) => property.value = value,
+            None => self.properties.push(CustomProperty {
+                name: name.to_string(),
+                value,
+            }),
+        }
+    }
+
+    /// Removes a property by name and returns its value.
+    pub fn remove(&mut self, name: &str) -> Option<CustomValue> {
+        let index = self
+            .properties
+            .iter()
+            .position(|p| p.name.eq_ignore_ascii_case(name))?;
+        Some(self.properties.remove(index).value)
+    }
+
+    pub fn to_xml(&self) -> Result<String> {
+        let mut writer = Writer::new(Cursor::new(Vec::new()));
+        let mut root = BytesStart::new("Properties");
+        root.push_attribute((
+            "xmlns",
+            "http://schemas.openxmlformats.org/officeDocument/2006/custom-properties",
+        ));
+        root.push_attribute((
+            "xmlns:vt",
+            "http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes",
+        ));
+        writer.write_event(Event::Start(root))?;
+
+        // Property ids start at 2; 0 and 1 are reserved.
+        for (pid, property) in (2..).zip(&self.properties) {
+            let mut element = BytesStart::new("property");
+            element.push_attribute(("fmtid", FMTID_USER_DEFINED));
+            element.push_attribute(("pid", pid.to_string().as_str()));
+            element.push_attribute(("name", property.name.as_str()));
+            writer.write_event(Event::Start(element))?;
+
+            let (tag, text) = match &property.value {
+                CustomValue::Text(text) => ("vt:lpwstr", text.clone()),
+                CustomValue::Integer(value) => ("vt:i4", value.to_string()),
+                CustomValue::Number(value) => ("vt:r8", value.to_string()),
+                CustomValue::Bool(value) => ("vt:bool", value.to_string()),
+                CustomValue::Date(text) => ("vt:filetime", text.clone()),
+            };
+            writer.write_event(Event::Start(BytesStart::new(tag)))?;
+            writer.write_event(Event::Text(BytesText::new(&text)))?;
+            writer.write_event(Event::End(BytesEnd::new(tag)))?;
+            writer.write_event(Event::End(BytesEnd::new("property")))?;
+        }
+
+        writer.write_event(Event::End(BytesEnd::new("Properties")))?;
+        let result = writer.into_inner().into_inner();
+        Ok(String::from_utf8(result)?)
+    }
+}
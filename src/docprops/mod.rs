@@ -1,5 +1,7 @@
 pub mod app;
 pub mod core;
+pub mod custom;
 
 pub use app::AppProps;
 pub use core::CoreProps;
+pub use custom::{CustomProperty, CustomProps, CustomValue};
@@ -0,0 +1,841 @@
+//! 文档构建：以链式调用组合标题、段落、列表、表格、超链接和图片
+//!
+//! 构建器生成现有的元素类型，并自动登记所需的样式、编号定义、关系和媒体文件。
+
+use std::path::Path;
+
+use crate::common::relations::types;
+use crate::error::{OfficeError, Result};
+
+use super::elements::drawing::{EMU_PER_PIXEL, EMU_PER_TWIP};
+use super::elements::{
+    BodyContent, Drawing, Hyperlink, Paragraph, ParagraphContent, Run, RunContent, Table,
+    TableCell, TableRow,
+};
+use super::fields::field_runs;
+use super::links::link_run;
+use super::numbering::ListPreset;
+use super::properties::{
+    Justification, JustificationVal, NumPr, OnOff, ParagraphProperties, ParagraphStyle,
+    RunProperties, TableCellProperties, TableGrid, TableProperties, TableRowProperties, TableWidth,
+    Val,
+};
+use super::styles::BuiltinStyle;
+use super::toc::TocOptions;
+use super::Docx;
+
+/// 表格的默认总宽度（缇），约为 A4 纸减去两侧 2.54 厘米页边距
+pub(super) const DEFAULT_TABLE_WIDTH: i32 = 9026;
+
+/// 图片格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// PNG
+    Png,
+    /// JPEG
+    Jpeg,
+    /// GIF
+    Gif,
+    /// BMP
+    Bmp,
+}
+
+impl ImageFormat {
+    /// 根据文件头识别图片格式
+    ///
+    /// # 参数
+    /// * `data` - 图片数据
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match data {
+            [0x89, b'P', b'N', b'G', ..] => Some(ImageFormat::Png),
+            [0xFF, 0xD8, ..] => Some(ImageFormat::Jpeg),
+            [b'G', b'I', b'F', b'8', ..] => Some(ImageFormat::Gif),
+            [b'B', b'M', ..] => Some(ImageFormat::Bmp),
+            _ => None,
+        }
+    }
+
+    /// 文件扩展名
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpeg",
+            ImageFormat::Gif => "gif",
+            ImageFormat::Bmp => "bmp",
+        }
+    }
+
+    /// 读取图片的像素尺寸
+    fn pixel_size(self, data: &[u8]) -> Option<(u32, u32)> {
+        let be16 = |i: usize| Some(u16::from_be_bytes([*data.get(i)?, *data.get(i + 1)?]) as u32);
+        let le16 = |i: usize| Some(u16::from_le_bytes([*data.get(i)?, *data.get(i + 1)?]) as u32);
+        let be32 = |i: usize| Some(u32::from_be_bytes(data.get(i..i + 4)?.try_into().ok()?));
+        let le32 = |i: usize| Some(i32::from_le_bytes(data.get(i..i + 4)?.try_into().ok()?));
+        match self {
+            ImageFormat::Png => Some((be32(16)?, be32(20)?)),
+            ImageFormat::Gif => Some((le16(6)?, le16(8)?)),
+            ImageFormat::Bmp => Some((le32(18)?.unsigned_abs(), le32(22)?.unsigned_abs())),
+            ImageFormat::Jpeg => {
+                // 查找 SOFn 段（C4、C8、CC 除外），其中记录了高度和宽度
+                let mut i = 2;
+                while i + 9 < data.len() {
+                    if data[i] != 0xFF {
+                        return None;
+                    }
+                    let marker = data[i + 1];
+                    if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+                        return Some((be16(i + 7)?, be16(i + 5)?));
+                    }
+                    i += 2 + be16(i + 2)? as usize;
+                }
+                None
+            }
+        }
+    }
+}
+
+/// 待插入文档的图片
+#[derive(Debug, Clone)]
+pub struct Image {
+    /// 图片数据
+    pub data: Vec<u8>,
+    /// 图片格式
+    pub format: ImageFormat,
+    /// 显示宽度（EMU）
+    pub width: i64,
+    /// 显示高度（EMU）
+    pub height: i64,
+    /// 替代文字
+    pub description: Option<String>,
+}
+
+impl Image {
+    /// 从图片数据创建，显示尺寸按 96 DPI 由像素尺寸换算
+    ///
+    /// # 参数
+    /// * `data` - PNG、JPEG、GIF 或 BMP 图片数据
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        let format = ImageFormat::detect(&data)
+            .ok_or_else(|| OfficeError::Unsupported("unknown image format".to_string()))?;
+        let (width, height) = format.pixel_size(&data).ok_or_else(|| {
+            OfficeError::InvalidFormat(format!("cannot read {} image size", format.extension()))
+        })?;
+        Ok(Image {
+            data,
+            format,
+            width: width as i64 * EMU_PER_PIXEL,
+            height: height as i64 * EMU_PER_PIXEL,
+            description: None,
+        })
+    }
+
+    /// 从文件读取图片
+    ///
+    /// # 参数
+    /// * `path` - 图片文件路径
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Image::from_bytes(std::fs::read(path)?)
+    }
+
+    /// 按比例缩放到指定宽度
+    ///
+    /// # 参数
+    /// * `width` - 宽度（EMU）
+    pub fn scale_to_width(&mut self, width: i64) {
+        if self.width > 0 {
+            self.height = self.height * width / self.width;
+        }
+        self.width = width;
+    }
+
+    /// 宽度超过指定值时按比例缩小
+    ///
+    /// # 参数
+    /// * `max_width` - 最大宽度（EMU）
+    pub fn fit_width(&mut self, max_width: i64) {
+        if self.width > max_width {
+            self.scale_to_width(max_width);
+        }
+    }
+}
+
+/// 段落内尚未登记关系的内容
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+enum InlineItem {
+    Run(Run),
+    Hyperlink { url: String, runs: Vec<Run> },
+    Link(Hyperlink),
+    Image(Box<Image>),
+}
+
+/// 段落构建器
+#[derive(Debug, Clone, Default)]
+pub struct ParagraphBuilder {
+    properties: ParagraphProperties,
+    items: Vec<InlineItem>,
+    bookmark: Option<String>,
+}
+
+impl From<&str> for ParagraphBuilder {
+    fn from(text: &str) -> Self {
+        ParagraphBuilder::new().text(text)
+    }
+}
+
+impl From<String> for ParagraphBuilder {
+    fn from(text: String) -> Self {
+        ParagraphBuilder::new().text(text)
+    }
+}
+
+impl ParagraphBuilder {
+    /// 创建空段落构建器
+    pub fn new() -> Self {
+        ParagraphBuilder::default()
+    }
+
+    /// 设置段落样式
+    ///
+    /// # 参数
+    /// * `style_id` - 段落样式ID
+    pub fn style(mut self, style_id: impl Into<String>) -> Self {
+        self.properties.style = Some(ParagraphStyle {
+            val: style_id.into(),
+        });
+        self
+    }
+
+    /// 设置对齐方式
+    ///
+    /// # 参数
+    /// * `alignment` - 对齐方式
+    pub fn align(mut self, alignment: JustificationVal) -> Self {
+        self.properties.justification = Some(Justification { val: alignment });
+        self
+    }
+
+    /// 将段落加入列表
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    /// * `level` - 级别
+    pub fn list(mut self, num_id: i32, level: i32) -> Self {
+        self.properties.num_pr = Some(NumPr::new(num_id, level));
+        self
+    }
+
+    /// 设置完整的段落属性
+    ///
+    /// # 参数
+    /// * `properties` - 段落属性
+    pub fn properties(mut self, properties: ParagraphProperties) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    /// 追加普通文本
+    ///
+    /// # 参数
+    /// * `text` - 文本
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.styled(text, None)
+    }
+
+    /// 追加粗体文本
+    ///
+    /// # 参数
+    /// * `text` - 文本
+    pub fn bold(self, text: impl Into<String>) -> Self {
+        let properties = RunProperties {
+            bold: Some(OnOff::ON),
+            ..Default::default()
+        };
+        self.styled(text, Some(properties))
+    }
+
+    /// 追加斜体文本
+    ///
+    /// # 参数
+    /// * `text` - 文本
+    pub fn italic(self, text: impl Into<String>) -> Self {
+        let properties = RunProperties {
+            italic: Some(OnOff::ON),
+            ..Default::default()
+        };
+        self.styled(text, Some(properties))
+    }
+
+    /// 追加带格式的文本
+    ///
+    /// # 参数
+    /// * `text` - 文本
+    /// * `properties` - 文本运行属性
+    pub fn styled(self, text: impl Into<String>, properties: Option<RunProperties>) -> Self {
+        self.run(text_run(text.into(), properties))
+    }
+
+    /// 追加文本运行
+    ///
+    /// # 参数
+    /// * `run` - 文本运行
+    pub fn run(mut self, run: Run) -> Self {
+        self.items.push(InlineItem::Run(run));
+        self
+    }
+
+    /// 追加指向外部地址的超链接
+    ///
+    /// # 参数
+    /// * `text` - 显示文本
+    /// * `url` - 链接地址
+    pub fn hyperlink(mut self, text: impl Into<String>, url: impl Into<String>) -> Self {
+        self.items.push(InlineItem::Hyperlink {
+            url: url.into(),
+            runs: vec![link_run(text.into())],
+        });
+        self
+    }
+
+    /// 追加指向文档内书签的超链接
+    ///
+    /// # 参数
+    /// * `text` - 显示文本
+    /// * `bookmark` - 书签名称
+    pub fn internal_link(mut self, text: impl Into<String>, bookmark: impl Into<String>) -> Self {
+        let runs = vec![link_run(text.into())];
+        self.items
+            .push(InlineItem::Link(Hyperlink::internal(bookmark, runs)));
+        self
+    }
+
+    /// 用书签包围整个段落，书签ID在生成段落时分配
+    ///
+    /// # 参数
+    /// * `name` - 书签名称，应在文档中唯一
+    pub fn bookmark(mut self, name: impl Into<String>) -> Self {
+        self.bookmark = Some(name.into());
+        self
+    }
+
+    /// 追加复杂域，如 `PAGE`、`DATE \@ "yyyy-MM-dd"`
+    ///
+    /// # 参数
+    /// * `instruction` - 域代码
+    /// * `result` - 缓存的域结果文本
+    pub fn field(mut self, instruction: &str, result: &str) -> Self {
+        self.items.extend(
+            field_runs(instruction, result)
+                .into_iter()
+                .map(InlineItem::Run),
+        );
+        self
+    }
+
+    /// 追加内联图片
+    ///
+    /// # 参数
+    /// * `image` - 图片
+    pub fn image(mut self, image: Image) -> Self {
+        self.items.push(InlineItem::Image(Box::new(image)));
+        self
+    }
+
+    /// 追加换行符
+    pub fn line_break(self) -> Self {
+        self.run(Run {
+            content: vec![RunContent::Break],
+            ..Default::default()
+        })
+    }
+
+    /// 追加分页符
+    pub fn page_break(self) -> Self {
+        self.run(Run {
+            content: vec![RunContent::PageBreak],
+            ..Default::default()
+        })
+    }
+}
+
+fn text_run(text: String, properties: Option<RunProperties>) -> Run {
+    Run {
+        properties,
+        content: vec![RunContent::Text(text)],
+    }
+}
+
+impl Docx {
+    /// 创建文档构建器
+    pub fn builder() -> DocumentBuilder {
+        DocumentBuilder::new()
+    }
+
+    /// 由段落构建器生成段落，登记其中超链接和图片的关系，但不插入文档
+    ///
+    /// # 参数
+    /// * `builder` - 段落构建器或文本
+    pub fn create_paragraph(&mut self, builder: impl Into<ParagraphBuilder>) -> Paragraph {
+        let builder = builder.into();
+        let properties = builder.properties;
+        let content = builder
+            .items
+            .into_iter()
+            .map(|item| match item {
+                InlineItem::Run(run) => ParagraphContent::Run(run),
+                InlineItem::Hyperlink { url, runs } => {
+                    let r_id = self.add_hyperlink_relationship(&url);
+                    ParagraphContent::Hyperlink(Hyperlink::external(r_id, runs))
+                }
+                InlineItem::Link(hyperlink) => ParagraphContent::Hyperlink(hyperlink),
+                InlineItem::Image(image) => ParagraphContent::Run(Run {
+                    properties: None,
+                    content: vec![RunContent::Drawing(self.add_image_part(*image))],
+                }),
+            })
+            .collect();
+        let mut paragraph = Paragraph {
+            properties: (properties != ParagraphProperties::default()).then_some(properties),
+            content,
+        };
+        if let Some(name) = builder.bookmark {
+            paragraph.wrap_bookmark(self.next_bookmark_id(), name);
+        }
+        paragraph
+    }
+
+    /// 将图片保存为媒体文件并登记关系，返回引用该图片的内联绘图
+    ///
+    /// # 参数
+    /// * `image` - 图片
+    pub fn add_image_part(&mut self, image: Image) -> Drawing {
+        let extension = image.format.extension();
+        let index = (1..)
+            .find(|i| {
+                !self
+                    .media
+                    .contains_key(&format!("word/media/image{i}.{extension}"))
+            })
+            .unwrap_or(1);
+        let file_name = format!("image{index}.{extension}");
+        self.media
+            .insert(format!("word/media/{file_name}"), image.data);
+        let rels = self.relationships.get_or_insert_with(Default::default);
+        let embed = rels.add(types::IMAGE, &format!("media/{file_name}"), false);
+
+        // 绘图ID须在文档中唯一：取已有最大ID，再加上媒体文件数保证本次构建中递增
+        let max_id = self
+            .paragraphs()
+            .iter()
+            .flat_map(|p| &p.content)
+            .filter_map(|c| match c {
+                ParagraphContent::Run(run) => Some(run),
+                _ => None,
+            })
+            .flat_map(|run| &run.content)
+            .filter_map(|c| match c {
+                RunContent::Drawing(d) => Some(d.id),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+        let id = max_id + self.media.len() as u32;
+        Drawing {
+            width: image.width,
+            height: image.height,
+            id,
+            name: format!("Picture {id}"),
+            description: image.description,
+            embed,
+        }
+    }
+
+    /// 在文档末尾添加段落
+    ///
+    /// # 参数
+    /// * `builder` - 段落构建器或文本
+    pub fn add_paragraph(&mut self, builder: impl Into<ParagraphBuilder>) -> &mut Paragraph {
+        let paragraph = self.create_paragraph(builder);
+        self.push_paragraph(paragraph)
+    }
+
+    /// 在文档末尾添加标题，自动添加对应的内置标题样式
+    ///
+    /// # 参数
+    /// * `text` - 标题文本
+    /// * `level` - 标题级别（1–9）
+    pub fn add_heading(&mut self, text: impl Into<String>, level: u8) -> &mut Paragraph {
+        let style_id = self
+            .styles
+            .ensure_builtin(BuiltinStyle::Heading(level.clamp(1, 9)))
+            .style_id
+            .clone();
+        self.add_paragraph(ParagraphBuilder::new().style(style_id).text(text))
+    }
+
+    /// 在文档末尾添加列表，返回列表的编号ID
+    ///
+    /// # 参数
+    /// * `preset` - 列表预设
+    /// * `items` - 列表项及其级别
+    pub fn add_list<I, T>(&mut self, preset: ListPreset, items: I) -> i32
+    where
+        I: IntoIterator<Item = (i32, T)>,
+        T: Into<ParagraphBuilder>,
+    {
+        let num_id = self.numbering.add_preset_list(preset);
+        let style_id = self
+            .styles
+            .ensure_builtin(BuiltinStyle::ListParagraph)
+            .style_id
+            .clone();
+        for (level, item) in items {
+            let builder = item.into().style(style_id.clone()).list(num_id, level);
+            self.add_paragraph(builder);
+        }
+        num_id
+    }
+
+    /// 在文档末尾添加表格，各行单元格数不同时按最多的一行补齐；
+    /// 表格使用内置的网格样式，列宽平均分配
+    ///
+    /// # 参数
+    /// * `rows` - 行，每行为单元格内容的序列
+    /// * `header` - 第一行是否为标题行（跨页时重复）
+    pub fn add_table<R, C>(&mut self, rows: R, header: bool) -> &mut Table
+    where
+        R: IntoIterator,
+        R::Item: IntoIterator<Item = C>,
+        C: Into<ParagraphBuilder>,
+    {
+        let table = self.create_table(rows, header);
+        self.document.body.content.push(BodyContent::Table(table));
+        match self.document.body.content.last_mut() {
+            Some(BodyContent::Table(table)) => table,
+            _ => unreachable!("a table was just pushed"),
+        }
+    }
+
+    /// 生成表格并登记网格样式，但不插入文档；格式与 [`Docx::add_table`] 相同
+    ///
+    /// # 参数
+    /// * `rows` - 行，每行为单元格内容的序列
+    /// * `header` - 第一行是否为标题行（跨页时重复）
+    pub fn create_table<R, C>(&mut self, rows: R, header: bool) -> Table
+    where
+        R: IntoIterator,
+        R::Item: IntoIterator<Item = C>,
+        C: Into<ParagraphBuilder>,
+    {
+        let rows: Vec<Vec<Paragraph>> = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(|c| self.create_paragraph(c)).collect())
+            .collect();
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        let column_width = DEFAULT_TABLE_WIDTH / columns as i32;
+        let style_id = self
+            .styles
+            .ensure_builtin(BuiltinStyle::TableGrid)
+            .style_id
+            .clone();
+
+        let cell = |paragraph: Paragraph| TableCell {
+            properties: Some(TableCellProperties {
+                width: Some(TableWidth::dxa(column_width)),
+                ..Default::default()
+            }),
+            content: vec![BodyContent::Paragraph(paragraph)],
+        };
+        let table_rows = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut paragraphs)| {
+                paragraphs.resize_with(columns, Paragraph::default);
+                TableRow {
+                    properties: (header && index == 0).then(|| TableRowProperties {
+                        header: Some(OnOff::ON),
+                        ..Default::default()
+                    }),
+                    content: paragraphs.into_iter().map(cell).map(Into::into).collect(),
+                }
+                .into()
+            })
+            .collect();
+
+        Table {
+            properties: Some(TableProperties {
+                style: Some(Val::new(style_id)),
+                width: Some(TableWidth::auto()),
+                ..Default::default()
+            }),
+            grid: Some(TableGrid::from_widths(vec![column_width; columns])),
+            content: table_rows,
+        }
+    }
+
+    /// 在文档末尾添加分页符
+    pub fn add_page_break(&mut self) -> &mut Paragraph {
+        self.add_paragraph(ParagraphBuilder::new().page_break())
+    }
+
+    /// 在文档末尾添加只含一张图片的段落，图片宽度超过版心时按比例缩小
+    ///
+    /// # 参数
+    /// * `image` - 图片
+    pub fn add_image(&mut self, mut image: Image) -> &mut Paragraph {
+        image.fit_width(DEFAULT_TABLE_WIDTH as i64 * EMU_PER_TWIP);
+        self.add_paragraph(ParagraphBuilder::new().image(image))
+    }
+
+    fn push_paragraph(&mut self, paragraph: Paragraph) -> &mut Paragraph {
+        self.document
+            .body
+            .content
+            .push(BodyContent::Paragraph(paragraph));
+        match self.document.body.content.last_mut() {
+            Some(BodyContent::Paragraph(paragraph)) => paragraph,
+            _ => unreachable!("a paragraph was just pushed"),
+        }
+    }
+}
+
+/// 文档构建器，以链式调用依次向文档末尾添加内容
+#[derive(Debug, Clone)]
+pub struct DocumentBuilder {
+    docx: Docx,
+    /// 目录的插入位置和选项，在完成构建时生成，以便收录其后添加的标题
+    toc: Option<(usize, TocOptions)>,
+}
+
+impl Default for DocumentBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocumentBuilder {
+    /// 创建包含默认段落样式的空文档构建器
+    pub fn new() -> Self {
+        let mut docx = Docx::default();
+        docx.styles.ensure_builtin(BuiltinStyle::Normal);
+        docx.styles
+            .ensure_builtin(BuiltinStyle::DefaultParagraphFont);
+        DocumentBuilder { docx, toc: None }
+    }
+
+    /// 在已有文档末尾继续构建
+    ///
+    /// # 参数
+    /// * `docx` - 已有文档
+    pub fn from_docx(docx: Docx) -> Self {
+        DocumentBuilder { docx, toc: None }
+    }
+
+    /// 添加标题
+    ///
+    /// # 参数
+    /// * `text` - 标题文本
+    /// * `level` - 标题级别（1–9）
+    pub fn heading(mut self, text: impl Into<String>, level: u8) -> Self {
+        self.docx.add_heading(text, level);
+        self
+    }
+
+    /// 添加段落
+    ///
+    /// # 参数
+    /// * `paragraph` - 段落构建器或文本
+    pub fn paragraph(mut self, paragraph: impl Into<ParagraphBuilder>) -> Self {
+        self.docx.add_paragraph(paragraph);
+        self
+    }
+
+    /// 添加项目符号列表
+    ///
+    /// # 参数
+    /// * `items` - 列表项
+    pub fn bullet_list<T: Into<ParagraphBuilder>>(
+        self,
+        items: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.list(ListPreset::Bullet, items.into_iter().map(|item| (0, item)))
+    }
+
+    /// 添加编号列表
+    ///
+    /// # 参数
+    /// * `items` - 列表项
+    pub fn numbered_list<T: Into<ParagraphBuilder>>(
+        self,
+        items: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.list(
+            ListPreset::Numbered,
+            items.into_iter().map(|item| (0, item)),
+        )
+    }
+
+    /// 添加多级列表
+    ///
+    /// # 参数
+    /// * `preset` - 列表预设
+    /// * `items` - 列表项及其级别
+    pub fn list<T: Into<ParagraphBuilder>>(
+        mut self,
+        preset: ListPreset,
+        items: impl IntoIterator<Item = (i32, T)>,
+    ) -> Self {
+        self.docx.add_list(preset, items);
+        self
+    }
+
+    /// 添加表格
+    ///
+    /// # 参数
+    /// * `rows` - 行，每行为单元格内容的序列
+    pub fn table<R, C>(mut self, rows: R) -> Self
+    where
+        R: IntoIterator,
+        R::Item: IntoIterator<Item = C>,
+        C: Into<ParagraphBuilder>,
+    {
+        self.docx.add_table(rows, false);
+        self
+    }
+
+    /// 添加带标题行的表格
+    ///
+    /// # 参数
+    /// * `rows` - 行，第一行为标题行
+    pub fn table_with_header<R, C>(mut self, rows: R) -> Self
+    where
+        R: IntoIterator,
+        R::Item: IntoIterator<Item = C>,
+        C: Into<ParagraphBuilder>,
+    {
+        self.docx.add_table(rows, true);
+        self
+    }
+
+    /// 添加分页符
+    pub fn page_break(mut self) -> Self {
+        self.docx.add_page_break();
+        self
+    }
+
+    /// 添加图片
+    ///
+    /// # 参数
+    /// * `image` - 图片
+    pub fn image(mut self, image: Image) -> Self {
+        self.docx.add_image(image);
+        self
+    }
+
+    /// 在当前位置添加目录，目录项在完成构建时根据全部标题生成
+    ///
+    /// # 参数
+    /// * `options` - 目录选项
+    pub fn table_of_contents(mut self, options: TocOptions) -> Self {
+        self.toc = Some((self.docx.document.body.content.len(), options));
+        self
+    }
+
+    /// 完成构建
+    pub fn build(mut self) -> Docx {
+        if let Some((index, options)) = self.toc {
+            let (content, _) = self.docx.create_table_of_contents(&options);
+            self.docx
+                .document
+                .body
+                .content
+                .splice(index..index, content.into_iter().map(BodyContent::Paragraph));
+        }
+        self.docx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::test_utils::body_xml;
+
+    /// 只有文件头和尺寸的 PNG 数据
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13];
+        data.extend_from_slice(b"IHDR");
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn builder_composes_styled_content() {
+        let docx = Docx::builder()
+            .heading("Title", 1)
+            .paragraph(
+                ParagraphBuilder::new()
+                    .text("plain ")
+                    .bold("bold")
+                    .hyperlink(" link", "https://example.com"),
+            )
+            .bullet_list(["one", "two"])
+            .table_with_header([["h1", "h2"], ["a", "b"]])
+            .build();
+
+        let xml = body_xml(&docx);
+        assert!(xml.starts_with("<w:p><w:pPr><w:pStyle w:val=\"Heading1\"/></w:pPr>"), "{xml}");
+        assert!(xml.contains("<w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">bold</w:t>"));
+        assert!(xml.contains("<w:hyperlink r:id=\"rId"), "{xml}");
+        assert!(xml.contains("<w:tblHeader/>"), "{xml}");
+
+        for style_id in ["Normal", "Heading1", "ListParagraph", "TableGrid"] {
+            assert!(docx.styles.find_style(style_id).is_some(), "{style_id}");
+        }
+        let rels = docx.relationships.as_ref().unwrap();
+        assert!(rels.iter().any(|r| r.target == "https://example.com" && r.external));
+
+        let labels: Vec<bool> = docx.list_labels().iter().map(Option::is_some).collect();
+        assert_eq!(labels, [false, false, true, true, false, false, false, false]);
+        let BodyContent::Table(table) = &docx.document.body.content[4] else {
+            panic!("table expected");
+        };
+        assert_eq!(table.to_string_grid(), [["h1", "h2"], ["a", "b"]]);
+    }
+
+    #[test]
+    fn images_are_stored_and_scaled_to_page_width() {
+        let mut image = Image::from_bytes(png(96, 48)).unwrap();
+        assert_eq!((image.width, image.height), (914_400, 457_200));
+        image.description = Some("logo".to_string());
+
+        let docx = Docx::builder()
+            .image(image)
+            .image(Image::from_bytes(png(2000, 1000)).unwrap())
+            .build();
+        assert_eq!(docx.media.len(), 2);
+        assert!(docx.media.contains_key("word/media/image2.png"));
+
+        let drawings: Vec<&Drawing> = docx
+            .paragraphs()
+            .into_iter()
+            .flat_map(|p| &p.content)
+            .filter_map(|c| match c {
+                ParagraphContent::Run(run) => Some(run),
+                _ => None,
+            })
+            .flat_map(|run| &run.content)
+            .filter_map(|c| match c {
+                RunContent::Drawing(drawing) => Some(drawing),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(drawings.len(), 2);
+        assert_ne!(drawings[0].id, drawings[1].id);
+        assert_eq!(drawings[0].description.as_deref(), Some("logo"));
+        let max_width = DEFAULT_TABLE_WIDTH as i64 * EMU_PER_TWIP;
+        assert_eq!(drawings[1].width, max_width);
+        assert_eq!(drawings[1].height, max_width / 2);
+        assert!(Image::from_bytes(b"nope".to_vec()).is_err());
+    }
+}
@@ -0,0 +1,838 @@
+//! 文档比较：对齐两个文档的正文并生成带修订标记的新文档
+
+use super::elements::paragraph::visible_runs;
+use super::elements::{BodyContent, Paragraph, ParagraphContent, Run, RunContent, RunRevision};
+use super::properties::{
+    ParagraphProperties, ParagraphPropertiesChange, RevisionMark, RunProperties,
+    RunPropertiesChange,
+};
+use super::Docx;
+
+/// 比较粒度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompareGranularity {
+    /// 以整段为单位标记修改
+    Paragraph,
+    /// 以词为单位标记修改（中日韩文字按单字处理）
+    #[default]
+    Word,
+    /// 以字符为单位标记修改
+    Character,
+}
+
+/// 文档比较选项
+#[derive(Debug, Clone)]
+pub struct CompareOptions {
+    /// 比较粒度
+    pub granularity: CompareGranularity,
+    /// 写入修订标记的作者名称
+    pub author: String,
+    /// 写入修订标记的日期 (ISO 8601)
+    pub date: Option<String>,
+    /// 是否将格式差异标记为格式修订
+    pub compare_formatting: bool,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        CompareOptions {
+            granularity: CompareGranularity::default(),
+            author: "Comparison".to_string(),
+            date: None,
+            compare_formatting: true,
+        }
+    }
+}
+
+/// 比较两个文档，生成以修订标记表示差异的新文档
+///
+/// 结果文档以修订后的文档为基础（样式、编号、关系等均取自 `revised`），
+/// 两个输入文档中已有的修订会先被接受再参与比较。
+/// 发生修改的段落中，书签以及内容未变的超链接、简单域和内容控件原样保留；
+/// 内部有修改的超链接、简单域和内容控件展开为普通文本运行。
+///
+/// # 参数
+/// * `original` - 原始文档
+/// * `revised` - 修订后的文档
+/// * `options` - 比较选项
+pub fn compare(original: &Docx, revised: &Docx, options: &CompareOptions) -> Docx {
+    let mut old = original.document.clone();
+    old.accept_all_revisions();
+    let mut new = revised.document.clone();
+    new.accept_all_revisions();
+
+    let mut comparer = Comparer {
+        options,
+        next_id: 1,
+    };
+    // 节属性和根元素的命名空间沿用修订后的文档，只替换正文内容
+    let content = comparer.blocks(&old.body.content, &new.body.content);
+    let mut result = revised.clone();
+    result.document.body.content = content;
+    result
+}
+
+/// 序列比较的结果操作
+#[derive(Debug, Clone, Copy)]
+enum DiffOp {
+    /// 两侧相同的元素（原始下标，修订后下标）
+    Equal(usize, usize),
+    /// 仅存在于原始序列的元素
+    Delete(usize),
+    /// 仅存在于修订后序列的元素
+    Insert(usize),
+}
+
+/// 基于最长公共子序列计算两个序列的差异
+fn diff<T: PartialEq>(a: &[T], b: &[T]) -> Vec<DiffOp> {
+    // 先去掉公共前缀和后缀以缩小动态规划表
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+    let (n, m) = (a_mid.len(), b_mid.len());
+
+    // table[i * (m + 1) + j] 为 a_mid[i..] 与 b_mid[j..] 的最长公共子序列长度
+    let width = m + 1;
+    let mut table = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i * width + j] = if a_mid[i] == b_mid[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<DiffOp> = (0..prefix).map(|k| DiffOp::Equal(k, k)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && a_mid[i] == b_mid[j] {
+            ops.push(DiffOp::Equal(prefix + i, prefix + j));
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && table[(i + 1) * width + j] >= table[i * width + j + 1]) {
+            ops.push(DiffOp::Delete(prefix + i));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Insert(prefix + j));
+            j += 1;
+        }
+    }
+    ops.extend((0..suffix).map(|k| DiffOp::Equal(prefix + n + k, prefix + m + k)));
+    ops
+}
+
+/// 将差异操作分组为相同元素和修改块（被删除的下标，被插入的下标）
+enum Hunk {
+    Equal(usize, usize),
+    Changed(Vec<usize>, Vec<usize>),
+}
+
+fn hunks(ops: &[DiffOp]) -> Vec<Hunk> {
+    let mut hunks = Vec::new();
+    let (mut deleted, mut inserted) = (Vec::new(), Vec::new());
+    for op in ops {
+        match *op {
+            DiffOp::Equal(i, j) => {
+                if !deleted.is_empty() || !inserted.is_empty() {
+                    hunks.push(Hunk::Changed(
+                        std::mem::take(&mut deleted),
+                        std::mem::take(&mut inserted),
+                    ));
+                }
+                hunks.push(Hunk::Equal(i, j));
+            }
+            DiffOp::Delete(i) => deleted.push(i),
+            DiffOp::Insert(j) => inserted.push(j),
+        }
+    }
+    if !deleted.is_empty() || !inserted.is_empty() {
+        hunks.push(Hunk::Changed(deleted, inserted));
+    }
+    hunks
+}
+
+/// 段落内的比较单元
+struct Token {
+    /// 用于比较的文本
+    key: String,
+    /// 对应的运行内容
+    content: RunContent,
+    /// 所属运行的属性
+    properties: Option<RunProperties>,
+}
+
+/// 段落内片段的修订状态
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SegmentKind {
+    Unchanged,
+    Inserted,
+    Deleted,
+}
+
+/// 合并后的段落片段，最终生成一个文本运行
+struct Segment {
+    kind: SegmentKind,
+    content: Vec<RunContent>,
+    properties: Option<RunProperties>,
+    /// 格式发生变化时的原始属性
+    old_properties: Option<RunProperties>,
+}
+
+struct Comparer<'a> {
+    options: &'a CompareOptions,
+    next_id: i32,
+}
+
+impl Comparer<'_> {
+    fn next_id(&mut self) -> i32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// 比较两组正文块
+    fn blocks(&mut self, old: &[BodyContent], new: &[BodyContent]) -> Vec<BodyContent> {
+        let old_keys: Vec<String> = old.iter().map(block_key).collect();
+        let new_keys: Vec<String> = new.iter().map(block_key).collect();
+
+        let mut result = Vec::new();
+        for hunk in hunks(&diff(&old_keys, &new_keys)) {
+            match hunk {
+                Hunk::Equal(i, j) => result.push(self.pair(&old[i], &new[j])),
+                Hunk::Changed(deleted, inserted) => {
+                    // 按相似度将被删除的块与被插入的块配对，未配对的整块标记
+                    let (mut d, mut n) = (0, 0);
+                    for (pd, pn) in self.align(old, new, &deleted, &inserted) {
+                        for &i in &deleted[d..pd] {
+                            result.push(self.mark(&old[i], SegmentKind::Deleted));
+                        }
+                        for &j in &inserted[n..pn] {
+                            result.push(self.mark(&new[j], SegmentKind::Inserted));
+                        }
+                        result.push(self.pair(&old[deleted[pd]], &new[inserted[pn]]));
+                        (d, n) = (pd + 1, pn + 1);
+                    }
+                    for &i in &deleted[d..] {
+                        result.push(self.mark(&old[i], SegmentKind::Deleted));
+                    }
+                    for &j in &inserted[n..] {
+                        result.push(self.mark(&new[j], SegmentKind::Inserted));
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// 在修改块中按顺序寻找足够相似的块对，返回在 `deleted`/`inserted` 中的位置
+    fn align(
+        &self,
+        old: &[BodyContent],
+        new: &[BodyContent],
+        deleted: &[usize],
+        inserted: &[usize],
+    ) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        let mut start = 0;
+        for (pd, &i) in deleted.iter().enumerate() {
+            let found = (start..inserted.len()).find(|&pn| {
+                let j = inserted[pn];
+                self.can_pair(&old[i], &new[j]) && similarity(&old[i], &new[j]) >= 0.5
+            });
+            if let Some(pn) = found {
+                pairs.push((pd, pn));
+                start = pn + 1;
+            }
+        }
+        pairs
+    }
+
+    /// 判断两个不同的正文块能否逐项比较
+    fn can_pair(&self, old: &BodyContent, new: &BodyContent) -> bool {
+        match (old, new) {
+            (BodyContent::Paragraph(_), BodyContent::Paragraph(_)) => {
+                self.options.granularity != CompareGranularity::Paragraph
+            }
+            (BodyContent::Table(o), BodyContent::Table(n)) => {
+                o.rows()
+                    .map(|r| r.cells().count())
+                    .eq(n.rows().map(|r| r.cells().count()))
+            }
+            (BodyContent::Sdt(o), BodyContent::Sdt(n)) => o.properties.tag == n.properties.tag,
+            _ => false,
+        }
+    }
+
+    /// 比较两个对应的正文块
+    fn pair(&mut self, old: &BodyContent, new: &BodyContent) -> BodyContent {
+        match (old, new) {
+            // 没有变化的段落原样保留，其中的超链接、书签、域和内容控件不受影响
+            (BodyContent::Paragraph(o), BodyContent::Paragraph(n)) if self.unchanged(o, n) => {
+                new.clone()
+            }
+            (BodyContent::Paragraph(o), BodyContent::Paragraph(n)) => {
+                BodyContent::Paragraph(self.paragraph(Some(o), Some(n)))
+            }
+            (BodyContent::Table(o), BodyContent::Table(n)) => {
+                let mut table = n.clone();
+                for (row, old_row) in table.rows_mut().zip(o.rows()) {
+                    for (cell, old_cell) in row.cells_mut().zip(old_row.cells()) {
+                        cell.content = self.blocks(&old_cell.content, &cell.content);
+                    }
+                }
+                BodyContent::Table(table)
+            }
+            (BodyContent::Sdt(o), BodyContent::Sdt(n)) => {
+                let mut sdt = n.clone();
+                sdt.content = self.blocks(&o.content, &n.content);
+                BodyContent::Sdt(sdt)
+            }
+            _ => new.clone(),
+        }
+    }
+
+    /// 将整个正文块标记为插入或删除
+    fn mark(&mut self, block: &BodyContent, kind: SegmentKind) -> BodyContent {
+        match block {
+            BodyContent::Paragraph(p) => BodyContent::Paragraph(match kind {
+                SegmentKind::Deleted => self.paragraph(Some(p), None),
+                _ => self.paragraph(None, Some(p)),
+            }),
+            BodyContent::Table(t) => {
+                let mut table = t.clone();
+                for row in table.rows_mut() {
+                    // 行本身也标记为插入或删除，接受或拒绝后不会留下空行
+                    let revision = Some(self.revision_mark());
+                    let properties = row.properties.get_or_insert_with(Default::default);
+                    if kind == SegmentKind::Deleted {
+                        properties.deleted = revision;
+                    } else {
+                        properties.inserted = revision;
+                    }
+                    for cell in row.cells_mut() {
+                        cell.content = cell.content.iter().map(|b| self.mark(b, kind)).collect();
+                    }
+                }
+                BodyContent::Table(table)
+            }
+            BodyContent::Sdt(s) => {
+                let mut sdt = s.clone();
+                sdt.content = sdt.content.iter().map(|b| self.mark(b, kind)).collect();
+                BodyContent::Sdt(sdt)
+            }
+        }
+    }
+
+    /// 两个段落的文本相同，且格式相同或不比较格式
+    fn unchanged(&self, old: &Paragraph, new: &Paragraph) -> bool {
+        let granularity = self.options.granularity;
+        let (a, b) = (tokens(&old.content, granularity), tokens(&new.content, granularity));
+        let formatting = self.options.compare_formatting;
+        a.len() == b.len()
+            && a.iter().zip(&b).all(|(x, y)| {
+                x.key == y.key
+                    && (!formatting || normalized(&x.properties) == normalized(&y.properties))
+            })
+            && (!formatting
+                || normalized_paragraph(&old.properties) == normalized_paragraph(&new.properties))
+    }
+
+    /// 比较两个段落的内容，任一侧缺失时表示整段插入或删除
+    fn paragraph(&mut self, old: Option<&Paragraph>, new: Option<&Paragraph>) -> Paragraph {
+        let mut paragraph = Paragraph {
+            properties: new.or(old).and_then(|p| p.properties.clone()),
+            content: Vec::new(),
+        };
+
+        // 整段插入或删除时同时标记段落标记
+        if old.is_none() || new.is_none() {
+            let revision = Some(self.revision_mark());
+            let mark = paragraph
+                .properties
+                .get_or_insert_with(ParagraphProperties::default)
+                .run_properties
+                .get_or_insert_with(RunProperties::default);
+            if new.is_some() {
+                mark.inserted = revision;
+            } else {
+                mark.deleted = revision;
+            }
+        }
+
+        // 段落格式修订
+        if let (Some(o), Some(n)) = (old, new) {
+            let old_props = normalized_paragraph(&o.properties);
+            if self.options.compare_formatting && old_props != normalized_paragraph(&n.properties) {
+                let change = ParagraphPropertiesChange {
+                    id: self.next_id(),
+                    author: self.options.author.clone(),
+                    date: self.options.date.clone(),
+                    properties: Box::new(old_props.unwrap_or_default()),
+                };
+                paragraph
+                    .properties
+                    .get_or_insert_with(ParagraphProperties::default)
+                    .change = Some(change);
+            }
+        }
+
+        let granularity = self.options.granularity;
+        let old_tokens = old.map(|p| tokens(&p.content, granularity)).unwrap_or_default();
+        let items = new.map_or(&[][..], |p| &p.content);
+        // 修订后段落中每个顶层内容项的比较单元
+        let item_tokens: Vec<Vec<Token>> = items
+            .iter()
+            .map(|item| tokens(std::slice::from_ref(item), granularity))
+            .collect();
+        let new_tokens: Vec<&Token> = item_tokens.iter().flatten().collect();
+        let old_keys: Vec<&str> = old_tokens.iter().map(|t| t.key.as_str()).collect();
+        let new_keys: Vec<&str> = new_tokens.iter().map(|t| t.key.as_str()).collect();
+
+        // 每个修订后单元之前被删除的原始单元，以及修订后单元的状态和格式变化前的属性
+        let mut deleted_before = vec![Vec::new(); new_tokens.len() + 1];
+        let mut states: Vec<(SegmentKind, Option<RunProperties>)> = Vec::new();
+        for op in diff(&old_keys, &new_keys) {
+            match op {
+                DiffOp::Equal(i, j) => {
+                    let old_props = normalized(&old_tokens[i].properties);
+                    let changed = self.options.compare_formatting
+                        && old_props != normalized(&new_tokens[j].properties);
+                    let old_properties = changed.then(|| old_props.unwrap_or_default());
+                    states.push((SegmentKind::Unchanged, old_properties));
+                }
+                DiffOp::Delete(i) => deleted_before[states.len()].push(i),
+                DiffOp::Insert(_) => states.push((SegmentKind::Inserted, None)),
+            }
+        }
+
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut start = 0;
+        for (item, item_tokens) in items.iter().zip(&item_tokens) {
+            let range = start..start + item_tokens.len();
+            start = range.end;
+            if !matches!(item, ParagraphContent::Run(_)) {
+                // 书签和内容未变的超链接、简单域、内容控件原样保留
+                let intact = range
+                    .clone()
+                    .all(|j| states[j] == (SegmentKind::Unchanged, None))
+                    && range.clone().skip(1).all(|j| deleted_before[j].is_empty());
+                if intact {
+                    if !range.is_empty() {
+                        for &i in &deleted_before[range.start] {
+                            push_segment(&mut segments, SegmentKind::Deleted, &old_tokens[i], None);
+                        }
+                    }
+                    self.flush(&mut segments, &mut paragraph.content);
+                    paragraph.content.push(item.clone());
+                    continue;
+                }
+            }
+            for j in range {
+                for &i in &deleted_before[j] {
+                    push_segment(&mut segments, SegmentKind::Deleted, &old_tokens[i], None);
+                }
+                let (kind, old_properties) = states[j].clone();
+                push_segment(&mut segments, kind, new_tokens[j], old_properties);
+            }
+        }
+        for &i in &deleted_before[new_tokens.len()] {
+            push_segment(&mut segments, SegmentKind::Deleted, &old_tokens[i], None);
+        }
+        self.flush(&mut segments, &mut paragraph.content);
+        paragraph
+    }
+
+    /// 将累积的片段转换为文本运行和插入、删除修订，追加到段落内容
+    fn flush(&mut self, segments: &mut Vec<Segment>, content: &mut Vec<ParagraphContent>) {
+        for segment in segments.drain(..) {
+            let mut properties = segment.properties;
+            if let Some(old_properties) = segment.old_properties {
+                properties.get_or_insert_with(RunProperties::default).change =
+                    Some(RunPropertiesChange {
+                        id: self.next_id(),
+                        author: self.options.author.clone(),
+                        date: self.options.date.clone(),
+                        properties: Box::new(old_properties),
+                    });
+            }
+            let run = Run {
+                properties,
+                content: segment.content,
+            };
+            let item = match segment.kind {
+                SegmentKind::Unchanged => ParagraphContent::Run(run),
+                SegmentKind::Inserted => ParagraphContent::Insertion(self.revision(run)),
+                SegmentKind::Deleted => ParagraphContent::Deletion(self.revision(run)),
+            };
+            content.push(item);
+        }
+    }
+
+    /// 新的插入或删除标记
+    fn revision_mark(&mut self) -> RevisionMark {
+        RevisionMark {
+            id: self.next_id(),
+            author: self.options.author.clone(),
+            date: self.options.date.clone(),
+        }
+    }
+
+    fn revision(&mut self, run: Run) -> RunRevision {
+        RunRevision {
+            id: self.next_id(),
+            author: self.options.author.clone(),
+            date: self.options.date.clone(),
+            runs: vec![run],
+        }
+    }
+}
+
+/// 追加一个比较单元，与前一个片段状态和格式相同时合并
+fn push_segment(
+    segments: &mut Vec<Segment>,
+    kind: SegmentKind,
+    token: &Token,
+    old_properties: Option<RunProperties>,
+) {
+    let content = match (&token.content, kind) {
+        (RunContent::Text(t), SegmentKind::Deleted) => RunContent::DeletedText(t.clone()),
+        (RunContent::InstrText(t), SegmentKind::Deleted) => RunContent::DeletedInstrText(t.clone()),
+        (c, _) => c.clone(),
+    };
+
+    if let Some(last) = segments.last_mut() {
+        if last.kind == kind
+            && last.properties == token.properties
+            && last.old_properties == old_properties
+        {
+            match (last.content.last_mut(), &content) {
+                (Some(RunContent::Text(a)), RunContent::Text(b))
+                | (Some(RunContent::DeletedText(a)), RunContent::DeletedText(b)) => a.push_str(b),
+                _ => last.content.push(content),
+            }
+            return;
+        }
+    }
+
+    segments.push(Segment {
+        kind,
+        content: vec![content],
+        properties: token.properties.clone(),
+        old_properties,
+    });
+}
+
+/// 计算两个正文块按词比较时的相似度（0 到 1）
+fn similarity(old: &BodyContent, new: &BodyContent) -> f64 {
+    let keys = |block: &BodyContent| -> Vec<String> {
+        match block {
+            BodyContent::Paragraph(p) => tokens(&p.content, CompareGranularity::Word)
+                .into_iter()
+                .map(|t| t.key)
+                .collect(),
+            BodyContent::Table(_) | BodyContent::Sdt(_) => vec![block_key(block)],
+        }
+    };
+    let (a, b) = (keys(old), keys(new));
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let common = diff(&a, &b)
+        .iter()
+        .filter(|op| matches!(op, DiffOp::Equal(..)))
+        .count();
+    (2 * common) as f64 / (a.len() + b.len()) as f64
+}
+
+/// 去掉修订记录，并将空属性视为无属性
+fn normalized(properties: &Option<RunProperties>) -> Option<RunProperties> {
+    let mut props = properties.clone()?;
+    props.change = None;
+    (props != RunProperties::default()).then_some(props)
+}
+
+fn normalized_paragraph(properties: &Option<ParagraphProperties>) -> Option<ParagraphProperties> {
+    let mut props = properties.clone()?;
+    props.change = None;
+    (props != ParagraphProperties::default()).then_some(props)
+}
+
+/// 计算正文块的比较键
+fn block_key(block: &BodyContent) -> String {
+    match block {
+        BodyContent::Paragraph(p) => {
+            let mut key = String::from("p:");
+            for token in tokens(&p.content, CompareGranularity::Paragraph) {
+                key.push_str(&token.key);
+            }
+            key
+        }
+        BodyContent::Table(t) => {
+            let mut key = String::from("t:");
+            for row in t.rows() {
+                for cell in row.cells() {
+                    for block in &cell.content {
+                        key.push_str(&block_key(block));
+                        key.push('\u{1e}');
+                    }
+                    key.push('\u{1f}');
+                }
+                key.push('\n');
+            }
+            key
+        }
+        BodyContent::Sdt(sdt) => {
+            let mut key = String::from("s:");
+            for block in &sdt.content {
+                key.push_str(&block_key(block));
+                key.push('\u{1e}');
+            }
+            key
+        }
+    }
+}
+
+/// 将段落内容拆分为比较单元
+fn tokens(content: &[ParagraphContent], granularity: CompareGranularity) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let push_run = |run: &Run| {
+        for content in &run.content {
+            let (key, content) = match content {
+                RunContent::Text(text) => {
+                    for piece in split_text(text, granularity) {
+                        tokens.push(Token {
+                            key: piece.clone(),
+                            content: RunContent::Text(piece),
+                            properties: run.properties.clone(),
+                        });
+                    }
+                    continue;
+                }
+                RunContent::Break => ("\n", RunContent::Break),
+                RunContent::PageBreak => ("\u{c}", RunContent::PageBreak),
+                RunContent::Tab => ("\t", RunContent::Tab),
+                RunContent::Drawing(drawing) => {
+                    tokens.push(Token {
+                        key: format!("\u{fffc}{}", drawing.embed),
+                        content: RunContent::Drawing(drawing.clone()),
+                        properties: run.properties.clone(),
+                    });
+                    continue;
+                }
+                RunContent::FieldChar(field_char) => {
+                    tokens.push(Token {
+                        key: format!("\u{2}{:?}", field_char.char_type),
+                        content: RunContent::FieldChar(field_char.clone()),
+                        properties: run.properties.clone(),
+                    });
+                    continue;
+                }
+                RunContent::InstrText(instruction) => {
+                    tokens.push(Token {
+                        key: format!("\u{3}{instruction}"),
+                        content: RunContent::InstrText(instruction.clone()),
+                        properties: run.properties.clone(),
+                    });
+                    continue;
+                }
+                RunContent::FootnoteReference(_)
+                | RunContent::EndnoteReference(_)
+                | RunContent::RawDrawing(_) => {
+                    tokens.push(Token {
+                        key: format!("\u{4}{content:?}"),
+                        content: content.clone(),
+                        properties: run.properties.clone(),
+                    });
+                    continue;
+                }
+                RunContent::DeletedText(_) | RunContent::DeletedInstrText(_) => continue,
+            };
+            tokens.push(Token {
+                key: key.to_string(),
+                content,
+                properties: run.properties.clone(),
+            });
+        }
+    };
+
+    visible_runs(content).for_each(push_run);
+    tokens
+}
+
+/// 按比较粒度切分文本
+fn split_text(text: &str, granularity: CompareGranularity) -> Vec<String> {
+    if granularity == CompareGranularity::Character {
+        return text.chars().map(String::from).collect();
+    }
+
+    let mut pieces: Vec<String> = Vec::new();
+    let mut previous: Option<CharClass> = None;
+    for c in text.chars() {
+        let class = CharClass::of(c);
+        match pieces.last_mut() {
+            Some(last) if previous == Some(class) && class.groups() => last.push(c),
+            _ => pieces.push(c.to_string()),
+        }
+        previous = Some(class);
+    }
+    pieces
+}
+
+/// 分词时使用的字符类别
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Word,
+    Space,
+    Ideograph,
+    Other,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        if is_ideograph(c) {
+            CharClass::Ideograph
+        } else if c.is_alphanumeric() || c == '_' {
+            CharClass::Word
+        } else if c.is_whitespace() {
+            CharClass::Space
+        } else {
+            CharClass::Other
+        }
+    }
+
+    /// 同类字符是否合并为一个单元
+    fn groups(self) -> bool {
+        matches!(self, CharClass::Word | CharClass::Space)
+    }
+}
+
+/// 中日韩文字没有词间空格，按单字比较
+fn is_ideograph(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::test_utils::{body_xml, docx, p};
+
+    #[test]
+    fn changed_words_are_marked() {
+        let original = docx(&p("one two three"));
+        let revised = docx(&p("one four three"));
+
+        let result = compare(&original, &revised, &CompareOptions::default());
+        let xml = body_xml(&result);
+        assert!(xml.contains("<w:delText xml:space=\"preserve\">two</w:delText>"), "{xml}");
+        assert!(xml.contains("<w:t xml:space=\"preserve\">four</w:t>"), "{xml}");
+
+        let mut accepted = result.clone();
+        accepted.document.accept_all_revisions();
+        assert_eq!(accepted.document.body.content.len(), 1);
+        let mut rejected = result;
+        rejected.document.reject_all_revisions();
+        let xml = body_xml(&rejected);
+        assert!(xml.contains("<w:t xml:space=\"preserve\">two</w:t>"), "{xml}");
+        assert!(!xml.contains("four") && !xml.contains("<w:ins "), "{xml}");
+    }
+
+    #[test]
+    fn unchanged_paragraph_keeps_hyperlinks_and_bookmarks() {
+        let linked = "<w:p><w:bookmarkStart w:id=\"0\" w:name=\"top\"/>\
+            <w:hyperlink r:id=\"rId9\"><w:r><w:t>link</w:t></w:r></w:hyperlink>\
+            <w:bookmarkEnd w:id=\"0\"/></w:p>";
+        let original = docx(&format!("{linked}{}", p("old")));
+        let revised = docx(&format!("{linked}{}", p("new")));
+
+        let xml = body_xml(&compare(&original, &revised, &CompareOptions::default()));
+        assert!(xml.contains("w:hyperlink"), "{xml}");
+        assert!(xml.contains("<w:bookmarkStart"), "{xml}");
+        assert!(xml.contains("<w:del "), "{xml}");
+        assert!(xml.contains("<w:ins "), "{xml}");
+    }
+
+    #[test]
+    fn deleted_field_code_uses_del_instr_text() {
+        let field = "<w:p><w:r><w:t>Page </w:t></w:r>\
+            <w:r><w:fldChar w:fldCharType=\"begin\"/></w:r>\
+            <w:r><w:instrText xml:space=\"preserve\"> PAGE </w:instrText></w:r>\
+            <w:r><w:fldChar w:fldCharType=\"end\"/></w:r></w:p>";
+        let original = docx(field);
+        let revised = docx("<w:p><w:r><w:t>Page </w:t></w:r></w:p>");
+
+        let result = compare(&original, &revised, &CompareOptions::default());
+        let xml = body_xml(&result);
+        let deleted = "<w:delInstrText xml:space=\"preserve\"> PAGE </w:delInstrText>";
+        assert!(xml.contains(deleted), "{xml}");
+        assert!(!xml.contains("<w:instrText"), "{xml}");
+
+        let mut rejected = result;
+        rejected.document.reject_all_revisions();
+        let restored = "<w:instrText xml:space=\"preserve\"> PAGE </w:instrText>";
+        assert!(body_xml(&rejected).contains(restored));
+    }
+
+    #[test]
+    fn result_keeps_revised_section_properties() {
+        let section = "<w:sectPr><w:pgSz w:w=\"16838\" w:h=\"11906\"/></w:sectPr>";
+        let original = docx(&p("text"));
+        let revised = docx(&format!("{}{section}", p("text!")));
+
+        let result = compare(&original, &revised, &CompareOptions::default());
+        assert_eq!(
+            result.document.body.section_properties,
+            revised.document.body.section_properties
+        );
+        assert!(result.document.body.section_properties.is_some());
+        assert_eq!(result.document.xmlns_r, revised.document.xmlns_r);
+    }
+
+    #[test]
+    fn changed_paragraph_keeps_links_and_bookmarks_around_changes() {
+        let paragraph = |word: &str| {
+            format!(
+                "<w:p><w:bookmarkStart w:id=\"0\" w:name=\"top\"/>\
+                 <w:r><w:t xml:space=\"preserve\">see {word} </w:t></w:r>\
+                 <w:hyperlink r:id=\"rId9\"><w:r><w:t>link</w:t></w:r></w:hyperlink>\
+                 <w:bookmarkEnd w:id=\"0\"/></w:p>"
+            )
+        };
+        let original = docx(&paragraph("old"));
+        let revised = docx(&paragraph("new"));
+
+        let result = compare(&original, &revised, &CompareOptions::default());
+        let xml = body_xml(&result);
+        let link = "<w:hyperlink><w:r><w:t xml:space=\"preserve\">link</w:t></w:r></w:hyperlink>";
+        assert!(xml.contains(link), "{xml}");
+        assert!(xml.starts_with("<w:p><w:bookmarkStart"), "{xml}");
+        assert!(xml.contains("<w:bookmarkEnd w:id=\"0\"/></w:p>"), "{xml}");
+        assert!(xml.contains("<w:delText xml:space=\"preserve\">old</w:delText>"), "{xml}");
+
+        let mut accepted = result;
+        accepted.document.accept_all_revisions();
+        assert_eq!(body_xml(&accepted).matches("w:hyperlink").count(), 2);
+    }
+
+    #[test]
+    fn inserted_table_rows_are_marked() {
+        let table = "<w:tbl><w:tblGrid><w:gridCol w:w=\"2000\"/></w:tblGrid>\
+            <w:tr><w:tc><w:p><w:r><w:t>cell</w:t></w:r></w:p></w:tc></w:tr></w:tbl>";
+        let original = docx(&p("text"));
+        let revised = docx(&format!("{}{table}", p("text")));
+
+        let xml = body_xml(&compare(&original, &revised, &CompareOptions::default()));
+        assert!(xml.contains("<w:tr><w:trPr><w:ins w:id="), "{xml}");
+
+        let xml = body_xml(&compare(&revised, &original, &CompareOptions::default()));
+        assert!(xml.contains("<w:tr><w:trPr><w:del w:id="), "{xml}");
+    }
+}
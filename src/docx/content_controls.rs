@@ -0,0 +1,1068 @@
+//! 内容控件（结构化文档标记）：按文档顺序列出内容控件，按标记填充文本、复选框、
+//! 下拉列表、日期和图片，并用数组数据展开重复节
+//!
+//! 内容控件可以位于正文（包围段落和表格）、段落（包围文本运行）、表格（包围若干行，
+//! 见 [`TableContent`]）和表格行（包围若干单元格，见 [`RowContent`]）中。
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::{OfficeError, Result};
+
+use super::builder::Image;
+use super::elements::paragraph::visible_runs;
+use super::elements::{
+    BlockSdt, BodyContent, Drawing, Paragraph, ParagraphContent, RowContent, Run, RunContent,
+    RunSdt, TableCell, TableContent, TableItem, TableRow, TableSdt,
+};
+use super::fields::{text_content, FieldDateTime};
+use super::properties::{SdtProperties, SdtType};
+use super::Docx;
+
+/// 日期选取器未指定显示格式时使用的格式
+const DEFAULT_DATE_FORMAT: &str = "M/d/yyyy";
+
+/// 文档中的一个内容控件
+#[derive(Debug, Clone)]
+pub enum ContentControl<'a> {
+    /// 块级内容控件，包含段落和表格
+    Block(&'a BlockSdt),
+    /// 运行级内容控件，位于段落中
+    Run(&'a RunSdt),
+    /// 行级内容控件，包含表格行
+    Rows(&'a TableSdt<TableRow>),
+    /// 单元格级内容控件，包含单元格
+    Cells(&'a TableSdt<TableCell>),
+}
+
+impl<'a> ContentControl<'a> {
+    /// 内容控件属性
+    pub fn properties(&self) -> &'a SdtProperties {
+        match self {
+            ContentControl::Block(sdt) => &sdt.properties,
+            ContentControl::Run(sdt) => &sdt.properties,
+            ContentControl::Rows(sdt) => &sdt.properties,
+            ContentControl::Cells(sdt) => &sdt.properties,
+        }
+    }
+
+    /// 标记
+    pub fn tag(&self) -> Option<&'a str> {
+        self.properties().tag()
+    }
+
+    /// 显示的名称
+    pub fn alias(&self) -> Option<&'a str> {
+        self.properties().alias()
+    }
+
+    /// 类型
+    pub fn sdt_type(&self) -> SdtType {
+        self.properties().sdt_type()
+    }
+
+    /// 内容的文本；表格行中的单元格以制表符分隔，各行以换行符分隔
+    pub fn text(&self) -> String {
+        let cells_text = |cells: &mut dyn Iterator<Item = &TableCell>| {
+            cells.map(|c| c.text()).collect::<Vec<_>>().join("\t")
+        };
+        match self {
+            ContentControl::Block(sdt) => sdt.text(),
+            ContentControl::Run(sdt) => sdt.text(),
+            ContentControl::Rows(sdt) => sdt
+                .items()
+                .map(|row| cells_text(&mut row.cells()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ContentControl::Cells(sdt) => cells_text(&mut sdt.items()),
+        }
+    }
+}
+
+impl Docx {
+    /// 按文档顺序列出正文中的全部内容控件，外层内容控件排在其包含的内容控件之前
+    pub fn content_controls(&self) -> Vec<ContentControl<'_>> {
+        let mut controls = Vec::new();
+        collect_blocks(&self.document.body.content, &mut controls);
+        controls
+    }
+
+    /// 查找第一个具有指定标记的内容控件
+    ///
+    /// # 参数
+    /// * `tag` - 标记
+    pub fn content_control(&self, tag: &str) -> Option<ContentControl<'_>> {
+        self.content_controls()
+            .into_iter()
+            .find(|control| control.tag() == Some(tag))
+    }
+
+    /// 用文本填充具有指定标记的全部内容控件，返回填充的数量
+    ///
+    /// 文本内容控件的内容替换为沿用原有格式的文本，`\n` 在块级内容控件中分段、在其他位置
+    /// 换行；复选框按 `1`/`true`/`yes`/`on`/`x` 选中，其余取消选中；下拉列表按值或显示
+    /// 文本选择列表项；日期选取器接受 `2024-05-01` 形式的日期并按其显示格式输出。填充后
+    /// 不再显示占位符。失败时文档保持不变。
+    ///
+    /// # 参数
+    /// * `tag` - 标记
+    /// * `value` - 填充的值
+    pub fn fill_content_control(&mut self, tag: &str, value: &str) -> Result<usize> {
+        let mut values = Map::new();
+        values.insert(tag.to_string(), Value::String(value.to_string()));
+        self.fill_with(&values)
+    }
+
+    /// 用数据模型填充内容控件，数据可以是任意可序列化的类型，返回填充的数量
+    ///
+    /// 字段名对应内容控件的标记：字符串和数字按 [`Docx::fill_content_control`] 填充，
+    /// 布尔值设置复选框，`null` 被忽略；对象在对应的组、重复节项等内容控件中按其字段继续
+    /// 填充；数组展开重复节：以第一项为模板为每个元素复制一项并按元素填充，空数组删除
+    /// 全部项。失败时文档保持不变。
+    ///
+    /// # 参数
+    /// * `data` - 数据模型，序列化后须为对象
+    pub fn fill_content_controls<T: Serialize + ?Sized>(&mut self, data: &T) -> Result<usize> {
+        let data = serde_json::to_value(data).map_err(|e| {
+            OfficeError::InvalidFormat(format!("invalid content control data: {e}"))
+        })?;
+        let Value::Object(values) = data else {
+            return Err(OfficeError::InvalidFormat(
+                "content control data must be an object".to_string(),
+            ));
+        };
+        self.fill_with(&values)
+    }
+
+    /// 在具有指定标记的图片内容控件中放入图片，返回放入的数量
+    ///
+    /// 内容控件中已有图片时只替换图片数据，并按原图片的大小等比缩放；否则插入原始大小的
+    /// 图片。没有匹配的图片内容控件时不保存图片。
+    ///
+    /// # 参数
+    /// * `tag` - 标记
+    /// * `image` - 图片
+    pub fn set_content_control_image(&mut self, tag: &str, image: Image) -> Result<usize> {
+        let matched = self
+            .content_controls()
+            .iter()
+            .filter(|control| control.tag() == Some(tag))
+            .map(|control| control.sdt_type())
+            .collect::<Vec<_>>();
+        if matched.is_empty() {
+            return Ok(0);
+        }
+        if let Some(other) = matched.iter().find(|t| **t != SdtType::Picture) {
+            return Err(OfficeError::InvalidFormat(format!(
+                "content control '{tag}' is not a picture control ({other:?})"
+            )));
+        }
+
+        let drawing = self.add_image_part(image);
+        let mut placer = ImagePlacer {
+            tag,
+            drawing,
+            placed: 0,
+        };
+        placer.blocks(&mut self.document.body.content);
+        Ok(placer.placed)
+    }
+
+    /// 按值映射填充内容控件，成功后才写回正文
+    fn fill_with(&mut self, values: &Map<String, Value>) -> Result<usize> {
+        let next_id = self
+            .content_controls()
+            .iter()
+            .filter_map(|control| control.properties().id())
+            .max()
+            .unwrap_or(0);
+        let mut filler = Filler { next_id, filled: 0 };
+        let mut content = self.document.body.content.clone();
+        filler.blocks(values, &mut content)?;
+        self.document.body.content = content;
+        Ok(filler.filled)
+    }
+}
+
+fn collect_blocks<'a>(content: &'a [BodyContent], controls: &mut Vec<ContentControl<'a>>) {
+    for item in content {
+        match item {
+            BodyContent::Paragraph(paragraph) => collect_inline(&paragraph.content, controls),
+            BodyContent::Table(table) => collect_rows(&table.content, controls),
+            BodyContent::Sdt(sdt) => {
+                controls.push(ContentControl::Block(sdt));
+                collect_blocks(&sdt.content, controls);
+            }
+        }
+    }
+}
+
+fn collect_inline<'a>(content: &'a [ParagraphContent], controls: &mut Vec<ContentControl<'a>>) {
+    for item in content {
+        if let ParagraphContent::Sdt(sdt) = item {
+            controls.push(ContentControl::Run(sdt));
+            collect_inline(&sdt.content, controls);
+        }
+    }
+}
+
+fn collect_rows<'a>(content: &'a [TableContent], controls: &mut Vec<ContentControl<'a>>) {
+    for item in content {
+        match item {
+            TableItem::Item(row) => collect_cells(&row.content, controls),
+            TableItem::Sdt(sdt) => {
+                controls.push(ContentControl::Rows(sdt));
+                collect_rows(&sdt.content, controls);
+            }
+        }
+    }
+}
+
+fn collect_cells<'a>(content: &'a [RowContent], controls: &mut Vec<ContentControl<'a>>) {
+    for item in content {
+        match item {
+            TableItem::Item(cell) => collect_blocks(&cell.content, controls),
+            TableItem::Sdt(sdt) => {
+                controls.push(ContentControl::Cells(sdt));
+                collect_cells(&sdt.content, controls);
+            }
+        }
+    }
+}
+
+/// 按值映射填充内容控件
+struct Filler {
+    /// 已使用的最大内容控件ID，复制重复节项时分配新ID
+    next_id: i32,
+    filled: usize,
+}
+
+impl Filler {
+    fn blocks(&mut self, values: &Map<String, Value>, content: &mut [BodyContent]) -> Result<()> {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(paragraph) => self.inline(values, &mut paragraph.content)?,
+                BodyContent::Table(table) => self.table_items(values, &mut table.content)?,
+                BodyContent::Sdt(sdt) => match lookup(values, &sdt.properties) {
+                    Some(Value::Array(items)) => {
+                        check_repeating(&sdt.properties)?;
+                        sdt.content = self.repeat_blocks(items, &sdt.content)?;
+                        self.filled += 1;
+                    }
+                    Some(Value::Object(inner)) => {
+                        self.blocks(inner, &mut sdt.content)?;
+                        self.filled += 1;
+                    }
+                    Some(value) => {
+                        fill_blocks(&mut sdt.properties, &mut sdt.content, value, true)?;
+                        self.filled += 1;
+                    }
+                    None => self.blocks(values, &mut sdt.content)?,
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn inline(
+        &mut self,
+        values: &Map<String, Value>,
+        content: &mut [ParagraphContent],
+    ) -> Result<()> {
+        for item in content {
+            let ParagraphContent::Sdt(sdt) = item else {
+                continue;
+            };
+            match lookup(values, &sdt.properties) {
+                Some(Value::Array(_)) => {
+                    check_repeating(&sdt.properties)?;
+                    return Err(OfficeError::InvalidFormat(format!(
+                        "repeating section '{}' must contain paragraphs or table rows",
+                        sdt.properties.tag().unwrap_or_default()
+                    )));
+                }
+                Some(Value::Object(inner)) => {
+                    self.inline(inner, &mut sdt.content)?;
+                    self.filled += 1;
+                }
+                Some(value) => {
+                    fill_inline(sdt, value, true)?;
+                    self.filled += 1;
+                }
+                None => self.inline(values, &mut sdt.content)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 填充表格或表格行中的行级或单元格级内容控件，以及其中单元格里的内容控件
+    fn table_items<T: Cells>(
+        &mut self,
+        values: &Map<String, Value>,
+        content: &mut [TableItem<T>],
+    ) -> Result<()> {
+        for item in content {
+            let sdt = match item {
+                TableItem::Item(item) => {
+                    item.fill(self, values)?;
+                    continue;
+                }
+                TableItem::Sdt(sdt) => sdt,
+            };
+            match lookup(values, &sdt.properties) {
+                Some(Value::Array(entries)) => {
+                    check_repeating(&sdt.properties)?;
+                    sdt.content = self.repeat_items(entries, &sdt.content)?;
+                    self.filled += 1;
+                }
+                Some(Value::Object(inner)) => {
+                    self.table_items(inner, &mut sdt.content)?;
+                    self.filled += 1;
+                }
+                Some(value) => {
+                    fill_group(sdt, value, true)?;
+                    self.filled += 1;
+                }
+                None => self.table_items(values, &mut sdt.content)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 以重复节的第一项为模板，为每个数组元素复制一项并填充
+    fn repeat_items<T: Cells>(
+        &mut self,
+        entries: &[Value],
+        section: &[TableItem<T>],
+    ) -> Result<Vec<TableItem<T>>> {
+        let Some(template) = section
+            .iter()
+            .find(|item| {
+                matches!(item, TableItem::Sdt(sdt) if sdt.properties.repeating_section_item.is_some())
+            })
+            .or(section.first())
+            .cloned()
+        else {
+            return Ok(Vec::new());
+        };
+        let mut output = Vec::new();
+        for entry in entries {
+            let mut copy = template.clone();
+            self.renumber_items(std::slice::from_mut(&mut copy), &mut HashMap::new());
+            match (entry, &mut copy) {
+                // 模板本身是重复节项时，数据字段对应项内的内容控件
+                (Value::Object(inner), TableItem::Sdt(sdt))
+                    if sdt.properties.repeating_section_item.is_some() =>
+                {
+                    self.table_items(inner, &mut sdt.content)?;
+                }
+                (Value::Object(inner), _) => {
+                    self.table_items(inner, std::slice::from_mut(&mut copy))?;
+                }
+                (Value::Null, _) => {}
+                (value, _) => {
+                    if let Some(first) = copy.items_mut().find_map(Cells::first_cell) {
+                        let mut properties = SdtProperties::default();
+                        fill_blocks(&mut properties, &mut first.content, value, true)?;
+                    }
+                }
+            }
+            output.push(copy);
+        }
+        Ok(output)
+    }
+
+    /// 以重复节的第一项为模板，为每个数组元素复制一项并填充
+    fn repeat_blocks(
+        &mut self,
+        entries: &[Value],
+        section: &[BodyContent],
+    ) -> Result<Vec<BodyContent>> {
+        let Some(template) = section
+            .iter()
+            .find(|item| {
+                matches!(item, BodyContent::Sdt(sdt) if sdt.properties.repeating_section_item.is_some())
+            })
+            .or(section.first())
+            .cloned()
+        else {
+            return Ok(Vec::new());
+        };
+        let mut output = Vec::new();
+        for entry in entries {
+            let mut copy = vec![template.clone()];
+            self.renumber_blocks(&mut copy, &mut HashMap::new());
+            match entry {
+                Value::Object(inner) => {
+                    // 模板本身是重复节项时，数据字段对应项内的内容控件
+                    if let [BodyContent::Sdt(sdt)] = copy.as_mut_slice() {
+                        if sdt.properties.repeating_section_item.is_some() {
+                            self.blocks(inner, &mut sdt.content)?;
+                            output.append(&mut copy);
+                            continue;
+                        }
+                    }
+                    self.blocks(inner, &mut copy)?;
+                }
+                Value::Null => {}
+                value => match copy.as_mut_slice() {
+                    [BodyContent::Sdt(sdt)] => {
+                        let mut properties = SdtProperties::default();
+                        fill_blocks(&mut properties, &mut sdt.content, value, true)?;
+                    }
+                    content => {
+                        let mut properties = SdtProperties::default();
+                        let mut blocks = content.to_vec();
+                        fill_blocks(&mut properties, &mut blocks, value, true)?;
+                        copy = blocks;
+                    }
+                },
+            }
+            output.append(&mut copy);
+        }
+        Ok(output)
+    }
+
+    /// 为复制出的内容控件分配新ID，同一个ID在复制中映射为同一个新ID
+    fn renumber(&mut self, properties: &mut SdtProperties, ids: &mut HashMap<i32, i32>) {
+        if let Some(id) = &mut properties.id {
+            id.val = *ids.entry(id.val).or_insert_with(|| {
+                self.next_id = self.next_id.wrapping_add(1);
+                self.next_id
+            });
+        }
+    }
+
+    fn renumber_blocks(&mut self, content: &mut [BodyContent], ids: &mut HashMap<i32, i32>) {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(paragraph) => {
+                    self.renumber_inline(&mut paragraph.content, ids)
+                }
+                BodyContent::Table(table) => self.renumber_items(&mut table.content, ids),
+                BodyContent::Sdt(sdt) => {
+                    self.renumber(&mut sdt.properties, ids);
+                    self.renumber_blocks(&mut sdt.content, ids);
+                }
+            }
+        }
+    }
+
+    fn renumber_items<T: Cells>(
+        &mut self,
+        content: &mut [TableItem<T>],
+        ids: &mut HashMap<i32, i32>,
+    ) {
+        for item in content {
+            match item {
+                TableItem::Item(item) => item.renumber_content(self, ids),
+                TableItem::Sdt(sdt) => {
+                    self.renumber(&mut sdt.properties, ids);
+                    self.renumber_items(&mut sdt.content, ids);
+                }
+            }
+        }
+    }
+
+    fn renumber_inline(&mut self, content: &mut [ParagraphContent], ids: &mut HashMap<i32, i32>) {
+        for item in content {
+            if let ParagraphContent::Sdt(sdt) = item {
+                self.renumber(&mut sdt.properties, ids);
+                self.renumber_inline(&mut sdt.content, ids);
+            }
+        }
+    }
+}
+
+/// 表格行和单元格中供填充和复制使用的内容
+trait Cells: Clone {
+    /// 第一个单元格，作为行级或单元格级内容控件的文本位置
+    fn first_cell(&mut self) -> Option<&mut TableCell>;
+
+    /// 填充其中的内容控件
+    fn fill(&mut self, filler: &mut Filler, values: &Map<String, Value>) -> Result<()>;
+
+    /// 按数据绑定更新其中的内容控件
+    fn bind(&mut self, binder: &mut Binder<'_, '_>) -> Result<()>;
+
+    /// 为内容中的内容控件分配新ID
+    fn renumber_content(&mut self, filler: &mut Filler, ids: &mut HashMap<i32, i32>);
+}
+
+impl Cells for TableRow {
+    fn first_cell(&mut self) -> Option<&mut TableCell> {
+        self.cells_mut().next()
+    }
+
+    fn fill(&mut self, filler: &mut Filler, values: &Map<String, Value>) -> Result<()> {
+        filler.table_items(values, &mut self.content)
+    }
+
+    fn bind(&mut self, binder: &mut Binder<'_, '_>) -> Result<()> {
+        binder.table_items(&mut self.content)
+    }
+
+    fn renumber_content(&mut self, filler: &mut Filler, ids: &mut HashMap<i32, i32>) {
+        filler.renumber_items(&mut self.content, ids);
+    }
+}
+
+impl Cells for TableCell {
+    fn first_cell(&mut self) -> Option<&mut TableCell> {
+        Some(self)
+    }
+
+    fn fill(&mut self, filler: &mut Filler, values: &Map<String, Value>) -> Result<()> {
+        filler.blocks(values, &mut self.content)
+    }
+
+    fn bind(&mut self, binder: &mut Binder<'_, '_>) -> Result<()> {
+        binder.blocks(&mut self.content)
+    }
+
+    fn renumber_content(&mut self, filler: &mut Filler, ids: &mut HashMap<i32, i32>) {
+        filler.renumber_blocks(&mut self.content, ids);
+    }
+}
+
+/// 按数据绑定更新内容控件的显示内容，返回更新的数量
+///
+/// 不在列表中的值和无法解析的日期按原样显示；图片内容控件不更新。
+///
+/// # 参数
+/// * `content` - 段落和表格
+/// * `bound` - 返回内容控件绑定的值，`None` 表示没有绑定或绑定的节点不存在
+pub(super) fn fill_bound(content: &mut [BodyContent], bound: &mut BoundValue<'_>) -> Result<usize> {
+    let mut binder = Binder { bound, filled: 0 };
+    binder.blocks(content)?;
+    Ok(binder.filled)
+}
+
+/// 内容控件绑定的值
+pub(super) type BoundValue<'f> = dyn FnMut(&SdtProperties) -> Result<Option<String>> + 'f;
+
+/// 按数据绑定填充内容控件
+struct Binder<'b, 'f> {
+    bound: &'b mut BoundValue<'f>,
+    filled: usize,
+}
+
+impl Binder<'_, '_> {
+    /// 内容控件绑定的值，图片内容控件视为没有绑定
+    fn value(&mut self, properties: &SdtProperties) -> Result<Option<Value>> {
+        if properties.sdt_type() == SdtType::Picture {
+            return Ok(None);
+        }
+        Ok((self.bound)(properties)?.map(Value::String))
+    }
+
+    fn blocks(&mut self, content: &mut [BodyContent]) -> Result<()> {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(paragraph) => self.inline(&mut paragraph.content)?,
+                BodyContent::Table(table) => self.table_items(&mut table.content)?,
+                BodyContent::Sdt(sdt) => match self.value(&sdt.properties)? {
+                    Some(value) => {
+                        fill_blocks(&mut sdt.properties, &mut sdt.content, &value, false)?;
+                        self.filled += 1;
+                    }
+                    None => self.blocks(&mut sdt.content)?,
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn inline(&mut self, content: &mut [ParagraphContent]) -> Result<()> {
+        for item in content {
+            let ParagraphContent::Sdt(sdt) = item else {
+                continue;
+            };
+            match self.value(&sdt.properties)? {
+                Some(value) => {
+                    fill_inline(sdt, &value, false)?;
+                    self.filled += 1;
+                }
+                None => self.inline(&mut sdt.content)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 更新表格或表格行中的行级或单元格级内容控件，以及其中单元格里的内容控件
+    fn table_items<T: Cells>(&mut self, content: &mut [TableItem<T>]) -> Result<()> {
+        for item in content {
+            match item {
+                TableItem::Item(item) => item.bind(self)?,
+                TableItem::Sdt(sdt) => match self.value(&sdt.properties)? {
+                    Some(value) => {
+                        fill_group(sdt, &value, false)?;
+                        self.filled += 1;
+                    }
+                    None => self.table_items(&mut sdt.content)?,
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 按内容控件的标记查找值，`null` 视为没有值
+fn lookup<'v>(values: &'v Map<String, Value>, properties: &SdtProperties) -> Option<&'v Value> {
+    values
+        .get(properties.tag()?)
+        .filter(|value| !value.is_null())
+}
+
+fn check_repeating(properties: &SdtProperties) -> Result<()> {
+    match properties.repeating_section {
+        Some(_) => Ok(()),
+        None => Err(OfficeError::InvalidFormat(format!(
+            "content control '{}' is not a repeating section",
+            properties.tag().unwrap_or_default()
+        ))),
+    }
+}
+
+/// 按内容控件的类型设置值，返回应显示的文本，并清除占位符状态
+///
+/// # 参数
+/// * `properties` - 内容控件属性
+/// * `value` - 值
+/// * `strict` - 为 `false` 时不在列表中的值和无法解析的日期按原样显示，而不是返回错误
+fn set_value(properties: &mut SdtProperties, value: &Value, strict: bool) -> Result<String> {
+    let tag = properties.tag().unwrap_or_default().to_string();
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        _ => String::new(),
+    };
+
+    let display = match properties.sdt_type() {
+        SdtType::Checkbox => {
+            let checked = match value {
+                Value::Bool(flag) => *flag,
+                Value::Number(number) => number.as_f64().is_some_and(|n| n != 0.0),
+                _ => matches!(
+                    text.trim().to_lowercase().as_str(),
+                    "1" | "true" | "yes" | "on" | "x" | "\u{2612}"
+                ),
+            };
+            let checkbox = properties.checkbox.get_or_insert_with(Default::default);
+            let state = checkbox.checked.get_or_insert_with(Default::default);
+            state.val = if checked { "1" } else { "0" }.to_string();
+            checkbox.symbol().to_string()
+        }
+        SdtType::DropDownList | SdtType::ComboBox => {
+            let combo = properties.combo_box.is_some();
+            let list = properties
+                .drop_down_list
+                .as_mut()
+                .or(properties.combo_box.as_mut())
+                .expect("list control");
+            let selected = list
+                .items
+                .iter()
+                .find(|item| item.value == text)
+                .or_else(|| list.items.iter().find(|item| item.text() == text))
+                .cloned();
+            match selected {
+                Some(item) => {
+                    list.last_value = Some(item.value.clone());
+                    item.text().to_string()
+                }
+                None if combo || !strict => {
+                    list.last_value = Some(text.clone());
+                    text
+                }
+                None => {
+                    return Err(OfficeError::InvalidFormat(format!(
+                        "'{text}' is not an item of drop-down list '{tag}'"
+                    )))
+                }
+            }
+        }
+        SdtType::Date => {
+            let settings = properties.date.get_or_insert_with(Default::default);
+            match FieldDateTime::parse(&text) {
+                Some(date) => {
+                    settings.full_date = Some(format!(
+                        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+                        date.year, date.month, date.day, date.hour, date.minute, date.second
+                    ));
+                    let format = settings
+                        .format
+                        .as_ref()
+                        .map_or(DEFAULT_DATE_FORMAT, |f| f.val.as_str());
+                    date.format(format)
+                }
+                None if !strict => {
+                    settings.full_date = None;
+                    text
+                }
+                None => {
+                    return Err(OfficeError::InvalidFormat(format!(
+                        "invalid date '{text}' for content control '{tag}'"
+                    )))
+                }
+            }
+        }
+        SdtType::Picture => {
+            return Err(OfficeError::InvalidFormat(format!(
+                "content control '{tag}' holds a picture, use set_content_control_image"
+            )))
+        }
+        _ => text,
+    };
+    properties.showing_placeholder = None;
+    Ok(display)
+}
+
+/// 沿用内容中第一个运行的格式创建文本运行；内容为占位符时使用内容控件的运行属性
+fn text_run(
+    properties: &SdtProperties,
+    content: &[ParagraphContent],
+    placeholder: bool,
+    text: &str,
+) -> Run {
+    let template = visible_runs(content).next().filter(|_| !placeholder);
+    Run {
+        properties: match template {
+            Some(run) => run.properties.clone(),
+            None => properties.run_properties.clone(),
+        },
+        content: text_content(text),
+    }
+}
+
+/// 用值替换运行级内容控件的内容
+fn fill_inline(sdt: &mut RunSdt, value: &Value, strict: bool) -> Result<()> {
+    let placeholder = sdt.properties.showing_placeholder.is_some();
+    let text = set_value(&mut sdt.properties, value, strict)?;
+    let run = text_run(&sdt.properties, &sdt.content, placeholder, &text);
+    sdt.content = vec![ParagraphContent::Run(run)];
+    Ok(())
+}
+
+/// 用值填充行级或单元格级内容控件，文本放入其中的第一个单元格
+fn fill_group<T: Cells>(sdt: &mut TableSdt<T>, value: &Value, strict: bool) -> Result<()> {
+    let TableSdt {
+        properties,
+        content,
+    } = sdt;
+    match content
+        .iter_mut()
+        .flat_map(TableItem::items_mut)
+        .find_map(Cells::first_cell)
+    {
+        Some(first) => fill_blocks(properties, &mut first.content, value, strict)?,
+        None => {
+            set_value(properties, value, strict)?;
+        }
+    }
+    Ok(())
+}
+
+/// 用值替换块级内容：每行文本一个段落，沿用第一个段落及其第一个运行的格式
+fn fill_blocks(
+    properties: &mut SdtProperties,
+    content: &mut Vec<BodyContent>,
+    value: &Value,
+    strict: bool,
+) -> Result<()> {
+    let placeholder = properties.showing_placeholder.is_some();
+    let text = set_value(properties, value, strict)?;
+    let template = content.iter().find_map(|item| match item {
+        BodyContent::Paragraph(paragraph) => Some(paragraph),
+        _ => None,
+    });
+    let paragraph_properties = template.and_then(|p| p.properties.clone());
+    let runs = template.map_or(&[][..], |p| p.content.as_slice());
+    let paragraphs = text
+        .split('\n')
+        .map(|line| Paragraph {
+            properties: paragraph_properties.clone(),
+            content: vec![ParagraphContent::Run(text_run(
+                properties,
+                runs,
+                placeholder,
+                line,
+            ))],
+        })
+        .map(BodyContent::Paragraph)
+        .collect();
+    *content = paragraphs;
+    Ok(())
+}
+
+/// 在图片内容控件中放入图片
+struct ImagePlacer<'a> {
+    tag: &'a str,
+    drawing: Drawing,
+    placed: usize,
+}
+
+impl ImagePlacer<'_> {
+    fn blocks(&mut self, content: &mut [BodyContent]) {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(paragraph) => self.inline(&mut paragraph.content),
+                BodyContent::Table(table) => self.rows(&mut table.content),
+                BodyContent::Sdt(sdt) if sdt.properties.tag() == Some(self.tag) => {
+                    self.place_blocks(&mut sdt.content);
+                }
+                BodyContent::Sdt(sdt) => self.blocks(&mut sdt.content),
+            }
+        }
+    }
+
+    fn inline(&mut self, content: &mut [ParagraphContent]) {
+        for item in content {
+            let ParagraphContent::Sdt(sdt) = item else {
+                continue;
+            };
+            if sdt.properties.tag() == Some(self.tag) {
+                sdt.properties.showing_placeholder = None;
+                self.place(&mut sdt.content);
+            } else {
+                self.inline(&mut sdt.content);
+            }
+        }
+    }
+
+    fn rows(&mut self, content: &mut [TableContent]) {
+        for item in content {
+            match item {
+                TableItem::Item(row) => self.cells(&mut row.content),
+                TableItem::Sdt(sdt) if sdt.properties.tag() == Some(self.tag) => {
+                    for cell in sdt.items_mut().flat_map(TableRow::cells_mut) {
+                        self.place_blocks(&mut cell.content);
+                    }
+                }
+                TableItem::Sdt(sdt) => self.rows(&mut sdt.content),
+            }
+        }
+    }
+
+    fn cells(&mut self, content: &mut [RowContent]) {
+        for item in content {
+            match item {
+                TableItem::Item(cell) => self.blocks(&mut cell.content),
+                TableItem::Sdt(sdt) if sdt.properties.tag() == Some(self.tag) => {
+                    for cell in sdt.items_mut() {
+                        self.place_blocks(&mut cell.content);
+                    }
+                }
+                TableItem::Sdt(sdt) => self.cells(&mut sdt.content),
+            }
+        }
+    }
+
+    /// 在块级内容的第一个段落中放入图片，没有段落时添加一个
+    fn place_blocks(&mut self, content: &mut Vec<BodyContent>) {
+        let paragraph = content.iter_mut().find_map(|item| match item {
+            BodyContent::Paragraph(paragraph) => Some(paragraph),
+            _ => None,
+        });
+        match paragraph {
+            Some(paragraph) => self.place(&mut paragraph.content),
+            None => {
+                let mut paragraph = Paragraph::default();
+                self.place(&mut paragraph.content);
+                content.push(BodyContent::Paragraph(paragraph));
+            }
+        }
+    }
+
+    /// 替换内容中的第一个图片，没有图片时用只含图片的运行替换内容
+    fn place(&mut self, content: &mut Vec<ParagraphContent>) {
+        let existing = content
+            .iter_mut()
+            .filter_map(|item| match item {
+                ParagraphContent::Run(run) => Some(run),
+                _ => None,
+            })
+            .flat_map(|run| run.content.iter_mut())
+            .find_map(|item| match item {
+                RunContent::Drawing(drawing) => Some(drawing),
+                _ => None,
+            });
+        match existing {
+            Some(drawing) => {
+                let (width, height) = fit(&self.drawing, drawing.width, drawing.height);
+                drawing.width = width;
+                drawing.height = height;
+                drawing.embed = self.drawing.embed.clone();
+                drawing.description = self.drawing.description.clone();
+            }
+            None => {
+                let mut drawing = self.drawing.clone();
+                drawing.id += self.placed as u32;
+                drawing.name = format!("Picture {}", drawing.id);
+                *content = vec![ParagraphContent::Run(Run {
+                    properties: None,
+                    content: vec![RunContent::Drawing(drawing)],
+                })];
+            }
+        }
+        self.placed += 1;
+    }
+}
+
+/// 把图片等比缩放到指定的框内
+fn fit(image: &Drawing, width: i64, height: i64) -> (i64, i64) {
+    if image.width <= 0 || image.height <= 0 || width <= 0 || height <= 0 {
+        return (width, height);
+    }
+    let scale = (width as f64 / image.width as f64).min(height as f64 / image.height as f64);
+    (
+        (image.width as f64 * scale).round() as i64,
+        (image.height as f64 * scale).round() as i64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::test_utils::{body_xml, docx, p};
+
+    fn cell(text: &str) -> String {
+        format!("<w:tc>{}</w:tc>", p(text))
+    }
+
+    fn row_sdt(tag: &str, rows: &str) -> String {
+        format!(
+            "<w:sdt><w:sdtPr><w:tag w:val=\"{tag}\"/></w:sdtPr>\
+             <w:sdtContent>{rows}</w:sdtContent></w:sdt>"
+        )
+    }
+
+    #[test]
+    fn adjacent_row_controls_with_same_properties_stay_separate() {
+        let row = |text: &str| format!("<w:tr>{}</w:tr>", cell(text));
+        let mut doc = docx(&format!(
+            "<w:tbl>{}{}{}</w:tbl>",
+            row("H"),
+            row_sdt("row", &row("A")),
+            row_sdt("row", &row("B"))
+        ));
+
+        let controls = doc.content_controls();
+        assert_eq!(controls.len(), 2);
+        assert!(matches!(controls[0], ContentControl::Rows(_)));
+        assert_eq!(controls[0].text(), "A");
+        assert_eq!(controls[1].text(), "B");
+        assert_eq!(body_xml(&doc).matches("<w:sdt>").count(), 2);
+
+        assert_eq!(doc.fill_content_control("row", "X").unwrap(), 2);
+        let xml = body_xml(&doc);
+        assert_eq!(xml.matches("<w:sdt>").count(), 2);
+        assert_eq!(xml.matches(">X</w:t>").count(), 2);
+        assert!(xml.contains(">H</w:t>"));
+    }
+
+    #[test]
+    fn cell_controls_are_listed_and_filled() {
+        let mut doc = docx(&format!(
+            "<w:tbl><w:tr>{}<w:sdt><w:sdtPr><w:tag w:val=\"c\"/></w:sdtPr>\
+             <w:sdtContent>{}{}</w:sdtContent></w:sdt></w:tr></w:tbl>",
+            cell("1"),
+            cell("2"),
+            cell("3")
+        ));
+        let control = doc.content_control("c").unwrap();
+        assert!(matches!(control, ContentControl::Cells(_)));
+        assert_eq!(control.text(), "2\t3");
+
+        assert_eq!(doc.fill_content_control("c", "v").unwrap(), 1);
+        let BodyContent::Table(table) = &doc.document.body.content[0] else {
+            panic!("table expected");
+        };
+        let texts: Vec<_> = table.rows().flat_map(TableRow::cells).map(TableCell::text).collect();
+        assert_eq!(texts, ["1", "v", "3"]);
+    }
+
+    #[test]
+    fn repeating_section_rows_are_expanded() {
+        let item = "<w:sdt><w:sdtPr><w:id w:val=\"2\"/><w15:repeatingSectionItem/></w:sdtPr>\
+             <w:sdtContent><w:tr><w:tc><w:p><w:sdt><w:sdtPr><w:id w:val=\"3\"/>\
+             <w:tag w:val=\"name\"/></w:sdtPr><w:sdtContent><w:r><w:t>?</w:t></w:r>\
+             </w:sdtContent></w:sdt></w:p></w:tc></w:tr></w:sdtContent></w:sdt>";
+        let mut doc = docx(&format!(
+            "<w:tbl><w:sdt><w:sdtPr><w:id w:val=\"1\"/><w:tag w:val=\"items\"/>\
+             <w15:repeatingSection/></w:sdtPr><w:sdtContent>{item}</w:sdtContent></w:sdt></w:tbl>"
+        ));
+        let data = serde_json::json!({ "items": [{ "name": "a" }, { "name": "b" }] });
+        doc.fill_content_controls(&data).unwrap();
+
+        let names: Vec<_> = doc
+            .content_controls()
+            .iter()
+            .filter(|c| c.tag() == Some("name"))
+            .map(|c| (c.text(), c.properties().id()))
+            .collect();
+        assert_eq!(names, [("a".to_string(), Some(5)), ("b".to_string(), Some(7))]);
+        let xml = body_xml(&doc);
+        assert_eq!(xml.matches("<w:tr>").count(), 2);
+        assert_eq!(xml.matches("<w15:repeatingSectionItem/>").count(), 2);
+    }
+
+    #[test]
+    fn block_and_inline_controls_are_filled_by_type() {
+        let control = |tag: &str, properties: &str, text: &str| {
+            format!(
+                "<w:sdt><w:sdtPr><w:tag w:val=\"{tag}\"/>{properties}</w:sdtPr>\
+                 <w:sdtContent><w:r><w:t>{text}</w:t></w:r></w:sdtContent></w:sdt>"
+            )
+        };
+        let mut doc = docx(&format!(
+            "<w:sdt><w:sdtPr><w:alias w:val=\"Intro\"/><w:tag w:val=\"intro\"/></w:sdtPr>\
+             <w:sdtContent>{}{}</w:sdtContent></w:sdt>\
+             <w:p><w:r><w:t xml:space=\"preserve\">Dear </w:t></w:r>{}</w:p>\
+             <w:p>{}</w:p><w:p>{}</w:p>",
+            p("one"),
+            p("two"),
+            control("name", "", "?").replace("<w:r>", "<w:r><w:rPr><w:b/></w:rPr>"),
+            control("ok", "<w14:checkbox><w14:checked w14:val=\"0\"/></w14:checkbox>", "☐"),
+            control(
+                "pick",
+                "<w:dropDownList><w:listItem w:displayText=\"Yes\" w:value=\"y\"/>\
+                 </w:dropDownList>",
+                "?"
+            )
+        ));
+
+        let listed: Vec<_> = doc
+            .content_controls()
+            .iter()
+            .map(|c| (c.tag().unwrap().to_string(), c.sdt_type(), c.text()))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                ("intro".to_string(), SdtType::RichText, "one\ntwo".to_string()),
+                ("name".to_string(), SdtType::RichText, "?".to_string()),
+                ("ok".to_string(), SdtType::Checkbox, "☐".to_string()),
+                ("pick".to_string(), SdtType::DropDownList, "?".to_string()),
+            ]
+        );
+        assert_eq!(doc.content_control("intro").unwrap().alias(), Some("Intro"));
+
+        let data = serde_json::json!({ "intro": "a\nb", "name": "Ann", "ok": true, "pick": "y" });
+        assert_eq!(doc.fill_content_controls(&data).unwrap(), 4);
+        let xml = body_xml(&doc);
+        for expected in [
+            format!("<w:sdtContent>{}{}</w:sdtContent>", p("a"), p("b")),
+            "<w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">Ann</w:t></w:r>".to_string(),
+            "<w14:checked w14:val=\"1\"/>".to_string(),
+            "<w:t xml:space=\"preserve\">☒</w:t>".to_string(),
+            "<w:dropDownList w:lastValue=\"y\">".to_string(),
+            "<w:t xml:space=\"preserve\">Yes</w:t>".to_string(),
+        ] {
+            assert!(xml.contains(&expected), "{expected}");
+        }
+    }
+}
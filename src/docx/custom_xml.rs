@@ -0,0 +1,440 @@
+//! 自定义 XML 部件与数据绑定：读写 `customXml/item*.xml` 及其属性部件（数据存储项ID和
+//! 架构引用），并按内容控件的 `w:dataBinding` 在自定义 XML 中求值 XPath，更新内容控件的
+//! 显示内容
+//!
+//! 模板中的内容控件通过数据存储项ID (`w:storeItemID`) 和 XPath (`w:xpath`) 绑定到自定义
+//! XML 中的节点。替换自定义 XML 部件的内容后调用 [`Docx::update_data_bindings`]，即可
+//! 用新的数据填充模板。
+
+use std::io::{Cursor, Read, Seek};
+
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use regex::Regex;
+use sxd_document::Package;
+use sxd_xpath::{Context, Factory, Value};
+
+use crate::common::package::OfficePackage;
+use crate::common::relations::{types, Relationships};
+use crate::error::{OfficeError, Result};
+
+use super::content_controls::fill_bound;
+use super::properties::{DataBinding, SdtProperties};
+use super::Docx;
+
+/// 数据存储项属性部件的命名空间
+const DATASTORE_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/customXml";
+
+/// 自定义 XML 部件
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CustomXmlPart {
+    /// 数据存储项ID（`ds:itemID`），如 `{6C3C8BC8-F283-45AE-878A-BAB7291924A1}`；
+    /// 为 `None` 时不写出属性部件，内容控件也无法绑定到该部件
+    pub item_id: Option<String>,
+    /// 架构引用（`ds:schemaRef` 的 `ds:uri`）
+    pub schema_refs: Vec<String>,
+    /// 部件的 XML 内容
+    pub xml: String,
+}
+
+impl CustomXmlPart {
+    /// 创建自定义 XML 部件
+    ///
+    /// # 参数
+    /// * `item_id` - 数据存储项ID
+    /// * `xml` - XML 内容
+    pub fn new(item_id: impl Into<String>, xml: impl Into<String>) -> Self {
+        CustomXmlPart {
+            item_id: Some(item_id.into()),
+            schema_refs: Vec::new(),
+            xml: xml.into(),
+        }
+    }
+
+    /// 是否具有指定的数据存储项ID，忽略大小写
+    ///
+    /// # 参数
+    /// * `item_id` - 数据存储项ID
+    pub fn has_item_id(&self, item_id: &str) -> bool {
+        self.item_id
+            .as_deref()
+            .is_some_and(|id| id.eq_ignore_ascii_case(item_id))
+    }
+
+    /// 从属性部件 (`customXml/itemProps*.xml`) 中读取数据存储项ID和架构引用
+    ///
+    /// # 参数
+    /// * `xml_content` - 属性部件的 XML 内容
+    pub fn read_properties(&mut self, xml_content: &str) -> Result<()> {
+        let mut reader = Reader::from_str(xml_content);
+        let decoder = reader.decoder();
+        self.schema_refs.clear();
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) | Event::Empty(e) => {
+                    let element = e.local_name();
+                    for attr in e.attributes() {
+                        let attr = attr?;
+                        let value = attr.decode_and_unescape_value(decoder)?.into_owned();
+                        match (element.as_ref(), attr.key.local_name().as_ref()) {
+                            (b"datastoreItem", b"itemID") => self.item_id = Some(value),
+                            (b"schemaRef", b"uri") => self.schema_refs.push(value),
+                            _ => {}
+                        }
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// 生成属性部件的 XML 内容
+    pub fn properties_xml(&self) -> Result<String> {
+        let mut writer = Writer::new(Cursor::new(Vec::new()));
+        writer.write_event(Event::Decl(BytesDecl::new(
+            "1.0",
+            Some("UTF-8"),
+            Some("no"),
+        )))?;
+        let mut root = BytesStart::new("ds:datastoreItem");
+        root.push_attribute(("ds:itemID", self.item_id.as_deref().unwrap_or_default()));
+        root.push_attribute(("xmlns:ds", DATASTORE_NS));
+        writer.write_event(Event::Start(root))?;
+
+        writer.write_event(Event::Start(BytesStart::new("ds:schemaRefs")))?;
+        for uri in &self.schema_refs {
+            let mut element = BytesStart::new("ds:schemaRef");
+            element.push_attribute(("ds:uri", uri.as_str()));
+            writer.write_event(Event::Empty(element))?;
+        }
+        writer.write_event(Event::End(BytesEnd::new("ds:schemaRefs")))?;
+
+        writer.write_event(Event::End(BytesEnd::new("ds:datastoreItem")))?;
+        let result = writer.into_inner().into_inner();
+        Ok(String::from_utf8(result)?)
+    }
+
+    /// 在 XML 内容中求值 XPath，返回第一个匹配节点的文本；没有匹配的节点时返回 `None`
+    ///
+    /// # 参数
+    /// * `xpath` - XPath 表达式，如 `/ns0:invoice[1]/ns0:customer[1]`
+    /// * `prefix_mappings` - 命名空间前缀声明，如 `xmlns:ns0='urn:invoice'`
+    pub fn select(&self, xpath: &str, prefix_mappings: Option<&str>) -> Result<Option<String>> {
+        let package = self.parse()?;
+        select(&package, xpath, prefix_mappings)
+    }
+
+    /// 解析 XML 内容
+    fn parse(&self) -> Result<Package> {
+        let xml = self.xml.trim_start_matches('\u{feff}');
+        sxd_document::parser::parse(xml).map_err(|e| {
+            OfficeError::InvalidFormat(format!(
+                "invalid custom XML part {}: {e}",
+                self.item_id.as_deref().unwrap_or_default()
+            ))
+        })
+    }
+}
+
+impl Docx {
+    /// 查找具有指定数据存储项ID的自定义 XML 部件，忽略大小写
+    ///
+    /// # 参数
+    /// * `item_id` - 数据存储项ID
+    pub fn custom_xml_part(&self, item_id: &str) -> Option<&CustomXmlPart> {
+        self.custom_xml
+            .iter()
+            .find(|part| part.has_item_id(item_id))
+    }
+
+    /// 查找具有指定数据存储项ID的自定义 XML 部件的可变引用，忽略大小写
+    ///
+    /// # 参数
+    /// * `item_id` - 数据存储项ID
+    pub fn custom_xml_part_mut(&mut self, item_id: &str) -> Option<&mut CustomXmlPart> {
+        self.custom_xml
+            .iter_mut()
+            .find(|part| part.has_item_id(item_id))
+    }
+
+    /// 添加自定义 XML 部件，数据存储项ID不能与已有的部件重复
+    ///
+    /// # 参数
+    /// * `part` - 自定义 XML 部件
+    pub fn add_custom_xml_part(&mut self, part: CustomXmlPart) -> Result<()> {
+        if let Some(id) = &part.item_id {
+            if self.custom_xml_part(id).is_some() {
+                return Err(OfficeError::InvalidFormat(format!(
+                    "custom XML part {id} already exists"
+                )));
+            }
+        }
+        self.custom_xml.push(part);
+        Ok(())
+    }
+
+    /// 替换自定义 XML 部件的内容并更新绑定的内容控件，返回更新的数量
+    ///
+    /// XML 内容无法解析或 XPath 无效时返回错误，文档保持不变。
+    ///
+    /// # 参数
+    /// * `item_id` - 数据存储项ID
+    /// * `xml` - 新的 XML 内容
+    pub fn replace_custom_xml(&mut self, item_id: &str, xml: impl Into<String>) -> Result<usize> {
+        let index = self
+            .custom_xml
+            .iter()
+            .position(|part| part.has_item_id(item_id))
+            .ok_or_else(|| {
+                OfficeError::InvalidFormat(format!("custom XML part {item_id} not found"))
+            })?;
+        let previous = std::mem::replace(&mut self.custom_xml[index].xml, xml.into());
+        let result = self.custom_xml[index]
+            .parse()
+            .and_then(|_| self.update_data_bindings());
+        if result.is_err() {
+            self.custom_xml[index].xml = previous;
+        }
+        result
+    }
+
+    /// 按数据绑定 (`w:dataBinding`) 在自定义 XML 部件中求值 XPath，用节点的文本更新正文中
+    /// 绑定的内容控件，返回更新的数量
+    ///
+    /// 绑定的部件或节点不存在时内容控件保持不变；未指定数据存储项ID时使用第一个有匹配
+    /// 节点的部件。值按内容控件的类型显示：复选框按 `true`/`1` 选中，下拉列表显示值对应
+    /// 的列表项，日期按显示格式输出，不在列表中的值和无法解析的日期按原样显示；图片
+    /// 内容控件不更新。失败时文档保持不变。
+    pub fn update_data_bindings(&mut self) -> Result<usize> {
+        let mut packages: Vec<Option<Package>> = self.custom_xml.iter().map(|_| None).collect();
+        let parts = &self.custom_xml;
+        let mut bound = |properties: &SdtProperties| {
+            let Some(binding) = &properties.data_binding else {
+                return Ok(None);
+            };
+            for (index, part) in parts.iter().enumerate() {
+                if let Some(id) = &binding.store_item_id {
+                    if !part.has_item_id(id) {
+                        continue;
+                    }
+                }
+                if packages[index].is_none() {
+                    packages[index] = Some(part.parse()?);
+                }
+                let package = packages[index].as_ref().expect("parsed package");
+                let value = evaluate(package, binding)?;
+                if value.is_some() || binding.store_item_id.is_some() {
+                    return Ok(value);
+                }
+            }
+            Ok(None)
+        };
+
+        let mut content = self.document.body.content.clone();
+        let updated = fill_bound(&mut content, &mut bound)?;
+        self.document.body.content = content;
+        Ok(updated)
+    }
+}
+
+/// 按数据绑定求值 XPath
+fn evaluate(package: &Package, binding: &DataBinding) -> Result<Option<String>> {
+    select(package, &binding.xpath, binding.prefix_mappings.as_deref())
+}
+
+/// 在已解析的 XML 中求值 XPath，返回第一个匹配节点的文本或表达式的字符串值
+fn select(package: &Package, xpath: &str, prefix_mappings: Option<&str>) -> Result<Option<String>> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        OfficeError::InvalidFormat(format!("invalid XPath '{xpath}': {e}"))
+    };
+    let expression = Factory::new()
+        .build(xpath)
+        .map_err(|e| invalid(&e))?
+        .ok_or_else(|| invalid(&"empty expression"))?;
+
+    let mut context = Context::new();
+    if let Some(mappings) = prefix_mappings {
+        let declaration =
+            Regex::new(r#"xmlns:([\w.-]+)\s*=\s*(?:'([^']*)'|"([^"]*)")"#).expect("valid regex");
+        for captures in declaration.captures_iter(mappings) {
+            let uri = captures
+                .get(2)
+                .or(captures.get(3))
+                .map_or("", |m| m.as_str());
+            context.set_namespace(&captures[1], uri);
+        }
+    }
+
+    let document = package.as_document();
+    let value = expression
+        .evaluate(&context, document.root())
+        .map_err(|e| invalid(&e))?;
+    Ok(match value {
+        Value::Nodeset(nodes) => nodes.document_order_first().map(|node| node.string_value()),
+        other => Some(other.string()),
+    })
+}
+
+/// 读取包中的自定义 XML 部件，按编号排序；属性部件通过部件的关系文件查找
+///
+/// # 参数
+/// * `package` - DOCX包
+pub(crate) fn read_custom_xml<R: Read + Seek>(
+    package: &mut OfficePackage<R>,
+) -> Result<Vec<CustomXmlPart>> {
+    let mut items: Vec<(u32, String)> = package
+        .file_names()
+        .into_iter()
+        .filter_map(|name| {
+            let number = name.strip_prefix("customXml/item")?.strip_suffix(".xml")?;
+            Some((number.parse().ok()?, name))
+        })
+        .collect();
+    items.sort();
+
+    let mut parts = Vec::new();
+    for (number, path) in items {
+        let data = package.read_binary_by_path(&path)?;
+        let mut part = CustomXmlPart {
+            xml: String::from_utf8_lossy(&data).into_owned(),
+            ..Default::default()
+        };
+
+        let props_name = package
+            .read_file_by_path(&format!("customXml/_rels/item{number}.xml.rels"))
+            .ok()
+            .and_then(|xml| Relationships::from_xml(&xml).ok())
+            .and_then(|rels| {
+                rels.find_by_type(types::CUSTOM_XML_PROPS)
+                    .map(|rel| rel.target.clone())
+            })
+            .unwrap_or_else(|| format!("itemProps{number}.xml"));
+        if let Ok(props) = package.read_file_by_path(&format!("customXml/{props_name}")) {
+            // 属性部件无法解析时视为没有数据存储项ID
+            if part.read_properties(&props).is_err() {
+                part.item_id = None;
+                part.schema_refs.clear();
+            }
+        }
+        parts.push(part);
+    }
+    Ok(parts)
+}
+
+/// 生成自定义 XML 部件、属性部件和部件关系文件的包内路径与内容，部件按顺序编号
+///
+/// # 参数
+/// * `parts` - 自定义 XML 部件
+pub(crate) fn custom_xml_files(parts: &[CustomXmlPart]) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut files = Vec::new();
+    for (number, part) in (1..).zip(parts) {
+        files.push((
+            format!("customXml/item{number}.xml"),
+            part.xml.clone().into_bytes(),
+        ));
+        if part.item_id.is_none() {
+            continue;
+        }
+        let props_name = format!("itemProps{number}.xml");
+        files.push((
+            format!("customXml/{props_name}"),
+            part.properties_xml()?.into_bytes(),
+        ));
+        let mut rels = Relationships::default();
+        rels.add(types::CUSTOM_XML_PROPS, &props_name, false);
+        files.push((
+            format!("customXml/_rels/item{number}.xml.rels"),
+            rels.to_xml()?.into_bytes(),
+        ));
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::test_utils::{body_xml, docx};
+
+    const ITEM_ID: &str = "{6C3C8BC8-F283-45AE-878A-BAB7291924A1}";
+
+    /// 发票数据，`name` 为客户名称
+    fn invoice(name: &str) -> String {
+        format!("<?xml version=\"1.0\"?><inv xmlns=\"urn:invoice\"><name>{name}</name></inv>")
+    }
+
+    /// 绑定到发票客户名称的内容控件所在的文档
+    fn bound_document() -> Docx {
+        let mut docx = docx(&format!(
+            "<w:p><w:sdt><w:sdtPr><w:tag w:val=\"name\"/>\
+             <w:dataBinding w:prefixMappings=\"xmlns:ns0='urn:invoice'\" \
+             w:xpath=\"/ns0:inv[1]/ns0:name[1]\" w:storeItemID=\"{ITEM_ID}\"/></w:sdtPr>\
+             <w:sdtContent><w:r><w:t>?</w:t></w:r></w:sdtContent></w:sdt></w:p>"
+        ));
+        docx.add_custom_xml_part(CustomXmlPart::new(ITEM_ID, invoice("Ann")))
+            .unwrap();
+        docx
+    }
+
+    #[test]
+    fn properties_round_trip() {
+        let mut part = CustomXmlPart::new(ITEM_ID, invoice("Ann"));
+        part.schema_refs.push("urn:invoice".to_string());
+        let xml = part.properties_xml().unwrap();
+        assert!(xml.contains(&format!("<ds:datastoreItem ds:itemID=\"{ITEM_ID}\"")));
+        assert!(xml.contains("<ds:schemaRef ds:uri=\"urn:invoice\"/>"));
+
+        let mut read = CustomXmlPart {
+            xml: part.xml.clone(),
+            ..Default::default()
+        };
+        read.read_properties(&xml).unwrap();
+        assert_eq!(read, part);
+        assert!(read.has_item_id(&ITEM_ID.to_lowercase()));
+
+        let files: Vec<String> = custom_xml_files(&[part, CustomXmlPart::default()])
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(
+            files,
+            [
+                "customXml/item1.xml",
+                "customXml/itemProps1.xml",
+                "customXml/_rels/item1.xml.rels",
+                "customXml/item2.xml",
+            ]
+        );
+    }
+
+    #[test]
+    fn xpath_selects_namespaced_nodes() {
+        let part = CustomXmlPart::new(ITEM_ID, invoice("Ann"));
+        let mappings = Some("xmlns:ns0='urn:invoice'");
+        let select = |xpath: &str| part.select(xpath, mappings).unwrap();
+        assert_eq!(select("/ns0:inv/ns0:name").as_deref(), Some("Ann"));
+        assert_eq!(select("/ns0:inv/ns0:missing"), None);
+        assert_eq!(select("count(/ns0:inv/*)").as_deref(), Some("1"));
+        assert!(part.select("/ns0:inv[", mappings).is_err());
+    }
+
+    #[test]
+    fn bound_controls_follow_replaced_data() {
+        let mut docx = bound_document();
+        assert!(docx
+            .add_custom_xml_part(CustomXmlPart::new(ITEM_ID.to_lowercase(), invoice("x")))
+            .is_err());
+        assert_eq!(docx.update_data_bindings().unwrap(), 1);
+        assert!(body_xml(&docx).contains("<w:t xml:space=\"preserve\">Ann</w:t>"));
+
+        assert_eq!(docx.replace_custom_xml(ITEM_ID, invoice("Bob")).unwrap(), 1);
+        assert_eq!(docx.content_control("name").unwrap().text(), "Bob");
+
+        assert!(docx.replace_custom_xml(ITEM_ID, "<inv>").is_err());
+        assert_eq!(docx.custom_xml_part(ITEM_ID).unwrap().xml, invoice("Bob"));
+        assert!(docx.replace_custom_xml("{missing}", invoice("x")).is_err());
+    }
+}
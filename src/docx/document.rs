@@ -1,33 +1,74 @@
 use quick_xml::events::Event;
 use quick_xml::Reader;
-use serde::Serialize;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
 
 use crate::common::relations::Relationships;
+use crate::common::xml_element::XmlElement;
 use crate::error::Result;
 
-use super::elements::{BodyContent, Paragraph, Table};
+use super::elements::{BlockSdt, BodyContent, Paragraph, Table};
+use super::properties::SectionProperties;
 
 /// DOCX文档结构体，表示整个文档
-#[derive(Debug, Serialize)]
-#[serde(rename = "w:document")]
+#[derive(Debug, Clone)]
 pub struct Document {
     /// 文档主体内容
-    #[serde(rename = "w:body")]
     pub body: Body,
     /// WordML命名空间
-    #[serde(rename = "@xmlns:w")]
     pub xmlns_w: String,
     /// 关系命名空间
-    #[serde(rename = "@xmlns:r")]
     pub xmlns_r: String,
+    /// 标记兼容性命名空间
+    pub xmlns_mc: String,
+    /// Word 2010 扩展命名空间（复选框内容控件）
+    pub xmlns_w14: String,
+    /// Word 2012 扩展命名空间（重复节内容控件）
+    pub xmlns_w15: String,
+    /// 原文档根元素上的其他命名空间声明，如 `("xmlns:wp14", "...")`；
+    /// 保存时原样写回，使保留下来的未知元素中的前缀仍有定义
+    pub other_namespaces: Vec<(String, String)>,
+    /// 不支持时可以忽略的扩展命名空间前缀
+    pub mc_ignorable: String,
+}
+
+impl Serialize for Document {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        // 根元素上的命名空间声明不固定，以映射的形式写出
+        serializer.serialize_newtype_variant("Document", 0, "w:document", &DocumentContent(self))
+    }
+}
+
+/// 文档根元素的属性和内容
+struct DocumentContent<'a>(&'a Document);
+
+impl Serialize for DocumentContent<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let doc = self.0;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("@xmlns:w", &doc.xmlns_w)?;
+        map.serialize_entry("@xmlns:r", &doc.xmlns_r)?;
+        map.serialize_entry("@xmlns:mc", &doc.xmlns_mc)?;
+        map.serialize_entry("@xmlns:w14", &doc.xmlns_w14)?;
+        map.serialize_entry("@xmlns:w15", &doc.xmlns_w15)?;
+        for (name, uri) in &doc.other_namespaces {
+            map.serialize_entry(&format!("@{name}"), uri)?;
+        }
+        map.serialize_entry("@mc:Ignorable", &doc.mc_ignorable)?;
+        map.serialize_entry("w:body", &doc.body)?;
+        map.end()
+    }
 }
 
 /// 文档主体结构体，包含文档的主要内容
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Body {
     /// 主体内容，可以是段落或表格等
-    #[serde(rename = "value")]
+    #[serde(rename = "$value")]
     pub content: Vec<BodyContent>,
+    /// 最后一节的节属性
+    #[serde(rename = "w:sectPr", skip_serializing_if = "Option::is_none")]
+    pub section_properties: Option<SectionProperties>,
 }
 
 impl Document {
@@ -36,6 +77,11 @@ impl Document {
             body: Body::default(),
             xmlns_w: "http://schemas.openxmlformats.org/wordprocessingml/2006/main".to_string(),
             xmlns_r: "http://schemas.openxmlformats.org/officeDocument/2006/relationships".to_string(),
+            xmlns_mc: "http://schemas.openxmlformats.org/markup-compatibility/2006".to_string(),
+            xmlns_w14: "http://schemas.microsoft.com/office/word/2010/wordml".to_string(),
+            xmlns_w15: "http://schemas.microsoft.com/office/word/2012/wordml".to_string(),
+            other_namespaces: Vec::new(),
+            mc_ignorable: "w14 w15".to_string(),
         }
     }
 }
@@ -46,14 +92,6 @@ impl Default for Document {
     }
 }
 
-impl Default for Body {
-    fn default() -> Self {
-        Body {
-            content: Vec::new(),
-        }
-    }
-}
-
 impl Document {
     /// 从XML内容解析Document
     /// 
@@ -73,6 +111,9 @@ impl Document {
         // 读取XML事件，寻找body标签
         loop {
             match reader.read_event_into(&mut buf)? {
+                Event::Start(e) if e.name().as_ref() == b"w:document" => {
+                    doc.read_root_attributes(&e, reader.decoder())?;
+                }
                 Event::Start(e) if e.name().as_ref() == b"w:body" => {
                     // 解析body内容
                     doc.body = Body::from_body_reader(&mut reader, e.name(), rels)?;
@@ -88,6 +129,44 @@ impl Document {
     }
 }
 
+impl Document {
+    /// 读取根元素上的命名空间声明和可忽略前缀；固定写出的命名空间之外的声明保存在
+    /// `other_namespaces` 中
+    ///
+    /// # 参数
+    /// * `root` - 根元素起始标签
+    /// * `decoder` - 文本解码器
+    fn read_root_attributes(
+        &mut self,
+        root: &quick_xml::events::BytesStart,
+        decoder: quick_xml::encoding::Decoder,
+    ) -> Result<()> {
+        for attr in root.attributes() {
+            let attr = attr?;
+            let value = attr.decode_and_unescape_value(decoder)?.into_owned();
+            match attr.key.as_ref() {
+                b"xmlns:w" | b"xmlns:r" | b"xmlns:mc" | b"xmlns:w14" | b"xmlns:w15" => {}
+                b"mc:Ignorable" => {
+                    // 固定写出的扩展命名空间始终可以忽略
+                    let mut prefixes: Vec<&str> = value.split_whitespace().collect();
+                    for prefix in ["w14", "w15"] {
+                        if !prefixes.contains(&prefix) {
+                            prefixes.push(prefix);
+                        }
+                    }
+                    self.mc_ignorable = prefixes.join(" ");
+                }
+                key if key.starts_with(b"xmlns:") => {
+                    let name = decoder.decode(key)?.into_owned();
+                    self.other_namespaces.push((name, value));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Body {
     /// 从XML读取器中解析body内容
     /// 
@@ -95,7 +174,7 @@ impl Body {
     /// * `reader` - XML读取器
     /// * `tag_name` - 当前标签名称
     /// * `rels` - 文档关系信息
-    fn from_body_reader<R: std::io::BufRead>(
+    pub(super) fn from_body_reader<R: std::io::BufRead>(
         reader: &mut Reader<R>,
         tag_name: quick_xml::name::QName,
         rels: Option<&Relationships>,
@@ -118,6 +197,16 @@ impl Body {
                         let table = Table::from_xml_reader(reader, e.name(), rels)?;
                         body.content.push(BodyContent::Table(table));
                     }
+                    // 块级内容控件标签
+                    b"w:sdt" => {
+                        let sdt = BlockSdt::from_xml_reader(reader, e.name(), rels)?;
+                        body.content.push(BodyContent::Sdt(sdt));
+                    }
+                    // 最后一节的节属性
+                    b"w:sectPr" => {
+                        let sect_pr = XmlElement::from_reader(reader, &e)?;
+                        body.section_properties = Some(SectionProperties::from_element(&sect_pr)?);
+                    }
                     // 其他标签直接跳过
                     _ => {
                         reader.read_to_end_into(e.name(), &mut Vec::new())?;
@@ -128,6 +217,9 @@ impl Body {
                     body.content
                         .push(BodyContent::Paragraph(Paragraph::default()));
                 }
+                Event::Empty(e) if e.name().as_ref() == b"w:sectPr" => {
+                    body.section_properties = Some(SectionProperties::default());
+                }
                 // 结束标签，结束解析
                 Event::End(e) if e.name() == tag_name => break,
                 Event::Eof => break,
@@ -138,4 +230,28 @@ impl Body {
 
         Ok(body)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_namespaces_are_kept() {
+        let xml = "<w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\" \
+            xmlns:mc=\"http://schemas.openxmlformats.org/markup-compatibility/2006\" \
+            xmlns:w16se=\"http://schemas.microsoft.com/office/word/2015/wordml/symex\" \
+            mc:Ignorable=\"w16se\"><w:body><w:p><w:pPr><w16se:foo w16se:val=\"1\"/></w:pPr>\
+            </w:p></w:body></w:document>";
+        let document = Document::from_xml(xml, None).unwrap();
+        assert_eq!(document.mc_ignorable, "w16se w14 w15");
+
+        let saved = quick_xml::se::to_string(&document).unwrap();
+        assert!(saved.contains(
+            "xmlns:w16se=\"http://schemas.microsoft.com/office/word/2015/wordml/symex\""
+        ));
+        assert!(saved.contains("<w16se:foo w16se:val=\"1\"/>"));
+        let reparsed = Document::from_xml(&saved, None).unwrap();
+        assert_eq!(reparsed.other_namespaces, document.other_namespaces);
+    }
+}
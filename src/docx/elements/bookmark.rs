@@ -0,0 +1,78 @@
+use quick_xml::encoding::Decoder;
+use quick_xml::events::BytesStart;
+use serde::Serialize;
+
+use crate::error::Result;
+
+/// 书签起点 (`w:bookmarkStart`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BookmarkStart {
+    /// 书签ID，与对应的书签终点相同，在文档中唯一
+    #[serde(rename = "@w:id")]
+    pub id: i32,
+    /// 书签名称，内部超链接通过名称引用书签
+    #[serde(rename = "@w:name")]
+    pub name: String,
+}
+
+/// 书签终点 (`w:bookmarkEnd`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BookmarkEnd {
+    /// 书签ID
+    #[serde(rename = "@w:id")]
+    pub id: i32,
+}
+
+impl BookmarkStart {
+    /// 创建书签起点
+    ///
+    /// # 参数
+    /// * `id` - 书签ID
+    /// * `name` - 书签名称
+    pub fn new(id: i32, name: impl Into<String>) -> Self {
+        BookmarkStart {
+            id,
+            name: name.into(),
+        }
+    }
+
+    /// 从标签属性中解析书签起点
+    ///
+    /// # 参数
+    /// * `tag` - `w:bookmarkStart` 标签
+    /// * `decoder` - 解码器
+    pub fn from_tag(tag: &BytesStart, decoder: Decoder) -> Result<Self> {
+        let mut bookmark = BookmarkStart::default();
+        for attr in tag.attributes() {
+            let attr = attr?;
+            let value = attr.decode_and_unescape_value(decoder)?;
+            match attr.key.as_ref() {
+                b"w:id" => bookmark.id = value.parse().unwrap_or_default(),
+                b"w:name" => bookmark.name = value.into_owned(),
+                _ => {}
+            }
+        }
+        Ok(bookmark)
+    }
+}
+
+impl BookmarkEnd {
+    /// 从标签属性中解析书签终点
+    ///
+    /// # 参数
+    /// * `tag` - `w:bookmarkEnd` 标签
+    /// * `decoder` - 解码器
+    pub fn from_tag(tag: &BytesStart, decoder: Decoder) -> Result<Self> {
+        let mut bookmark = BookmarkEnd::default();
+        for attr in tag.attributes() {
+            let attr = attr?;
+            if attr.key.as_ref() == b"w:id" {
+                bookmark.id = attr
+                    .decode_and_unescape_value(decoder)?
+                    .parse()
+                    .unwrap_or_default();
+            }
+        }
+        Ok(bookmark)
+    }
+}
@@ -0,0 +1,315 @@
+use serde::{Serialize, Serializer};
+
+use crate::common::xml_element::XmlElement;
+
+/// 每像素对应的 EMU（English Metric Unit），按 96 DPI 计算
+pub const EMU_PER_PIXEL: i64 = 9525;
+/// 每缇对应的 EMU
+pub const EMU_PER_TWIP: i64 = 635;
+
+/// 内联图片 (`w:drawing` / `wp:inline`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Drawing {
+    /// 宽度（EMU）
+    pub width: i64,
+    /// 高度（EMU）
+    pub height: i64,
+    /// 绘图对象ID，在文档中唯一
+    pub id: u32,
+    /// 名称
+    pub name: String,
+    /// 替代文字
+    pub description: Option<String>,
+    /// 图片部件的关系ID
+    pub embed: String,
+}
+
+impl Drawing {
+    /// 从 `w:drawing` 元素解析绘图，只识别内联图片；浮动对象、图表等其他绘图返回 `None`
+    ///
+    /// # 参数
+    /// * `element` - 绘图元素
+    pub fn from_element(element: &XmlElement) -> Option<Self> {
+        let inline = element.child("wp:inline")?;
+        let mut drawing = Drawing::default();
+        let mut pending = vec![inline];
+        while let Some(element) = pending.pop() {
+            let attribute = |name| element.attribute(name).unwrap_or_default();
+            match element.name.as_str() {
+                "wp:extent" => {
+                    drawing.width = attribute("cx").parse().unwrap_or_default();
+                    drawing.height = attribute("cy").parse().unwrap_or_default();
+                }
+                "wp:docPr" => {
+                    drawing.id = attribute("id").parse().unwrap_or_default();
+                    drawing.name = attribute("name").to_string();
+                    drawing.description = element.attribute("descr").map(str::to_string);
+                }
+                "a:blip" => drawing.embed = attribute("r:embed").to_string(),
+                _ => pending.extend(element.children.iter().rev()),
+            }
+        }
+        (!drawing.embed.is_empty()).then_some(drawing)
+    }
+}
+
+const NS_WP: &str = "http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing";
+const NS_A: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+const NS_PIC: &str = "http://schemas.openxmlformats.org/drawingml/2006/picture";
+
+#[derive(Serialize)]
+struct InlineXml<'a> {
+    #[serde(rename = "@xmlns:wp")]
+    xmlns_wp: &'static str,
+    #[serde(rename = "@distT")]
+    dist_t: u32,
+    #[serde(rename = "@distB")]
+    dist_b: u32,
+    #[serde(rename = "@distL")]
+    dist_l: u32,
+    #[serde(rename = "@distR")]
+    dist_r: u32,
+    #[serde(rename = "wp:extent")]
+    extent: SizeXml,
+    #[serde(rename = "wp:docPr")]
+    doc_pr: DocPrXml<'a>,
+    #[serde(rename = "wp:cNvGraphicFramePr")]
+    frame_pr: FramePrXml,
+    #[serde(rename = "a:graphic")]
+    graphic: GraphicXml<'a>,
+}
+
+#[derive(Serialize)]
+struct SizeXml {
+    #[serde(rename = "@cx")]
+    cx: i64,
+    #[serde(rename = "@cy")]
+    cy: i64,
+}
+
+#[derive(Serialize)]
+struct DocPrXml<'a> {
+    #[serde(rename = "@id")]
+    id: u32,
+    #[serde(rename = "@name")]
+    name: &'a str,
+    #[serde(rename = "@descr", skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct FramePrXml {
+    #[serde(rename = "a:graphicFrameLocks")]
+    locks: LocksXml,
+}
+
+#[derive(Serialize)]
+struct LocksXml {
+    #[serde(rename = "@xmlns:a")]
+    xmlns_a: &'static str,
+    #[serde(rename = "@noChangeAspect")]
+    no_change_aspect: u8,
+}
+
+#[derive(Serialize)]
+struct GraphicXml<'a> {
+    #[serde(rename = "@xmlns:a")]
+    xmlns_a: &'static str,
+    #[serde(rename = "a:graphicData")]
+    data: GraphicDataXml<'a>,
+}
+
+#[derive(Serialize)]
+struct GraphicDataXml<'a> {
+    #[serde(rename = "@uri")]
+    uri: &'static str,
+    #[serde(rename = "pic:pic")]
+    picture: PictureXml<'a>,
+}
+
+#[derive(Serialize)]
+struct PictureXml<'a> {
+    #[serde(rename = "@xmlns:pic")]
+    xmlns_pic: &'static str,
+    #[serde(rename = "pic:nvPicPr")]
+    non_visual: NonVisualXml<'a>,
+    #[serde(rename = "pic:blipFill")]
+    blip_fill: BlipFillXml<'a>,
+    #[serde(rename = "pic:spPr")]
+    shape: ShapeXml,
+}
+
+#[derive(Serialize)]
+struct NonVisualXml<'a> {
+    #[serde(rename = "pic:cNvPr")]
+    properties: DocPrXml<'a>,
+    #[serde(rename = "pic:cNvPicPr")]
+    picture_properties: (),
+}
+
+#[derive(Serialize)]
+struct BlipFillXml<'a> {
+    #[serde(rename = "a:blip")]
+    blip: BlipXml<'a>,
+    #[serde(rename = "a:stretch")]
+    stretch: StretchXml,
+}
+
+#[derive(Serialize)]
+struct BlipXml<'a> {
+    #[serde(rename = "@r:embed")]
+    embed: &'a str,
+}
+
+#[derive(Serialize)]
+struct StretchXml {
+    #[serde(rename = "a:fillRect")]
+    fill_rect: (),
+}
+
+#[derive(Serialize)]
+struct ShapeXml {
+    #[serde(rename = "a:xfrm")]
+    transform: TransformXml,
+    #[serde(rename = "a:prstGeom")]
+    geometry: GeometryXml,
+}
+
+#[derive(Serialize)]
+struct TransformXml {
+    #[serde(rename = "a:off")]
+    offset: OffsetXml,
+    #[serde(rename = "a:ext")]
+    extent: SizeXml,
+}
+
+#[derive(Serialize)]
+struct OffsetXml {
+    #[serde(rename = "@x")]
+    x: i64,
+    #[serde(rename = "@y")]
+    y: i64,
+}
+
+#[derive(Serialize)]
+struct GeometryXml {
+    #[serde(rename = "@prst")]
+    preset: &'static str,
+    #[serde(rename = "a:avLst")]
+    adjustments: (),
+}
+
+#[derive(Serialize)]
+struct DrawingXml<'a> {
+    #[serde(rename = "wp:inline")]
+    inline: InlineXml<'a>,
+}
+
+impl Serialize for Drawing {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let doc_pr = || DocPrXml {
+            id: self.id,
+            name: &self.name,
+            description: self.description.as_deref(),
+        };
+        let size = || SizeXml {
+            cx: self.width,
+            cy: self.height,
+        };
+
+        DrawingXml {
+            inline: InlineXml {
+                xmlns_wp: NS_WP,
+                dist_t: 0,
+                dist_b: 0,
+                dist_l: 0,
+                dist_r: 0,
+                extent: size(),
+                doc_pr: doc_pr(),
+                frame_pr: FramePrXml {
+                    locks: LocksXml {
+                        xmlns_a: NS_A,
+                        no_change_aspect: 1,
+                    },
+                },
+                graphic: GraphicXml {
+                    xmlns_a: NS_A,
+                    data: GraphicDataXml {
+                        uri: NS_PIC,
+                        picture: PictureXml {
+                            xmlns_pic: NS_PIC,
+                            non_visual: NonVisualXml {
+                                properties: doc_pr(),
+                                picture_properties: (),
+                            },
+                            blip_fill: BlipFillXml {
+                                blip: BlipXml { embed: &self.embed },
+                                stretch: StretchXml { fill_rect: () },
+                            },
+                            shape: ShapeXml {
+                                transform: TransformXml {
+                                    offset: OffsetXml { x: 0, y: 0 },
+                                    extent: size(),
+                                },
+                                geometry: GeometryXml {
+                                    preset: "rect",
+                                    adjustments: (),
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::docx::elements::{BodyContent, ParagraphContent, RunContent};
+    use crate::docx::test_utils::{body_xml, docx};
+
+    const ANCHOR: &str = "<w:drawing><wp:anchor distT=\"0\" distB=\"0\" distL=\"114300\" \
+        distR=\"114300\" simplePos=\"0\" relativeHeight=\"1\" behindDoc=\"0\" locked=\"0\" \
+        layoutInCell=\"1\" allowOverlap=\"1\"><wp:simplePos x=\"0\" y=\"0\"/>\
+        <wp:positionH relativeFrom=\"column\"><wp:posOffset>1270</wp:posOffset></wp:positionH>\
+        <wp:extent cx=\"5486400\" cy=\"3200400\"/><wp:docPr id=\"3\" name=\"Chart 3\"/>\
+        <a:graphic><a:graphicData uri=\"http://schemas.openxmlformats.org/drawingml/2006/chart\">\
+        <c:chart r:id=\"rId9\"/></a:graphicData></a:graphic></wp:anchor></w:drawing>";
+
+    #[test]
+    fn unrecognised_drawing_is_kept() {
+        let docx = docx(&format!("<w:p><w:r>{ANCHOR}</w:r></w:p>"));
+        let BodyContent::Paragraph(paragraph) = &docx.document.body.content[0] else {
+            panic!("expected a paragraph");
+        };
+        let ParagraphContent::Run(run) = &paragraph.content[0] else {
+            panic!("expected a run");
+        };
+        assert!(matches!(&run.content[0], RunContent::RawDrawing(d) if d.name == "w:drawing"));
+        assert_eq!(body_xml(&docx), format!("<w:p><w:r>{ANCHOR}</w:r></w:p>"));
+    }
+
+    #[test]
+    fn inline_picture_is_recognised() {
+        let docx = docx(
+            "<w:p><w:r><w:drawing><wp:inline><wp:extent cx=\"100\" cy=\"200\"/>\
+             <wp:docPr id=\"1\" name=\"Picture 1\" descr=\"logo\"/><a:graphic><a:graphicData>\
+             <pic:pic><pic:blipFill><a:blip r:embed=\"rId5\"/></pic:blipFill></pic:pic>\
+             </a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>",
+        );
+        let BodyContent::Paragraph(paragraph) = &docx.document.body.content[0] else {
+            panic!("expected a paragraph");
+        };
+        let ParagraphContent::Run(run) = &paragraph.content[0] else {
+            panic!("expected a run");
+        };
+        let RunContent::Drawing(drawing) = &run.content[0] else {
+            panic!("expected a picture");
+        };
+        assert_eq!((drawing.width, drawing.height, drawing.id), (100, 200, 1));
+        assert_eq!(drawing.description.as_deref(), Some("logo"));
+        assert_eq!(drawing.embed, "rId5");
+    }
+}
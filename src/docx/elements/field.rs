@@ -0,0 +1,161 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{OfficeError, Result};
+
+use super::run::Run;
+
+/// 复杂域字符的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FieldCharType {
+    /// 域开始，其后为域代码
+    #[default]
+    Begin,
+    /// 域代码与域结果的分隔
+    Separate,
+    /// 域结束
+    End,
+}
+
+/// 复杂域字符 (`w:fldChar`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FieldChar {
+    /// 域字符类型
+    #[serde(rename = "@w:fldCharType")]
+    pub char_type: FieldCharType,
+    /// 域结果已过期，打开文档时需要更新；仅用于域开始字符
+    #[serde(rename = "@w:dirty", skip_serializing_if = "Option::is_none")]
+    pub dirty: Option<bool>,
+    /// 域已锁定，不应更新；仅用于域开始字符
+    #[serde(rename = "@w:fldLock", skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+}
+
+impl FieldChar {
+    /// 创建域字符
+    ///
+    /// # 参数
+    /// * `char_type` - 域字符类型
+    pub fn new(char_type: FieldCharType) -> Self {
+        FieldChar {
+            char_type,
+            ..Default::default()
+        }
+    }
+
+    /// 从标签属性中解析域字符
+    ///
+    /// # 参数
+    /// * `tag` - `w:fldChar` 标签
+    pub fn from_tag(tag: &BytesStart) -> Result<Self> {
+        let mut field_char = FieldChar::default();
+        for attr in tag.attributes() {
+            let attr = attr?;
+            let value = attr.value.as_ref();
+            match attr.key.as_ref() {
+                b"w:fldCharType" => {
+                    field_char.char_type = match value {
+                        b"separate" => FieldCharType::Separate,
+                        b"end" => FieldCharType::End,
+                        _ => FieldCharType::Begin,
+                    }
+                }
+                b"w:dirty" => field_char.dirty = Some(on_off(value)),
+                b"w:fldLock" => field_char.locked = Some(on_off(value)),
+                _ => {}
+            }
+        }
+        Ok(field_char)
+    }
+}
+
+/// 简单域 (`w:fldSimple`)，域代码保存在属性中，内容为域结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SimpleField {
+    /// 域代码
+    #[serde(rename = "@w:instr")]
+    pub instruction: String,
+    /// 域结果已过期，打开文档时需要更新
+    #[serde(rename = "@w:dirty", skip_serializing_if = "Option::is_none")]
+    pub dirty: Option<bool>,
+    /// 域已锁定，不应更新
+    #[serde(rename = "@w:fldLock", skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+    /// 域结果的文本运行
+    #[serde(rename = "$value")]
+    pub runs: Vec<Run>,
+}
+
+impl SimpleField {
+    /// 创建简单域
+    ///
+    /// # 参数
+    /// * `instruction` - 域代码
+    /// * `runs` - 域结果的文本运行
+    pub fn new(instruction: impl Into<String>, runs: Vec<Run>) -> Self {
+        SimpleField {
+            instruction: instruction.into(),
+            runs,
+            ..Default::default()
+        }
+    }
+
+    /// 从XML读取器中解析简单域
+    ///
+    /// # 参数
+    /// * `reader` - XML读取器
+    /// * `start_tag` - 起始标签
+    /// * `empty` - 是否为空标签，空标签没有域结果
+    pub fn from_xml_reader<R: std::io::BufRead>(
+        reader: &mut Reader<R>,
+        start_tag: &BytesStart,
+        empty: bool,
+    ) -> Result<Self> {
+        let mut field = SimpleField::default();
+        let decoder = reader.decoder();
+        for attr in start_tag.attributes() {
+            let attr = attr?;
+            match attr.key.as_ref() {
+                b"w:instr" => {
+                    field.instruction = attr.decode_and_unescape_value(decoder)?.into_owned()
+                }
+                b"w:dirty" => field.dirty = Some(on_off(&attr.value)),
+                b"w:fldLock" => field.locked = Some(on_off(&attr.value)),
+                _ => {}
+            }
+        }
+        if empty {
+            return Ok(field);
+        }
+
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(e) if e.name().as_ref() == b"w:r" => {
+                    field.runs.push(Run::from_xml_reader(reader, e.name())?);
+                }
+                Event::Start(e) => {
+                    reader.read_to_end_into(e.name(), &mut Vec::new())?;
+                }
+                // 处理简单域结束标签
+                Event::End(e) if e.name() == start_tag.name() => break,
+                // 处理意外的文件结束
+                Event::Eof => {
+                    return Err(OfficeError::InvalidFormat(
+                        "Unexpected EOF in simple field".to_string(),
+                    ))
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+        Ok(field)
+    }
+}
+
+/// 解析开关型属性值，缺省值和 `true`/`1`/`on` 为真
+pub(crate) fn on_off(value: &[u8]) -> bool {
+    !matches!(value, b"false" | b"0" | b"off")
+}
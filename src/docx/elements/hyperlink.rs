@@ -7,18 +7,94 @@ use crate::error::{OfficeError, Result};
 
 use super::run::Run;
 
+/// 超链接的目标
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HyperlinkTarget {
+    /// 外部地址；同时指定了书签时，书签以 `#` 附加在地址之后
+    Url(String),
+    /// 文档内的书签名称
+    Bookmark(String),
+}
+
 /// 超链接结构体，表示文档中的超链接元素
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Hyperlink {
-    /// 关系ID，用于查找超链接的目标URL
-    #[serde(rename = "@r:id")]
+    /// 关系ID，用于查找超链接的目标URL；内部链接为空
+    #[serde(rename = "@r:id", skip_serializing_if = "String::is_empty")]
     pub r_id: String,
+    /// 目标书签名称
+    #[serde(rename = "@w:anchor", skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+    /// 鼠标悬停时显示的提示文字
+    #[serde(rename = "@w:tooltip", skip_serializing_if = "Option::is_none")]
+    pub tooltip: Option<String>,
+    /// 是否将目标加入已访问链接历史
+    #[serde(rename = "@w:history", skip_serializing_if = "Option::is_none")]
+    pub history: Option<bool>,
     /// 超链接中的文本运行列表
     #[serde(rename = "$value")]
     pub runs: Vec<Run>,
 }
 
 impl Hyperlink {
+    /// 创建指向外部地址的超链接，地址须已登记为外部关系
+    ///
+    /// # 参数
+    /// * `r_id` - 关系ID
+    /// * `runs` - 显示的文本运行
+    pub fn external(r_id: impl Into<String>, runs: Vec<Run>) -> Self {
+        Hyperlink {
+            r_id: r_id.into(),
+            history: Some(true),
+            runs,
+            ..Default::default()
+        }
+    }
+
+    /// 创建指向文档内书签的超链接
+    ///
+    /// # 参数
+    /// * `anchor` - 书签名称
+    /// * `runs` - 显示的文本运行
+    pub fn internal(anchor: impl Into<String>, runs: Vec<Run>) -> Self {
+        Hyperlink {
+            anchor: Some(anchor.into()),
+            history: Some(true),
+            runs,
+            ..Default::default()
+        }
+    }
+
+    /// 设置提示文字
+    ///
+    /// # 参数
+    /// * `tooltip` - 提示文字
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// 解析超链接的目标；关系ID无法解析且没有书签时返回 `None`
+    ///
+    /// # 参数
+    /// * `rels` - 文档关系信息
+    pub fn target(&self, rels: Option<&Relationships>) -> Option<HyperlinkTarget> {
+        let url = rels
+            .filter(|_| !self.r_id.is_empty())
+            .and_then(|rels| rels.get_target(&self.r_id));
+        match (url, &self.anchor) {
+            (Some(url), Some(anchor)) => Some(HyperlinkTarget::Url(format!("{url}#{anchor}"))),
+            (Some(url), None) => Some(HyperlinkTarget::Url(url.clone())),
+            (None, Some(anchor)) => Some(HyperlinkTarget::Bookmark(anchor.clone())),
+            (None, None) => None,
+        }
+    }
+
+    /// 获取超链接的显示文本
+    pub fn text(&self) -> String {
+        self.runs.iter().map(|run| run.text()).collect()
+    }
+
     /// 从XML读取器中解析超链接
     /// 
     /// # 参数
@@ -35,16 +111,18 @@ impl Hyperlink {
 
         // 获取解码器，用于解码属性值
         let decoder = reader.decoder();
-        // 如果有关系信息，则解析关系ID
-        if rels.is_some() {
-            for attr in start_tag.attributes() {
-                let attr = attr?;
-                // 查找关系ID属性
-                if attr.key.as_ref() == b"r:id" {
-                    let r_id = attr.decode_and_unescape_value(decoder)?.to_string();
-                    hyperlink.r_id = r_id;
-                    break;
+        for attr in start_tag.attributes() {
+            let attr = attr?;
+            let value = attr.decode_and_unescape_value(decoder)?;
+            match attr.key.as_ref() {
+                // 关系ID仅在有关系信息时保留
+                b"r:id" if rels.is_some() => hyperlink.r_id = value.into_owned(),
+                b"w:anchor" => hyperlink.anchor = Some(value.into_owned()),
+                b"w:tooltip" => hyperlink.tooltip = Some(value.into_owned()),
+                b"w:history" => {
+                    hyperlink.history = Some(!matches!(value.as_ref(), "0" | "false" | "off"))
                 }
+                _ => {}
             }
         }
 
@@ -1,22 +1,38 @@
 use serde::Serialize;
 
+/// 书签模块
+pub mod bookmark;
+/// 绘图模块
+pub mod drawing;
+/// 域模块
+pub mod field;
 /// 超链接模块
 pub mod hyperlink;
 /// 段落模块
 pub mod paragraph;
+/// 修订模块
+pub mod revision;
 /// 文本运行模块
 pub mod run;
+/// 内容控件模块
+pub mod sdt;
 /// 表格模块
 pub mod table;
 
-pub use hyperlink::Hyperlink;
+pub use bookmark::{BookmarkEnd, BookmarkStart};
+pub use drawing::Drawing;
+pub use field::{FieldChar, FieldCharType, SimpleField};
+pub use hyperlink::{Hyperlink, HyperlinkTarget};
 pub use paragraph::{Paragraph, ParagraphContent};
-pub use run::{Run, RunContent};
-pub use table::{Table, TableCell, TableRow};
+pub use revision::{RevisionKind, RunRevision};
+pub use run::{NoteReference, Run, RunContent};
+pub use sdt::{BlockSdt, RunSdt, TableItem, TableSdt};
+pub use table::{RowContent, Table, TableCell, TableContent, TableRow};
 
 /// 文档主体内容枚举，表示文档主体中可能包含的元素类型
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "kebab-case")]
+#[allow(clippy::large_enum_variant)]
 pub enum BodyContent {
     /// 段落
     #[serde(rename = "w:p")]
@@ -24,6 +40,9 @@ pub enum BodyContent {
     /// 表格
     #[serde(rename = "w:tbl")]
     Table(Table),
+    /// 块级内容控件
+    #[serde(rename = "w:sdt")]
+    Sdt(BlockSdt),
 }
 
 impl From<Paragraph> for BodyContent {
@@ -36,4 +55,9 @@ impl From<Table> for BodyContent {
     fn from(t: Table) -> Self {
         BodyContent::Table(t)
     }
-}
\ No newline at end of file
+}
+impl From<BlockSdt> for BodyContent {
+    fn from(sdt: BlockSdt) -> Self {
+        BodyContent::Sdt(sdt)
+    }
+}
@@ -1,20 +1,24 @@
-use quick_xml::de::from_str;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 use serde::Serialize;
 
 use crate::common::relations::Relationships;
-use crate::common::xml_utils::read_element_xml;
-use crate::docx::properties::ParagraphProperties;
+use crate::common::xml_element::XmlElement;
+use crate::docx::properties::{NumPr, ParagraphProperties};
 use crate::docx::styles::{Style, Styles};
 use crate::error::{OfficeError, Result};
 
+use super::bookmark::{BookmarkEnd, BookmarkStart};
+use super::field::SimpleField;
 use super::hyperlink::Hyperlink;
+use super::revision::RunRevision;
 use super::run::Run;
+use super::sdt::RunSdt;
 
 /// 段落内容枚举，表示段落中可能包含的内容类型
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "kebab-case")]
+#[allow(clippy::large_enum_variant)]
 pub enum ParagraphContent {
     /// 文本运行
     #[serde(rename = "w:r")]
@@ -22,6 +26,30 @@ pub enum ParagraphContent {
     /// 超链接
     #[serde(rename = "w:hyperlink")]
     Hyperlink(Hyperlink),
+    /// 修订：插入的文本运行
+    #[serde(rename = "w:ins")]
+    Insertion(RunRevision),
+    /// 修订：删除的文本运行
+    #[serde(rename = "w:del")]
+    Deletion(RunRevision),
+    /// 修订：移动源处的文本运行
+    #[serde(rename = "w:moveFrom")]
+    MoveFrom(RunRevision),
+    /// 修订：移动目标处的文本运行
+    #[serde(rename = "w:moveTo")]
+    MoveTo(RunRevision),
+    /// 简单域
+    #[serde(rename = "w:fldSimple")]
+    SimpleField(SimpleField),
+    /// 书签起点
+    #[serde(rename = "w:bookmarkStart")]
+    BookmarkStart(BookmarkStart),
+    /// 书签终点
+    #[serde(rename = "w:bookmarkEnd")]
+    BookmarkEnd(BookmarkEnd),
+    /// 运行级内容控件
+    #[serde(rename = "w:sdt")]
+    Sdt(RunSdt),
 }
 
 impl From<Run> for ParagraphContent {
@@ -36,15 +64,21 @@ impl From<Hyperlink> for ParagraphContent {
     }
 }
 
+impl From<RunSdt> for ParagraphContent {
+    fn from(sdt: RunSdt) -> Self {
+        ParagraphContent::Sdt(sdt)
+    }
+}
+
 /// 段落结构体，表示文档中的段落元素
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename = "w:p")]
 pub struct Paragraph {
     /// 段落属性
     #[serde(rename = "w:pPr", skip_serializing_if = "Option::is_none")]
     pub properties: Option<ParagraphProperties>,
     /// 段落内容列表
-    #[serde(rename = "$content")]
+    #[serde(rename = "$value")]
     pub content: Vec<ParagraphContent>,
 }
 
@@ -60,6 +94,53 @@ impl Paragraph {
             .and_then(|s| styles.find_style(&s.val))
     }
 
+    /// 将段落加入列表的指定级别
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    /// * `level` - 级别
+    pub fn set_list(&mut self, num_id: i32, level: i32) {
+        let properties = self.properties.get_or_insert_with(Default::default);
+        properties.num_pr = Some(NumPr::new(num_id, level));
+    }
+
+    /// 用书签包围段落的全部内容
+    ///
+    /// # 参数
+    /// * `id` - 书签ID，须在文档中唯一
+    /// * `name` - 书签名称
+    pub fn wrap_bookmark(&mut self, id: i32, name: impl Into<String>) {
+        self.content.insert(
+            0,
+            ParagraphContent::BookmarkStart(BookmarkStart::new(id, name)),
+        );
+        self.content
+            .push(ParagraphContent::BookmarkEnd(BookmarkEnd { id }));
+    }
+
+    /// 获取段落中书签起点的列表，包括内容控件中的书签
+    pub fn bookmarks(&self) -> impl Iterator<Item = &BookmarkStart> {
+        inline_items(&self.content).filter_map(|item| match item {
+            ParagraphContent::BookmarkStart(bookmark) => Some(bookmark),
+            _ => None,
+        })
+    }
+
+    /// 获取段落中的超链接，包括内容控件中的超链接
+    pub fn hyperlinks(&self) -> impl Iterator<Item = &Hyperlink> {
+        inline_items(&self.content).filter_map(|item| match item {
+            ParagraphContent::Hyperlink(hyperlink) => Some(hyperlink),
+            _ => None,
+        })
+    }
+
+    /// 获取段落的可见文本，包含超链接和插入修订中的文本，不包含被删除的文本
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        push_text(&self.content, &mut text);
+        text
+    }
+
     /// 从XML读取器中解析段落
     /// 
     /// # 参数
@@ -73,6 +154,7 @@ impl Paragraph {
     ) -> Result<Self> {
         let mut paragraph = Paragraph::default();
         let mut buf = Vec::new();
+        let decoder = reader.decoder();
 
         // 循环读取段落中的内容
         loop {
@@ -81,8 +163,8 @@ impl Paragraph {
                 Event::Start(e) => match e.name().as_ref() {
                     // 段落属性标签
                     b"w:pPr" => {
-                        let p_pr_xml = read_element_xml(reader, &e)?;
-                        paragraph.properties = from_str(&p_pr_xml).ok();
+                        let p_pr = XmlElement::from_reader(reader, &e)?;
+                        paragraph.properties = Some(ParagraphProperties::from_element(&p_pr)?);
                     }
                     // 文本运行标签
                     b"w:r" => {
@@ -96,11 +178,65 @@ impl Paragraph {
                             .content
                             .push(ParagraphContent::Hyperlink(hyperlink));
                     }
+                    // 修订标签
+                    b"w:ins" => {
+                        let revision = RunRevision::from_xml_reader(reader, &e)?;
+                        paragraph.content.push(ParagraphContent::Insertion(revision));
+                    }
+                    b"w:del" => {
+                        let revision = RunRevision::from_xml_reader(reader, &e)?;
+                        paragraph.content.push(ParagraphContent::Deletion(revision));
+                    }
+                    b"w:moveFrom" => {
+                        let revision = RunRevision::from_xml_reader(reader, &e)?;
+                        paragraph.content.push(ParagraphContent::MoveFrom(revision));
+                    }
+                    b"w:moveTo" => {
+                        let revision = RunRevision::from_xml_reader(reader, &e)?;
+                        paragraph.content.push(ParagraphContent::MoveTo(revision));
+                    }
+                    // 简单域标签
+                    b"w:fldSimple" => {
+                        let field = SimpleField::from_xml_reader(reader, &e, false)?;
+                        paragraph.content.push(ParagraphContent::SimpleField(field));
+                    }
+                    // 运行级内容控件标签
+                    b"w:sdt" => {
+                        let sdt = RunSdt::from_xml_reader(reader, e.name(), rels)?;
+                        paragraph.content.push(ParagraphContent::Sdt(sdt));
+                    }
+                    // 书签标签
+                    b"w:bookmarkStart" => {
+                        let bookmark = BookmarkStart::from_tag(&e, decoder)?;
+                        paragraph.content.push(ParagraphContent::BookmarkStart(bookmark));
+                        reader.read_to_end_into(e.name(), &mut Vec::new())?;
+                    }
+                    b"w:bookmarkEnd" => {
+                        let bookmark = BookmarkEnd::from_tag(&e, decoder)?;
+                        paragraph.content.push(ParagraphContent::BookmarkEnd(bookmark));
+                        reader.read_to_end_into(e.name(), &mut Vec::new())?;
+                    }
                     // 其他标签直接跳过
                     _ => {
                         reader.read_to_end_into(e.name(), &mut Vec::new())?;
                     }
                 },
+                // 处理空的简单域和书签标签
+                Event::Empty(e) => match e.name().as_ref() {
+                    b"w:fldSimple" => {
+                        let field = SimpleField::from_xml_reader(reader, &e, true)?;
+                        paragraph.content.push(ParagraphContent::SimpleField(field));
+                    }
+                    b"w:bookmarkStart" => {
+                        let bookmark = BookmarkStart::from_tag(&e, decoder)?;
+                        paragraph.content.push(ParagraphContent::BookmarkStart(bookmark));
+                    }
+                    b"w:bookmarkEnd" => {
+                        let bookmark = BookmarkEnd::from_tag(&e, decoder)?;
+                        paragraph.content.push(ParagraphContent::BookmarkEnd(bookmark));
+                    }
+                    _ => {}
+                },
                 // 处理段落结束标签
                 Event::End(e) if e.name() == tag_name => break,
                 // 处理意外的文件结束
@@ -115,4 +251,99 @@ impl Paragraph {
         }
         Ok(paragraph)
     }
-}
\ No newline at end of file
+}
+
+/// 按顺序遍历段落内容，运行级内容控件被展开为其中的内容
+fn inline_items(content: &[ParagraphContent]) -> Box<dyn Iterator<Item = &ParagraphContent> + '_> {
+    Box::new(content.iter().flat_map(|item| match item {
+        ParagraphContent::Sdt(sdt) => inline_items(&sdt.content),
+        _ => Box::new(std::iter::once(item)),
+    }))
+}
+
+/// 按顺序遍历构成段落可见文本的运行，与 [`Paragraph::text`] 一致
+///
+/// # 参数
+/// * `content` - 段落内容
+pub(crate) fn visible_runs(content: &[ParagraphContent]) -> Box<dyn Iterator<Item = &Run> + '_> {
+    content_runs(content, false)
+}
+
+/// 按顺序遍历段落内容中的运行，包括超链接、简单域、插入修订和运行级内容控件中的运行
+///
+/// # 参数
+/// * `content` - 段落内容
+/// * `deleted` - 是否包括删除和移出修订中的运行
+pub(crate) fn content_runs(
+    content: &[ParagraphContent],
+    deleted: bool,
+) -> Box<dyn Iterator<Item = &Run> + '_> {
+    Box::new(
+        content
+            .iter()
+            .flat_map(move |item| -> Box<dyn Iterator<Item = &Run>> {
+                match item {
+                    ParagraphContent::Run(run) => Box::new(std::iter::once(run)),
+                    ParagraphContent::Hyperlink(hyperlink) => Box::new(hyperlink.runs.iter()),
+                    ParagraphContent::SimpleField(field) => Box::new(field.runs.iter()),
+                    ParagraphContent::Insertion(revision) | ParagraphContent::MoveTo(revision) => {
+                        Box::new(revision.runs.iter())
+                    }
+                    ParagraphContent::Deletion(revision) | ParagraphContent::MoveFrom(revision)
+                        if deleted =>
+                    {
+                        Box::new(revision.runs.iter())
+                    }
+                    ParagraphContent::Sdt(sdt) => content_runs(&sdt.content, deleted),
+                    ParagraphContent::Deletion(_)
+                    | ParagraphContent::MoveFrom(_)
+                    | ParagraphContent::BookmarkStart(_)
+                    | ParagraphContent::BookmarkEnd(_) => Box::new(std::iter::empty()),
+                }
+            }),
+    )
+}
+
+/// 按顺序可变地遍历段落内容中的运行，范围与 [`content_runs`] 相同
+///
+/// # 参数
+/// * `content` - 段落内容
+/// * `deleted` - 是否包括删除和移出修订中的运行
+pub(crate) fn content_runs_mut(
+    content: &mut [ParagraphContent],
+    deleted: bool,
+) -> Box<dyn Iterator<Item = &mut Run> + '_> {
+    Box::new(
+        content
+            .iter_mut()
+            .flat_map(move |item| -> Box<dyn Iterator<Item = &mut Run>> {
+                match item {
+                    ParagraphContent::Run(run) => Box::new(std::iter::once(run)),
+                    ParagraphContent::Hyperlink(hyperlink) => Box::new(hyperlink.runs.iter_mut()),
+                    ParagraphContent::SimpleField(field) => Box::new(field.runs.iter_mut()),
+                    ParagraphContent::Insertion(revision) | ParagraphContent::MoveTo(revision) => {
+                        Box::new(revision.runs.iter_mut())
+                    }
+                    ParagraphContent::Deletion(revision) | ParagraphContent::MoveFrom(revision)
+                        if deleted =>
+                    {
+                        Box::new(revision.runs.iter_mut())
+                    }
+                    ParagraphContent::Sdt(sdt) => content_runs_mut(&mut sdt.content, deleted),
+                    ParagraphContent::Deletion(_)
+                    | ParagraphContent::MoveFrom(_)
+                    | ParagraphContent::BookmarkStart(_)
+                    | ParagraphContent::BookmarkEnd(_) => Box::new(std::iter::empty()),
+                }
+            }),
+    )
+}
+
+/// 将段落内容的可见文本追加到 `text`，包含内容控件中的文本
+///
+/// # 参数
+/// * `content` - 段落内容
+/// * `text` - 输出的文本
+pub(super) fn push_text(content: &[ParagraphContent], text: &mut String) {
+    visible_runs(content).for_each(|run| text.push_str(&run.text()));
+}
@@ -0,0 +1,95 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::Serialize;
+
+use crate::error::{OfficeError, Result};
+
+use super::run::Run;
+
+/// 修订类型枚举
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionKind {
+    /// 插入 (`w:ins`)
+    Insertion,
+    /// 删除 (`w:del`)
+    Deletion,
+    /// 移动源 (`w:moveFrom`)
+    MoveFrom,
+    /// 移动目标 (`w:moveTo`)
+    MoveTo,
+    /// 运行格式修改 (`w:rPrChange`)
+    RunFormatting,
+    /// 段落格式修改 (`w:pPrChange`)
+    ParagraphFormatting,
+}
+
+/// 运行级修订结构体，表示被插入、删除或移动的一组文本运行
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RunRevision {
+    /// 修订ID
+    #[serde(rename = "@w:id")]
+    pub id: i32,
+    /// 修订作者
+    #[serde(rename = "@w:author")]
+    pub author: String,
+    /// 修订日期 (ISO 8601)
+    #[serde(rename = "@w:date", skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// 修订中的文本运行列表
+    #[serde(rename = "w:r")]
+    pub runs: Vec<Run>,
+}
+
+impl RunRevision {
+    /// 从XML读取器中解析运行级修订
+    ///
+    /// # 参数
+    /// * `reader` - XML读取器
+    /// * `start_tag` - 起始标签
+    pub fn from_xml_reader<R: std::io::BufRead>(
+        reader: &mut Reader<R>,
+        start_tag: &BytesStart,
+    ) -> Result<Self> {
+        let mut revision = RunRevision::default();
+        let mut buf = Vec::new();
+
+        // 解析修订ID、作者和日期
+        let decoder = reader.decoder();
+        for attr in start_tag.attributes() {
+            let attr = attr?;
+            let value = attr.decode_and_unescape_value(decoder)?;
+            match attr.key.as_ref() {
+                b"w:id" => revision.id = value.parse().unwrap_or_default(),
+                b"w:author" => revision.author = value.into_owned(),
+                b"w:date" => revision.date = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+
+        // 循环读取修订中的文本运行
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(e) => match e.name().as_ref() {
+                    // 文本运行标签
+                    b"w:r" => revision.runs.push(Run::from_xml_reader(reader, e.name())?),
+                    // 其他标签直接跳过
+                    _ => {
+                        reader.read_to_end_into(e.name(), &mut Vec::new())?;
+                    }
+                },
+                // 处理修订结束标签
+                Event::End(e) if e.name() == start_tag.name() => break,
+                // 处理意外的文件结束
+                Event::Eof => {
+                    return Err(OfficeError::InvalidFormat(
+                        "Unexpected EOF in revision".to_string(),
+                    ))
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(revision)
+    }
+}
@@ -1,14 +1,17 @@
-use quick_xml::de::from_str;
-use quick_xml::events::Event;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 
-use crate::common::xml_utils::read_element_xml;
+use crate::common::xml_element::XmlElement;
 use crate::docx::properties::RunProperties;
+
+use super::drawing::Drawing;
+use super::field::{on_off, FieldChar};
 use crate::error::{OfficeError, Result};
 
 /// 文本运行结构体，表示文档中具有相同属性的一段文本
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename = "w:r")]
 pub struct Run {
     /// 文本运行属性
@@ -20,20 +23,106 @@ pub struct Run {
 }
 
 /// 文本运行内容枚举，表示文本运行中可能包含的内容类型
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub enum RunContent {
     /// 文本内容
-    #[serde(rename = "w:t")]
+    #[serde(rename = "w:t", serialize_with = "serialize_preserved_text")]
     Text(String),
+    /// 修订中被删除的文本
+    #[serde(rename = "w:delText", serialize_with = "serialize_preserved_text")]
+    DeletedText(String),
     /// 换行符
     #[serde(rename = "w:br")]
     Break,
     /// 制表符
     #[serde(rename = "w:tab")]
     Tab,
+    /// 分页符
+    #[serde(rename = "w:br", serialize_with = "serialize_page_break")]
+    PageBreak,
+    /// 内联图片
+    #[serde(rename = "w:drawing")]
+    Drawing(Drawing),
+    /// 复杂域字符
+    #[serde(rename = "w:fldChar")]
+    FieldChar(FieldChar),
+    /// 复杂域的域代码
+    #[serde(rename = "w:instrText", serialize_with = "serialize_preserved_text")]
+    InstrText(String),
+    /// 修订中被删除的域代码
+    #[serde(rename = "w:delInstrText", serialize_with = "serialize_preserved_text")]
+    DeletedInstrText(String),
+    /// 脚注引用
+    #[serde(rename = "w:footnoteReference")]
+    FootnoteReference(NoteReference),
+    /// 尾注引用
+    #[serde(rename = "w:endnoteReference")]
+    EndnoteReference(NoteReference),
+    /// 无法识别的绘图（浮动图片、图表、形状等），保存时原样写回
+    #[serde(untagged)]
+    RawDrawing(XmlElement),
+}
+
+/// 脚注或尾注引用，通过编号指向脚注或尾注部件中的注释
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NoteReference {
+    /// 注释编号，对应 `footnotes.xml` 或 `endnotes.xml` 中的 `w:id`
+    #[serde(rename = "@w:id")]
+    pub id: i64,
+    /// 引用标记由其后的文本给出，不自动编号
+    #[serde(rename = "@w:customMarkFollows", skip_serializing_if = "Option::is_none")]
+    pub custom_mark_follows: Option<bool>,
+}
+
+impl NoteReference {
+    /// 从标签属性中解析注释引用
+    ///
+    /// # 参数
+    /// * `tag` - `w:footnoteReference` 或 `w:endnoteReference` 标签
+    pub fn from_tag(tag: &BytesStart) -> Result<Self> {
+        let mut reference = NoteReference::default();
+        for attr in tag.attributes() {
+            let attr = attr?;
+            match attr.key.as_ref() {
+                b"w:id" => {
+                    reference.id = std::str::from_utf8(&attr.value)
+                        .ok()
+                        .and_then(|v| v.parse().ok())
+                        .ok_or_else(|| {
+                            OfficeError::InvalidFormat("Invalid note reference id".to_string())
+                        })?
+                }
+                b"w:customMarkFollows" => reference.custom_mark_follows = Some(on_off(&attr.value)),
+                _ => {}
+            }
+        }
+        Ok(reference)
+    }
 }
 
 impl Run {
+    /// 获取文本运行的可见文本，制表符和换行符（包括分页符）分别转换为 `\t` 和 `\n`，
+    /// 被删除的文本、图片和域代码不计入
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for content in &self.content {
+            match content {
+                RunContent::Text(t) => text.push_str(t),
+                RunContent::Break | RunContent::PageBreak => text.push('\n'),
+                RunContent::Tab => text.push('\t'),
+                RunContent::DeletedText(_)
+                | RunContent::Drawing(_)
+                | RunContent::RawDrawing(_)
+                | RunContent::FieldChar(_)
+                | RunContent::InstrText(_)
+                | RunContent::DeletedInstrText(_)
+                | RunContent::FootnoteReference(_)
+                | RunContent::EndnoteReference(_) => {}
+            }
+        }
+        text
+    }
+
     /// 从XML读取器中解析文本运行
     /// 
     /// # 参数
@@ -53,8 +142,8 @@ impl Run {
                 Event::Start(e) => match e.name().as_ref() {
                     // 文本运行属性标签
                     b"w:rPr" => {
-                        let r_pr_xml = read_element_xml(reader, &e)?;
-                        run.properties = from_str(&r_pr_xml).ok();
+                        let r_pr = XmlElement::from_reader(reader, &e)?;
+                        run.properties = Some(RunProperties::from_element(&r_pr)?);
                     }
                     // 文本标签
                     b"w:t" => {
@@ -63,6 +152,49 @@ impl Run {
                             e.name(),
                         )?));
                     }
+                    // 删除文本标签
+                    b"w:delText" => {
+                        run.content.push(RunContent::DeletedText(read_text_node(
+                            reader,
+                            e.name(),
+                        )?));
+                    }
+                    // 域代码标签
+                    b"w:instrText" => {
+                        run.content.push(RunContent::InstrText(read_text_node(
+                            reader,
+                            e.name(),
+                        )?));
+                    }
+                    // 被删除的域代码标签
+                    b"w:delInstrText" => {
+                        run.content.push(RunContent::DeletedInstrText(read_text_node(
+                            reader,
+                            e.name(),
+                        )?));
+                    }
+                    // 域字符标签
+                    b"w:fldChar" => {
+                        run.content.push(RunContent::FieldChar(FieldChar::from_tag(&e)?));
+                        reader.read_to_end_into(e.name(), &mut Vec::new())?;
+                    }
+                    // 脚注和尾注引用标签
+                    b"w:footnoteReference" => {
+                        run.content.push(RunContent::FootnoteReference(NoteReference::from_tag(&e)?));
+                        reader.read_to_end_into(e.name(), &mut Vec::new())?;
+                    }
+                    b"w:endnoteReference" => {
+                        run.content.push(RunContent::EndnoteReference(NoteReference::from_tag(&e)?));
+                        reader.read_to_end_into(e.name(), &mut Vec::new())?;
+                    }
+                    // 绘图标签
+                    b"w:drawing" => {
+                        let element = XmlElement::from_reader(reader, &e)?;
+                        run.content.push(match Drawing::from_element(&element) {
+                            Some(drawing) => RunContent::Drawing(drawing),
+                            None => RunContent::RawDrawing(element),
+                        });
+                    }
                     // 其他标签直接跳过
                     _ => {
                         reader.read_to_end_into(e.name(), &mut Vec::new())?;
@@ -72,10 +204,34 @@ impl Run {
                 Event::Empty(e) => match e.name().as_ref() {
                     // 空文本标签
                     b"w:t" => run.content.push(RunContent::Text(String::new())),
-                    // 换行标签
-                    b"w:br" => run.content.push(RunContent::Break),
+                    b"w:delText" => run.content.push(RunContent::DeletedText(String::new())),
+                    // 换行标签，区分分页符
+                    b"w:br" => {
+                        let page = e
+                            .try_get_attribute("w:type")?
+                            .is_some_and(|a| a.value.as_ref() == b"page");
+                        run.content.push(if page {
+                            RunContent::PageBreak
+                        } else {
+                            RunContent::Break
+                        });
+                    }
                     // 制表符标签
                     b"w:tab" => run.content.push(RunContent::Tab),
+                    // 域字符标签
+                    b"w:fldChar" => {
+                        run.content.push(RunContent::FieldChar(FieldChar::from_tag(&e)?))
+                    }
+                    b"w:instrText" => run.content.push(RunContent::InstrText(String::new())),
+                    b"w:delInstrText" => {
+                        run.content.push(RunContent::DeletedInstrText(String::new()))
+                    }
+                    b"w:footnoteReference" => {
+                        run.content.push(RunContent::FootnoteReference(NoteReference::from_tag(&e)?))
+                    }
+                    b"w:endnoteReference" => {
+                        run.content.push(RunContent::EndnoteReference(NoteReference::from_tag(&e)?))
+                    }
                     _ => {}
                 },
                 // 处理文本运行结束标签
@@ -112,6 +268,14 @@ pub(crate) fn read_text_node<R: std::io::BufRead>(
             Event::Text(t) => {
                 text_val.push_str(t.decode()?.as_ref());
             }
+            // 处理实体引用，如 `&amp;`、`&#x4E2D;`
+            Event::GeneralRef(r) => {
+                if let Some(ch) = r.resolve_char_ref()? {
+                    text_val.push(ch);
+                } else if let Some(entity) = resolve_predefined_entity(&r.decode()?) {
+                    text_val.push_str(entity);
+                }
+            }
             // 处理文本标签结束
             Event::End(end) if end.name() == tag_name => break,
             // 处理意外的文件结束
@@ -125,4 +289,32 @@ pub(crate) fn read_text_node<R: std::io::BufRead>(
         text_buf.clear();
     }
     Ok(text_val)
-}
\ No newline at end of file
+}
+
+/// 以 `xml:space="preserve"` 序列化文本节点，避免首尾空格被丢弃
+fn serialize_preserved_text<S: Serializer>(text: &str, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    #[derive(Clone, Serialize)]
+    struct PreservedText<'a> {
+        #[serde(rename = "@xml:space")]
+        space: &'static str,
+        #[serde(rename = "$text")]
+        text: &'a str,
+    }
+
+    PreservedText {
+        space: "preserve",
+        text,
+    }
+    .serialize(serializer)
+}
+
+/// 以 `w:type="page"` 序列化分页符
+fn serialize_page_break<S: Serializer>(serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct PageBreak {
+        #[serde(rename = "@w:type")]
+        break_type: &'static str,
+    }
+
+    PageBreak { break_type: "page" }.serialize(serializer)
+}
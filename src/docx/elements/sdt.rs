@@ -0,0 +1,352 @@
+use quick_xml::de::from_str;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::QName;
+use quick_xml::Reader;
+use serde::ser::Serializer;
+use serde::Serialize;
+
+use crate::common::relations::Relationships;
+use crate::common::xml_utils::read_element_xml;
+use crate::docx::document::Body;
+use crate::docx::properties::SdtProperties;
+use crate::error::{OfficeError, Result};
+
+use super::paragraph::{push_text, Paragraph, ParagraphContent};
+use super::table::push_lines;
+use super::BodyContent;
+
+/// 块级内容控件，包含段落和表格
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BlockSdt {
+    /// 内容控件属性
+    #[serde(rename = "w:sdtPr")]
+    pub properties: SdtProperties,
+    /// 内容控件中的段落和表格
+    #[serde(rename = "w:sdtContent", serialize_with = "serialize_content")]
+    pub content: Vec<BodyContent>,
+}
+
+/// 运行级内容控件，位于段落中，包含文本运行等段落内容
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RunSdt {
+    /// 内容控件属性
+    #[serde(rename = "w:sdtPr")]
+    pub properties: SdtProperties,
+    /// 内容控件中的段落内容
+    #[serde(rename = "w:sdtContent", serialize_with = "serialize_content")]
+    pub content: Vec<ParagraphContent>,
+}
+
+/// 表格中的行或表格行中的单元格，可以被行级或单元格级内容控件包围
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum TableItem<T> {
+    /// 表格行或单元格
+    Item(T),
+    /// 包围若干表格行或单元格的内容控件
+    Sdt(TableSdt<T>),
+}
+
+/// 行级或单元格级内容控件，包含表格行或单元格
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(bound = "T: TableElement")]
+pub struct TableSdt<T> {
+    /// 内容控件属性
+    #[serde(rename = "w:sdtPr")]
+    pub properties: SdtProperties,
+    /// 内容控件中的表格行或单元格
+    #[serde(rename = "w:sdtContent", serialize_with = "serialize_content")]
+    pub content: Vec<TableItem<T>>,
+}
+
+/// 可以被行级或单元格级内容控件包围的表格元素，即表格行和单元格
+pub trait TableElement: Serialize + Sized {
+    /// 元素标签名称
+    const TAG: &'static str;
+
+    /// 从XML读取器中解析元素
+    ///
+    /// # 参数
+    /// * `reader` - XML读取器
+    /// * `tag_name` - 标签名称
+    /// * `rels` - 文档关系信息
+    fn from_xml_reader<R: std::io::BufRead>(
+        reader: &mut Reader<R>,
+        tag_name: QName,
+        rels: Option<&Relationships>,
+    ) -> Result<Self>;
+}
+
+impl BlockSdt {
+    /// 创建块级内容控件
+    ///
+    /// # 参数
+    /// * `properties` - 内容控件属性
+    /// * `content` - 段落和表格
+    pub fn new(properties: SdtProperties, content: Vec<BodyContent>) -> Self {
+        BlockSdt {
+            properties,
+            content,
+        }
+    }
+
+    /// 获取内容控件的文本，各段落之间以换行符分隔
+    pub fn text(&self) -> String {
+        let mut lines = Vec::new();
+        push_lines(&self.content, &mut lines);
+        lines.join("\n")
+    }
+
+    /// 从XML读取器中解析块级内容控件
+    ///
+    /// # 参数
+    /// * `reader` - XML读取器
+    /// * `tag_name` - 标签名称
+    /// * `rels` - 文档关系信息
+    pub fn from_xml_reader<R: std::io::BufRead>(
+        reader: &mut Reader<R>,
+        tag_name: QName,
+        rels: Option<&Relationships>,
+    ) -> Result<Self> {
+        let mut content = Vec::new();
+        let properties = read_sdt(reader, tag_name, |reader, name, _| {
+            content = Body::from_body_reader(reader, name, rels)?.content;
+            Ok(())
+        })?;
+        Ok(BlockSdt {
+            properties,
+            content,
+        })
+    }
+}
+
+impl RunSdt {
+    /// 创建运行级内容控件
+    ///
+    /// # 参数
+    /// * `properties` - 内容控件属性
+    /// * `content` - 段落内容
+    pub fn new(properties: SdtProperties, content: Vec<ParagraphContent>) -> Self {
+        RunSdt {
+            properties,
+            content,
+        }
+    }
+
+    /// 获取内容控件的可见文本
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        push_text(&self.content, &mut text);
+        text
+    }
+
+    /// 从XML读取器中解析运行级内容控件
+    ///
+    /// # 参数
+    /// * `reader` - XML读取器
+    /// * `tag_name` - 标签名称
+    /// * `rels` - 文档关系信息
+    pub fn from_xml_reader<R: std::io::BufRead>(
+        reader: &mut Reader<R>,
+        tag_name: QName,
+        rels: Option<&Relationships>,
+    ) -> Result<Self> {
+        let mut content = Vec::new();
+        let properties = read_sdt(reader, tag_name, |reader, name, _| {
+            content = Paragraph::from_xml_reader(reader, name, rels)?.content;
+            Ok(())
+        })?;
+        Ok(RunSdt {
+            properties,
+            content,
+        })
+    }
+}
+
+impl<T> TableItem<T> {
+    /// 按文档顺序遍历其中的表格行或单元格，包括嵌套内容控件中的元素
+    pub fn items(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        match self {
+            TableItem::Item(item) => Box::new(std::iter::once(item)),
+            TableItem::Sdt(sdt) => sdt.items(),
+        }
+    }
+
+    /// 按文档顺序可变地遍历其中的表格行或单元格，包括嵌套内容控件中的元素
+    pub fn items_mut(&mut self) -> Box<dyn Iterator<Item = &mut T> + '_> {
+        match self {
+            TableItem::Item(item) => Box::new(std::iter::once(item)),
+            TableItem::Sdt(sdt) => sdt.items_mut(),
+        }
+    }
+}
+
+impl<T> From<T> for TableItem<T> {
+    fn from(item: T) -> Self {
+        TableItem::Item(item)
+    }
+}
+
+impl<T: TableElement> Serialize for TableItem<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            TableItem::Item(item) => serializer.serialize_newtype_variant("TableItem", 0, T::TAG, item),
+            TableItem::Sdt(sdt) => serializer.serialize_newtype_variant("TableItem", 1, "w:sdt", sdt),
+        }
+    }
+}
+
+impl<T> TableSdt<T> {
+    /// 创建行级或单元格级内容控件
+    ///
+    /// # 参数
+    /// * `properties` - 内容控件属性
+    /// * `content` - 表格行或单元格
+    pub fn new(properties: SdtProperties, content: Vec<TableItem<T>>) -> Self {
+        TableSdt {
+            properties,
+            content,
+        }
+    }
+
+    /// 按文档顺序遍历内容控件中的表格行或单元格，包括嵌套内容控件中的元素
+    pub fn items(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.content.iter().flat_map(TableItem::items))
+    }
+
+    /// 按文档顺序可变地遍历内容控件中的表格行或单元格，包括嵌套内容控件中的元素
+    pub fn items_mut(&mut self) -> Box<dyn Iterator<Item = &mut T> + '_> {
+        Box::new(self.content.iter_mut().flat_map(TableItem::items_mut))
+    }
+}
+
+impl<T: TableElement> TableSdt<T> {
+    /// 从XML读取器中解析行级或单元格级内容控件
+    ///
+    /// # 参数
+    /// * `reader` - XML读取器
+    /// * `tag_name` - 标签名称
+    /// * `rels` - 文档关系信息
+    pub fn from_xml_reader<R: std::io::BufRead>(
+        reader: &mut Reader<R>,
+        tag_name: QName,
+        rels: Option<&Relationships>,
+    ) -> Result<Self> {
+        let mut content = Vec::new();
+        let properties = read_sdt(reader, tag_name, |reader, name, _| {
+            let mut buf = Vec::new();
+            loop {
+                match reader.read_event_into(&mut buf)? {
+                    Event::Start(e) => {
+                        if let Some(item) = read_table_item(reader, &e, rels)? {
+                            content.push(item);
+                        }
+                    }
+                    Event::End(e) if e.name() == name => break,
+                    Event::Eof => {
+                        return Err(OfficeError::InvalidFormat(
+                            "Unexpected EOF in content control".to_string(),
+                        ))
+                    }
+                    _ => {}
+                }
+                buf.clear();
+            }
+            Ok(())
+        })?;
+        Ok(TableSdt {
+            properties,
+            content,
+        })
+    }
+}
+
+/// 解析表格或表格行中以 `start` 开始的子元素：表格行或单元格，以及包围它们的内容控件；
+/// 其他元素按表格中的处理方式跳过，返回 `None`
+///
+/// # 参数
+/// * `reader` - XML读取器
+/// * `start` - 子元素的开始标签
+/// * `rels` - 文档关系信息
+pub(crate) fn read_table_item<R, T>(
+    reader: &mut Reader<R>,
+    start: &BytesStart,
+    rels: Option<&Relationships>,
+) -> Result<Option<TableItem<T>>>
+where
+    R: std::io::BufRead,
+    T: TableElement,
+{
+    let name = start.name();
+    if name.as_ref() == T::TAG.as_bytes() {
+        Ok(Some(TableItem::Item(T::from_xml_reader(reader, name, rels)?)))
+    } else if name.as_ref() == b"w:sdt" {
+        Ok(Some(TableItem::Sdt(TableSdt::from_xml_reader(reader, name, rels)?)))
+    } else {
+        reader.read_to_end_into(name, &mut Vec::new())?;
+        Ok(None)
+    }
+}
+
+/// 解析 `w:sdt` 元素：读取属性，并在遇到 `w:sdtContent` 时调用 `content` 解析其中的内容
+///
+/// # 参数
+/// * `reader` - XML读取器
+/// * `tag_name` - 标签名称
+/// * `content` - 解析内容的函数，参数为读取器、`w:sdtContent` 标签名称和已读取的属性
+pub(crate) fn read_sdt<R, F>(
+    reader: &mut Reader<R>,
+    tag_name: QName,
+    mut content: F,
+) -> Result<SdtProperties>
+where
+    R: std::io::BufRead,
+    F: FnMut(&mut Reader<R>, QName, &SdtProperties) -> Result<()>,
+{
+    let mut properties = SdtProperties::default();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => match e.name().as_ref() {
+                // 内容控件属性标签
+                b"w:sdtPr" => {
+                    let sdt_pr_xml = read_element_xml(reader, &e)?;
+                    properties = from_str(&sdt_pr_xml).unwrap_or_default();
+                }
+                // 内容标签
+                b"w:sdtContent" => content(reader, e.name(), &properties)?,
+                // 其他标签（如 w:sdtEndPr）直接跳过
+                _ => {
+                    reader.read_to_end_into(e.name(), &mut Vec::new())?;
+                }
+            },
+            // 处理内容控件结束标签
+            Event::End(e) if e.name() == tag_name => break,
+            // 处理意外的文件结束
+            Event::Eof => {
+                return Err(OfficeError::InvalidFormat(
+                    "Unexpected EOF in content control".to_string(),
+                ))
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(properties)
+}
+
+/// 将内容序列化为 `w:sdtContent` 的子元素
+fn serialize_content<S: Serializer, T: Serialize>(
+    content: &[T],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    SdtContent { items: content }.serialize(serializer)
+}
+
+#[derive(Serialize)]
+struct SdtContent<'a, T> {
+    #[serde(rename = "$value")]
+    items: &'a [T],
+}
+
@@ -1,40 +1,100 @@
+use quick_xml::de::from_str;
 use quick_xml::events::Event;
+use quick_xml::name::QName;
 use quick_xml::Reader;
 use serde::Serialize;
 
 use crate::common::relations::Relationships;
+use crate::common::xml_element::XmlElement;
+use crate::common::xml_utils::read_element_xml;
+use crate::docx::properties::{
+    TableCellProperties, TableGrid, TableProperties, TableRowProperties,
+};
 use crate::error::{OfficeError, Result};
 
-use super::{BodyContent, Paragraph};
+use super::sdt::{read_table_item, TableElement, TableItem};
+use super::{BlockSdt, BodyContent, Paragraph};
 
 /// 表格结构体，表示文档中的表格元素
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename = "w:tbl")]
 pub struct Table {
-    /// 表格行列表
-    #[serde(rename = "w:tr")]
-    pub rows: Vec<TableRow>,
+    /// 表格属性
+    #[serde(rename = "w:tblPr", skip_serializing_if = "Option::is_none")]
+    pub properties: Option<TableProperties>,
+    /// 表格网格（列宽定义）
+    #[serde(rename = "w:tblGrid", skip_serializing_if = "Option::is_none")]
+    pub grid: Option<TableGrid>,
+    /// 表格内容：表格行和行级内容控件
+    #[serde(rename = "$value")]
+    pub content: Vec<TableContent>,
 }
 
 /// 表格行结构体，表示表格中的一行
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename = "w:tr")]
 pub struct TableRow {
-    /// 表格单元格列表
-    #[serde(rename = "w:tc")]
-    pub cells: Vec<TableCell>,
+    /// 行属性
+    #[serde(rename = "w:trPr", skip_serializing_if = "Option::is_none")]
+    pub properties: Option<TableRowProperties>,
+    /// 行内容：单元格和单元格级内容控件
+    #[serde(rename = "$value")]
+    pub content: Vec<RowContent>,
 }
 
 /// 表格单元格结构体，表示表格中的一个单元格
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename = "w:tc")]
 pub struct TableCell {
+    /// 单元格属性
+    #[serde(rename = "w:tcPr", skip_serializing_if = "Option::is_none")]
+    pub properties: Option<TableCellProperties>,
     /// 单元格内容，可以是段落或其他元素
     #[serde(rename = "$value")]
     pub content: Vec<BodyContent>,
 }
 
+/// 表格中的内容：表格行或行级内容控件
+pub type TableContent = TableItem<TableRow>;
+
+/// 表格行中的内容：单元格或单元格级内容控件
+pub type RowContent = TableItem<TableCell>;
+
+impl TableElement for TableRow {
+    const TAG: &'static str = "w:tr";
+
+    fn from_xml_reader<R: std::io::BufRead>(
+        reader: &mut Reader<R>,
+        tag_name: QName,
+        rels: Option<&Relationships>,
+    ) -> Result<Self> {
+        TableRow::from_xml_reader(reader, tag_name, rels)
+    }
+}
+
+impl TableElement for TableCell {
+    const TAG: &'static str = "w:tc";
+
+    fn from_xml_reader<R: std::io::BufRead>(
+        reader: &mut Reader<R>,
+        tag_name: QName,
+        rels: Option<&Relationships>,
+    ) -> Result<Self> {
+        TableCell::from_xml_reader(reader, tag_name, rels)
+    }
+}
+
 impl Table {
+    /// 按文档顺序遍历全部表格行，包括行级内容控件中的行
+    pub fn rows(&self) -> impl Iterator<Item = &TableRow> + '_ {
+        self.content.iter().flat_map(TableItem::items)
+    }
+
+    /// 按文档顺序可变地遍历全部表格行，包括行级内容控件中的行
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut TableRow> + '_ {
+        self.content.iter_mut().flat_map(TableItem::items_mut)
+    }
+
     /// 从XML读取器中解析表格
     /// 
     /// # 参数
@@ -52,11 +112,19 @@ impl Table {
         // 循环读取表格中的行
         loop {
             match reader.read_event_into(&mut buf)? {
-                // 处理行开始标签
-                Event::Start(e) if e.name().as_ref() == b"w:tr" => {
-                    table
-                        .rows
-                        .push(TableRow::from_xml_reader(reader, e.name(), rels)?);
+                // 处理行和行级内容控件开始标签
+                Event::Start(e) if matches!(e.name().as_ref(), b"w:tr" | b"w:sdt") => {
+                    table.content.extend(read_table_item(reader, &e, rels)?);
+                }
+                // 表格属性标签
+                Event::Start(e) if e.name().as_ref() == b"w:tblPr" => {
+                    let tbl_pr = XmlElement::from_reader(reader, &e)?;
+                    table.properties = Some(TableProperties::from_element(&tbl_pr)?);
+                }
+                // 表格网格标签
+                Event::Start(e) if e.name().as_ref() == b"w:tblGrid" => {
+                    let grid_xml = read_element_xml(reader, &e)?;
+                    table.grid = from_str(&grid_xml).ok();
                 }
                 // 处理表格结束标签
                 Event::End(e) if e.name() == tag_name => break,
@@ -75,6 +143,16 @@ impl Table {
 }
 
 impl TableRow {
+    /// 按文档顺序遍历行中的全部单元格，包括单元格级内容控件中的单元格
+    pub fn cells(&self) -> impl Iterator<Item = &TableCell> + '_ {
+        self.content.iter().flat_map(TableItem::items)
+    }
+
+    /// 按文档顺序可变地遍历行中的全部单元格，包括单元格级内容控件中的单元格
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut TableCell> + '_ {
+        self.content.iter_mut().flat_map(TableItem::items_mut)
+    }
+
     /// 从XML读取器中解析表格行
     /// 
     /// # 参数
@@ -92,10 +170,14 @@ impl TableRow {
         // 循环读取行中的单元格
         loop {
             match reader.read_event_into(&mut buf)? {
-                // 处理单元格开始标签
-                Event::Start(e) if e.name().as_ref() == b"w:tc" => {
-                    row.cells
-                        .push(TableCell::from_xml_reader(reader, e.name(), rels)?);
+                // 处理单元格和单元格级内容控件开始标签
+                Event::Start(e) if matches!(e.name().as_ref(), b"w:tc" | b"w:sdt") => {
+                    row.content.extend(read_table_item(reader, &e, rels)?);
+                }
+                // 行属性标签
+                Event::Start(e) if e.name().as_ref() == b"w:trPr" => {
+                    let tr_pr = XmlElement::from_reader(reader, &e)?;
+                    row.properties = Some(TableRowProperties::from_element(&tr_pr)?);
                 }
                 // 处理行结束标签
                 Event::End(e) if e.name() == tag_name => break,
@@ -114,6 +196,24 @@ impl TableRow {
 }
 
 impl TableCell {
+    /// 创建只包含一个空段落的单元格
+    ///
+    /// # 参数
+    /// * `properties` - 单元格属性
+    pub fn empty(properties: Option<TableCellProperties>) -> Self {
+        TableCell {
+            properties,
+            content: vec![BodyContent::Paragraph(Paragraph::default())],
+        }
+    }
+
+    /// 获取单元格的文本，各段落之间以换行符分隔
+    pub fn text(&self) -> String {
+        let mut lines = Vec::new();
+        push_lines(&self.content, &mut lines);
+        lines.join("\n")
+    }
+
     /// 从XML读取器中解析表格单元格
     /// 
     /// # 参数
@@ -133,6 +233,11 @@ impl TableCell {
             match reader.read_event_into(&mut buf)? {
                 // 处理开始标签
                 Event::Start(e) => match e.name().as_ref() {
+                    // 单元格属性标签
+                    b"w:tcPr" => {
+                        let tc_pr = XmlElement::from_reader(reader, &e)?;
+                        cell.properties = Some(TableCellProperties::from_element(&tc_pr)?);
+                    }
                     // 段落标签
                     b"w:p" => {
                         let p = Paragraph::from_xml_reader(reader, e.name(), rels)?;
@@ -143,11 +248,20 @@ impl TableCell {
                         let t = Table::from_xml_reader(reader, e.name(), rels)?;
                         cell.content.push(BodyContent::Table(t));
                     }
+                    // 块级内容控件标签
+                    b"w:sdt" => {
+                        let sdt = BlockSdt::from_xml_reader(reader, e.name(), rels)?;
+                        cell.content.push(BodyContent::Sdt(sdt));
+                    }
                     // 其他标签直接跳过
                     _ => {
                         reader.read_to_end_into(e.name(), &mut Vec::new())?;
                     }
                 },
+                // 空段落标签
+                Event::Empty(e) if e.name().as_ref() == b"w:p" => {
+                    cell.content.push(BodyContent::Paragraph(Paragraph::default()));
+                }
                 // 处理单元格结束标签
                 Event::End(e) if e.name() == tag_name => break,
                 // 处理意外的文件结束
@@ -162,4 +276,72 @@ impl TableCell {
         }
         Ok(cell)
     }
-}
\ No newline at end of file
+}
+
+/// 将段落和表格的文本按行追加到 `lines`，表格的每个单元格占一行
+///
+/// # 参数
+/// * `content` - 段落和表格
+/// * `lines` - 输出的文本行
+pub(super) fn push_lines(content: &[BodyContent], lines: &mut Vec<String>) {
+    for block in content {
+        match block {
+            BodyContent::Paragraph(p) => lines.push(p.text()),
+            BodyContent::Table(t) => {
+                for cell in t.rows().flat_map(TableRow::cells) {
+                    lines.push(cell.text());
+                }
+            }
+            BodyContent::Sdt(sdt) => push_lines(&sdt.content, lines),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::docx::elements::BodyContent;
+    use crate::docx::test_utils::{body_xml, docx};
+
+    const TABLE: &str = "<w:tbl><w:tblPr><w:tblStyle w:val=\"TableGrid\"/>\
+        <w:tblW w:w=\"5000\" w:type=\"pct\"/>\
+        <w:tblBorders><w:top w:val=\"single\" w:sz=\"4\" w:space=\"0\" w:color=\"auto\"/>\
+        </w:tblBorders><w:tblLayout w:type=\"fixed\"/></w:tblPr>\
+        <w:tblGrid><w:gridCol w:w=\"2000\"/><w:gridCol w:w=\"3000\"/></w:tblGrid>\
+        <w:tr><w:trPr><w:tblHeader/></w:trPr>\
+        <w:tc><w:tcPr><w:tcW w:w=\"5000\" w:type=\"dxa\"/><w:gridSpan w:val=\"2\"/></w:tcPr>\
+        <w:p><w:r><w:t xml:space=\"preserve\">head</w:t></w:r></w:p></w:tc></w:tr>\
+        <w:tr><w:tc><w:tcPr><w:vMerge w:val=\"restart\"/></w:tcPr>\
+        <w:p><w:r><w:t xml:space=\"preserve\">a</w:t></w:r></w:p></w:tc>\
+        <w:tc><w:p><w:r><w:t xml:space=\"preserve\">b</w:t></w:r></w:p></w:tc></w:tr>\
+        <w:tr><w:tc><w:tcPr><w:vMerge/></w:tcPr><w:p/></w:tc>\
+        <w:tc><w:p><w:r><w:t xml:space=\"preserve\">c</w:t></w:r></w:p></w:tc></w:tr></w:tbl>";
+
+    #[test]
+    fn table_round_trip() {
+        assert_eq!(body_xml(&docx(TABLE)), TABLE);
+    }
+
+    #[test]
+    fn table_structure_is_parsed() {
+        let docx = docx(TABLE);
+        let BodyContent::Table(table) = &docx.document.body.content[0] else {
+            panic!("expected table");
+        };
+        let widths: Vec<_> = table.grid.iter().flat_map(|g| &g.columns).map(|c| c.width).collect();
+        assert_eq!(widths, [2000, 3000]);
+
+        let rows: Vec<_> = table.rows().collect();
+        assert_eq!(rows.len(), 3);
+        let header = rows[0].cells().next().unwrap().properties.as_ref().unwrap();
+        assert_eq!(header.grid_span.as_ref().map(|s| s.val), Some(2));
+        let merged: Vec<_> = rows[1..]
+            .iter()
+            .map(|row| {
+                let cell = row.cells().next().unwrap();
+                cell.properties.as_ref().and_then(|p| p.v_merge.as_ref()).map(|m| m.is_restart())
+            })
+            .collect();
+        assert_eq!(merged, [Some(true), Some(false)]);
+        assert_eq!(rows[2].cells().nth(1).unwrap().text(), "c");
+    }
+}
@@ -0,0 +1,1235 @@
+//! 域：解析简单域与复杂域、解析域代码，并更新无需排版即可计算的域结果
+//!
+//! 复杂域由 `w:fldChar` 的开始、分隔、结束三个字符和其间的 `w:instrText` 组成，
+//! 可以跨越多个文本运行甚至多个段落，也可以相互嵌套；简单域 `w:fldSimple`
+//! 把域代码保存在属性中。更新时计算 DATE、TIME、REF、SEQ、DOCPROPERTY 以及
+//! TITLE、AUTHOR 等文档信息域；PAGE、NUMPAGES、PAGEREF、TOC 等依赖分页的域
+//! 只标记为需要更新，由 Word 打开文档时刷新。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::elements::paragraph::visible_runs;
+use super::elements::{FieldChar, FieldCharType, Paragraph, ParagraphContent, Run, RunContent};
+use super::list_numbering::ListLabel;
+use super::number_format::format_number;
+use super::numbering::NumberFormat;
+use super::properties::RunProperties;
+use super::Docx;
+
+/// 域代码中的开关，如 `\@ "yyyy-MM-dd"`、`\* MERGEFORMAT`、`\h`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSwitch {
+    /// 开关名称，不含反斜杠，如 `@`、`*`、`h`
+    pub name: String,
+    /// 开关参数
+    pub value: Option<String>,
+}
+
+/// 解析后的域代码
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldInstruction {
+    /// 域类型，统一为大写，如 `DATE`、`REF`、`SEQ`
+    pub field_type: String,
+    /// 位置参数，如书签名称、序列名称
+    pub arguments: Vec<String>,
+    /// 开关列表，按出现顺序排列
+    pub switches: Vec<FieldSwitch>,
+}
+
+impl FieldInstruction {
+    /// 解析域代码
+    ///
+    /// 引号内的 `\"` 和 `\\` 按转义处理；第一个开关之后的普通参数归属于其前面
+    /// 尚无参数的开关。
+    ///
+    /// # 参数
+    /// * `instruction` - 域代码，如 `SEQ Figure \* ARABIC`
+    pub fn parse(instruction: &str) -> Self {
+        let mut parsed = FieldInstruction::default();
+        let mut chars = instruction.chars().peekable();
+        let mut first = true;
+
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+                continue;
+            }
+            let mut token = String::new();
+            let quoted = c == '"';
+            if quoted {
+                chars.next();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if matches!(chars.peek(), Some('"' | '\\')) => {
+                            token.extend(chars.next());
+                        }
+                        _ => token.push(c),
+                    }
+                }
+            } else {
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || (c == '"' && !token.is_empty()) {
+                        break;
+                    }
+                    token.push(c);
+                    chars.next();
+                }
+            }
+
+            if first {
+                parsed.field_type = token.to_uppercase();
+                first = false;
+            } else if !quoted && token.starts_with('\\') && token.len() > 1 {
+                let mut rest = token[1..].chars();
+                let name = rest.next().map(String::from).unwrap_or_default();
+                let value = Some(rest.as_str().to_string()).filter(|v| !v.is_empty());
+                parsed.switches.push(FieldSwitch { name, value });
+            } else if let Some(switch) = parsed.switches.last_mut().filter(|s| s.value.is_none()) {
+                switch.value = Some(token);
+            } else {
+                parsed.arguments.push(token);
+            }
+        }
+        parsed
+    }
+
+    /// 获取指定开关，名称区分大小写
+    ///
+    /// # 参数
+    /// * `name` - 开关名称，不含反斜杠
+    pub fn switch(&self, name: &str) -> Option<&FieldSwitch> {
+        self.switches.iter().find(|s| s.name == name)
+    }
+
+    /// 判断是否包含指定开关
+    ///
+    /// # 参数
+    /// * `name` - 开关名称，不含反斜杠
+    pub fn has_switch(&self, name: &str) -> bool {
+        self.switch(name).is_some()
+    }
+
+    /// 获取指定开关的参数
+    ///
+    /// # 参数
+    /// * `name` - 开关名称，不含反斜杠
+    pub fn switch_value(&self, name: &str) -> Option<&str> {
+        self.switch(name).and_then(|s| s.value.as_deref())
+    }
+
+    /// 获取第一个位置参数
+    pub fn argument(&self) -> Option<&str> {
+        self.arguments.first().map(String::as_str)
+    }
+}
+
+impl fmt::Display for FieldInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_token(f: &mut fmt::Formatter<'_>, token: &str) -> fmt::Result {
+            if token.is_empty() || token.contains(|c: char| c.is_whitespace() || c == '"') {
+                write!(
+                    f,
+                    " \"{}\"",
+                    token.replace('\\', "\\\\").replace('"', "\\\"")
+                )
+            } else {
+                write!(f, " {token}")
+            }
+        }
+
+        f.write_str(&self.field_type)?;
+        for argument in &self.arguments {
+            write_token(f, argument)?;
+        }
+        for switch in &self.switches {
+            write!(f, " \\{}", switch.name)?;
+            if let Some(value) = &switch.value {
+                write_token(f, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 域的存储形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// 简单域 (`w:fldSimple`)
+    Simple,
+    /// 复杂域 (`w:fldChar` / `w:instrText`)
+    Complex,
+}
+
+/// 域字符在文档中的位置：段落序号、段落内容序号、文本运行内容序号
+type Location = (usize, usize, usize);
+
+/// 域在文档中的位置，用于写回更新结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FieldAnchor {
+    Simple {
+        item: usize,
+    },
+    /// 位于超链接或修订中的域字符没有位置，不能更新
+    Complex {
+        begin: Option<Location>,
+        separate: Option<Location>,
+        end: Option<Location>,
+    },
+}
+
+/// 文档中的一个域
+#[derive(Debug, Clone)]
+pub struct Field {
+    /// 存储形式
+    pub kind: FieldKind,
+    /// 域代码；嵌套在域代码中的域以其结果文本代入
+    pub instruction: String,
+    /// 缓存的域结果文本运行，跨段落的结果以换行分隔
+    pub result: Vec<Run>,
+    /// 是否标记为需要更新
+    pub dirty: bool,
+    /// 是否已锁定
+    pub locked: bool,
+    /// 域开始处的段落序号，与 [`Docx::paragraphs`] 的顺序一致
+    pub paragraph: usize,
+    anchor: FieldAnchor,
+}
+
+impl Field {
+    /// 解析域代码
+    pub fn parse(&self) -> FieldInstruction {
+        FieldInstruction::parse(&self.instruction)
+    }
+
+    /// 获取域类型，如 `PAGE`、`REF`
+    pub fn field_type(&self) -> String {
+        self.parse().field_type
+    }
+
+    /// 获取缓存的域结果文本
+    pub fn result_text(&self) -> String {
+        self.result.iter().map(Run::text).collect()
+    }
+}
+
+/// 生成复杂域的文本运行：开始字符、域代码、分隔字符、域结果和结束字符
+///
+/// # 参数
+/// * `instruction` - 域代码
+/// * `result` - 域结果文本，可为空
+pub fn field_runs(instruction: &str, result: &str) -> Vec<Run> {
+    let run = |content: Vec<RunContent>| Run {
+        properties: None,
+        content,
+    };
+    let field_char = |char_type| RunContent::FieldChar(FieldChar::new(char_type));
+    vec![
+        run(vec![field_char(FieldCharType::Begin)]),
+        run(vec![RunContent::InstrText(format!(
+            " {} ",
+            instruction.trim()
+        ))]),
+        run(vec![field_char(FieldCharType::Separate)]),
+        run(text_content(result)),
+        run(vec![field_char(FieldCharType::End)]),
+    ]
+}
+
+/// 将文本转换为文本运行内容，`\n` 和 `\t` 分别转换为换行符和制表符
+pub(super) fn text_content(text: &str) -> Vec<RunContent> {
+    let mut content = Vec::new();
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            content.push(RunContent::Break);
+        }
+        for (j, piece) in line.split('\t').enumerate() {
+            if j > 0 {
+                content.push(RunContent::Tab);
+            }
+            if !piece.is_empty() {
+                content.push(RunContent::Text(piece.to_string()));
+            }
+        }
+    }
+    content
+}
+
+/// 日期时间，用于日期类域的计算和格式化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FieldDateTime {
+    /// 年
+    pub year: i32,
+    /// 月（1–12）
+    pub month: u32,
+    /// 日（1–31）
+    pub day: u32,
+    /// 时（0–23）
+    pub hour: u32,
+    /// 分
+    pub minute: u32,
+    /// 秒
+    pub second: u32,
+}
+
+impl FieldDateTime {
+    /// 当前时间（UTC）
+    pub fn now() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+        let time = seconds.rem_euclid(86_400) as u32;
+        FieldDateTime {
+            year,
+            month,
+            day,
+            hour: time / 3600,
+            minute: time / 60 % 60,
+            second: time % 60,
+        }
+    }
+
+    /// 解析 ISO 8601 日期时间，如 `2024-01-31T08:30:00Z`，忽略时区
+    ///
+    /// # 参数
+    /// * `text` - 日期时间文本
+    pub fn parse(text: &str) -> Option<Self> {
+        let (date, time) = text.trim().split_once('T').unwrap_or((text.trim(), ""));
+        let mut date = date.splitn(3, '-').map(|p| p.parse::<u32>().ok());
+        let year = date.next()?? as i32;
+        let month = date.next().flatten().unwrap_or(1);
+        let day = date.next().flatten().unwrap_or(1);
+        let time = time.trim_end_matches('Z');
+        let time = time.split(['+', '-']).next().unwrap_or_default();
+        let mut time = time
+            .split(':')
+            .map(|p| p.split('.').next().and_then(|p| p.parse::<u32>().ok()));
+        let mut next = || time.next().flatten().unwrap_or(0);
+        let (hour, minute, second) = (next(), next(), next());
+        ((1..=12).contains(&month) && (1..=31).contains(&day)).then_some(FieldDateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// 星期，0 为星期日
+    pub fn weekday(&self) -> u32 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        (days + 4).rem_euclid(7) as u32
+    }
+
+    /// 按 Word 的日期图片格式化，如 `yyyy-MM-dd`、`dddd, MMMM d, yyyy`、`h:mm am/pm`；
+    /// 单引号内的文本原样输出
+    ///
+    /// # 参数
+    /// * `picture` - 日期图片
+    pub fn format(&self, picture: &str) -> String {
+        const MONTHS: [&str; 12] = [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ];
+        const DAYS: [&str; 7] = [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ];
+
+        // 月份超出 1–12 时没有名称，输出数字
+        let month_name = self.month.checked_sub(1).and_then(|m| MONTHS.get(m as usize));
+        let chars: Vec<char> = picture.chars().collect();
+        let mut out = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '\'' {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c == '\'')
+                    .map_or(chars.len(), |p| i + 1 + p);
+                out.extend(&chars[i + 1..end]);
+                i = end + 1;
+                continue;
+            }
+            let rest: String = chars[i..].iter().collect();
+            if rest.starts_with("AM/PM") || rest.starts_with("am/pm") {
+                let marker = if self.hour < 12 { "AM" } else { "PM" };
+                if c == 'a' {
+                    out.push_str(&marker.to_lowercase());
+                } else {
+                    out.push_str(marker);
+                }
+                i += 5;
+                continue;
+            }
+            let count = chars[i..].iter().take_while(|&&x| x == c).count();
+            let hour12 = match self.hour % 12 {
+                0 => 12,
+                h => h,
+            };
+            let number = |value: u32| {
+                if count >= 2 {
+                    format!("{value:02}")
+                } else {
+                    value.to_string()
+                }
+            };
+            match c {
+                'y' | 'Y' if count >= 3 => out.push_str(&format!("{:04}", self.year)),
+                'y' | 'Y' => out.push_str(&format!("{:02}", self.year.rem_euclid(100))),
+                'M' if count >= 3 => match month_name {
+                    Some(name) if count >= 4 => out.push_str(name),
+                    Some(name) => out.push_str(&name[..3]),
+                    None => out.push_str(&number(self.month)),
+                },
+                'M' => out.push_str(&number(self.month)),
+                'd' | 'D' if count >= 4 => out.push_str(DAYS[self.weekday() as usize]),
+                'd' | 'D' if count == 3 => out.push_str(&DAYS[self.weekday() as usize][..3]),
+                'd' | 'D' => out.push_str(&number(self.day)),
+                'H' => out.push_str(&number(self.hour)),
+                'h' => out.push_str(&number(hour12)),
+                'm' => out.push_str(&number(self.minute)),
+                's' | 'S' => out.push_str(&number(self.second)),
+                _ => {
+                    out.extend(&chars[i..i + count]);
+                }
+            }
+            i += count;
+        }
+        out
+    }
+}
+
+/// 由公历日期计算自 1970-01-01 起的天数
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// 由自 1970-01-01 起的天数计算公历日期
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as i32, month, day)
+}
+
+/// 域更新选项
+#[derive(Debug, Clone)]
+pub struct FieldUpdateOptions {
+    /// DATE、TIME 使用的当前时间，`None` 时取系统时间（UTC）
+    pub now: Option<FieldDateTime>,
+    /// 是否将无法计算的依赖分页的域标记为需要更新
+    pub mark_dirty: bool,
+}
+
+impl Default for FieldUpdateOptions {
+    fn default() -> Self {
+        FieldUpdateOptions {
+            now: None,
+            mark_dirty: true,
+        }
+    }
+}
+
+/// 域的计算结果
+enum Evaluation {
+    /// 新的结果文本
+    Text(String),
+    /// 依赖分页，需要由 Word 更新
+    NeedsLayout,
+    /// 无法计算，保持原结果
+    Unchanged,
+}
+
+/// REF 找不到书签时 Word 显示的结果
+const REFERENCE_ERROR: &str = "Error! Reference source not found.";
+
+impl Docx {
+    /// 按域开始的位置顺序获取文档中的所有域，包括表格中的域和嵌套的域
+    pub fn fields(&self) -> Vec<Field> {
+        scan(&self.paragraphs())
+    }
+
+    /// 以默认选项更新域，返回结果被改写的域的数量
+    pub fn update_fields(&mut self) -> usize {
+        self.update_fields_with(&FieldUpdateOptions::default())
+    }
+
+    /// 更新域，返回结果被改写的域的数量
+    ///
+    /// 锁定的域不更新；结果跨段落的复杂域只能标记为需要更新。
+    ///
+    /// # 参数
+    /// * `options` - 更新选项
+    pub fn update_fields_with(&mut self, options: &FieldUpdateOptions) -> usize {
+        let fields = self.fields();
+        let evaluations = Evaluator::new(self, options).evaluate(&fields);
+
+        let mut dirty = Vec::new();
+        let mut edits: Vec<(Location, &Field, String)> = Vec::new();
+        // 结果区域被改写的域，其内部的域随之被替换，不再单独更新
+        let mut replaced: Vec<(Location, Location)> = Vec::new();
+        for (field, evaluation) in fields.iter().zip(evaluations) {
+            let inside = |loc: Location| replaced.iter().any(|&(from, to)| from < loc && loc < to);
+            match (&field.anchor, evaluation) {
+                (_, Evaluation::Unchanged) => {}
+                (_, Evaluation::NeedsLayout) => {
+                    if options.mark_dirty && !field.dirty {
+                        dirty.push(field);
+                    }
+                }
+                (FieldAnchor::Simple { item }, Evaluation::Text(text)) => {
+                    let loc = (field.paragraph, *item, 0);
+                    if !inside(loc) {
+                        edits.push((loc, field, text));
+                    }
+                }
+                (
+                    FieldAnchor::Complex {
+                        begin,
+                        separate,
+                        end: Some(end),
+                    },
+                    Evaluation::Text(text),
+                ) => {
+                    let start = separate.unwrap_or(*end);
+                    let same_paragraph = start.0 == end.0;
+                    if same_paragraph && !begin.is_some_and(inside) {
+                        replaced.push((start, *end));
+                        edits.push((start, field, text));
+                    } else if options.mark_dirty && !field.dirty {
+                        dirty.push(field);
+                    }
+                }
+                (FieldAnchor::Complex { .. }, Evaluation::Text(_)) => {}
+            }
+        }
+
+        let mut paragraphs = self.paragraphs_mut();
+        for field in dirty {
+            mark_dirty(&mut paragraphs, field);
+        }
+        // 从后往前改写，保证前面的位置不受影响
+        edits.sort_by_key(|(loc, ..)| std::cmp::Reverse(*loc));
+        let count = edits.len();
+        for (_, field, text) in edits {
+            let properties = field.result.iter().find_map(|r| r.properties.clone());
+            match &field.anchor {
+                FieldAnchor::Simple { item } => {
+                    if let Some(ParagraphContent::SimpleField(simple)) =
+                        paragraphs[field.paragraph].content.get_mut(*item)
+                    {
+                        simple.runs = vec![Run {
+                            properties,
+                            content: text_content(&text),
+                        }];
+                    }
+                }
+                FieldAnchor::Complex {
+                    separate,
+                    end: Some(end),
+                    ..
+                } => replace_result(paragraphs[end.0], *separate, *end, properties, &text),
+                FieldAnchor::Complex { .. } => {}
+            }
+        }
+        count
+    }
+}
+
+/// 将域标记为需要更新
+fn mark_dirty(paragraphs: &mut [&mut Paragraph], field: &Field) {
+    match field.anchor {
+        FieldAnchor::Simple { item } => {
+            if let Some(ParagraphContent::SimpleField(simple)) =
+                paragraphs[field.paragraph].content.get_mut(item)
+            {
+                simple.dirty = Some(true);
+            }
+        }
+        FieldAnchor::Complex {
+            begin: Some((p, item, offset)),
+            ..
+        } => {
+            if let Some(ParagraphContent::Run(run)) = paragraphs[p].content.get_mut(item) {
+                if let Some(RunContent::FieldChar(field_char)) = run.content.get_mut(offset) {
+                    field_char.dirty = Some(true);
+                }
+            }
+        }
+        FieldAnchor::Complex { .. } => {}
+    }
+}
+
+/// 用新文本替换复杂域在分隔字符与结束字符之间的结果；没有分隔字符时在结束字符前补上
+fn replace_result(
+    paragraph: &mut Paragraph,
+    separate: Option<Location>,
+    end: Location,
+    properties: Option<RunProperties>,
+    text: &str,
+) {
+    let (_, end_item, end_offset) = end;
+    let Some((_, sep_item, sep_offset)) = separate else {
+        if let Some(ParagraphContent::Run(run)) = paragraph.content.get_mut(end_item) {
+            let mut content = vec![RunContent::FieldChar(FieldChar::new(
+                FieldCharType::Separate,
+            ))];
+            content.extend(text_content(text));
+            run.content.splice(end_offset..end_offset, content);
+        }
+        return;
+    };
+
+    if sep_item == end_item {
+        if let Some(ParagraphContent::Run(run)) = paragraph.content.get_mut(sep_item) {
+            run.content
+                .splice(sep_offset + 1..end_offset, text_content(text));
+        }
+        return;
+    }
+
+    if let Some(ParagraphContent::Run(run)) = paragraph.content.get_mut(sep_item) {
+        run.content.truncate(sep_offset + 1);
+    }
+    if let Some(ParagraphContent::Run(run)) = paragraph.content.get_mut(end_item) {
+        run.content.drain(..end_offset);
+    }
+    // 结果中的书签保留在新结果之后
+    let bookmarks: Vec<ParagraphContent> = paragraph
+        .content
+        .drain(sep_item + 1..end_item)
+        .filter(|item| {
+            matches!(
+                item,
+                ParagraphContent::BookmarkStart(_) | ParagraphContent::BookmarkEnd(_)
+            )
+        })
+        .collect();
+    let result = ParagraphContent::Run(Run {
+        properties,
+        content: text_content(text),
+    });
+    paragraph.content.splice(
+        sep_item + 1..sep_item + 1,
+        std::iter::once(result).chain(bookmarks),
+    );
+}
+
+/// 正在读取的复杂域
+struct Frame {
+    order: usize,
+    instruction: String,
+    result: Vec<Run>,
+    in_result: bool,
+    paragraph: usize,
+    dirty: bool,
+    locked: bool,
+    begin: Option<Location>,
+    separate: Option<Location>,
+}
+
+/// 按文档顺序扫描段落，收集全部域
+fn scan(paragraphs: &[&Paragraph]) -> Vec<Field> {
+    let mut scanner = Scanner::default();
+    for (p, paragraph) in paragraphs.iter().enumerate() {
+        scanner.paragraph = p;
+        for (i, item) in paragraph.content.iter().enumerate() {
+            match item {
+                ParagraphContent::Run(run) => scanner.run(run, Some(i)),
+                ParagraphContent::Hyperlink(hyperlink) => {
+                    hyperlink.runs.iter().for_each(|r| scanner.run(r, None))
+                }
+                ParagraphContent::Insertion(revision) | ParagraphContent::MoveTo(revision) => {
+                    revision.runs.iter().for_each(|r| scanner.run(r, None))
+                }
+                ParagraphContent::Sdt(sdt) => {
+                    visible_runs(&sdt.content).for_each(|r| scanner.run(r, None))
+                }
+                ParagraphContent::SimpleField(simple) => {
+                    let field = Field {
+                        kind: FieldKind::Simple,
+                        instruction: simple.instruction.trim().to_string(),
+                        result: simple.runs.clone(),
+                        dirty: simple.dirty.unwrap_or(false),
+                        locked: simple.locked.unwrap_or(false),
+                        paragraph: p,
+                        anchor: FieldAnchor::Simple { item: i },
+                    };
+                    let order = scanner.next_order();
+                    simple.runs.iter().for_each(|r| scanner.deliver(r.clone()));
+                    scanner.complete(order, field);
+                }
+                ParagraphContent::Deletion(_)
+                | ParagraphContent::MoveFrom(_)
+                | ParagraphContent::BookmarkStart(_)
+                | ParagraphContent::BookmarkEnd(_) => {}
+            }
+        }
+        // 结果跨段落的域以换行分隔各段
+        scanner.deliver(Run {
+            properties: None,
+            content: vec![RunContent::Break],
+        });
+    }
+
+    let mut fields = scanner.fields;
+    fields.sort_by_key(|(order, _)| *order);
+    fields.into_iter().map(|(_, field)| field).collect()
+}
+
+#[derive(Default)]
+struct Scanner {
+    paragraph: usize,
+    stack: Vec<Frame>,
+    fields: Vec<(usize, Field)>,
+    order: usize,
+}
+
+impl Scanner {
+    fn next_order(&mut self) -> usize {
+        self.order += 1;
+        self.order
+    }
+
+    /// 读取文本运行；`item` 为文本运行在段落内容中的序号，位于超链接等元素内时为 `None`
+    fn run(&mut self, run: &Run, item: Option<usize>) {
+        let mut plain = Run {
+            properties: run.properties.clone(),
+            content: Vec::new(),
+        };
+        for (offset, content) in run.content.iter().enumerate() {
+            let location = item.map(|i| (self.paragraph, i, offset));
+            match content {
+                RunContent::FieldChar(field_char) => {
+                    if !plain.content.is_empty() {
+                        self.deliver(std::mem::replace(
+                            &mut plain,
+                            Run {
+                                properties: run.properties.clone(),
+                                content: Vec::new(),
+                            },
+                        ));
+                    }
+                    self.field_char(field_char, location);
+                }
+                RunContent::InstrText(text) => {
+                    if let Some(top) = self.stack.last_mut().filter(|f| !f.in_result) {
+                        top.instruction.push_str(text);
+                    }
+                }
+                RunContent::DeletedText(_) | RunContent::DeletedInstrText(_) => {}
+                other => plain.content.push(other.clone()),
+            }
+        }
+        if !plain.content.is_empty() {
+            self.deliver(plain);
+        }
+    }
+
+    fn field_char(&mut self, field_char: &FieldChar, location: Option<Location>) {
+        match field_char.char_type {
+            FieldCharType::Begin => {
+                let order = self.next_order();
+                self.stack.push(Frame {
+                    order,
+                    instruction: String::new(),
+                    result: Vec::new(),
+                    in_result: false,
+                    paragraph: self.paragraph,
+                    dirty: field_char.dirty.unwrap_or(false),
+                    locked: field_char.locked.unwrap_or(false),
+                    begin: location,
+                    separate: None,
+                });
+            }
+            FieldCharType::Separate => {
+                if let Some(top) = self.stack.last_mut() {
+                    top.in_result = true;
+                    top.separate = location;
+                }
+            }
+            FieldCharType::End => {
+                let Some(frame) = self.stack.pop() else {
+                    return;
+                };
+                let mut result = frame.result;
+                // 去掉段落末尾追加的换行
+                while result.last().is_some_and(|r| {
+                    r.properties.is_none() && matches!(r.content.as_slice(), [RunContent::Break])
+                }) {
+                    result.pop();
+                }
+                let field = Field {
+                    kind: FieldKind::Complex,
+                    instruction: frame.instruction.trim().to_string(),
+                    result,
+                    dirty: frame.dirty,
+                    locked: frame.locked,
+                    paragraph: frame.paragraph,
+                    anchor: FieldAnchor::Complex {
+                        begin: frame.begin,
+                        separate: frame.separate,
+                        end: location,
+                    },
+                };
+                self.complete(frame.order, field);
+            }
+        }
+    }
+
+    /// 把域结果内容交给仍在读取结果的外层域
+    fn deliver(&mut self, run: Run) {
+        for frame in self.stack.iter_mut().rev() {
+            if !frame.in_result {
+                break;
+            }
+            frame.result.push(run.clone());
+        }
+    }
+
+    /// 完成一个域；处于外层域代码中的域以结果文本代入外层域代码
+    fn complete(&mut self, order: usize, field: Field) {
+        if let Some(parent) = self.stack.last_mut().filter(|f| !f.in_result) {
+            parent.instruction.push_str(&field.result_text());
+        }
+        self.fields.push((order, field));
+    }
+}
+
+/// 按文档顺序计算域结果
+struct Evaluator<'a> {
+    docx: &'a Docx,
+    now: FieldDateTime,
+    /// 书签名称到书签文本和所在段落的映射
+    bookmarks: HashMap<String, (String, usize)>,
+    /// 每个段落之前（含）各大纲级别标题出现的累计次数
+    headings: Vec<[usize; 9]>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(docx: &'a Docx, options: &FieldUpdateOptions) -> Self {
+        let paragraphs = docx.paragraphs();
+        let resolver = docx.style_resolver();
+
+        let mut bookmarks: HashMap<String, (String, usize)> = HashMap::new();
+        let mut open: Vec<(i32, String)> = Vec::new();
+        let mut headings = Vec::with_capacity(paragraphs.len());
+        let mut counts = [0usize; 9];
+        for (p, paragraph) in paragraphs.iter().enumerate() {
+            let level = resolver
+                .paragraph_properties(paragraph, None)
+                .outline_level
+                .map(|l| l.val as usize)
+                .filter(|&l| l < 9);
+            if let Some(level) = level {
+                counts[level..].iter_mut().for_each(|c| *c += 1);
+            }
+            headings.push(counts);
+
+            for item in &paragraph.content {
+                let text = match item {
+                    ParagraphContent::BookmarkStart(b) => {
+                        open.push((b.id, b.name.clone()));
+                        bookmarks
+                            .entry(b.name.clone())
+                            .or_insert((String::new(), p));
+                        continue;
+                    }
+                    ParagraphContent::BookmarkEnd(b) => {
+                        open.retain(|(id, _)| *id != b.id);
+                        continue;
+                    }
+                    ParagraphContent::Run(run) => run.text(),
+                    ParagraphContent::Hyperlink(h) => h.text(),
+                    ParagraphContent::SimpleField(f) => f.runs.iter().map(Run::text).collect(),
+                    ParagraphContent::Insertion(r) | ParagraphContent::MoveTo(r) => {
+                        r.runs.iter().map(Run::text).collect()
+                    }
+                    ParagraphContent::Sdt(sdt) => sdt.text(),
+                    ParagraphContent::Deletion(_) | ParagraphContent::MoveFrom(_) => continue,
+                };
+                for (_, name) in &open {
+                    if let Some((value, _)) = bookmarks.get_mut(name) {
+                        value.push_str(&text);
+                    }
+                }
+            }
+            for (_, name) in &open {
+                if let Some((value, _)) = bookmarks.get_mut(name) {
+                    value.push('\n');
+                }
+            }
+        }
+        for (value, _) in bookmarks.values_mut() {
+            let trimmed = value.trim_end_matches('\n').len();
+            value.truncate(trimmed);
+        }
+
+        Evaluator {
+            docx,
+            now: options.now.unwrap_or_else(FieldDateTime::now),
+            bookmarks,
+            headings,
+        }
+    }
+
+    fn evaluate(&self, fields: &[Field]) -> Vec<Evaluation> {
+        let mut labels = None;
+        // SEQ 序列名称到当前值和重新编号依据的标题计数
+        let mut sequences: HashMap<String, (i32, usize)> = HashMap::new();
+
+        fields
+            .iter()
+            .map(|field| {
+                if field.locked {
+                    return Evaluation::Unchanged;
+                }
+                let instruction = field.parse();
+                let value = match instruction.field_type.as_str() {
+                    "DATE" | "TIME" => {
+                        let default = if instruction.field_type == "DATE" {
+                            "M/d/yyyy"
+                        } else {
+                            "h:mm am/pm"
+                        };
+                        let picture = instruction.switch_value("@").unwrap_or(default);
+                        Some(self.now.format(picture))
+                    }
+                    "CREATEDATE" | "SAVEDATE" => {
+                        let core = self.docx.core_props.as_ref();
+                        let date = if instruction.field_type == "CREATEDATE" {
+                            core.and_then(|c| c.created.as_deref())
+                        } else {
+                            core.and_then(|c| c.modified.as_deref())
+                        };
+                        let picture = instruction
+                            .switch_value("@")
+                            .unwrap_or("M/d/yyyy h:mm:ss am/pm");
+                        date.and_then(FieldDateTime::parse)
+                            .map(|d| d.format(picture))
+                    }
+                    "TITLE" | "AUTHOR" | "COMMENTS" | "LASTSAVEDBY" | "REVNUM" => {
+                        let name = match instruction.field_type.as_str() {
+                            "TITLE" => "Title",
+                            "AUTHOR" => "Author",
+                            "COMMENTS" => "Comments",
+                            "LASTSAVEDBY" => "LastSavedBy",
+                            _ => "RevisionNumber",
+                        };
+                        Some(
+                            self.document_property(name, &instruction)
+                                .unwrap_or_default(),
+                        )
+                    }
+                    "DOCPROPERTY" => instruction
+                        .argument()
+                        .and_then(|name| self.document_property(name, &instruction)),
+                    "SEQ" => instruction
+                        .argument()
+                        .map(|name| self.sequence(name, &instruction, field, &mut sequences)),
+                    "PAGE" | "NUMPAGES" | "SECTIONPAGES" | "SECTION" | "PAGEREF" | "TOC"
+                    | "INDEX" | "NUMWORDS" | "NUMCHARS" => return Evaluation::NeedsLayout,
+                    "REF" => {
+                        let labels = labels.get_or_insert_with(|| self.docx.list_labels());
+                        let name = instruction.argument().unwrap_or_default();
+                        Some(self.reference(name, &instruction, field, labels))
+                    }
+                    other => {
+                        // 域类型为书签名称时等同于 REF
+                        let name = field.instruction.split_whitespace().next().unwrap_or(other);
+                        if self.bookmarks.contains_key(name) {
+                            let labels = labels.get_or_insert_with(|| self.docx.list_labels());
+                            Some(self.reference(name, &instruction, field, labels))
+                        } else {
+                            None
+                        }
+                    }
+                };
+                match value {
+                    Some(text) => Evaluation::Text(apply_format(text, &instruction)),
+                    None => Evaluation::Unchanged,
+                }
+            })
+            .collect()
+    }
+
+    /// 计算 SEQ 域：`\r` 重设编号，`\c` 重复上一编号，`\h` 隐藏结果，
+    /// `\s` 在指定级别的标题之后重新编号
+    fn sequence(
+        &self,
+        name: &str,
+        instruction: &FieldInstruction,
+        field: &Field,
+        sequences: &mut HashMap<String, (i32, usize)>,
+    ) -> String {
+        let heading = instruction
+            .switch_value("s")
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|l| (1..=9).contains(l))
+            .and_then(|l| {
+                self.headings
+                    .get(field.paragraph)
+                    .map(|counts| counts[l - 1])
+            })
+            .unwrap_or(0);
+        let entry = sequences.entry(name.to_uppercase()).or_insert((0, heading));
+        if entry.1 != heading {
+            *entry = (0, heading);
+        }
+        if let Some(start) = instruction.switch_value("r") {
+            entry.0 = start.parse().unwrap_or(entry.0);
+        } else if !instruction.has_switch("c") {
+            entry.0 += 1;
+        }
+        if instruction.has_switch("h") {
+            String::new()
+        } else {
+            entry.0.to_string()
+        }
+    }
+
+    /// 计算 REF 域：书签文本，或 `\n`/`\r`/`\w` 指定的书签所在段落编号，
+    /// `\p` 附加相对位置 `above`/`below`
+    fn reference(
+        &self,
+        name: &str,
+        instruction: &FieldInstruction,
+        field: &Field,
+        labels: &[Option<ListLabel>],
+    ) -> String {
+        let Some((text, paragraph)) = self.bookmarks.get(name) else {
+            return REFERENCE_ERROR.to_string();
+        };
+        let label = labels.get(*paragraph).and_then(Option::as_ref);
+        let number = if instruction.has_switch("n") {
+            Some(label.map_or(String::new(), |l| format_number(l.value, l.format)))
+        } else if instruction.has_switch("r") || instruction.has_switch("w") {
+            Some(label.map_or(String::new(), |l| l.text.clone()))
+        } else {
+            None
+        };
+        let relative = if *paragraph <= field.paragraph {
+            "above"
+        } else {
+            "below"
+        };
+        let position = instruction.has_switch("p").then_some(relative);
+        match (number, position) {
+            (Some(number), Some(position)) => format!("{number} {position}"),
+            (Some(number), None) => number,
+            (None, Some(position)) => position.to_string(),
+            (None, None) => text.clone(),
+        }
+    }
+
+    /// 读取文档属性：核心属性的内置名称，或自定义属性
+    fn document_property(&self, name: &str, instruction: &FieldInstruction) -> Option<String> {
+        let core = self.docx.core_props.as_ref();
+        let date = |value: Option<&String>| {
+            let picture = instruction
+                .switch_value("@")
+                .unwrap_or("M/d/yyyy h:mm:ss am/pm");
+            value.map(|v| FieldDateTime::parse(v).map_or(v.clone(), |d| d.format(picture)))
+        };
+        let builtin = match name.to_ascii_lowercase().as_str() {
+            "title" => Some(core.and_then(|c| c.title.clone())),
+            "author" => Some(core.and_then(|c| c.creator.clone())),
+            "comments" => Some(core.and_then(|c| c.description.clone())),
+            "lastsavedby" => Some(core.and_then(|c| c.last_modified_by.clone())),
+            "revisionnumber" => Some(core.and_then(|c| c.revision.clone())),
+            "createtime" => Some(date(core.and_then(|c| c.created.as_ref()))),
+            "lastsavedtime" => Some(date(core.and_then(|c| c.modified.as_ref()))),
+            _ => None,
+        };
+        match builtin {
+            Some(value) => value,
+            None => self
+                .docx
+                .custom_props
+                .as_ref()
+                .and_then(|c| c.get(name))
+                .map(ToString::to_string),
+        }
+    }
+}
+
+/// 应用通用格式开关 `\*`：数字格式（ARABIC、ROMAN、alphabetic、CardText 等）
+/// 和大小写格式（Upper、Lower、FirstCap、Caps）
+fn apply_format(mut text: String, instruction: &FieldInstruction) -> String {
+    for switch in instruction.switches.iter().filter(|s| s.name == "*") {
+        let Some(format) = switch.value.as_deref() else {
+            continue;
+        };
+        let number_format = match format {
+            "ALPHABETIC" => Some(NumberFormat::UpperLetter),
+            "alphabetic" => Some(NumberFormat::LowerLetter),
+            "ROMAN" | "Roman" => Some(NumberFormat::UpperRoman),
+            "roman" => Some(NumberFormat::LowerRoman),
+            _ => match format.to_ascii_lowercase().as_str() {
+                "arabic" => Some(NumberFormat::Decimal),
+                "ordinal" => Some(NumberFormat::Ordinal),
+                "cardtext" => Some(NumberFormat::CardinalText),
+                "ordtext" => Some(NumberFormat::OrdinalText),
+                "hex" => Some(NumberFormat::Hex),
+                "dollartext" => Some(NumberFormat::DollarText),
+                "chinesenum1" => Some(NumberFormat::ChineseCounting),
+                "chinesenum2" => Some(NumberFormat::ChineseLegalSimplified),
+                "chinesenum3" => Some(NumberFormat::ChineseCountingThousand),
+                _ => None,
+            },
+        };
+        if let Some(number_format) = number_format {
+            if let Ok(value) = text.trim().parse::<i32>() {
+                text = format_number(value, number_format);
+            }
+            continue;
+        }
+        text = match format.to_ascii_lowercase().as_str() {
+            "upper" => text.to_uppercase(),
+            "lower" => text.to_lowercase(),
+            "firstcap" => {
+                let mut chars = text.chars();
+                chars.next().map_or(String::new(), |c| {
+                    c.to_uppercase().chain(chars.as_str().chars()).collect()
+                })
+            }
+            "caps" => text
+                .split(' ')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    chars.next().map_or(String::new(), |c| {
+                        c.to_uppercase()
+                            .chain(chars.as_str().to_lowercase().chars())
+                            .collect()
+                    })
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            "arabicdash" => match text.trim().parse::<i32>() {
+                Ok(value) => format!("- {value} -"),
+                Err(_) => text,
+            },
+            _ => text,
+        };
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::builder::ParagraphBuilder;
+    use crate::docx::test_utils::{body_xml, docx};
+
+    /// 复杂域段落 XML，域代码拆分在两个文本运行中
+    fn complex(instruction: &str, result: &str) -> String {
+        let (head, tail) = instruction.split_at(instruction.len() / 2);
+        format!(
+            "<w:r><w:fldChar w:fldCharType=\"begin\"/></w:r>\
+             <w:r><w:instrText xml:space=\"preserve\">{head}</w:instrText></w:r>\
+             <w:r><w:instrText xml:space=\"preserve\">{tail}</w:instrText></w:r>\
+             <w:r><w:fldChar w:fldCharType=\"separate\"/></w:r>\
+             <w:r><w:t>{result}</w:t></w:r><w:r><w:fldChar w:fldCharType=\"end\"/></w:r>"
+        )
+    }
+
+    #[test]
+    fn instruction_parsing() {
+        let parsed = FieldInstruction::parse(r#"ref _Ref12 \h \* MERGEFORMAT"#);
+        assert_eq!(parsed.field_type, "REF");
+        assert_eq!(parsed.argument(), Some("_Ref12"));
+        assert!(parsed.has_switch("h"));
+        assert_eq!(parsed.switch_value("*"), Some("MERGEFORMAT"));
+
+        let parsed = FieldInstruction::parse(r#"DATE \@ "d \"of\" MMMM""#);
+        assert_eq!(parsed.switch_value("@"), Some(r#"d "of" MMMM"#));
+        assert!(parsed.arguments.is_empty());
+    }
+
+    #[test]
+    fn date_pictures() {
+        let date = FieldDateTime::parse("2024-01-31T08:05:09Z").unwrap();
+        assert_eq!(date.format("yyyy-MM-dd HH:mm:ss"), "2024-01-31 08:05:09");
+        assert_eq!(date.format("dddd, MMMM d, yyyy"), "Wednesday, January 31, 2024");
+        assert_eq!(date.format("h:mm am/pm"), "8:05 am");
+
+        let invalid = FieldDateTime { month: 0, ..date };
+        assert_eq!(invalid.format("MMMM MMM M"), "00 00 0");
+    }
+
+    #[test]
+    fn fields_are_found_across_runs() {
+        let docx = docx(&format!(
+            "<w:p>{}<w:fldSimple w:instr=\" PAGE \"><w:r><w:t>3</w:t></w:r></w:fldSimple></w:p>",
+            complex(" SEQ Figure \\* ARABIC ", "9")
+        ));
+
+        let fields = docx.fields();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].kind, FieldKind::Complex);
+        assert_eq!(fields[0].field_type(), "SEQ");
+        assert_eq!(fields[0].result_text(), "9");
+        assert_eq!(fields[1].kind, FieldKind::Simple);
+        assert_eq!(fields[1].field_type(), "PAGE");
+        assert_eq!(fields[1].result_text(), "3");
+    }
+
+    #[test]
+    fn update_computes_results_and_marks_layout_fields() {
+        let mut docx = docx(&format!(
+            "<w:p><w:bookmarkStart w:id=\"0\" w:name=\"target\"/><w:r><w:t>Intro</w:t></w:r>\
+             <w:bookmarkEnd w:id=\"0\"/></w:p>\
+             <w:p>{}</w:p><w:p>{}</w:p><w:p>{}</w:p><w:p>{}</w:p>",
+            complex(" SEQ Table ", "x"),
+            complex(" SEQ Table ", "x"),
+            complex(" REF target \\h ", "old"),
+            complex(" DATE \\@ \"yyyy-MM-dd\" ", "then"),
+        ));
+        docx.add_paragraph(ParagraphBuilder::new().field("PAGE", "1"));
+
+        let options = FieldUpdateOptions {
+            now: FieldDateTime::parse("2024-05-06T00:00:00"),
+            mark_dirty: true,
+        };
+        assert_eq!(docx.update_fields_with(&options), 4);
+
+        let results: Vec<String> = docx.fields().iter().map(Field::result_text).collect();
+        assert_eq!(results, ["1", "2", "Intro", "2024-05-06", "1"]);
+        assert!(docx.fields()[4].dirty);
+        assert!(body_xml(&docx).contains("w:dirty=\"true\""));
+    }
+}
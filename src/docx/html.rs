@@ -0,0 +1,1117 @@
+//! HTML 导出：把文档转换为语义化的 HTML
+//!
+//! 标题按大纲级别输出为 `h1`–`h6`，列表按编号定义输出为嵌套的 `ol`/`ul`，表格的合并单元格
+//! 输出为 `colspan`/`rowspan`，超链接和书签输出为 `a`，图片输出为 `img`。格式取自样式解析
+//! 后的最终格式：按类名输出时，每个段落样式和字符样式生成一条 CSS 规则，元素只以内联样式
+//! 补充直接格式；按内联样式输出时，每个元素携带完整的格式。
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::error::Result;
+
+use super::elements::{
+    BodyContent, Drawing, HyperlinkTarget, Paragraph, ParagraphContent, Run, RunContent, Table,
+};
+use super::list_numbering::ListLabel;
+use super::markdown::heading_level;
+use super::numbering::NumberFormat;
+use super::properties::{
+    Border, HighlightColor, JustificationVal, LineSpacingRule, OnOff, ParagraphProperties,
+    ParagraphStyle, RunProperties, TableBorders, UnderlineType, VerticalAlignRun, VerticalJc,
+};
+use super::style_resolver::{CellContext, StyleResolver};
+use super::styles::StyleType;
+use super::{part_path, Docx};
+
+/// 样式链的最大深度，防止循环引用
+const MAX_CHAIN_DEPTH: usize = 32;
+
+/// 格式的输出方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HtmlStyleMode {
+    /// 由样式生成样式表，元素以类名引用样式，直接格式以内联样式补充
+    #[default]
+    Classes,
+    /// 不生成样式表，每个元素以内联样式携带完整格式，适用于邮件等不支持样式表的场合
+    Inline,
+}
+
+/// 图片的输出方式
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HtmlImageMode {
+    /// 以 base64 data URI 嵌入 HTML
+    #[default]
+    Embedded,
+    /// 以相对路径引用，参数为图片文件相对于 HTML 文件的目录，为空时与 HTML 文件位于同一目录
+    External(String),
+}
+
+/// HTML 导出选项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlOptions {
+    /// 格式的输出方式
+    pub style_mode: HtmlStyleMode,
+    /// 图片的输出方式
+    pub image_mode: HtmlImageMode,
+    /// 是否输出包含 `head` 和样式表的完整 HTML 文档，否则只输出正文内容
+    pub standalone: bool,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        HtmlOptions {
+            style_mode: HtmlStyleMode::Classes,
+            image_mode: HtmlImageMode::Embedded,
+            standalone: true,
+        }
+    }
+}
+
+/// HTML 导出结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlExport {
+    /// HTML 文本
+    pub html: String,
+    /// 由样式生成的样式表，按内联样式输出时为空
+    pub css: String,
+    /// 以相对路径引用的图片，键为相对于 HTML 文件的路径
+    pub images: BTreeMap<String, Vec<u8>>,
+}
+
+impl Docx {
+    /// 按默认选项把文档转换为完整的 HTML 文档，样式以类名引用，图片嵌入其中
+    pub fn to_html(&self) -> String {
+        self.export_html(&HtmlOptions::default()).html
+    }
+
+    /// 把文档转换为 HTML，返回文本、样式表和引用的图片
+    ///
+    /// # 参数
+    /// * `options` - 导出选项
+    pub fn export_html(&self, options: &HtmlOptions) -> HtmlExport {
+        let mut writer = HtmlWriter {
+            docx: self,
+            resolver: self.style_resolver(),
+            options,
+            labels: self.list_labels().into_iter(),
+            style_css: HashMap::new(),
+            images: BTreeMap::new(),
+            html: String::new(),
+            lists: Vec::new(),
+        };
+        writer.blocks(&self.document.body.content, None);
+        writer.close_lists();
+
+        let css = match options.style_mode {
+            HtmlStyleMode::Classes => writer.stylesheet(),
+            HtmlStyleMode::Inline => String::new(),
+        };
+        let html = if options.standalone {
+            let title = self
+                .core_props
+                .as_ref()
+                .and_then(|p| p.title.as_deref())
+                .unwrap_or_default();
+            let style = if css.is_empty() {
+                String::new()
+            } else {
+                format!("<style>\n{css}</style>\n")
+            };
+            format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n{style}</head>\n<body>\n{}</body>\n</html>\n",
+                escape(title),
+                writer.html
+            )
+        } else {
+            writer.html
+        };
+        HtmlExport {
+            html,
+            css,
+            images: writer.images,
+        }
+    }
+
+    /// 把文档保存为 HTML 文件，以相对路径引用的图片保存到相对于该文件的目录
+    ///
+    /// # 参数
+    /// * `path` - HTML 文件路径
+    /// * `options` - 导出选项
+    pub fn save_html<P: AsRef<Path>>(&self, path: P, options: &HtmlOptions) -> Result<()> {
+        let path = path.as_ref();
+        let export = self.export_html(options);
+        std::fs::write(path, &export.html)?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        for (relative, data) in &export.images {
+            let image_path = base.join(relative);
+            if let Some(dir) = image_path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(image_path, data)?;
+        }
+        Ok(())
+    }
+}
+
+/// CSS 声明，按属性名排序以保证输出稳定
+type Css = BTreeMap<&'static str, String>;
+
+/// 正在输出的列表
+struct OpenList {
+    level: i32,
+    tag: &'static str,
+    /// 下一个列表项按顺序应有的编号值
+    next_value: i32,
+}
+
+struct HtmlWriter<'a> {
+    docx: &'a Docx,
+    resolver: StyleResolver<'a>,
+    options: &'a HtmlOptions,
+    /// 按 [`Docx::paragraphs`] 顺序排列的列表编号，遍历段落时依次取出
+    labels: std::vec::IntoIter<Option<ListLabel>>,
+    /// 段落样式的完整格式，即样式表中对应规则的内容
+    style_css: HashMap<String, Css>,
+    images: BTreeMap<String, Vec<u8>>,
+    html: String,
+    /// 当前嵌套的列表，最后一个为最内层；除刚打开的列表外，各层都有未闭合的 `li`
+    lists: Vec<OpenList>,
+}
+
+impl HtmlWriter<'_> {
+    fn blocks(&mut self, content: &[BodyContent], cell: Option<&CellContext>) {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(paragraph) => self.paragraph(paragraph, cell),
+                BodyContent::Table(table) => {
+                    self.close_lists();
+                    self.table(table);
+                }
+                BodyContent::Sdt(sdt) => self.blocks(&sdt.content, cell),
+            }
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: &Paragraph, cell: Option<&CellContext>) {
+        let label = self.labels.next().flatten();
+        let heading = heading_level(&self.resolver, paragraph).filter(|level| *level <= 6);
+        let mut content = self.inline(paragraph, cell);
+        if content.is_empty() {
+            // 空段落在 Word 中占一行
+            content.push_str("<br>");
+        }
+
+        match (heading, label) {
+            (Some(level), label) => {
+                self.close_lists();
+                if let Some(label) = label {
+                    content.insert_str(0, &format!("{} ", escape(&label.text)));
+                }
+                let tag = format!("h{level}");
+                let attributes = self.block_attributes(paragraph, cell, false);
+                self.html
+                    .push_str(&format!("<{tag}{attributes}>{content}</{tag}>\n"));
+            }
+            (None, Some(label)) => {
+                let attributes = self.block_attributes(paragraph, cell, true);
+                self.list_item(&label, &attributes, &content);
+            }
+            (None, None) => {
+                self.close_lists();
+                let attributes = self.block_attributes(paragraph, cell, false);
+                self.html
+                    .push_str(&format!("<p{attributes}>{content}</p>\n"));
+            }
+        }
+    }
+
+    /// 输出列表项：关闭更深或类型不同的列表，必要时打开新列表
+    fn list_item(&mut self, label: &ListLabel, attributes: &str, content: &str) {
+        let tag = if label.is_bullet() { "ul" } else { "ol" };
+        while let Some(open) = self.lists.last() {
+            if open.level > label.level || (open.level == label.level && open.tag != tag) {
+                self.html.push_str(&format!("</li>\n</{}>\n", open.tag));
+                self.lists.pop();
+            } else {
+                break;
+            }
+        }
+        match self.lists.last() {
+            Some(open) if open.level == label.level => self.html.push_str("</li>\n"),
+            _ => {
+                let mut list_attributes = String::new();
+                if tag == "ol" {
+                    if label.value != 1 {
+                        list_attributes.push_str(&format!(" start=\"{}\"", label.value));
+                    }
+                    if let Some(kind) = list_type(label.format) {
+                        list_attributes.push_str(&format!(" type=\"{kind}\""));
+                    }
+                }
+                self.html.push_str(&format!("<{tag}{list_attributes}>\n"));
+                self.lists.push(OpenList {
+                    level: label.level,
+                    tag,
+                    next_value: label.value,
+                });
+            }
+        }
+
+        let open = self.lists.last_mut().expect("a list is open");
+        let value = if tag == "ol" && label.value != open.next_value {
+            format!(" value=\"{}\"", label.value)
+        } else {
+            String::new()
+        };
+        open.next_value = label.value + 1;
+        self.html
+            .push_str(&format!("<li{value}{attributes}>{content}"));
+    }
+
+    fn close_lists(&mut self) {
+        while let Some(open) = self.lists.pop() {
+            self.html.push_str(&format!("</li>\n</{}>\n", open.tag));
+        }
+    }
+
+    fn table(&mut self, table: &Table) {
+        let view = table.grid_view();
+        let mut contents = HashMap::new();
+        for (row_index, row) in table.rows().enumerate() {
+            for (cell_index, cell) in row.cells().enumerate() {
+                // 纵向合并的延续单元格不输出内容，但其中的段落仍须消耗列表编号
+                let position = view
+                    .cells()
+                    .iter()
+                    .find(|c| c.row == row_index && c.cell_index == cell_index);
+                let context = position.map(|c| CellContext::new(table, c.row, c.col));
+                let html = self.cell_content(&cell.content, context.as_ref());
+                if let Some(position) = position {
+                    contents.insert((position.row, position.col), html);
+                }
+            }
+        }
+
+        // 表格开头连续的标题行输出到 thead
+        let header_rows = table
+            .rows()
+            .take_while(|row| {
+                row.properties
+                    .as_ref()
+                    .and_then(|p| p.header)
+                    .is_some_and(|h| h.is_on())
+            })
+            .count();
+        let border = self.table_border(table);
+        let attributes = match self.options.style_mode {
+            HtmlStyleMode::Classes => table_style_id(self.docx, table)
+                .map(|id| format!(" class=\"{}\"", class_name(&id)))
+                .unwrap_or_default(),
+            HtmlStyleMode::Inline => " style=\"border-collapse: collapse\"".to_string(),
+        };
+
+        self.html.push_str(&format!("<table{attributes}>\n"));
+        for row in 0..view.rows() {
+            if row == 0 && header_rows > 0 {
+                self.html.push_str("<thead>\n");
+            } else if row == header_rows {
+                self.html.push_str("<tbody>\n");
+            }
+            self.html.push_str("<tr>\n");
+            let mut cells: Vec<_> = view.cells().iter().filter(|c| c.row == row).collect();
+            cells.sort_by_key(|c| c.col);
+            for position in cells {
+                let tag = if row < header_rows { "th" } else { "td" };
+                let mut attributes = String::new();
+                if position.col_span > 1 {
+                    attributes.push_str(&format!(" colspan=\"{}\"", position.col_span));
+                }
+                if position.row_span > 1 {
+                    attributes.push_str(&format!(" rowspan=\"{}\"", position.row_span));
+                }
+                let cell = table
+                    .rows()
+                    .nth(position.row)
+                    .and_then(|r| r.cells().nth(position.cell_index));
+                let mut css = Css::new();
+                if let Some(border) = &border {
+                    css.insert("border", border.clone());
+                }
+                if let Some(properties) = cell.and_then(|c| c.properties.as_ref()) {
+                    if let Some(fill) = properties.shading.as_ref().and_then(|s| s.fill.as_deref())
+                    {
+                        if let Some(color) = hex_color(fill) {
+                            css.insert("background-color", color);
+                        }
+                    }
+                    if let Some(align) = &properties.vertical_align {
+                        let value = match align.val {
+                            VerticalJc::Center => "middle",
+                            VerticalJc::Bottom => "bottom",
+                            _ => "top",
+                        };
+                        css.insert("vertical-align", value.to_string());
+                    }
+                }
+                if self.options.style_mode == HtmlStyleMode::Inline {
+                    css.entry("padding")
+                        .or_insert_with(|| "0 5.4pt".to_string());
+                    css.entry("vertical-align")
+                        .or_insert_with(|| "top".to_string());
+                }
+                attributes.push_str(&style_attribute(&css));
+                let content = contents
+                    .remove(&(position.row, position.col))
+                    .unwrap_or_default();
+                self.html
+                    .push_str(&format!("<{tag}{attributes}>\n{content}</{tag}>\n"));
+            }
+            self.html.push_str("</tr>\n");
+            if row + 1 == header_rows {
+                self.html.push_str("</thead>\n");
+            }
+        }
+        if view.rows() > header_rows {
+            self.html.push_str("</tbody>\n");
+        }
+        self.html.push_str("</table>\n");
+    }
+
+    /// 单元格内容，使用独立的列表状态
+    fn cell_content(&mut self, content: &[BodyContent], cell: Option<&CellContext>) -> String {
+        let html = std::mem::take(&mut self.html);
+        let lists = std::mem::take(&mut self.lists);
+        self.blocks(content, cell);
+        self.close_lists();
+        self.lists = lists;
+        std::mem::replace(&mut self.html, html)
+    }
+
+    /// 段落元素的 `class` 和 `style` 属性；列表项的缩进由列表本身体现，不再输出
+    fn block_attributes(
+        &mut self,
+        paragraph: &Paragraph,
+        cell: Option<&CellContext>,
+        list_item: bool,
+    ) -> String {
+        let mut css = block_css(&self.resolver, paragraph, cell);
+        if list_item {
+            for key in ["margin-left", "text-indent"] {
+                css.remove(key);
+            }
+        }
+        match self.options.style_mode {
+            HtmlStyleMode::Inline => style_attribute(&css),
+            HtmlStyleMode::Classes => {
+                let style_id = paragraph
+                    .properties
+                    .as_ref()
+                    .and_then(|p| p.style.as_ref())
+                    .map(|s| s.val.clone())
+                    .or_else(|| {
+                        self.docx
+                            .styles
+                            .default_style(StyleType::Paragraph)
+                            .map(|s| s.style_id.clone())
+                    });
+                let Some(style_id) = style_id else {
+                    return style_attribute(&css);
+                };
+                let base = self.paragraph_style_css(&style_id).clone();
+                format!(
+                    " class=\"{}\"{}",
+                    class_name(&style_id),
+                    style_attribute(&css_diff(&css, &base))
+                )
+            }
+        }
+    }
+
+    /// 段落的行内内容
+    fn inline(&mut self, paragraph: &Paragraph, cell: Option<&CellContext>) -> String {
+        let base = run_css(
+            &self
+                .resolver
+                .run_properties(paragraph, &Run::default(), cell),
+        );
+        let mut html = String::new();
+        self.inline_content(paragraph, &paragraph.content, cell, &base, &mut html);
+        html
+    }
+
+    /// 段落内容转换为 HTML，运行级内容控件中的内容一并转换
+    fn inline_content(
+        &mut self,
+        paragraph: &Paragraph,
+        content: &[ParagraphContent],
+        cell: Option<&CellContext>,
+        base: &Css,
+        html: &mut String,
+    ) {
+        for item in content {
+            let runs: &[Run] = match item {
+                ParagraphContent::Run(run) => std::slice::from_ref(run),
+                ParagraphContent::SimpleField(field) => &field.runs,
+                ParagraphContent::Insertion(revision) | ParagraphContent::MoveTo(revision) => {
+                    &revision.runs
+                }
+                ParagraphContent::Hyperlink(hyperlink) => {
+                    let text: String = hyperlink
+                        .runs
+                        .iter()
+                        .map(|run| self.run(paragraph, run, cell, base))
+                        .collect();
+                    let href = match self.docx.hyperlink_target(hyperlink) {
+                        Some(HyperlinkTarget::Url(url)) => url,
+                        Some(HyperlinkTarget::Bookmark(name)) => format!("#{name}"),
+                        None => {
+                            html.push_str(&text);
+                            continue;
+                        }
+                    };
+                    let title = hyperlink
+                        .tooltip
+                        .as_ref()
+                        .map(|t| format!(" title=\"{}\"", escape(t)))
+                        .unwrap_or_default();
+                    html.push_str(&format!("<a href=\"{}\"{title}>{text}</a>", escape(&href)));
+                    continue;
+                }
+                ParagraphContent::BookmarkStart(bookmark) => {
+                    html.push_str(&format!("<a id=\"{}\"></a>", escape(&bookmark.name)));
+                    continue;
+                }
+                ParagraphContent::Sdt(sdt) => {
+                    self.inline_content(paragraph, &sdt.content, cell, base, html);
+                    continue;
+                }
+                ParagraphContent::Deletion(_)
+                | ParagraphContent::MoveFrom(_)
+                | ParagraphContent::BookmarkEnd(_) => continue,
+            };
+            for run in runs {
+                html.push_str(&self.run(paragraph, run, cell, base));
+            }
+        }
+    }
+
+    /// 把一个运行转换为 HTML，格式与段落不同时包在 `span` 中；隐藏文字被忽略
+    fn run(
+        &mut self,
+        paragraph: &Paragraph,
+        run: &Run,
+        cell: Option<&CellContext>,
+        base: &Css,
+    ) -> String {
+        let properties = self.resolver.run_properties(paragraph, run, cell);
+        if properties.hidden.is_some_and(|h| h.is_on()) {
+            return String::new();
+        }
+        let mut html = String::new();
+        for content in &run.content {
+            match content {
+                RunContent::Text(text) => html.push_str(&escape(text)),
+                RunContent::Tab => html.push_str("&emsp;"),
+                RunContent::Break => html.push_str("<br>"),
+                RunContent::Drawing(drawing) => html.push_str(&self.image(drawing)),
+                RunContent::PageBreak
+                | RunContent::DeletedText(_)
+                | RunContent::RawDrawing(_)
+                | RunContent::FieldChar(_)
+                | RunContent::InstrText(_)
+                | RunContent::DeletedInstrText(_)
+                | RunContent::FootnoteReference(_)
+                | RunContent::EndnoteReference(_) => {}
+            }
+        }
+        if html.is_empty() {
+            return html;
+        }
+
+        let css = run_css(&properties);
+        let style_id = run
+            .properties
+            .as_ref()
+            .and_then(|p| p.style.clone())
+            .filter(|_| self.options.style_mode == HtmlStyleMode::Classes);
+        let attributes = match style_id {
+            Some(style) => {
+                // 字符样式的格式由样式表提供
+                let probe = Run {
+                    properties: Some(RunProperties {
+                        style: Some(ParagraphStyle {
+                            val: style.val.clone(),
+                        }),
+                        ..Default::default()
+                    }),
+                    content: Vec::new(),
+                };
+                let expected = run_css(&self.resolver.run_properties(paragraph, &probe, cell));
+                format!(
+                    " class=\"{}\"{}",
+                    class_name(&style.val),
+                    style_attribute(&css_diff(&css, &expected))
+                )
+            }
+            None => style_attribute(&css_diff(&css, base)),
+        };
+        if attributes.is_empty() {
+            html
+        } else {
+            format!("<span{attributes}>{html}</span>")
+        }
+    }
+
+    /// 图片元素，按选项嵌入数据或记录需要导出的图片
+    fn image(&mut self, drawing: &Drawing) -> String {
+        let Some(relationship) = self
+            .docx
+            .relationships
+            .as_ref()
+            .and_then(|rels| rels.get(&drawing.embed))
+        else {
+            return String::new();
+        };
+        let src = if relationship.external {
+            relationship.target.clone()
+        } else {
+            let path = part_path(&relationship.target);
+            let Some(data) = self.docx.media.get(&path) else {
+                return String::new();
+            };
+            let file_name = path.rsplit('/').next().unwrap_or(&path);
+            match &self.options.image_mode {
+                HtmlImageMode::Embedded => {
+                    format!(
+                        "data:{};base64,{}",
+                        image_mime(file_name),
+                        STANDARD.encode(data)
+                    )
+                }
+                HtmlImageMode::External(dir) => {
+                    let relative = if dir.is_empty() {
+                        file_name.to_string()
+                    } else {
+                        format!("{}/{file_name}", dir.trim_end_matches('/'))
+                    };
+                    self.images.insert(relative.clone(), data.clone());
+                    relative
+                }
+            }
+        };
+        let alt = drawing.description.as_deref().unwrap_or(&drawing.name);
+        // 96 DPI 下每像素 9525 EMU
+        format!(
+            "<img src=\"{}\" alt=\"{}\" width=\"{}\" height=\"{}\">",
+            escape(&src),
+            escape(alt),
+            (drawing.width + 4762) / 9525,
+            (drawing.height + 4762) / 9525
+        )
+    }
+
+    /// 段落样式的完整格式
+    fn paragraph_style_css(&mut self, style_id: &str) -> &Css {
+        let resolver = self.resolver;
+        self.style_css
+            .entry(style_id.to_string())
+            .or_insert_with(|| {
+                let probe = Paragraph {
+                    properties: Some(ParagraphProperties {
+                        style: Some(ParagraphStyle {
+                            val: style_id.to_string(),
+                        }),
+                        ..Default::default()
+                    }),
+                    content: Vec::new(),
+                };
+                block_css(&resolver, &probe, None)
+            })
+    }
+
+    /// 表格单元格的边框：按类名输出时只包括表格的直接格式，表格样式的边框由样式表提供
+    fn table_border(&self, table: &Table) -> Option<String> {
+        let direct = table.properties.as_ref().and_then(|p| p.borders.as_ref());
+        let borders = match (direct, self.options.style_mode) {
+            (Some(borders), _) => borders,
+            (None, HtmlStyleMode::Inline) => {
+                table_style_borders(self.docx, &table_style_id(self.docx, table)?)?
+            }
+            (None, HtmlStyleMode::Classes) => return None,
+        };
+        cell_border(borders)
+    }
+
+    /// 由段落、字符和表格样式生成样式表
+    fn stylesheet(&mut self) -> String {
+        let mut css = String::from(
+            "table { border-collapse: collapse; }\ntd, th { padding: 0 5.4pt; vertical-align: top; }\n",
+        );
+        let probe = Paragraph::default();
+        let normal_run = run_css(&self.resolver.run_properties(&probe, &Run::default(), None));
+        for style in &self.docx.styles.styles {
+            let class = class_name(&style.style_id);
+            match style.style_type {
+                StyleType::Paragraph => {
+                    let declarations = self.paragraph_style_css(&style.style_id);
+                    css.push_str(&format!(".{class} {{ {} }}\n", render_css(declarations)));
+                }
+                StyleType::Character => {
+                    let run = Run {
+                        properties: Some(RunProperties {
+                            style: Some(ParagraphStyle {
+                                val: style.style_id.clone(),
+                            }),
+                            ..Default::default()
+                        }),
+                        content: Vec::new(),
+                    };
+                    let declarations = css_diff(
+                        &run_css(&self.resolver.run_properties(&probe, &run, None)),
+                        &normal_run,
+                    );
+                    if !declarations.is_empty() {
+                        css.push_str(&format!(".{class} {{ {} }}\n", render_css(&declarations)));
+                    }
+                }
+                StyleType::Table => {
+                    let border =
+                        table_style_borders(self.docx, &style.style_id).and_then(cell_border);
+                    if let Some(border) = border {
+                        css.push_str(&format!(
+                            "table.{class} td, table.{class} th {{ border: {border}; }}\n"
+                        ));
+                    }
+                }
+                StyleType::Numbering => {}
+            }
+        }
+        css
+    }
+}
+
+/// 段落的完整格式，包括段落属性和段落默认的文字格式
+fn block_css(resolver: &StyleResolver, paragraph: &Paragraph, cell: Option<&CellContext>) -> Css {
+    let mut css = paragraph_css(&resolver.paragraph_properties(paragraph, cell));
+    css.extend(run_css(&resolver.run_properties(
+        paragraph,
+        &Run::default(),
+        cell,
+    )));
+    css
+}
+
+/// 段落属性对应的 CSS，长度由缇换算为磅
+fn paragraph_css(properties: &ParagraphProperties) -> Css {
+    let pt = |twips: i64| format!("{}pt", twips as f64 / 20.0);
+    let mut css = Css::new();
+    let spacing = properties.spacing.as_ref();
+    css.insert(
+        "margin-top",
+        pt(spacing.and_then(|s| s.before).unwrap_or(0) as i64),
+    );
+    css.insert(
+        "margin-bottom",
+        pt(spacing.and_then(|s| s.after).unwrap_or(0) as i64),
+    );
+    if let Some(line) = spacing.and_then(|s| s.line) {
+        let value = match spacing.and_then(|s| s.line_rule) {
+            Some(LineSpacingRule::Exact) | Some(LineSpacingRule::AtLeast) => pt(line as i64),
+            _ => format!("{}", (line as f64 / 240.0 * 100.0).round() / 100.0),
+        };
+        css.insert("line-height", value);
+    }
+    if let Some(indentation) = &properties.indentation {
+        if let Some(left) = indentation.left {
+            css.insert("margin-left", pt(left as i64));
+        }
+        if let Some(right) = indentation.right {
+            css.insert("margin-right", pt(right as i64));
+        }
+        if let Some(hanging) = indentation.hanging {
+            css.insert("text-indent", pt(-(hanging as i64)));
+        } else if let Some(first_line) = indentation.first_line {
+            css.insert("text-indent", pt(first_line as i64));
+        }
+    }
+    if let Some(justification) = &properties.justification {
+        let align = match justification.val {
+            JustificationVal::Center => "center",
+            JustificationVal::Right | JustificationVal::End => "right",
+            JustificationVal::Both | JustificationVal::Distribute => "justify",
+            _ => "left",
+        };
+        css.insert("text-align", align.to_string());
+    }
+    if let Some(fill) = properties.shading.as_ref().and_then(|s| s.fill.as_deref()) {
+        if let Some(color) = hex_color(fill) {
+            css.insert("background-color", color);
+        }
+    }
+    if let Some(borders) = &properties.borders {
+        let sides = [
+            ("border-top", &borders.top),
+            ("border-right", &borders.right),
+            ("border-bottom", &borders.bottom),
+            ("border-left", &borders.left),
+        ];
+        for (key, border) in sides {
+            if let Some(value) = border.as_ref().and_then(border_css) {
+                css.insert(key, value);
+            }
+        }
+    }
+    css
+}
+
+/// 运行属性对应的 CSS；字号、粗细和斜体总是输出，以覆盖浏览器对标题等元素的默认格式
+fn run_css(properties: &RunProperties) -> Css {
+    let on = |value: Option<OnOff>| value.is_some_and(|v| v.is_on());
+    let mut css = Css::new();
+    if let Some(fonts) = &properties.fonts {
+        let mut families: Vec<&str> = Vec::new();
+        for font in [&fonts.ascii, &fonts.east_asia].into_iter().flatten() {
+            if !families.contains(&font.as_str()) {
+                families.push(font);
+            }
+        }
+        if !families.is_empty() {
+            let families: Vec<String> = families.iter().map(|f| format!("'{f}'")).collect();
+            css.insert("font-family", families.join(", "));
+        }
+    }
+    // 未指定字号时 Word 使用 10 磅
+    let size = properties.size.as_ref().map_or(20, |s| s.val);
+    css.insert("font-size", format!("{}pt", size as f64 / 2.0));
+    let weight = if on(properties.bold) {
+        "bold"
+    } else {
+        "normal"
+    };
+    css.insert("font-weight", weight.to_string());
+    let style = if on(properties.italic) {
+        "italic"
+    } else {
+        "normal"
+    };
+    css.insert("font-style", style.to_string());
+
+    let mut decorations = Vec::new();
+    let underline = properties
+        .underline
+        .as_ref()
+        .and_then(|u| u.val)
+        .is_some_and(|u| u != UnderlineType::None);
+    if underline {
+        decorations.push("underline");
+    }
+    if on(properties.strike) || on(properties.double_strike) {
+        decorations.push("line-through");
+    }
+    if !decorations.is_empty() {
+        css.insert("text-decoration-line", decorations.join(" "));
+    }
+    if let Some(color) = properties.color.as_ref().and_then(|c| hex_color(&c.val)) {
+        css.insert("color", color);
+    }
+    let highlight = properties
+        .highlight
+        .as_ref()
+        .and_then(|h| highlight_color(h.val));
+    let shading = properties
+        .shading
+        .as_ref()
+        .and_then(|s| hex_color(s.fill.as_deref()?));
+    if let Some(background) = highlight.map(str::to_string).or(shading) {
+        css.insert("background-color", background);
+    }
+    match properties.vertical_align.as_ref().map(|v| v.val) {
+        Some(VerticalAlignRun::Superscript) => {
+            css.insert("vertical-align", "super".to_string());
+        }
+        Some(VerticalAlignRun::Subscript) => {
+            css.insert("vertical-align", "sub".to_string());
+        }
+        _ => {}
+    }
+    if on(properties.caps) {
+        css.insert("text-transform", "uppercase".to_string());
+    }
+    if on(properties.small_caps) {
+        css.insert("font-variant", "small-caps".to_string());
+    }
+    css
+}
+
+/// 相对于基础格式变化了的声明；基础格式有而实际格式没有的属性恢复为初始值
+fn css_diff(actual: &Css, base: &Css) -> Css {
+    let mut diff: Css = actual
+        .iter()
+        .filter(|(key, value)| base.get(*key) != Some(*value))
+        .map(|(key, value)| (*key, value.clone()))
+        .collect();
+    for key in base.keys().filter(|key| !actual.contains_key(*key)) {
+        let initial = match *key {
+            "text-decoration-line" | "text-transform" => "none",
+            "vertical-align" => "baseline",
+            "font-variant" | "line-height" => "normal",
+            "background-color" => "transparent",
+            "margin-left" | "margin-right" | "text-indent" => "0",
+            key if key.starts_with("border-") => "none",
+            _ => "initial",
+        };
+        diff.insert(key, initial.to_string());
+    }
+    diff
+}
+
+fn render_css(css: &Css) -> String {
+    css.iter()
+        .map(|(key, value)| format!("{key}: {value};"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn style_attribute(css: &Css) -> String {
+    if css.is_empty() {
+        String::new()
+    } else {
+        format!(" style=\"{}\"", escape(&render_css(css)))
+    }
+}
+
+/// 由样式ID生成合法的 CSS 类名
+fn class_name(style_id: &str) -> String {
+    let mut name: String = style_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// 表格使用的样式ID，未指定时为默认表格样式
+fn table_style_id(docx: &Docx, table: &Table) -> Option<String> {
+    table
+        .properties
+        .as_ref()
+        .and_then(|p| p.style.as_ref())
+        .map(|s| s.val.clone())
+        .or_else(|| {
+            docx.styles
+                .default_style(StyleType::Table)
+                .map(|s| s.style_id.clone())
+        })
+}
+
+/// 表格样式链中最先定义的表格边框
+fn table_style_borders<'a>(docx: &'a Docx, style_id: &str) -> Option<&'a TableBorders> {
+    let mut style = docx.styles.find_style(style_id);
+    for _ in 0..MAX_CHAIN_DEPTH {
+        let current = style?;
+        if let Some(borders) = current
+            .table_properties
+            .as_ref()
+            .and_then(|p| p.borders.as_ref())
+        {
+            return Some(borders);
+        }
+        style = docx.styles.find_style(&current.based_on.as_ref()?.val);
+    }
+    None
+}
+
+/// 单元格边框：HTML 表格不区分内外框线，优先使用内部横线
+fn cell_border(borders: &TableBorders) -> Option<String> {
+    [&borders.inside_h, &borders.top, &borders.left]
+        .into_iter()
+        .flatten()
+        .next()
+        .and_then(border_css)
+}
+
+/// 边框对应的 CSS，宽度以八分之一磅为单位
+fn border_css(border: &Border) -> Option<String> {
+    let style = match border.val.as_str() {
+        "nil" | "none" => return None,
+        "double" => "double",
+        "dotted" => "dotted",
+        "dashed" | "dashSmallGap" | "dotDash" | "dotDotDash" => "dashed",
+        _ => "solid",
+    };
+    let width = border.size.unwrap_or(4).max(2) as f64 / 8.0;
+    let color = border
+        .color
+        .as_deref()
+        .and_then(hex_color)
+        .unwrap_or_else(|| "#000000".to_string());
+    Some(format!("{width}pt {style} {color}"))
+}
+
+/// 十六进制颜色值转换为 CSS 颜色，`auto` 返回 `None`
+fn hex_color(value: &str) -> Option<String> {
+    (value.len() == 6 && value.chars().all(|c| c.is_ascii_hexdigit())).then(|| format!("#{value}"))
+}
+
+fn highlight_color(color: HighlightColor) -> Option<&'static str> {
+    let name = match color {
+        HighlightColor::Black => "black",
+        HighlightColor::Blue => "blue",
+        HighlightColor::Cyan => "cyan",
+        HighlightColor::Green => "lime",
+        HighlightColor::Magenta => "magenta",
+        HighlightColor::Red => "red",
+        HighlightColor::Yellow => "yellow",
+        HighlightColor::White => "white",
+        HighlightColor::DarkBlue => "navy",
+        HighlightColor::DarkCyan => "teal",
+        HighlightColor::DarkGreen => "green",
+        HighlightColor::DarkMagenta => "purple",
+        HighlightColor::DarkRed => "maroon",
+        HighlightColor::DarkYellow => "olive",
+        HighlightColor::DarkGray => "gray",
+        HighlightColor::LightGray => "silver",
+        HighlightColor::None => return None,
+    };
+    Some(name)
+}
+
+/// 有序列表的 `type` 属性，十进制及 HTML 无法表示的格式返回 `None`
+fn list_type(format: NumberFormat) -> Option<&'static str> {
+    match format {
+        NumberFormat::LowerLetter => Some("a"),
+        NumberFormat::UpperLetter => Some("A"),
+        NumberFormat::LowerRoman => Some("i"),
+        NumberFormat::UpperRoman => Some("I"),
+        _ => None,
+    }
+}
+
+fn image_mime(file_name: &str) -> &'static str {
+    let extension = file_name.rsplit('.').next().unwrap_or_default();
+    match extension.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "tif" | "tiff" => "image/tiff",
+        "emf" => "image/emf",
+        "wmf" => "image/wmf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// 转义 HTML 文本和属性值中的特殊字符
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::builder::ParagraphBuilder;
+    use crate::docx::test_utils::docx;
+
+    /// 只输出正文内容的导出选项
+    fn fragment(style_mode: HtmlStyleMode) -> HtmlOptions {
+        HtmlOptions {
+            style_mode,
+            standalone: false,
+            ..Default::default()
+        }
+    }
+
+    /// 包含标题、格式文本、超链接和编号列表的文档
+    fn sample() -> Docx {
+        Docx::builder()
+            .heading("A & B", 1)
+            .paragraph(
+                ParagraphBuilder::new()
+                    .text("plain ")
+                    .bold("bold")
+                    .hyperlink(" site", "https://example.com"),
+            )
+            .numbered_list(["one", "two"])
+            .build()
+    }
+
+    #[test]
+    fn styles_become_css_classes() {
+        let export = sample().export_html(&fragment(HtmlStyleMode::Classes));
+        let lines: Vec<&str> = export.html.lines().collect();
+        assert_eq!(lines[0], "<h1 class=\"Heading1\">A &amp; B</h1>");
+        assert_eq!(
+            lines[1],
+            "<p class=\"Normal\">plain <span style=\"font-weight: bold;\">bold</span>\
+             <a href=\"https://example.com\"><span style=\"color: #0563C1; \
+             text-decoration-line: underline;\"> site</span></a></p>"
+        );
+        assert_eq!(lines[2], "<ol>");
+        assert_eq!(lines[3], "<li class=\"ListParagraph\" style=\"margin-left: 0;\">one</li>");
+        assert!(export.css.contains(
+            ".Heading1 { font-size: 16pt; font-style: normal; font-weight: bold; \
+             margin-bottom: 0pt; margin-top: 12pt; }"
+        ));
+
+        let page = sample().to_html();
+        assert!(page.starts_with("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">"));
+        assert!(page.contains(&format!("<style>\n{}</style>", export.css)));
+    }
+
+    #[test]
+    fn inline_mode_carries_full_formatting() {
+        let export = sample().export_html(&fragment(HtmlStyleMode::Inline));
+        assert!(export.css.is_empty());
+        assert!(!export.html.contains("class="));
+        assert!(export.html.starts_with(
+            "<h1 style=\"font-size: 16pt; font-style: normal; font-weight: bold; \
+             margin-bottom: 0pt; margin-top: 12pt;\">A &amp; B</h1>"
+        ));
+    }
+
+    #[test]
+    fn merged_cells_use_spans() {
+        let cell = |properties: &str, text: &str| {
+            format!(
+                "<w:tc><w:tcPr>{properties}</w:tcPr>\
+                 <w:p><w:r><w:t xml:space=\"preserve\">{text}</w:t></w:r></w:p></w:tc>"
+            )
+        };
+        let docx = docx(&format!(
+            "<w:tbl><w:tblGrid><w:gridCol w:w=\"100\"/><w:gridCol w:w=\"100\"/></w:tblGrid>\
+             <w:tr>{}</w:tr><w:tr>{}{}</w:tr><w:tr>{}{}</w:tr></w:tbl>",
+            cell("<w:gridSpan w:val=\"2\"/>", "wide"),
+            cell("<w:vMerge w:val=\"restart\"/>", "tall"),
+            cell("", "x"),
+            cell("<w:vMerge/>", ""),
+            cell("", "y")
+        ));
+
+        let html = docx.export_html(&fragment(HtmlStyleMode::Classes)).html;
+        let cells: Vec<&str> = html.lines().filter(|l| l.starts_with("<td")).collect();
+        assert_eq!(cells, ["<td colspan=\"2\">", "<td rowspan=\"2\">", "<td>", "<td>"]);
+    }
+}
@@ -0,0 +1,1297 @@
+//! HTML 导入：把 HTML 文档或片段转换为段落、运行、表格和编号
+//!
+//! 支持常见的 HTML 子集：标题使用内置标题样式，段落和块级容器转换为段落，有序列表和
+//! 项目符号列表使用编号定义，表格支持跨行、跨列合并，粗体、斜体、下划线、删除线、上下标、
+//! 链接和以 data URI 内嵌的图片转换为对应的运行格式。元素的 `style` 属性中的常用行内
+//! CSS（颜色、背景色、字号、字体、粗细、对齐等）会映射为段落和运行属性，不支持样式表。
+//!
+//! 片段中 `href="#id"` 引用的元素会添加书签，链接改为指向该书签。
+
+use std::collections::{HashMap, HashSet};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use scraper::{ElementRef, Html, Node};
+
+use crate::error::{OfficeError, Result};
+
+use super::builder::{DocumentBuilder, Image, ParagraphBuilder, DEFAULT_TABLE_WIDTH};
+use super::elements::drawing::{EMU_PER_PIXEL, EMU_PER_TWIP};
+use super::elements::{
+    BodyContent, BookmarkEnd, BookmarkStart, Hyperlink, Paragraph, ParagraphContent, Run,
+    RunContent, Table, TableRow,
+};
+use super::links::link_run;
+use super::markdown_import::bookmark_name;
+use super::numbering::{ListPreset, NumFmt, NumberFormat};
+use super::properties::{
+    Border, Color, HighlightColor, Indentation, Justification, JustificationVal, NumPr, OnOff,
+    ParagraphBorders, ParagraphProperties, ParagraphStyle, RunFonts, RunProperties, Shading,
+    Underline, UnderlineType, Val, VerticalAlignRun, VerticalJc,
+};
+use super::styles::BuiltinStyle;
+use super::Docx;
+
+/// 未指定字号时作为相对字号基准的字号（半磅）
+const BASE_FONT_SIZE: u32 = 24;
+
+impl Docx {
+    /// 把 HTML 文档转换为新文档，使用内置样式
+    ///
+    /// # 参数
+    /// * `html` - HTML 文档，也可以是不含 `<html>`、`<body>` 的片段
+    pub fn from_html(html: &str) -> Docx {
+        let mut docx = DocumentBuilder::new().build();
+        let document = Html::parse_document(html);
+        let content = HtmlReader::new(&mut docx).read(body(&document));
+        docx.document.body.content.extend(content);
+        docx
+    }
+
+    /// 把 HTML 片段转换为正文内容，登记所需的样式、编号、超链接和图片，但不插入文档
+    ///
+    /// # 参数
+    /// * `html` - HTML 片段
+    pub fn create_html_content(&mut self, html: &str) -> Vec<BodyContent> {
+        let fragment = Html::parse_fragment(html);
+        HtmlReader::new(self).read(body(&fragment))
+    }
+
+    /// 在正文指定位置插入 HTML 片段，返回插入的内容数量
+    ///
+    /// # 参数
+    /// * `index` - 正文内容的插入位置
+    /// * `html` - HTML 片段
+    pub fn insert_html(&mut self, index: usize, html: &str) -> Result<usize> {
+        if index > self.document.body.content.len() {
+            return Err(OfficeError::InvalidFormat(format!(
+                "body index {index} out of range"
+            )));
+        }
+        let content = self.create_html_content(html);
+        let count = content.len();
+        self.document.body.content.splice(index..index, content);
+        Ok(count)
+    }
+
+    /// 在正文末尾添加 HTML 片段，返回添加的内容数量
+    ///
+    /// # 参数
+    /// * `html` - HTML 片段
+    pub fn add_html(&mut self, html: &str) -> usize {
+        let content = self.create_html_content(html);
+        let count = content.len();
+        self.document.body.content.extend(content);
+        count
+    }
+}
+
+/// 取 `<body>` 元素，片段没有时取根元素
+fn body(html: &Html) -> ElementRef<'_> {
+    let root = html.root_element();
+    root.child_elements()
+        .find(|e| e.value().name() == "body")
+        .unwrap_or(root)
+}
+
+/// 块级容器的段落格式，由外向内继承
+#[derive(Debug, Clone, Default)]
+struct BlockContext {
+    style: Option<BuiltinStyle>,
+    alignment: Option<JustificationVal>,
+    quote_depth: usize,
+    preformatted: bool,
+}
+
+/// 正在转换的列表
+struct ListState {
+    num_id: i32,
+    level: i32,
+    ordered: bool,
+    /// 当前列表项还没有段落，下一个段落显示编号
+    first: bool,
+}
+
+/// 表格单元格在网格中的位置
+struct CellPlacement<'a> {
+    element: ElementRef<'a>,
+    row: usize,
+    col: usize,
+    row_span: usize,
+    col_span: usize,
+}
+
+/// 递归遍历元素树，生成正文内容
+struct HtmlReader<'d> {
+    docx: &'d mut Docx,
+    blocks: Vec<BodyContent>,
+    paragraph: Option<Paragraph>,
+    /// 已输出的文本以空白结尾，后续文本的前导空白省略
+    space_before: bool,
+    block: BlockContext,
+    lists: Vec<ListState>,
+    link: Option<Hyperlink>,
+    /// 片段中被 `href="#id"` 引用的锚点
+    anchors: HashSet<String>,
+    /// 锚点对应的书签名称
+    bookmarks: HashMap<String, String>,
+    used: HashSet<String>,
+    /// 已添加书签的锚点，重复的 id 只在第一个元素上添加书签
+    placed: HashSet<String>,
+    next_bookmark_id: i32,
+    /// 等待添加到下一个段落的书签
+    pending_bookmarks: Vec<(i32, String)>,
+}
+
+impl<'d> HtmlReader<'d> {
+    fn new(docx: &'d mut Docx) -> Self {
+        let used = docx
+            .paragraphs()
+            .iter()
+            .flat_map(|p| p.bookmarks())
+            .map(|b| b.name.clone())
+            .collect();
+        let next_bookmark_id = docx.next_bookmark_id();
+        HtmlReader {
+            docx,
+            blocks: Vec::new(),
+            paragraph: None,
+            space_before: true,
+            block: BlockContext::default(),
+            lists: Vec::new(),
+            link: None,
+            anchors: HashSet::new(),
+            bookmarks: HashMap::new(),
+            used,
+            placed: HashSet::new(),
+            next_bookmark_id,
+            pending_bookmarks: Vec::new(),
+        }
+    }
+
+    fn read(mut self, root: ElementRef) -> Vec<BodyContent> {
+        let defined: HashSet<&str> = root
+            .descendent_elements()
+            .flat_map(|e| [e.attr("id"), anchor_name(e)])
+            .flatten()
+            .collect();
+        self.anchors = root
+            .descendent_elements()
+            .filter_map(|e| e.attr("href")?.strip_prefix('#'))
+            .filter(|anchor| defined.contains(anchor))
+            .map(str::to_string)
+            .collect();
+        self.children(root, &RunProperties::default());
+        self.finish_paragraph();
+        self.blocks
+    }
+
+    fn children(&mut self, element: ElementRef, format: &RunProperties) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.text(text, format),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child, format);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, element: ElementRef, format: &RunProperties) {
+        let name = element.value().name();
+        if matches!(
+            name,
+            "head" | "title" | "meta" | "link" | "script" | "style" | "template" | "noscript"
+        ) {
+            return;
+        }
+        let css = parse_style(element.attr("style"));
+        let mut format = format.clone();
+        let block = is_block(name);
+        self.inline_format(name, element, &mut format);
+        apply_run_css(&css, &mut format, !block);
+
+        let bookmark = [element.attr("id"), anchor_name(element)]
+            .into_iter()
+            .flatten()
+            .find(|id| self.anchors.contains(*id) && self.placed.insert(id.to_string()))
+            .map(|anchor| {
+                let id = self.next_bookmark_id;
+                self.next_bookmark_id += 1;
+                (id, self.bookmark_name(anchor))
+            });
+        let bookmark = match bookmark {
+            Some(bookmark) if block => {
+                self.finish_paragraph();
+                self.pending_bookmarks.push(bookmark);
+                None
+            }
+            Some((id, name)) => {
+                self.ensure_paragraph()
+                    .content
+                    .push(ParagraphContent::BookmarkStart(BookmarkStart::new(
+                        id, name,
+                    )));
+                Some(id)
+            }
+            None => None,
+        };
+
+        match name {
+            "br" => self.push_run(Run {
+                properties: run_properties(&format),
+                content: vec![RunContent::Break],
+            }),
+            "img" => self.image(element, &css, &format),
+            "a" => self.link(element, &format),
+            "hr" => {
+                self.finish_paragraph();
+                let properties = ParagraphProperties {
+                    borders: Some(ParagraphBorders {
+                        bottom: Some(Border::new("single", 6, "auto")),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                };
+                self.blocks.push(BodyContent::Paragraph(Paragraph {
+                    properties: Some(properties),
+                    content: Vec::new(),
+                }));
+            }
+            "table" => self.table(element, &format),
+            "ul" | "ol" => self.list(element, &css, &format),
+            "li" if !self.lists.is_empty() => self.item(element, &css, &format),
+            _ if block => self.block(name, element, &css, &format),
+            _ => self.children(element, &format),
+        }
+
+        if let Some(id) = bookmark {
+            self.ensure_paragraph()
+                .content
+                .push(ParagraphContent::BookmarkEnd(BookmarkEnd { id }));
+        }
+    }
+
+    /// 按元素名称设置运行格式
+    fn inline_format(&mut self, name: &str, element: ElementRef, format: &mut RunProperties) {
+        match name {
+            "b" | "strong" => format.bold = Some(OnOff::ON),
+            "i" | "em" | "cite" | "dfn" | "var" => format.italic = Some(OnOff::ON),
+            "u" | "ins" => format.underline = Some(single_underline()),
+            "s" | "strike" | "del" => format.strike = Some(OnOff::ON),
+            "sup" => format.vertical_align = Some(Val::new(VerticalAlignRun::Superscript)),
+            "sub" => format.vertical_align = Some(Val::new(VerticalAlignRun::Subscript)),
+            "mark" => format.highlight = Some(Val::new(HighlightColor::Yellow)),
+            "code" | "kbd" | "samp" | "tt" => {
+                let style = self.style_id(BuiltinStyle::HtmlCode);
+                format.style = Some(ParagraphStyle { val: style });
+            }
+            "th" => format.bold = Some(OnOff::ON),
+            "font" => {
+                if let Some(color) = element.attr("color").and_then(parse_color) {
+                    format.color = Some(Color {
+                        val: color,
+                        ..Default::default()
+                    });
+                }
+                if let Some(face) = element.attr("face") {
+                    set_font(format, face);
+                }
+                if let Some(size) = element.attr("size").and_then(font_tag_size) {
+                    format.size = Some(Val::new(size));
+                    format.size_cs = Some(Val::new(size));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn block(
+        &mut self,
+        name: &str,
+        element: ElementRef,
+        css: &[(String, String)],
+        format: &RunProperties,
+    ) {
+        self.finish_paragraph();
+        let saved = self.block.clone();
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse().unwrap_or(1);
+                self.block.style = Some(BuiltinStyle::Heading(level));
+            }
+            "pre" => {
+                self.block.style = Some(BuiltinStyle::HtmlPreformatted);
+                self.block.preformatted = true;
+            }
+            "blockquote" => self.block.quote_depth += 1,
+            "center" => self.block.alignment = Some(JustificationVal::Center),
+            _ => {}
+        }
+        if let Some(alignment) = alignment(element, css) {
+            self.block.alignment = Some(alignment);
+        }
+        let start = self.blocks.len();
+        self.children(element, format);
+        // 空段落和空标题也保留为段落
+        if self.paragraph.is_none()
+            && self.blocks.len() == start
+            && matches!(name, "p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "pre")
+        {
+            self.ensure_paragraph();
+        }
+        self.finish_paragraph();
+        self.block = saved;
+    }
+
+    fn list(&mut self, element: ElementRef, css: &[(String, String)], format: &RunProperties) {
+        self.finish_paragraph();
+        let ordered = element.value().name() == "ol";
+        let start = element
+            .attr("start")
+            .and_then(|s| s.trim().parse::<i32>().ok())
+            .unwrap_or(1);
+        let number_format = css_value(css, "list-style-type")
+            .and_then(list_style_format)
+            .or_else(|| element.attr("type").and_then(list_type_format))
+            .filter(|_| ordered);
+        let level = (self.lists.len() as i32).min(8);
+        let num_id = match self.lists.last() {
+            Some(parent) if parent.ordered == ordered && start == 1 && number_format.is_none() => {
+                parent.num_id
+            }
+            _ => {
+                let preset = if ordered {
+                    ListPreset::Numbered
+                } else {
+                    ListPreset::Bullet
+                };
+                let mut levels = preset.levels();
+                let definition = &mut levels[level as usize];
+                if start != 1 {
+                    definition.set_start(start);
+                }
+                if let Some(val) = number_format {
+                    definition.format = Some(NumFmt { val, format: None });
+                }
+                self.docx.numbering.add_list(levels)
+            }
+        };
+        self.lists.push(ListState {
+            num_id,
+            level,
+            ordered,
+            first: false,
+        });
+        self.children(element, format);
+        self.finish_paragraph();
+        self.lists.pop();
+    }
+
+    fn item(&mut self, element: ElementRef, css: &[(String, String)], format: &RunProperties) {
+        self.finish_paragraph();
+        if let Some(list) = self.lists.last_mut() {
+            list.first = true;
+        }
+        let saved = self.block.clone();
+        if let Some(alignment) = alignment(element, css) {
+            self.block.alignment = Some(alignment);
+        }
+        self.children(element, format);
+        // 空列表项也显示编号
+        if self.paragraph.is_none() && self.lists.last().is_some_and(|l| l.first) {
+            self.ensure_paragraph();
+        }
+        self.finish_paragraph();
+        self.block = saved;
+    }
+
+    fn table(&mut self, element: ElementRef, format: &RunProperties) {
+        self.finish_paragraph();
+        let mut rows = Vec::new();
+        for child in element.child_elements() {
+            match child.value().name() {
+                "caption" => self.block("caption", child, &[], format),
+                "thead" | "tbody" | "tfoot" => {
+                    let header = child.value().name() == "thead";
+                    rows.extend(
+                        child
+                            .child_elements()
+                            .filter(|e| e.value().name() == "tr")
+                            .map(|tr| (tr, header)),
+                    );
+                }
+                "tr" => rows.push((child, false)),
+                _ => {}
+            }
+        }
+
+        // 按跨行、跨列计算各单元格在网格中的位置
+        let mut occupied = HashSet::new();
+        let mut placements = Vec::new();
+        for (row, (tr, _)) in rows.iter().enumerate() {
+            let mut col = 0;
+            for cell in tr
+                .child_elements()
+                .filter(|e| matches!(e.value().name(), "td" | "th"))
+            {
+                while occupied.contains(&(row, col)) {
+                    col += 1;
+                }
+                let span = |name| {
+                    cell.attr(name)
+                        .and_then(|s| s.trim().parse::<usize>().ok())
+                        .unwrap_or(1)
+                        .clamp(1, 1000)
+                };
+                let (row_span, col_span) = (span("rowspan"), span("colspan"));
+                for r in row..row + row_span {
+                    for c in col..col + col_span {
+                        occupied.insert((r, c));
+                    }
+                }
+                placements.push(CellPlacement {
+                    element: cell,
+                    row,
+                    col,
+                    row_span,
+                    col_span,
+                });
+                col += col_span;
+            }
+        }
+        let columns = placements
+            .iter()
+            .map(|p| p.col + p.col_span)
+            .max()
+            .unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+        let row_count = placements
+            .iter()
+            .map(|p| p.row + p.row_span)
+            .max()
+            .unwrap_or(0)
+            .max(rows.len());
+
+        let mut table = self.docx.create_table(
+            vec![vec![ParagraphBuilder::new(); columns]; row_count],
+            false,
+        );
+        for (row, (tr, header)) in rows.iter().enumerate() {
+            let all_th = tr.child_elements().all(|e| e.value().name() == "th");
+            // 只有开头的连续标题行在跨页时重复
+            if (*header || all_th) && table.rows().take(row).all(is_header_row) {
+                if let Some(tr) = table.rows_mut().nth(row) {
+                    tr.properties.get_or_insert_with(Default::default).header = Some(OnOff::ON);
+                }
+            }
+        }
+        for placement in &placements {
+            self.fill_cell(&mut table, placement, format);
+        }
+        for placement in placements
+            .iter()
+            .filter(|p| p.row_span > 1 || p.col_span > 1)
+        {
+            let rows = placement.row..(placement.row + placement.row_span).min(row_count);
+            let cols = placement.col..placement.col + placement.col_span;
+            // 与已合并区域重叠的跨度无法合并，保留为独立单元格
+            let _ = table.merge_cells(rows, cols);
+        }
+        self.blocks.push(BodyContent::Table(table));
+    }
+
+    /// 转换单元格内容，单元格中的列表、引用等格式与外部无关
+    fn fill_cell(&mut self, table: &mut Table, placement: &CellPlacement, format: &RunProperties) {
+        let element = placement.element;
+        let css = parse_style(element.attr("style"));
+        let mut format = format.clone();
+        self.inline_format(element.value().name(), element, &mut format);
+        apply_run_css(&css, &mut format, false);
+
+        let saved_blocks = std::mem::take(&mut self.blocks);
+        let saved_lists = std::mem::take(&mut self.lists);
+        let saved_block = std::mem::replace(
+            &mut self.block,
+            BlockContext {
+                alignment: alignment(element, &css),
+                ..Default::default()
+            },
+        );
+        self.space_before = true;
+        self.children(element, &format);
+        self.finish_paragraph();
+        let mut content = std::mem::replace(&mut self.blocks, saved_blocks);
+        self.lists = saved_lists;
+        self.block = saved_block;
+
+        // 单元格必须以段落结尾
+        if !matches!(content.last(), Some(BodyContent::Paragraph(_))) {
+            content.push(BodyContent::Paragraph(Paragraph::default()));
+        }
+        let Some(cell) = table
+            .rows_mut()
+            .nth(placement.row)
+            .and_then(|r| r.cells_mut().nth(placement.col))
+        else {
+            return;
+        };
+        cell.content = content;
+        let properties = cell.properties.get_or_insert_with(Default::default);
+        if let Some(fill) = css_value(&css, "background-color")
+            .or_else(|| element.attr("bgcolor"))
+            .and_then(parse_color)
+        {
+            properties.shading = Some(fill_shading(fill));
+        }
+        let vertical = css_value(&css, "vertical-align").or_else(|| element.attr("valign"));
+        properties.vertical_align = match vertical.map(|v| v.to_ascii_lowercase()).as_deref() {
+            Some("top") => Some(Val::new(VerticalJc::Top)),
+            Some("middle") => Some(Val::new(VerticalJc::Center)),
+            Some("bottom") => Some(Val::new(VerticalJc::Bottom)),
+            _ => None,
+        };
+    }
+
+    fn link(&mut self, element: ElementRef, format: &RunProperties) {
+        let Some(href) = element.attr("href").map(str::trim) else {
+            self.children(element, format);
+            return;
+        };
+        let mut link = match href.strip_prefix('#') {
+            Some(anchor) => {
+                let anchor = match self.anchors.contains(anchor) {
+                    true => self.bookmark_name(anchor),
+                    false => anchor.to_string(),
+                };
+                Hyperlink::internal(anchor, Vec::new())
+            }
+            None => Hyperlink::external(self.docx.add_hyperlink_relationship(href), Vec::new()),
+        };
+        if let Some(title) = element.attr("title").filter(|t| !t.is_empty()) {
+            link = link.with_tooltip(title);
+        }
+        let mut format = format.clone();
+        if let Some(style) = link_run(String::new()).properties {
+            format.color = format.color.or(style.color);
+            format.underline = format.underline.or(style.underline);
+        }
+        let outer = self.link.replace(link);
+        self.children(element, &format);
+        self.flush_link();
+        self.link = outer;
+    }
+
+    /// 把链接中已转换的运行加入当前段落；链接跨越多个段落时每个段落各有一个超链接
+    fn flush_link(&mut self) {
+        let Some(link) = &mut self.link else {
+            return;
+        };
+        if link.runs.is_empty() {
+            return;
+        }
+        let part = Hyperlink {
+            runs: std::mem::take(&mut link.runs),
+            ..link.clone()
+        };
+        self.ensure_paragraph()
+            .content
+            .push(ParagraphContent::Hyperlink(part));
+    }
+
+    fn image(&mut self, element: ElementRef, css: &[(String, String)], format: &RunProperties) {
+        let alt = element.attr("alt").unwrap_or_default().trim();
+        let Some(mut image) = element
+            .attr("src")
+            .and_then(decode_data_uri)
+            .and_then(|data| Image::from_bytes(data).ok())
+        else {
+            if !alt.is_empty() {
+                self.text(alt, format);
+            }
+            return;
+        };
+        let width = css_value(css, "width")
+            .or_else(|| element.attr("width"))
+            .and_then(parse_pixels);
+        let height = css_value(css, "height")
+            .or_else(|| element.attr("height"))
+            .and_then(parse_pixels);
+        let (original_width, original_height) = (image.width, image.height);
+        match (width, height) {
+            (Some(width), Some(height)) => {
+                image.width = (width * EMU_PER_PIXEL as f64) as i64;
+                image.height = (height * EMU_PER_PIXEL as f64) as i64;
+            }
+            (Some(width), None) => image.scale_to_width((width * EMU_PER_PIXEL as f64) as i64),
+            (None, Some(height)) if original_height > 0 => {
+                image.height = (height * EMU_PER_PIXEL as f64) as i64;
+                image.width = original_width * image.height / original_height;
+            }
+            _ => {}
+        }
+        image.description = (!alt.is_empty()).then(|| alt.to_string());
+        image.fit_width(DEFAULT_TABLE_WIDTH as i64 * EMU_PER_TWIP);
+        let drawing = self.docx.add_image_part(image);
+        self.push_run(Run {
+            properties: run_properties(format),
+            content: vec![RunContent::Drawing(drawing)],
+        });
+        self.space_before = false;
+    }
+
+    fn text(&mut self, text: &str, format: &RunProperties) {
+        if self.block.preformatted {
+            self.preformatted_text(text, format);
+            return;
+        }
+        let collapsed = collapse_whitespace(text);
+        let mut text = collapsed.as_str();
+        if self.space_before {
+            text = text.trim_start_matches(' ');
+        }
+        if text.is_empty() {
+            return;
+        }
+        self.space_before = text.ends_with(' ');
+        self.push_text(text, run_properties(format));
+    }
+
+    /// 保留预格式文本中的空白，换行和制表符转换为换行符和制表符
+    fn preformatted_text(&mut self, text: &str, format: &RunProperties) {
+        let text = text.replace("\r\n", "\n");
+        let mut content = Vec::new();
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                content.push(RunContent::Break);
+            }
+            for (index, part) in line.split('\t').enumerate() {
+                if index > 0 {
+                    content.push(RunContent::Tab);
+                }
+                if !part.is_empty() {
+                    content.push(RunContent::Text(part.to_string()));
+                }
+            }
+        }
+        if !content.is_empty() {
+            self.push_run(Run {
+                properties: run_properties(format),
+                content,
+            });
+        }
+    }
+
+    /// 追加文本，格式与前一个文本运行相同时合并到该运行
+    fn push_text(&mut self, text: &str, properties: Option<RunProperties>) {
+        self.ensure_paragraph();
+        let last = match &mut self.link {
+            Some(link) => link.runs.last_mut(),
+            None => match self.paragraph.as_mut().and_then(|p| p.content.last_mut()) {
+                Some(ParagraphContent::Run(run)) => Some(run),
+                _ => None,
+            },
+        };
+        if let Some(run) = last.filter(|run| run.properties == properties) {
+            if let Some(RunContent::Text(last)) = run.content.last_mut() {
+                last.push_str(text);
+                return;
+            }
+        }
+        self.push_run(Run {
+            properties,
+            content: vec![RunContent::Text(text.to_string())],
+        });
+    }
+
+    fn push_run(&mut self, run: Run) {
+        self.ensure_paragraph();
+        match (&mut self.link, &mut self.paragraph) {
+            (Some(link), _) => link.runs.push(run),
+            (None, Some(paragraph)) => paragraph.content.push(ParagraphContent::Run(run)),
+            (None, None) => unreachable!("a paragraph was just started"),
+        }
+    }
+
+    fn ensure_paragraph(&mut self) -> &mut Paragraph {
+        if self.paragraph.is_none() {
+            let properties = self.paragraph_properties();
+            self.paragraph = Some(Paragraph {
+                properties: (properties != ParagraphProperties::default()).then_some(properties),
+                content: Vec::new(),
+            });
+            self.space_before = true;
+        }
+        self.paragraph
+            .as_mut()
+            .expect("a paragraph was just started")
+    }
+
+    /// 按所在的列表、引用和块级容器生成段落属性
+    fn paragraph_properties(&mut self) -> ParagraphProperties {
+        let mut properties = ParagraphProperties {
+            justification: self.block.alignment.clone().map(|val| Justification { val }),
+            ..Default::default()
+        };
+        let explicit = self.block.style;
+        let builtin = if let Some(list) = self.lists.last_mut() {
+            let (num_id, level, first) = (list.num_id, list.level, list.first);
+            list.first = false;
+            if first && explicit.is_none() {
+                properties.num_pr = Some(NumPr::new(num_id, level));
+            } else {
+                // 列表项中的后续段落与编号后的文字对齐
+                let left = self
+                    .docx
+                    .numbering
+                    .level_paragraph_properties(num_id, level)
+                    .and_then(|p| p.indentation?.left);
+                properties.indentation = left.map(|left| Indentation {
+                    left: Some(left),
+                    ..Default::default()
+                });
+            }
+            Some(explicit.unwrap_or(BuiltinStyle::ListParagraph))
+        } else {
+            explicit.or((self.block.quote_depth > 0).then_some(BuiltinStyle::BlockText))
+        };
+        if let Some(builtin) = builtin {
+            properties.style = Some(ParagraphStyle {
+                val: self.style_id(builtin),
+            });
+        }
+        properties
+    }
+
+    /// 结束当前段落：去掉末尾空白，添加等待中的书签
+    fn finish_paragraph(&mut self) {
+        self.flush_link();
+        let Some(mut paragraph) = self.paragraph.take() else {
+            return;
+        };
+        self.space_before = true;
+        if !self.block.preformatted {
+            trim_end(&mut paragraph);
+        }
+        for (id, name) in self.pending_bookmarks.drain(..) {
+            paragraph.wrap_bookmark(id, name);
+        }
+        self.blocks.push(BodyContent::Paragraph(paragraph));
+    }
+
+    /// 锚点对应的书签名称，首次使用时生成，不与文档中已有的书签重名
+    fn bookmark_name(&mut self, anchor: &str) -> String {
+        if let Some(name) = self.bookmarks.get(anchor) {
+            return name.clone();
+        }
+        let name = bookmark_name(anchor, &self.used);
+        self.used.insert(name.clone());
+        self.bookmarks.insert(anchor.to_string(), name.clone());
+        name
+    }
+
+    fn style_id(&mut self, builtin: BuiltinStyle) -> String {
+        self.docx.styles.ensure_builtin(builtin).style_id.clone()
+    }
+}
+
+/// `<a name="…">` 定义的锚点
+fn anchor_name(element: ElementRef<'_>) -> Option<&str> {
+    match element.value().name() {
+        "a" => element.attr("name"),
+        _ => None,
+    }
+}
+
+/// 是否为转换为独立段落的块级元素
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "div"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "blockquote"
+            | "pre"
+            | "center"
+            | "address"
+            | "article"
+            | "aside"
+            | "section"
+            | "header"
+            | "footer"
+            | "main"
+            | "nav"
+            | "figure"
+            | "figcaption"
+            | "details"
+            | "summary"
+            | "dl"
+            | "dt"
+            | "dd"
+            | "form"
+            | "fieldset"
+            | "legend"
+            | "li"
+            | "ul"
+            | "ol"
+            | "table"
+            | "hr"
+    )
+}
+
+/// 是否为标题行
+fn is_header_row(row: &TableRow) -> bool {
+    row.properties
+        .as_ref()
+        .is_some_and(|p| p.header.is_some_and(|h| h.is_on()))
+}
+
+/// 无格式时返回 `None`
+fn run_properties(format: &RunProperties) -> Option<RunProperties> {
+    (*format != RunProperties::default()).then(|| format.clone())
+}
+
+/// 合并连续空白为一个空格；不间断空格不属于空白
+fn collapse_whitespace(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut space = false;
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            if !space {
+                result.push(' ');
+            }
+            space = true;
+        } else {
+            result.push(c);
+            space = false;
+        }
+    }
+    result
+}
+
+/// 去掉段落末尾文本的空白
+fn trim_end(paragraph: &mut Paragraph) {
+    let run = match paragraph.content.last_mut() {
+        Some(ParagraphContent::Run(run)) => run,
+        Some(ParagraphContent::Hyperlink(link)) => match link.runs.last_mut() {
+            Some(run) => run,
+            None => return,
+        },
+        _ => return,
+    };
+    if let Some(RunContent::Text(text)) = run.content.last_mut() {
+        let len = text.trim_end_matches(' ').len();
+        text.truncate(len);
+        if text.is_empty() {
+            run.content.pop();
+        }
+    }
+    let empty = match paragraph.content.last() {
+        Some(ParagraphContent::Run(run)) => run.content.is_empty(),
+        Some(ParagraphContent::Hyperlink(link)) => {
+            link.runs.last().is_some_and(|r| r.content.is_empty())
+        }
+        _ => false,
+    };
+    if empty {
+        match paragraph.content.last_mut() {
+            Some(ParagraphContent::Hyperlink(link)) if link.runs.len() > 1 => {
+                link.runs.pop();
+            }
+            _ => {
+                paragraph.content.pop();
+            }
+        }
+    }
+}
+
+/// 解码 `data:` URI 中的 base64 数据
+fn decode_data_uri(src: &str) -> Option<Vec<u8>> {
+    let (header, data) = src.trim().strip_prefix("data:")?.split_once(',')?;
+    if !header.ends_with(";base64") {
+        return None;
+    }
+    let data: String = data.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+    STANDARD.decode(data).ok()
+}
+
+/// 解析 `style` 属性为小写属性名和值的列表，忽略 `!important`
+fn parse_style(style: Option<&str>) -> Vec<(String, String)> {
+    let Some(style) = style else {
+        return Vec::new();
+    };
+    style
+        .split(';')
+        .filter_map(|declaration| {
+            let (name, value) = declaration.split_once(':')?;
+            let value = value.trim();
+            let value = value.strip_suffix("!important").unwrap_or(value).trim();
+            Some((name.trim().to_ascii_lowercase(), value.to_string()))
+        })
+        .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+        .collect()
+}
+
+/// 取最后一个同名声明的值
+fn css_value<'a>(css: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    css.iter()
+        .rev()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_str())
+}
+
+/// 把行内 CSS 的文字格式应用到运行格式；块级元素和单元格的背景色不作为文字底纹
+fn apply_run_css(css: &[(String, String)], format: &mut RunProperties, inline: bool) {
+    for (name, value) in css {
+        let lower = value.to_ascii_lowercase();
+        match name.as_str() {
+            "font-weight" => {
+                let bold = match lower.as_str() {
+                    "bold" | "bolder" => true,
+                    "normal" | "lighter" => false,
+                    _ => match lower.parse::<u32>() {
+                        Ok(weight) => weight >= 600,
+                        Err(_) => continue,
+                    },
+                };
+                format.bold = Some(if bold { OnOff::ON } else { OnOff::OFF });
+            }
+            "font-style" => {
+                let italic = matches!(lower.as_str(), "italic" | "oblique");
+                format.italic = Some(if italic { OnOff::ON } else { OnOff::OFF });
+            }
+            "text-decoration" | "text-decoration-line" => {
+                if lower.contains("none") {
+                    format.underline = None;
+                    format.strike = None;
+                }
+                if lower.contains("underline") {
+                    format.underline = Some(single_underline());
+                }
+                if lower.contains("line-through") {
+                    format.strike = Some(OnOff::ON);
+                }
+            }
+            "color" => {
+                if let Some(color) = parse_color(value) {
+                    format.color = Some(Color {
+                        val: color,
+                        ..Default::default()
+                    });
+                }
+            }
+            "background-color" | "background" if inline => {
+                if let Some(fill) = parse_color(value) {
+                    format.shading = Some(fill_shading(fill));
+                }
+            }
+            "font-size" => {
+                let parent = format.size.as_ref().map_or(BASE_FONT_SIZE, |s| s.val);
+                if let Some(size) = parse_font_size(&lower, parent) {
+                    format.size = Some(Val::new(size));
+                    format.size_cs = Some(Val::new(size));
+                }
+            }
+            "font-family" => set_font(format, value),
+            "vertical-align" => match lower.as_str() {
+                "super" => format.vertical_align = Some(Val::new(VerticalAlignRun::Superscript)),
+                "sub" => format.vertical_align = Some(Val::new(VerticalAlignRun::Subscript)),
+                "baseline" => format.vertical_align = None,
+                _ => {}
+            },
+            "font-variant" => {
+                format.small_caps = (lower == "small-caps").then_some(OnOff::ON);
+            }
+            "text-transform" => {
+                format.caps = (lower == "uppercase").then_some(OnOff::ON);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn single_underline() -> Underline {
+    Underline {
+        val: Some(UnderlineType::Single),
+        ..Default::default()
+    }
+}
+
+fn fill_shading(fill: String) -> Shading {
+    Shading {
+        val: "clear".to_string(),
+        color: Some("auto".to_string()),
+        fill: Some(fill),
+        ..Default::default()
+    }
+}
+
+/// 使用字体列表中的第一个字体，通用字体族映射为常用字体
+fn set_font(format: &mut RunProperties, families: &str) {
+    let Some(family) = families
+        .split(',')
+        .map(|f| f.trim().trim_matches(['"', '\'']).trim())
+        .find(|f| !f.is_empty())
+    else {
+        return;
+    };
+    let font = match family.to_ascii_lowercase().as_str() {
+        "monospace" => "Courier New",
+        "serif" => "Times New Roman",
+        "sans-serif" => "Arial",
+        _ => family,
+    }
+    .to_string();
+    format.fonts = Some(RunFonts {
+        ascii: Some(font.clone()),
+        h_ansi: Some(font.clone()),
+        east_asia: Some(font.clone()),
+        cs: Some(font),
+        ..Default::default()
+    });
+}
+
+/// 解析 CSS 字号为半磅，相对字号以上级字号为基准
+fn parse_font_size(value: &str, parent: u32) -> Option<u32> {
+    let points = match value {
+        "xx-small" => 7.5,
+        "x-small" => 7.5,
+        "small" => 10.0,
+        "medium" => 12.0,
+        "large" => 13.5,
+        "x-large" => 18.0,
+        "xx-large" => 24.0,
+        "xxx-large" => 36.0,
+        "smaller" => parent as f64 / 2.0 / 1.2,
+        "larger" => parent as f64 / 2.0 * 1.2,
+        _ => {
+            let number = |suffix: &str| value.strip_suffix(suffix)?.trim().parse::<f64>().ok();
+            if let Some(pt) = number("pt") {
+                pt
+            } else if let Some(px) = number("px") {
+                px * 0.75
+            } else if let Some(em) = number("rem") {
+                em * BASE_FONT_SIZE as f64 / 2.0
+            } else if let Some(em) = number("em") {
+                em * parent as f64 / 2.0
+            } else if let Some(percent) = number("%") {
+                percent / 100.0 * parent as f64 / 2.0
+            } else {
+                return None;
+            }
+        }
+    };
+    let half_points = (points * 2.0).round();
+    (1.0..=3276.0).contains(&half_points).then_some(half_points as u32)
+}
+
+/// `<font size>` 的 1～7 级字号（半磅），支持 `+1`、`-1` 等相对写法
+fn font_tag_size(value: &str) -> Option<u32> {
+    const SIZES: [u32; 7] = [16, 20, 24, 28, 36, 48, 72];
+    let value = value.trim();
+    let level = match value.strip_prefix('+') {
+        Some(delta) => 3 + delta.parse::<i32>().ok()?,
+        None if value.starts_with('-') => 3 + value.parse::<i32>().ok()?,
+        None => value.parse::<i32>().ok()?,
+    };
+    Some(SIZES[level.clamp(1, 7) as usize - 1])
+}
+
+/// 解析 CSS 长度为像素，支持 `px`、`pt` 和不带单位的数字
+fn parse_pixels(value: &str) -> Option<f64> {
+    let value = value.trim().to_ascii_lowercase();
+    let pixels = if let Some(pt) = value.strip_suffix("pt") {
+        pt.trim().parse::<f64>().ok()? / 0.75
+    } else {
+        value
+            .strip_suffix("px")
+            .unwrap_or(&value)
+            .trim()
+            .parse()
+            .ok()?
+    };
+    (pixels > 0.0).then_some(pixels)
+}
+
+/// 解析 CSS 颜色为 6 位大写十六进制，支持 `#rgb`、`#rrggbb`、`rgb()` 和常用颜色名称
+fn parse_color(value: &str) -> Option<String> {
+    let value = value.trim().to_ascii_lowercase();
+    if let Some(hex) = value.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        return match hex.len() {
+            3 | 4 => Some(hex.chars().take(3).flat_map(|c| [c, c]).collect::<String>()),
+            6 | 8 => Some(hex[..6].to_string()),
+            _ => None,
+        }
+        .map(|hex| hex.to_ascii_uppercase());
+    }
+    if let Some(args) = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))
+    {
+        let args = args.strip_suffix(')')?;
+        let channels: Vec<u8> = args
+            .split([',', ' ', '/'])
+            .filter(|s| !s.is_empty())
+            .take(3)
+            .map(|s| match s.strip_suffix('%') {
+                Some(p) => p.parse::<f64>().ok().map(|p| (p * 2.55).round() as u8),
+                None => s.parse::<f64>().ok().map(|v| v.clamp(0.0, 255.0) as u8),
+            })
+            .collect::<Option<_>>()?;
+        if channels.len() != 3 {
+            return None;
+        }
+        return Some(format!(
+            "{:02X}{:02X}{:02X}",
+            channels[0], channels[1], channels[2]
+        ));
+    }
+    let hex = match value.as_str() {
+        "black" => "000000",
+        "white" => "FFFFFF",
+        "red" => "FF0000",
+        "green" => "008000",
+        "blue" => "0000FF",
+        "yellow" => "FFFF00",
+        "gray" | "grey" => "808080",
+        "silver" => "C0C0C0",
+        "maroon" => "800000",
+        "purple" => "800080",
+        "fuchsia" | "magenta" => "FF00FF",
+        "lime" => "00FF00",
+        "olive" => "808000",
+        "navy" => "000080",
+        "teal" => "008080",
+        "aqua" | "cyan" => "00FFFF",
+        "orange" => "FFA500",
+        _ => return None,
+    };
+    Some(hex.to_string())
+}
+
+/// 元素的 `text-align` 或 `align` 对齐方式
+fn alignment(element: ElementRef<'_>, css: &[(String, String)]) -> Option<JustificationVal> {
+    let value = css_value(css, "text-align").or_else(|| element.attr("align"))?;
+    match value.trim().to_ascii_lowercase().as_str() {
+        "left" | "start" => Some(JustificationVal::Left),
+        "center" => Some(JustificationVal::Center),
+        "right" | "end" => Some(JustificationVal::Right),
+        "justify" => Some(JustificationVal::Both),
+        _ => None,
+    }
+}
+
+/// `list-style-type` 对应的编号格式
+fn list_style_format(value: &str) -> Option<NumberFormat> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "decimal" => Some(NumberFormat::Decimal),
+        "lower-alpha" | "lower-latin" => Some(NumberFormat::LowerLetter),
+        "upper-alpha" | "upper-latin" => Some(NumberFormat::UpperLetter),
+        "lower-roman" => Some(NumberFormat::LowerRoman),
+        "upper-roman" => Some(NumberFormat::UpperRoman),
+        _ => None,
+    }
+}
+
+/// `<ol type>` 对应的编号格式
+fn list_type_format(value: &str) -> Option<NumberFormat> {
+    match value.trim() {
+        "1" => Some(NumberFormat::Decimal),
+        "a" => Some(NumberFormat::LowerLetter),
+        "A" => Some(NumberFormat::UpperLetter),
+        "i" => Some(NumberFormat::LowerRoman),
+        "I" => Some(NumberFormat::UpperRoman),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::elements::HyperlinkTarget;
+    use crate::docx::test_utils::{body_xml, docx, p};
+
+    #[test]
+    fn inline_css_and_links_become_properties() {
+        let docx = Docx::from_html(
+            "<html><body><h2 id=\"sec\">Section</h2>\
+             <p style=\"text-align: center; color: #ff0000; font-size: 14pt\">a <b>b</b>\
+             x<sup>2</sup> <a href=\"#sec\">go</a> <a href=\"https://example.com\">ext</a></p>\
+             </body></html>",
+        );
+
+        let xml = body_xml(&docx);
+        assert!(xml.starts_with(
+            "<w:p><w:pPr><w:pStyle w:val=\"Heading2\"/></w:pPr>\
+             <w:bookmarkStart w:id=\"0\" w:name=\"sec\"/>"
+        ));
+        let red = "<w:color w:val=\"FF0000\"/><w:sz w:val=\"28\"/><w:szCs w:val=\"28\"/>";
+        for expected in [
+            "<w:p><w:pPr><w:jc w:val=\"center\"/></w:pPr>".to_string(),
+            format!("<w:r><w:rPr><w:b/>{red}</w:rPr><w:t xml:space=\"preserve\">b</w:t></w:r>"),
+            format!("{red}<w:vertAlign w:val=\"superscript\"/></w:rPr>"),
+        ] {
+            assert!(xml.contains(&expected), "{expected}");
+        }
+
+        let paragraphs = docx.paragraphs();
+        let targets: Vec<Option<HyperlinkTarget>> = paragraphs[1]
+            .hyperlinks()
+            .map(|link| docx.hyperlink_target(link))
+            .collect();
+        assert_eq!(
+            targets,
+            [
+                Some(HyperlinkTarget::Bookmark("sec".to_string())),
+                Some(HyperlinkTarget::Url("https://example.com".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_and_merged_cells() {
+        let docx = Docx::from_html(
+            "<ol><li>one<ul><li>inner</li></ul></li><li>two</li></ol>\
+             <table><tr><td colspan=\"2\">wide</td></tr>\
+             <tr><td rowspan=\"2\">tall</td><td>x</td></tr><tr><td>y</td></tr></table>",
+        );
+
+        let labels: Vec<Option<String>> = docx
+            .list_labels()
+            .into_iter()
+            .take(3)
+            .map(|label| label.map(|l| l.text))
+            .collect();
+        assert_eq!(labels, [Some("1.".into()), Some("o".into()), Some("2.".into())]);
+
+        let BodyContent::Table(table) = &docx.document.body.content[3] else {
+            panic!("table expected")
+        };
+        assert_eq!(table.to_string_grid(), [["wide", ""], ["tall", "x"], ["", "y"]]);
+        let xml = body_xml(&docx);
+        assert!(xml.contains("<w:gridSpan w:val=\"2\"/>"));
+        assert!(xml.contains("<w:vMerge w:val=\"restart\"/>"));
+        assert!(xml.contains("<w:vMerge/></w:tcPr><w:p/></w:tc>"));
+    }
+
+    #[test]
+    fn fragments_are_inserted_at_index() {
+        let mut docx = docx(&format!("{}{}", p("a"), p("d")));
+        assert_eq!(docx.insert_html(1, "<p>b</p><p>c</p>").unwrap(), 2);
+        assert!(docx.insert_html(5, "<p>x</p>").is_err());
+        assert_eq!(docx.add_html("<p>e</p>"), 1);
+        assert_eq!(body_xml(&docx), ["a", "b", "c", "d", "e"].map(p).concat());
+    }
+}
@@ -0,0 +1,199 @@
+//! 超链接与书签：创建外部链接和内部链接、管理书签、解析链接目标
+//!
+//! 外部链接的地址保存在文档关系中，超链接元素只引用关系ID；内部链接通过
+//! `w:anchor` 引用书签名称。书签ID在文档中唯一，名称在文档中也应唯一。
+
+use crate::common::relations::types;
+use crate::error::{OfficeError, Result};
+
+use super::elements::{
+    BookmarkStart, Hyperlink, HyperlinkTarget, ParagraphContent, Run, RunContent,
+};
+use super::properties::{Color, RunProperties, Underline, UnderlineType};
+use super::Docx;
+
+impl Docx {
+    /// 创建指向外部地址的超链接，并登记外部关系；不插入文档
+    ///
+    /// # 参数
+    /// * `text` - 显示文本
+    /// * `url` - 链接地址
+    pub fn create_hyperlink(&mut self, text: impl Into<String>, url: &str) -> Hyperlink {
+        Hyperlink::external(
+            self.add_hyperlink_relationship(url),
+            vec![link_run(text.into())],
+        )
+    }
+
+    /// 创建指向文档内书签的超链接；不插入文档
+    ///
+    /// # 参数
+    /// * `text` - 显示文本
+    /// * `bookmark` - 书签名称
+    pub fn create_internal_link(&self, text: impl Into<String>, bookmark: &str) -> Hyperlink {
+        Hyperlink::internal(bookmark, vec![link_run(text.into())])
+    }
+
+    /// 将外部地址登记为超链接关系，返回关系ID；相同地址复用已有关系
+    ///
+    /// # 参数
+    /// * `url` - 链接地址
+    pub fn add_hyperlink_relationship(&mut self, url: &str) -> String {
+        self.relationships
+            .get_or_insert_with(Default::default)
+            .add(types::HYPERLINK, url, true)
+    }
+
+    /// 按文档顺序获取所有超链接，包括表格中的超链接
+    pub fn hyperlinks(&self) -> Vec<&Hyperlink> {
+        self.paragraphs()
+            .into_iter()
+            .flat_map(|p| p.hyperlinks())
+            .collect()
+    }
+
+    /// 解析超链接的目标地址或书签
+    ///
+    /// # 参数
+    /// * `hyperlink` - 超链接
+    pub fn hyperlink_target(&self, hyperlink: &Hyperlink) -> Option<HyperlinkTarget> {
+        hyperlink.target(self.relationships.as_ref())
+    }
+
+    /// 按文档顺序获取所有书签
+    pub fn bookmarks(&self) -> Vec<&BookmarkStart> {
+        self.paragraphs()
+            .into_iter()
+            .flat_map(|p| p.bookmarks())
+            .collect()
+    }
+
+    /// 查找书签所在段落的序号，序号与 [`Docx::paragraphs`] 的顺序一致
+    ///
+    /// # 参数
+    /// * `name` - 书签名称
+    pub fn find_bookmark(&self, name: &str) -> Option<usize> {
+        self.paragraphs()
+            .iter()
+            .position(|p| p.bookmarks().any(|b| b.name == name))
+    }
+
+    /// 获取下一个未使用的书签ID
+    pub fn next_bookmark_id(&self) -> i32 {
+        self.bookmarks().iter().map(|b| b.id + 1).max().unwrap_or(0)
+    }
+
+    /// 为指定段落添加包围全部内容的书签，返回书签ID
+    ///
+    /// # 参数
+    /// * `paragraph_index` - 段落序号，与 [`Docx::paragraphs`] 的顺序一致
+    /// * `name` - 书签名称，不能与已有书签重复
+    pub fn add_bookmark(&mut self, paragraph_index: usize, name: &str) -> Result<i32> {
+        if name.is_empty() || self.find_bookmark(name).is_some() {
+            return Err(OfficeError::InvalidFormat(format!(
+                "bookmark name `{name}` is empty or already used"
+            )));
+        }
+        let id = self.next_bookmark_id();
+        let paragraph = self
+            .paragraphs_mut()
+            .into_iter()
+            .nth(paragraph_index)
+            .ok_or_else(|| {
+                OfficeError::InvalidFormat(format!("paragraph {paragraph_index} not found"))
+            })?;
+        paragraph.wrap_bookmark(id, name);
+        Ok(id)
+    }
+
+    /// 删除指定名称的书签，指向它的内部链接保持不变；书签不存在时返回 `false`
+    ///
+    /// # 参数
+    /// * `name` - 书签名称
+    pub fn remove_bookmark(&mut self, name: &str) -> bool {
+        let Some(id) = self
+            .bookmarks()
+            .iter()
+            .find(|b| b.name == name)
+            .map(|b| b.id)
+        else {
+            return false;
+        };
+        for paragraph in self.paragraphs_mut() {
+            paragraph.content.retain(|item| match item {
+                ParagraphContent::BookmarkStart(b) => b.id != id,
+                ParagraphContent::BookmarkEnd(b) => b.id != id,
+                _ => true,
+            });
+        }
+        true
+    }
+}
+
+/// 生成超链接的显示文本运行，使用与 Word 超链接样式一致的蓝色单下划线
+pub(crate) fn link_run(text: String) -> Run {
+    Run {
+        properties: Some(RunProperties {
+            color: Some(Color {
+                val: "0563C1".to_string(),
+                ..Default::default()
+            }),
+            underline: Some(Underline {
+                val: Some(UnderlineType::Single),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        content: vec![RunContent::Text(text)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::builder::ParagraphBuilder;
+    use crate::docx::test_utils::{body_xml, docx, p};
+
+    #[test]
+    fn hyperlinks_resolve_to_urls_and_bookmarks() {
+        let mut docx = docx(&p("target"));
+        let first = docx.add_hyperlink_relationship("https://example.com");
+        assert_eq!(docx.add_hyperlink_relationship("https://example.com"), first);
+
+        docx.add_paragraph(
+            ParagraphBuilder::new()
+                .hyperlink("site", "https://example.com")
+                .internal_link("back", "top"),
+        );
+        let links = docx.hyperlinks();
+        let targets: Vec<_> = links.iter().filter_map(|h| docx.hyperlink_target(h)).collect();
+        assert_eq!(
+            targets,
+            [
+                HyperlinkTarget::Url("https://example.com".to_string()),
+                HyperlinkTarget::Bookmark("top".to_string())
+            ]
+        );
+        assert!(body_xml(&docx).contains("<w:hyperlink w:anchor=\"top\""));
+    }
+
+    #[test]
+    fn bookmarks_are_added_found_and_removed() {
+        let mut docx = docx(&format!("{}{}", p("one"), p("two")));
+        assert_eq!(docx.add_bookmark(1, "second").unwrap(), 0);
+        assert_eq!(docx.add_bookmark(0, "first").unwrap(), 1);
+        assert!(docx.add_bookmark(0, "second").is_err());
+        assert!(docx.add_bookmark(5, "missing").is_err());
+
+        assert_eq!(docx.find_bookmark("second"), Some(1));
+        let xml = body_xml(&docx);
+        assert!(xml.contains("<w:bookmarkStart w:id=\"0\" w:name=\"second\"/>"), "{xml}");
+        assert!(xml.contains("<w:bookmarkEnd w:id=\"0\"/></w:p>"), "{xml}");
+
+        assert!(docx.remove_bookmark("second"));
+        assert!(!docx.remove_bookmark("second"));
+        assert_eq!(docx.find_bookmark("second"), None);
+        assert!(!body_xml(&docx).contains("w:id=\"0\""));
+        assert_eq!(docx.next_bookmark_id(), 2);
+    }
+}
@@ -0,0 +1,282 @@
+//! 列表编号：按文档顺序为列表段落计数并生成编号文本
+//!
+//! 引用同一抽象编号的编号共享计数器；带有 `w:lvlOverride` 的编号是独立的列表实例，
+//! 单独计数。段落编号后，更深的级别按各自的 `w:lvlRestart` 重新开始。
+
+use std::collections::HashMap;
+
+use super::elements::{BodyContent, Paragraph, TableRow};
+use super::numbering::{LevelSuffix, NumberFormat, Numbering};
+use super::style_resolver::{CellContext, StyleResolver};
+use super::styles::Styles;
+use super::Docx;
+
+/// 编号级别数
+const LEVEL_COUNT: usize = 9;
+
+/// 列表段落的编号
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListLabel {
+    /// 编号ID
+    pub num_id: i32,
+    /// 级别
+    pub level: i32,
+    /// 当前级别的编号值
+    pub value: i32,
+    /// 编号文本，如 `1.2`、`(a)`、`第一章`；项目符号为符号字符
+    pub text: String,
+    /// 当前级别的编号格式
+    pub format: NumberFormat,
+    /// 编号之后的分隔方式
+    pub suffix: LevelSuffix,
+}
+
+impl ListLabel {
+    /// 判断是否为项目符号
+    pub fn is_bullet(&self) -> bool {
+        self.format == NumberFormat::Bullet
+    }
+}
+
+/// 计数器所属的列表实例
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ListKey {
+    /// 共享计数的抽象编号
+    Abstract(i32),
+    /// 带级别覆盖的独立编号实例
+    Instance(i32),
+}
+
+/// 列表编号器，须按文档顺序依次传入段落
+#[derive(Debug, Clone)]
+pub struct ListNumberer<'a> {
+    resolver: StyleResolver<'a>,
+    numbering: &'a Numbering,
+    /// 各列表实例每一级的当前编号，`None` 表示尚未开始或已重新开始
+    counters: HashMap<ListKey, [Option<i32>; LEVEL_COUNT]>,
+}
+
+impl Docx {
+    /// 创建从文档开头计数的列表编号器
+    pub fn list_numberer(&self) -> ListNumberer<'_> {
+        ListNumberer::new(&self.styles, &self.numbering)
+    }
+
+    /// 按文档顺序计算所有段落（包括表格中的段落）的编号，
+    /// 结果与 [`Docx::paragraphs`] 的顺序一致，非列表段落对应 `None`
+    pub fn list_labels(&self) -> Vec<Option<ListLabel>> {
+        let mut numberer = self.list_numberer();
+        let mut labels = Vec::new();
+        numberer.walk(&self.document.body.content, None, &mut labels);
+        labels
+    }
+
+    /// 按文档顺序获取所有段落，包括表格单元格和内容控件中的段落
+    pub fn paragraphs(&self) -> Vec<&Paragraph> {
+        fn collect<'a>(content: &'a [BodyContent], paragraphs: &mut Vec<&'a Paragraph>) {
+            for item in content {
+                match item {
+                    BodyContent::Paragraph(p) => paragraphs.push(p),
+                    BodyContent::Table(table) => table
+                        .rows()
+                        .flat_map(TableRow::cells)
+                        .for_each(|cell| collect(&cell.content, paragraphs)),
+                    BodyContent::Sdt(sdt) => collect(&sdt.content, paragraphs),
+                }
+            }
+        }
+
+        let mut paragraphs = Vec::new();
+        collect(&self.document.body.content, &mut paragraphs);
+        paragraphs
+    }
+
+    /// 按文档顺序获取所有段落的可变引用，顺序与 [`Docx::paragraphs`] 一致
+    pub fn paragraphs_mut(&mut self) -> Vec<&mut Paragraph> {
+        fn collect<'a>(content: &'a mut [BodyContent], paragraphs: &mut Vec<&'a mut Paragraph>) {
+            for item in content {
+                match item {
+                    BodyContent::Paragraph(p) => paragraphs.push(p),
+                    BodyContent::Table(table) => table
+                        .rows_mut()
+                        .flat_map(TableRow::cells_mut)
+                        .for_each(|cell| collect(&mut cell.content, paragraphs)),
+                    BodyContent::Sdt(sdt) => collect(&mut sdt.content, paragraphs),
+                }
+            }
+        }
+
+        let mut paragraphs = Vec::new();
+        collect(&mut self.document.body.content, &mut paragraphs);
+        paragraphs
+    }
+}
+
+impl<'a> ListNumberer<'a> {
+    /// 创建列表编号器
+    ///
+    /// # 参数
+    /// * `styles` - 样式集合
+    /// * `numbering` - 编号定义
+    pub fn new(styles: &'a Styles, numbering: &'a Numbering) -> Self {
+        ListNumberer {
+            resolver: StyleResolver::new(styles, numbering),
+            numbering,
+            counters: HashMap::new(),
+        }
+    }
+
+    /// 为下一个段落计数并生成编号，段落不属于列表时返回 `None`
+    ///
+    /// # 参数
+    /// * `paragraph` - 段落
+    /// * `cell` - 段落所在的表格单元格，不在表格中时为 `None`
+    pub fn next_label(
+        &mut self,
+        paragraph: &Paragraph,
+        cell: Option<&CellContext>,
+    ) -> Option<ListLabel> {
+        let (num_id, level) = self.resolver.list_level(paragraph, cell)?;
+        self.count(num_id, level)
+    }
+
+    /// 在指定编号和级别上计数一次并生成编号
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    /// * `level` - 级别
+    pub fn count(&mut self, num_id: i32, level: i32) -> Option<ListLabel> {
+        let numbering = self.numbering;
+        let index = usize::try_from(level).ok().filter(|&l| l < LEVEL_COUNT)?;
+        let definition = numbering.resolve_level(num_id, level)?;
+        let key = if numbering.has_overrides(num_id) {
+            ListKey::Instance(num_id)
+        } else {
+            ListKey::Abstract(numbering.abstract_num_id(num_id)?)
+        };
+
+        let counters = self.counters.entry(key).or_default();
+        let value = counters[index].map_or(definition.start, |v| v + 1);
+        counters[index] = Some(value);
+
+        // 更深的级别按 lvlRestart 重新开始：未指定时在任一上级之后重新开始，
+        // 为 0 时从不重新开始，为 n 时在第 n 级（从 1 开始计）或更高级别之后重新开始
+        for (deeper, counter) in counters.iter_mut().enumerate().skip(index + 1) {
+            let restart = numbering
+                .resolve_level(num_id, deeper as i32)
+                .and_then(|d| d.restart);
+            if restart.is_none_or(|r| r > level) {
+                *counter = None;
+            }
+        }
+
+        // 尚未出现过的上级按其起始值显示
+        let values: Vec<i32> = (0..LEVEL_COUNT)
+            .map(|l| {
+                counters[l].unwrap_or_else(|| {
+                    numbering
+                        .resolve_level(num_id, l as i32)
+                        .map_or(1, |d| d.start)
+                })
+            })
+            .collect();
+
+        Some(ListLabel {
+            num_id,
+            level,
+            value,
+            text: numbering.render_label(num_id, &definition, &values),
+            format: definition.format,
+            suffix: definition.suffix,
+        })
+    }
+
+    /// 重置所有计数器
+    pub fn reset(&mut self) {
+        self.counters.clear();
+    }
+
+    /// 深度优先遍历主体内容，为每个段落计数
+    fn walk(
+        &mut self,
+        content: &[BodyContent],
+        cell: Option<&CellContext>,
+        labels: &mut Vec<Option<ListLabel>>,
+    ) {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(p) => labels.push(self.next_label(p, cell)),
+                BodyContent::Table(table) => {
+                    for (row_index, row) in table.rows().enumerate() {
+                        let mut col = row
+                            .properties
+                            .as_ref()
+                            .and_then(|p| p.grid_before.as_ref())
+                            .map_or(0, |g| g.val as usize);
+                        for table_cell in row.cells() {
+                            let context = CellContext::new(table, row_index, col);
+                            self.walk(&table_cell.content, Some(&context), labels);
+                            col += table_cell.properties.as_ref().map_or(1, |p| p.span());
+                        }
+                    }
+                }
+                BodyContent::Sdt(sdt) => self.walk(&sdt.content, cell, labels),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::docx::numbering::Numbering;
+    use crate::docx::test_utils::docx;
+
+    const NUMBERING: &str = "<w:numbering xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+        <w:abstractNum w:abstractNumId=\"0\">\
+        <w:lvl w:ilvl=\"0\"><w:start w:val=\"1\"/><w:numFmt w:val=\"decimal\"/><w:lvlText w:val=\"%1.\"/></w:lvl>\
+        <w:lvl w:ilvl=\"1\"><w:start w:val=\"1\"/><w:numFmt w:val=\"lowerLetter\"/><w:lvlText w:val=\"%1.%2)\"/></w:lvl>\
+        <w:lvl w:ilvl=\"2\"><w:start w:val=\"1\"/><w:numFmt w:val=\"upperRoman\"/><w:lvlRestart w:val=\"0\"/>\
+        <w:lvlText w:val=\"(%3)\"/></w:lvl></w:abstractNum>\
+        <w:num w:numId=\"1\"><w:abstractNumId w:val=\"0\"/></w:num>\
+        <w:num w:numId=\"2\"><w:abstractNumId w:val=\"0\"/></w:num>\
+        <w:num w:numId=\"3\"><w:abstractNumId w:val=\"0\"/>\
+        <w:lvlOverride w:ilvl=\"0\"><w:startOverride w:val=\"5\"/></w:lvlOverride></w:num>\
+        </w:numbering>";
+
+    /// 按（编号ID，级别）生成列表段落，返回各段落的编号文本
+    fn labels(items: &[(i32, i32)]) -> Vec<String> {
+        let body: String = items
+            .iter()
+            .map(|(num_id, level)| {
+                format!(
+                    "<w:p><w:pPr><w:numPr><w:ilvl w:val=\"{level}\"/><w:numId w:val=\"{num_id}\"/>\
+                     </w:numPr></w:pPr><w:r><w:t>x</w:t></w:r></w:p>"
+                )
+            })
+            .collect();
+        let mut docx = docx(&format!("{body}<w:p/>"));
+        docx.numbering = Numbering::from_xml(NUMBERING).unwrap();
+
+        let labels = docx.list_labels();
+        assert!(labels.last().unwrap().is_none());
+        labels.into_iter().flatten().map(|label| label.text).collect()
+    }
+
+    #[test]
+    fn deeper_levels_restart_after_parent() {
+        let texts = labels(&[(1, 0), (1, 1), (1, 1), (1, 0), (1, 1)]);
+        assert_eq!(texts, ["1.", "1.a)", "1.b)", "2.", "2.a)"]);
+    }
+
+    #[test]
+    fn level_restart_zero_keeps_counting() {
+        let texts = labels(&[(1, 0), (1, 2), (1, 0), (1, 2)]);
+        assert_eq!(texts, ["1.", "(I)", "2.", "(II)"]);
+    }
+
+    #[test]
+    fn nums_share_abstract_counters_unless_overridden() {
+        let texts = labels(&[(1, 0), (2, 0), (3, 0), (3, 0), (1, 0)]);
+        assert_eq!(texts, ["1.", "2.", "5.", "6.", "3."]);
+    }
+}
@@ -0,0 +1,663 @@
+//! Markdown 导出：把文档转换为 CommonMark/GFM
+//!
+//! 标题按段落的大纲级别输出为 ATX 标题，列表按编号定义区分有序列表和项目符号列表并按级别
+//! 缩进，表格输出为 GFM 管道表格（第一行作为表头，合并区域的内容只出现在左上角）。粗体、
+//! 斜体和删除线取自计算后的运行属性，标题样式自带的粗体不重复标记。图片以相对路径引用，
+//! 图片数据随导出结果一并返回。
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::error::Result;
+
+use super::elements::{
+    BodyContent, Drawing, Hyperlink, HyperlinkTarget, Paragraph, ParagraphContent, Run, RunContent,
+    Table, TableRow,
+};
+use super::list_numbering::ListLabel;
+use super::properties::OnOff;
+use super::style_resolver::{CellContext, StyleResolver};
+use super::{part_path, Docx};
+
+/// Markdown 导出选项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownOptions {
+    /// 图片文件相对于 Markdown 文件的目录，为空时与 Markdown 文件位于同一目录
+    pub image_dir: String,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        MarkdownOptions {
+            image_dir: "images".to_string(),
+        }
+    }
+}
+
+/// Markdown 导出结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkdownExport {
+    /// Markdown 文本
+    pub markdown: String,
+    /// 引用的图片，键为相对于 Markdown 文件的路径
+    pub images: BTreeMap<String, Vec<u8>>,
+}
+
+impl Docx {
+    /// 把文档转换为 Markdown 文本，图片按默认选项以 `images/` 下的相对路径引用
+    pub fn to_markdown(&self) -> String {
+        self.export_markdown(&MarkdownOptions::default()).markdown
+    }
+
+    /// 把文档转换为 Markdown，返回文本和引用的图片
+    ///
+    /// # 参数
+    /// * `options` - 导出选项
+    pub fn export_markdown(&self, options: &MarkdownOptions) -> MarkdownExport {
+        let labels = self.list_labels();
+        let mut writer = MarkdownWriter {
+            docx: self,
+            resolver: self.style_resolver(),
+            options,
+            anchors: heading_anchors(self, &labels),
+            labels: labels.into_iter(),
+            images: BTreeMap::new(),
+            markdown: String::new(),
+            list_indents: None,
+            in_table: false,
+        };
+        writer.blocks(&self.document.body.content);
+        if !writer.markdown.is_empty() {
+            writer.markdown.push('\n');
+        }
+        MarkdownExport {
+            markdown: writer.markdown,
+            images: writer.images,
+        }
+    }
+
+    /// 把文档保存为 Markdown 文件，图片保存到相对于该文件的图片目录
+    ///
+    /// # 参数
+    /// * `path` - Markdown 文件路径
+    /// * `options` - 导出选项
+    pub fn save_markdown<P: AsRef<Path>>(&self, path: P, options: &MarkdownOptions) -> Result<()> {
+        let path = path.as_ref();
+        let export = self.export_markdown(options);
+        std::fs::write(path, &export.markdown)?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        for (relative, data) in &export.images {
+            let image_path = base.join(relative);
+            if let Some(dir) = image_path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(image_path, data)?;
+        }
+        Ok(())
+    }
+}
+
+/// 一段格式相同的行内文本
+struct Span {
+    bold: bool,
+    italic: bool,
+    strike: bool,
+    /// 已转义的 Markdown 文本
+    text: String,
+}
+
+struct MarkdownWriter<'a> {
+    docx: &'a Docx,
+    resolver: StyleResolver<'a>,
+    options: &'a MarkdownOptions,
+    /// 书签名称到标题锚点的映射
+    anchors: HashMap<String, String>,
+    /// 按 [`Docx::paragraphs`] 顺序排列的列表编号，遍历段落时依次取出
+    labels: std::vec::IntoIter<Option<ListLabel>>,
+    images: BTreeMap<String, Vec<u8>>,
+    markdown: String,
+    /// 当前列表各级别的缩进宽度，不在列表中时为 `None`
+    list_indents: Option<Vec<usize>>,
+    /// 是否正在输出表格，表格中（包括嵌套表格）的换行输出为 `<br>`
+    in_table: bool,
+}
+
+impl MarkdownWriter<'_> {
+    /// 追加一个块，列表项之间不空行
+    fn push_block(&mut self, block: &str, list_item: bool) {
+        if !self.markdown.is_empty() {
+            let tight = list_item && self.list_indents.is_some();
+            self.markdown.push_str(if tight { "\n" } else { "\n\n" });
+        }
+        self.markdown.push_str(block);
+    }
+
+    fn blocks(&mut self, content: &[BodyContent]) {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(paragraph) => self.paragraph(paragraph),
+                BodyContent::Table(table) => self.table(table),
+                BodyContent::Sdt(sdt) => self.blocks(&sdt.content),
+            }
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: &Paragraph) {
+        let label = self.labels.next().flatten();
+        let heading = heading_level(&self.resolver, paragraph);
+        let text = self.inline(paragraph, None, heading.is_some());
+        if text.trim().is_empty() {
+            return;
+        }
+
+        if let Some(level) = heading {
+            let prefix = label
+                .map(|l| format!("{} ", escape(&l.text)))
+                .unwrap_or_default();
+            let hashes = "#".repeat(usize::from(level.min(6)));
+            self.list_indents = None;
+            self.push_block(&format!("{hashes} {prefix}{}", text.trim()), false);
+            return;
+        }
+
+        let Some(label) = label else {
+            self.list_indents = None;
+            self.push_block(&escape_block_start(text.trim()), false);
+            return;
+        };
+        let marker = if label.is_bullet() {
+            "-".to_string()
+        } else {
+            format!("{}.", label.value)
+        };
+        let level = usize::try_from(label.level).unwrap_or(0);
+        let mut indents = self.list_indents.clone().unwrap_or_default();
+        indents.truncate(level);
+        while indents.len() < level {
+            indents.push(2);
+        }
+        let indent: usize = indents.iter().sum();
+        indents.push(marker.len() + 1);
+        let continuation = format!("\n{}", " ".repeat(indent + marker.len() + 1));
+        let item = format!(
+            "{}{marker} {}",
+            " ".repeat(indent),
+            text.trim().replace('\n', &continuation)
+        );
+        self.push_block(&item, true);
+        self.list_indents = Some(indents);
+    }
+
+    fn table(&mut self, table: &Table) {
+        let view = table.grid_view();
+        let mut grid = vec![vec![String::new(); view.columns()]; view.rows()];
+        self.in_table = true;
+        for (row_index, row) in table.rows().enumerate() {
+            for (cell_index, cell) in row.cells().enumerate() {
+                // 纵向合并的延续单元格不输出内容，但其中的段落仍须消耗列表编号
+                let position = view
+                    .cells()
+                    .iter()
+                    .find(|c| c.row == row_index && c.cell_index == cell_index);
+                let context = position.map(|c| CellContext::new(table, c.row, c.col));
+                let text = self.cell(&cell.content, context.as_ref());
+                if let Some(position) = position {
+                    grid[position.row][position.col] = text;
+                }
+            }
+        }
+        self.in_table = false;
+        if grid.is_empty() || view.columns() == 0 {
+            return;
+        }
+
+        let line = |cells: &[String]| format!("| {} |", cells.join(" | "));
+        let mut lines = vec![line(&grid[0])];
+        lines.push(line(&vec!["---".to_string(); view.columns()]));
+        lines.extend(grid[1..].iter().map(|row| line(row)));
+        self.list_indents = None;
+        self.push_block(&lines.join("\n"), false);
+    }
+
+    /// 单元格内容，段落之间以 `<br>` 分隔，嵌套表格只保留文本
+    fn cell(&mut self, content: &[BodyContent], context: Option<&CellContext>) -> String {
+        let mut parts = Vec::new();
+        for item in content {
+            match item {
+                BodyContent::Paragraph(paragraph) => {
+                    let label = self.labels.next().flatten();
+                    let text = self.inline(paragraph, context, false);
+                    let prefix = label.map(|l| format!("{} ", escape(&l.text)));
+                    parts.push(format!("{}{}", prefix.unwrap_or_default(), text.trim()));
+                }
+                BodyContent::Table(table) => {
+                    for row in table.rows() {
+                        for cell in row.cells() {
+                            let text = self.cell(&cell.content, None);
+                            parts.push(text);
+                        }
+                    }
+                }
+                BodyContent::Sdt(sdt) => parts.push(self.cell(&sdt.content, context)),
+            }
+        }
+        parts.retain(|p| !p.is_empty());
+        parts.join("<br>")
+    }
+
+    /// 段落的行内内容
+    fn inline(
+        &mut self,
+        paragraph: &Paragraph,
+        cell: Option<&CellContext>,
+        heading: bool,
+    ) -> String {
+        let base = self
+            .resolver
+            .run_properties(paragraph, &Run::default(), cell);
+        let base_bold = heading && is_on(base.bold);
+        let base_italic = heading && is_on(base.italic);
+        let mut spans = Vec::new();
+        let mut markdown = String::new();
+        self.inline_content(
+            paragraph,
+            &paragraph.content,
+            cell,
+            (base_bold, base_italic),
+            &mut spans,
+            &mut markdown,
+        );
+        markdown.push_str(&render_spans(spans));
+        markdown
+    }
+
+    /// 段落内容转换为行内片段，链接直接写入 `markdown`，运行级内容控件中的内容一并转换
+    fn inline_content(
+        &mut self,
+        paragraph: &Paragraph,
+        content: &[ParagraphContent],
+        cell: Option<&CellContext>,
+        (base_bold, base_italic): (bool, bool),
+        spans: &mut Vec<Span>,
+        markdown: &mut String,
+    ) {
+        for item in content {
+            let runs: &[Run] = match item {
+                ParagraphContent::Run(run) => std::slice::from_ref(run),
+                ParagraphContent::SimpleField(field) => &field.runs,
+                ParagraphContent::Insertion(revision) | ParagraphContent::MoveTo(revision) => {
+                    &revision.runs
+                }
+                ParagraphContent::Hyperlink(hyperlink) => {
+                    markdown.push_str(&render_spans(std::mem::take(spans)));
+                    let mut link_spans = Vec::new();
+                    for run in &hyperlink.runs {
+                        self.run(
+                            paragraph,
+                            run,
+                            cell,
+                            (base_bold, base_italic),
+                            &mut link_spans,
+                        );
+                    }
+                    markdown.push_str(&self.link(hyperlink, render_spans(link_spans)));
+                    continue;
+                }
+                ParagraphContent::Sdt(sdt) => {
+                    let base = (base_bold, base_italic);
+                    self.inline_content(paragraph, &sdt.content, cell, base, spans, markdown);
+                    continue;
+                }
+                ParagraphContent::Deletion(_)
+                | ParagraphContent::MoveFrom(_)
+                | ParagraphContent::BookmarkStart(_)
+                | ParagraphContent::BookmarkEnd(_) => continue,
+            };
+            for run in runs {
+                self.run(paragraph, run, cell, (base_bold, base_italic), spans);
+            }
+        }
+    }
+
+    /// 把一个运行转换为行内片段，隐藏文字被忽略
+    fn run(
+        &mut self,
+        paragraph: &Paragraph,
+        run: &Run,
+        cell: Option<&CellContext>,
+        (base_bold, base_italic): (bool, bool),
+        spans: &mut Vec<Span>,
+    ) {
+        let properties = self.resolver.run_properties(paragraph, run, cell);
+        if is_on(properties.hidden) {
+            return;
+        }
+        let bold = is_on(properties.bold) && !base_bold;
+        let italic = is_on(properties.italic) && !base_italic;
+        let strike = is_on(properties.strike);
+        let line_break = if self.in_table { "<br>" } else { "\\\n" };
+        for content in &run.content {
+            let (text, formatted) = match content {
+                RunContent::Text(text) => (escape(text), true),
+                RunContent::Tab => (" ".to_string(), true),
+                RunContent::Break => (line_break.to_string(), false),
+                RunContent::Drawing(drawing) => (self.image(drawing), false),
+                RunContent::PageBreak
+                | RunContent::DeletedText(_)
+                | RunContent::RawDrawing(_)
+                | RunContent::FieldChar(_)
+                | RunContent::InstrText(_)
+                | RunContent::DeletedInstrText(_)
+                | RunContent::FootnoteReference(_)
+                | RunContent::EndnoteReference(_) => continue,
+            };
+            spans.push(Span {
+                bold: bold && formatted,
+                italic: italic && formatted,
+                strike: strike && formatted,
+                text,
+            });
+        }
+    }
+
+    fn link(&self, hyperlink: &Hyperlink, text: String) -> String {
+        let url = match self.docx.hyperlink_target(hyperlink) {
+            Some(HyperlinkTarget::Url(url)) => url,
+            Some(HyperlinkTarget::Bookmark(name)) => match self.anchors.get(&name) {
+                Some(anchor) => format!("#{anchor}"),
+                None => format!("#{name}"),
+            },
+            None => return text,
+        };
+        // 与强调标记一样，链接文字首尾的空白移到链接之外
+        let core = text.trim();
+        if core.is_empty() {
+            return format!("{text}[{}]({})", escape(&url), link_destination(&url));
+        }
+        let start = text.len() - text.trim_start().len();
+        let end = start + core.len();
+        format!(
+            "{}[{core}]({}){}",
+            &text[..start],
+            link_destination(&url),
+            &text[end..]
+        )
+    }
+
+    /// 图片引用，同时记录需要导出的图片数据
+    fn image(&mut self, drawing: &Drawing) -> String {
+        let alt = escape(drawing.description.as_deref().unwrap_or(&drawing.name));
+        let Some(relationship) = self
+            .docx
+            .relationships
+            .as_ref()
+            .and_then(|rels| rels.get(&drawing.embed))
+        else {
+            return String::new();
+        };
+        if relationship.external {
+            return format!("![{alt}]({})", link_destination(&relationship.target));
+        }
+        let path = part_path(&relationship.target);
+        let file_name = path.rsplit('/').next().unwrap_or(&path);
+        let relative = if self.options.image_dir.is_empty() {
+            file_name.to_string()
+        } else {
+            format!(
+                "{}/{file_name}",
+                self.options.image_dir.trim_end_matches('/')
+            )
+        };
+        if let Some(data) = self.docx.media.get(&path) {
+            self.images.insert(relative.clone(), data.clone());
+        }
+        format!("![{alt}]({})", link_destination(&relative))
+    }
+}
+
+/// 段落的标题级别（1–9），非标题返回 `None`
+pub(super) fn heading_level(resolver: &StyleResolver, paragraph: &Paragraph) -> Option<u8> {
+    let outline = resolver
+        .paragraph_properties(paragraph, None)
+        .outline_level?;
+    (outline.val < 9).then_some(outline.val + 1)
+}
+
+/// 计算标题的 GFM 锚点，并映射标题上的书签，使指向书签的内部链接可以跳转到标题
+fn heading_anchors(docx: &Docx, labels: &[Option<ListLabel>]) -> HashMap<String, String> {
+    fn count_paragraphs(content: &[BodyContent]) -> usize {
+        content
+            .iter()
+            .map(|item| match item {
+                BodyContent::Paragraph(_) => 1,
+                BodyContent::Table(table) => table
+                    .rows()
+                    .flat_map(TableRow::cells)
+                    .map(|cell| count_paragraphs(&cell.content))
+                    .sum(),
+                BodyContent::Sdt(sdt) => count_paragraphs(&sdt.content),
+            })
+            .sum()
+    }
+
+    /// 正文顶层（包括块级内容控件中）的段落及其在 [`Docx::paragraphs`] 中的序号
+    fn flow_paragraphs<'a>(
+        content: &'a [BodyContent],
+        index: &mut usize,
+        paragraphs: &mut Vec<(usize, &'a Paragraph)>,
+    ) {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(paragraph) => {
+                    paragraphs.push((*index, paragraph));
+                    *index += 1;
+                }
+                BodyContent::Table(_) => *index += count_paragraphs(std::slice::from_ref(item)),
+                BodyContent::Sdt(sdt) => flow_paragraphs(&sdt.content, index, paragraphs),
+            }
+        }
+    }
+
+    let resolver = docx.style_resolver();
+    let mut anchors = HashMap::new();
+    let mut used: HashMap<String, usize> = HashMap::new();
+    let mut paragraphs = Vec::new();
+    flow_paragraphs(&docx.document.body.content, &mut 0, &mut paragraphs);
+    for (index, paragraph) in paragraphs {
+        let label = labels.get(index).cloned().flatten();
+        if heading_level(&resolver, paragraph).is_none() || paragraph.text().trim().is_empty() {
+            continue;
+        }
+        let text = match label {
+            Some(label) => format!("{} {}", label.text, paragraph.text()),
+            None => paragraph.text(),
+        };
+        let slug = slugify(&text);
+        let count = used.entry(slug.clone()).or_insert(0);
+        let anchor = match *count {
+            0 => slug,
+            n => format!("{slug}-{n}"),
+        };
+        *count += 1;
+        for bookmark in paragraph.bookmarks() {
+            anchors.insert(bookmark.name.clone(), anchor.clone());
+        }
+    }
+    anchors
+}
+
+/// 按 GitHub 的规则生成标题锚点：转为小写，删除标点，空格替换为连字符
+pub(super) fn slugify(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 合并格式相同的相邻片段并添加强调标记
+fn render_spans(spans: Vec<Span>) -> String {
+    let mut markdown = String::new();
+    let mut spans = spans.into_iter().peekable();
+    while let Some(first) = spans.next() {
+        let mut text = first.text;
+        while let Some(next) = spans
+            .next_if(|s| (s.bold, s.italic, s.strike) == (first.bold, first.italic, first.strike))
+        {
+            text.push_str(&next.text);
+        }
+        let mut open = String::new();
+        if first.strike {
+            open.push_str("~~");
+        }
+        if first.bold {
+            open.push_str("**");
+        }
+        if first.italic {
+            open.push('*');
+        }
+        // 强调标记须紧贴非空白字符，首尾空白移到标记之外
+        let core = text.trim();
+        if open.is_empty() || core.is_empty() {
+            markdown.push_str(&text);
+            continue;
+        }
+        let start = text.len() - text.trim_start().len();
+        let end = start + core.len();
+        let close: String = open.chars().rev().collect();
+        markdown.push_str(&format!(
+            "{}{open}{core}{close}{}",
+            &text[..start],
+            &text[end..]
+        ));
+    }
+    markdown
+}
+
+/// 转义 Markdown 中有特殊含义的字符
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 转义段首会被识别为列表标记的字符，如 `- `、`+ `、`1. `
+fn escape_block_start(text: &str) -> String {
+    if text.starts_with(['-', '+']) {
+        return format!("\\{text}");
+    }
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 && text[digits..].starts_with(['.', ')']) {
+        return format!("{}\\{}", &text[..digits], &text[digits..]);
+    }
+    text.to_string()
+}
+
+/// 链接地址，包含空白或括号时使用尖括号包围
+fn link_destination(url: &str) -> String {
+    if url.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
+}
+
+fn is_on(value: Option<OnOff>) -> bool {
+    value.is_some_and(|v| v.is_on())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::builder::{Image, ParagraphBuilder};
+    use crate::docx::test_utils::docx;
+
+    #[test]
+    fn blocks_and_emphasis_are_exported() {
+        let docx = Docx::builder()
+            .heading("Intro *notes*", 1)
+            .paragraph(
+                ParagraphBuilder::new()
+                    .text("plain ")
+                    .bold("bold")
+                    .text(" and ")
+                    .italic("italic")
+                    .hyperlink(" site", "https://example.com"),
+            )
+            .heading("Details", 2)
+            .numbered_list(["first", "second"])
+            .bullet_list(["dot"])
+            .table_with_header([["h1", "h2"], ["a|b", "c"]])
+            .build();
+
+        assert_eq!(
+            docx.to_markdown(),
+            "# Intro \\*notes\\*\n\n\
+             plain **bold** and *italic* [site](https://example.com)\n\n\
+             ## Details\n\n\
+             1. first\n2. second\n- dot\n\n\
+             | h1 | h2 |\n| --- | --- |\n| a\\|b | c |\n"
+        );
+    }
+
+    #[test]
+    fn links_to_headings_and_images_use_relative_paths() {
+        let mut data = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13];
+        data.extend_from_slice(b"IHDR");
+        data.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 1]);
+        let mut image = Image::from_bytes(data.clone()).unwrap();
+        image.description = Some("logo".to_string());
+        let mut docx = Docx::builder()
+            .heading("Getting Started", 1)
+            .paragraph(
+                ParagraphBuilder::new()
+                    .internal_link("back", "intro")
+                    .text(" or ")
+                    .image(image),
+            )
+            .build();
+        docx.add_bookmark(0, "intro").unwrap();
+
+        let options = MarkdownOptions {
+            image_dir: "img/".to_string(),
+        };
+        let export = docx.export_markdown(&options);
+        assert_eq!(
+            export.markdown,
+            "# Getting Started\n\n[back](#getting-started) or ![logo](img/image1.png)\n"
+        );
+        assert_eq!(export.images.get("img/image1.png"), Some(&data));
+    }
+
+    #[test]
+    fn line_breaks_in_nested_table_cells_stay_in_the_row() {
+        let cell = |content: &str| format!("<w:tc>{content}</w:tc>");
+        let line = "<w:p><w:r><w:t>a</w:t><w:br/><w:t>b</w:t></w:r></w:p>";
+        let nested = format!("<w:tbl><w:tr>{}</w:tr></w:tbl><w:p/>", cell(line));
+        let docx = docx(&format!(
+            "<w:tbl><w:tblGrid><w:gridCol w:w=\"1000\"/><w:gridCol w:w=\"1000\"/></w:tblGrid>\
+             <w:tr>{}{}</w:tr></w:tbl>{}",
+            cell(line),
+            cell(&nested),
+            line
+        ));
+
+        assert_eq!(
+            docx.to_markdown(),
+            "| a<br>b | a<br>b |\n| --- | --- |\n\na\\\nb\n"
+        );
+    }
+}
@@ -0,0 +1,794 @@
+//! Markdown 导入：把 CommonMark/GFM 文本转换为带样式的文档
+//!
+//! 标题使用内置标题样式，有序列表和项目符号列表分别使用编号和项目符号列表预设，嵌套列表
+//! 使用对应的级别。代码块和行内代码使用等宽的“HTML 预设格式”和“HTML 代码”样式，引用使用
+//! “文本块”样式，表格使用网格型表格并以第一行作为标题行，分隔线转换为带下边框的空段落。
+//! 文档内链接（`#锚点`）指向的标题自动添加书签。
+//!
+//! 与 pandoc 的 `--reference-doc` 类似，可以提供参考文档：新文档沿用参考文档的样式、编号
+//! 定义、设置、文档属性和页面设置，参考文档中已有的同名样式优先于内置样式定义。
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use pulldown_cmark::{Alignment, Event, LinkType, Options, Parser, Tag, TagEnd};
+
+use crate::common::relations::types;
+use crate::error::Result;
+
+use super::builder::{DocumentBuilder, Image, ParagraphBuilder, DEFAULT_TABLE_WIDTH};
+use super::elements::drawing::EMU_PER_TWIP;
+use super::elements::{BodyContent, Hyperlink, Paragraph, ParagraphContent, Run, RunContent};
+use super::links::link_run;
+use super::markdown::slugify;
+use super::numbering::ListPreset;
+use super::properties::{
+    Border, Indentation, Justification, JustificationVal, NumPr, OnOff, ParagraphBorders,
+    ParagraphProperties, ParagraphStyle, RunProperties,
+};
+use super::styles::BuiltinStyle;
+use super::Docx;
+
+/// Markdown 导入选项
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownImportOptions<'a> {
+    /// 参考文档，沿用其样式、编号定义、设置、文档属性和页面设置，不包含其正文
+    pub reference: Option<&'a Docx>,
+    /// 解析图片相对路径的目录，为空时相对于当前工作目录
+    pub base_dir: Option<&'a Path>,
+}
+
+impl Docx {
+    /// 把 Markdown 文本转换为文档，使用内置样式，图片路径相对于当前工作目录
+    ///
+    /// # 参数
+    /// * `markdown` - CommonMark/GFM 文本
+    pub fn from_markdown(markdown: &str) -> Docx {
+        Docx::from_markdown_with(markdown, &MarkdownImportOptions::default())
+    }
+
+    /// 按选项把 Markdown 文本转换为文档；远程图片和无法读取的图片以替代文字代替
+    ///
+    /// # 参数
+    /// * `markdown` - CommonMark/GFM 文本
+    /// * `options` - 导入选项
+    pub fn from_markdown_with(markdown: &str, options: &MarkdownImportOptions) -> Docx {
+        let docx = base_document(options.reference);
+        let mut reader = MarkdownReader {
+            image_width: content_width(&docx),
+            docx,
+            base_dir: options.base_dir,
+            paragraph: None,
+            format: Format::default(),
+            link: None,
+            image: None,
+            code: None,
+            quote_depth: 0,
+            lists: Vec::new(),
+            table: None,
+            heading_id: None,
+            headings: Vec::new(),
+            slugs: HashMap::new(),
+            anchors: HashSet::new(),
+        };
+        let options = Options::ENABLE_TABLES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_HEADING_ATTRIBUTES;
+        for event in Parser::new_ext(markdown, options) {
+            reader.event(event);
+        }
+        reader.finish_paragraph();
+        reader.resolve_anchors();
+        reader.docx
+    }
+
+    /// 读取 Markdown 文件并转换为文档，图片路径相对于 Markdown 文件所在目录
+    ///
+    /// # 参数
+    /// * `path` - Markdown 文件路径
+    pub fn open_markdown<P: AsRef<Path>>(path: P) -> Result<Docx> {
+        let path = path.as_ref();
+        let markdown = std::fs::read_to_string(path)?;
+        let options = MarkdownImportOptions {
+            base_dir: path.parent(),
+            ..Default::default()
+        };
+        Ok(Docx::from_markdown_with(&markdown, &options))
+    }
+}
+
+/// 当前生效的强调格式，记录嵌套层数
+#[derive(Debug, Clone, Copy, Default)]
+struct Format {
+    bold: u32,
+    italic: u32,
+    strike: u32,
+}
+
+/// 尚未结束的图片，替代文字来自其中的文本
+struct PendingImage {
+    url: String,
+    title: String,
+    alt: String,
+}
+
+/// 正在转换的列表
+struct ListState {
+    num_id: i32,
+    level: i32,
+    ordered: bool,
+    /// 当前列表项还没有段落，下一个段落显示编号
+    first: bool,
+}
+
+/// 正在转换的表格
+struct TableState {
+    alignments: Vec<Alignment>,
+    rows: Vec<Vec<Paragraph>>,
+    in_head: bool,
+}
+
+/// 按解析事件依次向文档末尾添加内容
+struct MarkdownReader<'a> {
+    docx: Docx,
+    base_dir: Option<&'a Path>,
+    /// 图片的最大宽度（EMU），即版心宽度
+    image_width: i64,
+    paragraph: Option<Paragraph>,
+    format: Format,
+    link: Option<Hyperlink>,
+    image: Option<PendingImage>,
+    /// 代码块的文本，在代码块结束时生成段落
+    code: Option<String>,
+    quote_depth: usize,
+    lists: Vec<ListState>,
+    table: Option<TableState>,
+    /// 当前标题显式指定的锚点
+    heading_id: Option<Option<String>>,
+    /// 标题在正文中的位置及其锚点
+    headings: Vec<(usize, String)>,
+    /// 各锚点已使用的次数，重复的标题按 GitHub 的规则追加序号
+    slugs: HashMap<String, usize>,
+    /// 文档内链接引用的锚点
+    anchors: HashSet<String>,
+}
+
+impl MarkdownReader<'_> {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.text(&text),
+            Event::Code(text) => {
+                if let Some(image) = &mut self.image {
+                    image.alt.push_str(&text);
+                } else {
+                    let style = self.style_id(BuiltinStyle::HtmlCode);
+                    let mut properties = self.run_properties().unwrap_or_default();
+                    properties.style = Some(ParagraphStyle { val: style });
+                    self.push_text(&text, Some(properties));
+                }
+            }
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.push_run(Run {
+                properties: self.run_properties(),
+                content: vec![RunContent::Break],
+            }),
+            Event::InlineHtml(html) if is_line_break(&html) => self.push_run(Run {
+                properties: self.run_properties(),
+                content: vec![RunContent::Break],
+            }),
+            Event::Rule => {
+                self.finish_paragraph();
+                let properties = ParagraphProperties {
+                    borders: Some(ParagraphBorders {
+                        bottom: Some(Border::new("single", 6, "auto")),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                };
+                self.push_body(Paragraph {
+                    properties: Some(properties),
+                    content: Vec::new(),
+                });
+            }
+            Event::TaskListMarker(checked) => {
+                self.text(if checked { "\u{2612} " } else { "\u{2610} " })
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.start_paragraph(None),
+            Tag::Heading { level, id, .. } => {
+                self.start_paragraph(Some(BuiltinStyle::Heading(level as u8)));
+                self.heading_id = Some(id.map(|id| id.to_string()));
+            }
+            Tag::BlockQuote(_) => {
+                self.finish_paragraph();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.finish_paragraph();
+                self.code = Some(String::new());
+            }
+            Tag::List(start) => {
+                self.finish_paragraph();
+                self.start_list(start);
+            }
+            Tag::Item => {
+                self.finish_paragraph();
+                if let Some(list) = self.lists.last_mut() {
+                    list.first = true;
+                }
+            }
+            Tag::Table(alignments) => {
+                self.finish_paragraph();
+                self.table = Some(TableState {
+                    alignments,
+                    rows: Vec::new(),
+                    in_head: false,
+                });
+            }
+            Tag::TableHead | Tag::TableRow => {
+                if let Some(table) = &mut self.table {
+                    table.rows.push(Vec::new());
+                    table.in_head = matches!(tag, Tag::TableHead);
+                }
+            }
+            Tag::TableCell => self.start_paragraph(None),
+            Tag::Emphasis => self.format.italic += 1,
+            Tag::Strong => self.format.bold += 1,
+            Tag::Strikethrough => self.format.strike += 1,
+            Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                ..
+            } => self.start_link(link_type, &dest_url, &title),
+            Tag::Image {
+                dest_url, title, ..
+            } => {
+                self.image = Some(PendingImage {
+                    url: dest_url.to_string(),
+                    title: title.to_string(),
+                    alt: String::new(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::TableCell => self.finish_paragraph(),
+            TagEnd::Heading(_) => self.finish_heading(),
+            TagEnd::BlockQuote(_) => {
+                self.finish_paragraph();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            TagEnd::CodeBlock => self.finish_code_block(),
+            TagEnd::List(_) => {
+                self.finish_paragraph();
+                self.lists.pop();
+            }
+            TagEnd::Item => {
+                // 空列表项也显示编号
+                if self.paragraph.is_none() && self.lists.last().is_some_and(|l| l.first) {
+                    self.start_paragraph(None);
+                }
+                self.finish_paragraph();
+            }
+            TagEnd::TableHead => {
+                if let Some(table) = &mut self.table {
+                    table.in_head = false;
+                }
+            }
+            TagEnd::Table => self.finish_table(),
+            TagEnd::Emphasis => self.format.italic = self.format.italic.saturating_sub(1),
+            TagEnd::Strong => self.format.bold = self.format.bold.saturating_sub(1),
+            TagEnd::Strikethrough => self.format.strike = self.format.strike.saturating_sub(1),
+            TagEnd::Link => {
+                if let Some(link) = self.link.take() {
+                    self.ensure_paragraph()
+                        .content
+                        .push(ParagraphContent::Hyperlink(link));
+                }
+            }
+            TagEnd::Image => {
+                if let Some(image) = self.image.take() {
+                    self.finish_image(image);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 开始新段落，样式按所在的表格、列表和引用确定
+    fn start_paragraph(&mut self, style: Option<BuiltinStyle>) {
+        self.finish_paragraph();
+        let mut properties = ParagraphProperties::default();
+        if let Some(table) = &self.table {
+            let column = table.rows.last().map_or(0, Vec::len);
+            let alignment = match table.alignments.get(column) {
+                Some(Alignment::Left) => Some(JustificationVal::Left),
+                Some(Alignment::Center) => Some(JustificationVal::Center),
+                Some(Alignment::Right) => Some(JustificationVal::Right),
+                _ => None,
+            };
+            properties.justification = alignment.map(|val| Justification { val });
+        } else if let Some(list) = self.lists.last_mut() {
+            let builtin = style.unwrap_or(BuiltinStyle::ListParagraph);
+            let (num_id, level, first) = (list.num_id, list.level, list.first);
+            list.first = false;
+            if first && style.is_none() {
+                properties.num_pr = Some(NumPr::new(num_id, level));
+            } else {
+                // 列表项中的后续段落与编号后的文字对齐
+                let left = self
+                    .docx
+                    .numbering
+                    .level_paragraph_properties(num_id, level)
+                    .and_then(|p| p.indentation?.left);
+                properties.indentation = left.map(|left| Indentation {
+                    left: Some(left),
+                    ..Default::default()
+                });
+            }
+            properties.style = Some(ParagraphStyle {
+                val: self.style_id(builtin),
+            });
+        } else if let Some(builtin) =
+            style.or((self.quote_depth > 0).then_some(BuiltinStyle::BlockText))
+        {
+            properties.style = Some(ParagraphStyle {
+                val: self.style_id(builtin),
+            });
+        }
+        self.paragraph = Some(Paragraph {
+            properties: (properties != ParagraphProperties::default()).then_some(properties),
+            content: Vec::new(),
+        });
+    }
+
+    /// 结束当前段落，加入表格单元格或正文
+    fn finish_paragraph(&mut self) {
+        let Some(paragraph) = self.paragraph.take() else {
+            return;
+        };
+        match self.table.as_mut().and_then(|t| t.rows.last_mut()) {
+            Some(row) => row.push(paragraph),
+            None => self.push_body(paragraph),
+        }
+    }
+
+    fn ensure_paragraph(&mut self) -> &mut Paragraph {
+        if self.paragraph.is_none() {
+            // 紧凑列表的列表项文本不包含在段落中
+            self.start_paragraph(None);
+        }
+        self.paragraph
+            .as_mut()
+            .expect("a paragraph was just started")
+    }
+
+    fn push_body(&mut self, paragraph: Paragraph) {
+        self.docx
+            .document
+            .body
+            .content
+            .push(BodyContent::Paragraph(paragraph));
+    }
+
+    fn finish_heading(&mut self) {
+        let text = self.paragraph.as_ref().map(Paragraph::text);
+        self.finish_paragraph();
+        let (Some(text), Some(id)) = (text, self.heading_id.take()) else {
+            return;
+        };
+        let slug = id.unwrap_or_else(|| slugify(&text));
+        let count = self.slugs.entry(slug.clone()).or_insert(0);
+        let anchor = match *count {
+            0 => slug,
+            n => format!("{slug}-{n}"),
+        };
+        *count += 1;
+        let index = self.docx.document.body.content.len() - 1;
+        self.headings.push((index, anchor));
+    }
+
+    fn finish_code_block(&mut self) {
+        let Some(code) = self.code.take() else {
+            return;
+        };
+        let code = code.strip_suffix('\n').unwrap_or(&code);
+        let mut content = Vec::new();
+        for (index, line) in code.split('\n').enumerate() {
+            if index > 0 {
+                content.push(RunContent::Break);
+            }
+            for (index, part) in line.split('\t').enumerate() {
+                if index > 0 {
+                    content.push(RunContent::Tab);
+                }
+                if !part.is_empty() {
+                    content.push(RunContent::Text(part.to_string()));
+                }
+            }
+        }
+        self.start_paragraph(Some(BuiltinStyle::HtmlPreformatted));
+        if !content.is_empty() {
+            self.push_run(Run {
+                properties: None,
+                content,
+            });
+        }
+        self.finish_paragraph();
+    }
+
+    /// 开始列表：与上级列表类型相同且从 1 开始时沿用上级列表的编号，否则创建新编号
+    fn start_list(&mut self, start: Option<u64>) {
+        let ordered = start.is_some();
+        let start = start.map_or(1, |s| s.min(i32::MAX as u64) as i32);
+        let level = (self.lists.len() as i32).min(8);
+        let num_id = match self.lists.last() {
+            Some(parent) if parent.ordered == ordered && start == 1 => parent.num_id,
+            _ => {
+                let preset = if ordered {
+                    ListPreset::Numbered
+                } else {
+                    ListPreset::Bullet
+                };
+                let mut levels = preset.levels();
+                if start != 1 {
+                    levels[level as usize].set_start(start);
+                }
+                self.docx.numbering.add_list(levels)
+            }
+        };
+        self.lists.push(ListState {
+            num_id,
+            level,
+            ordered,
+            first: false,
+        });
+    }
+
+    fn finish_table(&mut self) {
+        self.finish_paragraph();
+        let Some(state) = self.table.take() else {
+            return;
+        };
+        let table = self.docx.add_table(
+            state
+                .rows
+                .iter()
+                .map(|row| vec![ParagraphBuilder::new(); row.len()]),
+            true,
+        );
+        for (row, paragraphs) in table.rows_mut().zip(state.rows) {
+            for (cell, paragraph) in row.cells_mut().zip(paragraphs) {
+                cell.content = vec![BodyContent::Paragraph(paragraph)];
+            }
+        }
+    }
+
+    fn start_link(&mut self, link_type: LinkType, url: &str, title: &str) {
+        let mut link = match url.strip_prefix('#') {
+            Some(anchor) => {
+                self.anchors.insert(anchor.to_string());
+                Hyperlink::internal(anchor, Vec::new())
+            }
+            None => {
+                let url = match link_type {
+                    LinkType::Email if !url.starts_with("mailto:") => format!("mailto:{url}"),
+                    _ => url.to_string(),
+                };
+                Hyperlink::external(self.docx.add_hyperlink_relationship(&url), Vec::new())
+            }
+        };
+        if !title.is_empty() {
+            link = link.with_tooltip(title);
+        }
+        self.link = Some(link);
+    }
+
+    fn finish_image(&mut self, image: PendingImage) {
+        let PendingImage { url, title, alt } = image;
+        let Some(mut image) = self.load_image(&url) else {
+            self.text(&alt);
+            return;
+        };
+        image.description = [alt, title].into_iter().find(|s| !s.is_empty());
+        image.fit_width(self.image_width);
+        let drawing = self.docx.add_image_part(image);
+        self.push_run(Run {
+            properties: self.run_properties(),
+            content: vec![RunContent::Drawing(drawing)],
+        });
+    }
+
+    /// 读取本地图片，远程图片返回 `None`
+    fn load_image(&self, url: &str) -> Option<Image> {
+        let path = match url.strip_prefix("file://") {
+            Some(path) => path,
+            None if url.contains("://") || url.starts_with("data:") => return None,
+            None => url,
+        };
+        let path = match self.base_dir {
+            Some(dir) => dir.join(path),
+            None => PathBuf::from(path),
+        };
+        Image::open(path).ok()
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some(image) = &mut self.image {
+            image.alt.push_str(text);
+        } else if let Some(code) = &mut self.code {
+            code.push_str(text);
+        } else if !text.is_empty() {
+            self.push_text(text, self.run_properties());
+        }
+    }
+
+    /// 追加文本，格式与前一个文本运行相同时合并到该运行
+    fn push_text(&mut self, text: &str, properties: Option<RunProperties>) {
+        let last = match &mut self.link {
+            Some(link) => link.runs.last_mut(),
+            None => match self.ensure_paragraph().content.last_mut() {
+                Some(ParagraphContent::Run(run)) => Some(run),
+                _ => None,
+            },
+        };
+        if let Some(run) = last.filter(|run| run.properties == properties) {
+            if let Some(RunContent::Text(last)) = run.content.last_mut() {
+                last.push_str(text);
+                return;
+            }
+        }
+        self.push_run(Run {
+            properties,
+            content: vec![RunContent::Text(text.to_string())],
+        });
+    }
+
+    fn push_run(&mut self, run: Run) {
+        match &mut self.link {
+            Some(link) => link.runs.push(run),
+            None => self
+                .ensure_paragraph()
+                .content
+                .push(ParagraphContent::Run(run)),
+        }
+    }
+
+    /// 按当前的强调、链接和表头状态生成运行属性
+    fn run_properties(&self) -> Option<RunProperties> {
+        let mut properties = match self.link {
+            Some(_) => link_run(String::new()).properties.unwrap_or_default(),
+            None => RunProperties::default(),
+        };
+        let header = self.table.as_ref().is_some_and(|t| t.in_head);
+        if self.format.bold > 0 || header {
+            properties.bold = Some(OnOff::ON);
+        }
+        if self.format.italic > 0 {
+            properties.italic = Some(OnOff::ON);
+        }
+        if self.format.strike > 0 {
+            properties.strike = Some(OnOff::ON);
+        }
+        (properties != RunProperties::default()).then_some(properties)
+    }
+
+    fn style_id(&mut self, builtin: BuiltinStyle) -> String {
+        self.docx.styles.ensure_builtin(builtin).style_id.clone()
+    }
+
+    /// 为链接引用的标题添加书签，并把链接的锚点改为书签名称
+    fn resolve_anchors(&mut self) {
+        let mut names = HashMap::new();
+        let mut used = HashSet::new();
+        for (index, anchor) in &self.headings {
+            if !self.anchors.contains(anchor) || names.contains_key(anchor) {
+                continue;
+            }
+            let name = bookmark_name(anchor, &used);
+            used.insert(name.clone());
+            let id = self.docx.next_bookmark_id();
+            if let Some(BodyContent::Paragraph(paragraph)) =
+                self.docx.document.body.content.get_mut(*index)
+            {
+                paragraph.wrap_bookmark(id, name.clone());
+            }
+            names.insert(anchor.clone(), name);
+        }
+        for paragraph in self.docx.paragraphs_mut() {
+            for item in &mut paragraph.content {
+                if let ParagraphContent::Hyperlink(Hyperlink {
+                    anchor: Some(anchor),
+                    ..
+                }) = item
+                {
+                    if let Some(name) = names.get(anchor.as_str()) {
+                        *anchor = name.clone();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 创建空文档：有参考文档时沿用其正文以外的部分，但不保留超链接、图片及其媒体文件
+fn base_document(reference: Option<&Docx>) -> Docx {
+    let Some(reference) = reference else {
+        return DocumentBuilder::new().build();
+    };
+    let mut docx = reference.clone();
+    docx.document.body.content.clear();
+    docx.media.clear();
+    if let Some(rels) = &mut docx.relationships {
+        let ids: Vec<String> = rels
+            .iter()
+            .filter(|r| r.rel_type == types::HYPERLINK || r.rel_type == types::IMAGE)
+            .map(|r| r.id.clone())
+            .collect();
+        for id in ids {
+            rels.remove(&id);
+        }
+    }
+    docx.styles.ensure_builtin(BuiltinStyle::Normal);
+    docx.styles
+        .ensure_builtin(BuiltinStyle::DefaultParagraphFont);
+    docx
+}
+
+/// 版心宽度（EMU），没有页面设置时使用默认表格宽度
+fn content_width(docx: &Docx) -> i64 {
+    let width = docx
+        .document
+        .body
+        .section_properties
+        .as_ref()
+        .and_then(|s| {
+            let (size, margins) = (s.page_size.as_ref()?, s.page_margins.as_ref()?);
+            Some(size.width - margins.left - margins.right)
+        })
+        .filter(|width| *width > 0)
+        .unwrap_or(DEFAULT_TABLE_WIDTH);
+    width as i64 * EMU_PER_TWIP
+}
+
+/// 由锚点生成书签名称：非字母数字替换为下划线，不以字母开头时加下划线前缀；
+/// 截断后保留追加序号的余地，不超过 Word 限制的 40 个字符
+pub(super) fn bookmark_name(anchor: &str, used: &HashSet<String>) -> String {
+    let mut base: String = anchor
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    if !base.starts_with(char::is_alphabetic) {
+        base.insert(0, '_');
+    }
+    let base: String = base.chars().take(36).collect();
+    let mut name = base.clone();
+    let mut n = 1;
+    while used.contains(&name) {
+        name = format!("{base}_{n}");
+        n += 1;
+    }
+    name
+}
+
+/// 是否为 `<br>`、`<br/>` 等换行标签
+fn is_line_break(html: &str) -> bool {
+    let tag = html.trim().to_ascii_lowercase();
+    tag.strip_prefix("<br")
+        .is_some_and(|rest| rest.starts_with(['>', '/', ' ']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::elements::HyperlinkTarget;
+    use crate::docx::properties::Val;
+    use crate::docx::test_utils::body_xml;
+
+    /// 文档中各段落的样式ID和文本
+    fn styled_texts(docx: &Docx) -> Vec<(String, String)> {
+        docx.paragraphs()
+            .iter()
+            .map(|p| {
+                let style = p.properties.as_ref().and_then(|p| p.style.as_ref());
+                (style.map(|s| s.val.clone()).unwrap_or_default(), p.text())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn blocks_use_builtin_styles() {
+        let docx = Docx::from_markdown(
+            "# Title\n\n1. one\n2. two\n   - nested\n\n> quoted\n\n\
+             ```\nfn main() {}\n```\n\n| a | b |\n|---|--:|\n| 1 | 2 |\n",
+        );
+
+        let expected = [
+            ("Heading1", "Title"),
+            ("ListParagraph", "one"),
+            ("ListParagraph", "two"),
+            ("ListParagraph", "nested"),
+            ("BlockText", "quoted"),
+            ("HTMLPreformatted", "fn main() {}"),
+            ("", "a"),
+            ("", "b"),
+            ("", "1"),
+            ("", "2"),
+        ];
+        let expected: Vec<(String, String)> = expected
+            .iter()
+            .map(|(style, text)| (style.to_string(), text.to_string()))
+            .collect();
+        assert_eq!(styled_texts(&docx), expected);
+        for (style_id, _) in &expected[..6] {
+            assert!(docx.styles.find_style(style_id).is_some(), "{style_id}");
+        }
+
+        let labels: Vec<Option<String>> = docx
+            .list_labels()
+            .into_iter()
+            .take(5)
+            .map(|label| label.map(|l| l.text))
+            .collect();
+        assert_eq!(labels, [None, Some("1.".into()), Some("2.".into()), Some("o".into()), None]);
+
+        let xml = body_xml(&docx);
+        assert!(xml.contains("<w:trPr><w:tblHeader/></w:trPr>"), "{xml}");
+        assert!(xml.contains("<w:jc w:val=\"right\"/></w:pPr><w:r><w:t xml:space=\"preserve\">2"));
+    }
+
+    #[test]
+    fn inline_formatting_and_internal_links() {
+        let docx = Docx::from_markdown(
+            "See [below](#next-part), **bold** *it* ~~gone~~ `code`.\n\n## Next part\n",
+        );
+
+        let xml = body_xml(&docx);
+        for expected in [
+            "<w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">bold</w:t>",
+            "<w:rPr><w:i/></w:rPr><w:t xml:space=\"preserve\">it</w:t>",
+            "<w:rPr><w:strike/></w:rPr><w:t xml:space=\"preserve\">gone</w:t>",
+            "<w:rPr><w:rStyle w:val=\"HTMLCode\"/></w:rPr><w:t xml:space=\"preserve\">code</w:t>",
+        ] {
+            assert!(xml.contains(expected), "{expected}");
+        }
+
+        let paragraphs = docx.paragraphs();
+        let link = paragraphs[0].hyperlinks().next().unwrap();
+        assert_eq!(
+            docx.hyperlink_target(link),
+            Some(HyperlinkTarget::Bookmark("next_part".to_string()))
+        );
+        let bookmarks: Vec<&str> = paragraphs[1].bookmarks().map(|b| b.name.as_str()).collect();
+        assert_eq!(bookmarks, ["next_part"]);
+    }
+
+    #[test]
+    fn reference_document_styles_are_kept() {
+        let mut reference = Docx::from_markdown("# Old\n");
+        let heading = reference.styles.find_style_mut("Heading1").unwrap();
+        heading.aliases = Some(Val::new("Custom".to_string()));
+
+        let options = MarkdownImportOptions {
+            reference: Some(&reference),
+            ..Default::default()
+        };
+        let docx = Docx::from_markdown_with("# New\n", &options);
+        assert_eq!(docx.paragraphs().len(), 1);
+        assert_eq!(docx.paragraphs()[0].text(), "New");
+        let heading = docx.styles.find_style("Heading1").unwrap();
+        assert_eq!(heading.aliases.as_ref().unwrap().val, "Custom");
+    }
+}
@@ -0,0 +1,886 @@
+//! 文档合并：把其他文档的正文追加到当前文档
+//!
+//! 正文中的样式ID、编号ID、关系ID和书签ID都只在各自的文档中有效，直接复制正文会使引用
+//! 失效或指向错误的定义。合并时先收集正文实际用到的样式和编号（包括基础样式、后续样式、
+//! 链接样式以及样式和编号之间的相互引用），按冲突策略导入并分配新ID；再复制超链接关系、
+//! 图片以及图表、页眉页脚等部件（连同部件自身的关系），最后改写复制出的正文中的全部引用。
+//! 无法导入关系的超链接只保留文字，图片和图表被删除。
+//!
+//! 脚注和尾注的内容不导入，复制出的正文中的脚注和尾注引用被删除；自定义 XML 部件只导入
+//! 当前文档中没有的数据存储项，没有数据存储项ID的部件不导入。
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use crate::common::relations::Relationships;
+use crate::common::xml_element::XmlElement;
+use crate::error::{OfficeError, Result};
+
+use super::elements::paragraph::{content_runs, content_runs_mut};
+use super::elements::{BodyContent, Paragraph, ParagraphContent, RunContent, TableRow};
+use super::numbering::AbstractNum;
+use super::properties::{ParagraphProperties, SectionProperties, SectionType, Val};
+use super::{part_path, relative_target, rels_path, resolve_part_path, Docx};
+
+/// 两个文档中存在相同样式ID时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StyleConflict {
+    /// 保留目标文档的样式，导入的内容改用目标文档中的格式
+    #[default]
+    KeepDestination,
+    /// 用源文档的样式覆盖目标文档的同ID样式
+    KeepSource,
+    /// 定义不同时以新ID导入源文档的样式，两部分内容各自保持原有格式
+    Rename,
+}
+
+/// 文档合并选项
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MergeOptions {
+    /// 样式冲突的处理方式
+    pub style_conflict: StyleConflict,
+    /// 在两个文档之间插入的分节符类型，为 `None` 时直接衔接
+    pub section_break: Option<SectionType>,
+}
+
+/// 单个样式的导入方式
+enum Import {
+    /// 不导入，沿用当前文档的同ID样式
+    Skip,
+    /// 以原ID导入，覆盖同ID样式
+    SameId,
+    /// 以新ID导入
+    Renamed,
+}
+
+/// 源文档ID到目标文档ID的映射
+#[derive(Default)]
+struct IdMap {
+    styles: HashMap<String, String>,
+    nums: HashMap<i32, i32>,
+    relationships: HashMap<String, String>,
+    /// 源文档部件和媒体文件的包内路径到当前文档中路径的映射
+    parts: HashMap<String, String>,
+    bookmarks: HashMap<String, String>,
+    bookmark_offset: i32,
+    next_drawing_id: u32,
+}
+
+impl Docx {
+    /// 合并多个文档文件，以第一个文档为基础依次追加其余文档
+    ///
+    /// # 参数
+    /// * `paths` - 文档路径，至少一个
+    /// * `options` - 合并选项
+    pub fn merge_files<P: AsRef<Path>>(paths: &[P], options: &MergeOptions) -> Result<Docx> {
+        let (first, rest) = paths
+            .split_first()
+            .ok_or_else(|| OfficeError::InvalidFormat("no documents to merge".to_string()))?;
+        let mut docx = Docx::open(first)?;
+        for path in rest {
+            docx.append_document(&Docx::open(path)?, options);
+        }
+        Ok(docx)
+    }
+
+    /// 把另一个文档的正文追加到当前文档末尾，同时导入正文依赖的样式、编号、超链接、图片、
+    /// 图表和页眉页脚，以及当前文档中没有的自定义 XML 部件
+    ///
+    /// 源文档的列表以新的编号定义导入，不会与当前文档的列表连续编号；与当前文档重名的书签
+    /// 会加上数字后缀，指向它们的内部链接随之更新。脚注和尾注不导入，其引用被删除。
+    ///
+    /// # 参数
+    /// * `source` - 源文档
+    /// * `options` - 合并选项
+    pub fn append_document(&mut self, source: &Docx, options: &MergeOptions) {
+        let mut body = source.document.body.clone();
+        let (style_ids, num_ids) = used_definitions(source, &body.content);
+
+        let mut map = IdMap::default();
+        let imports = self.plan_styles(source, &style_ids, options.style_conflict, &mut map);
+        self.import_numbering(source, &num_ids, &mut map);
+        self.import_styles(source, imports, &map);
+
+        map.bookmark_offset = self.next_bookmark_id();
+        map.next_drawing_id = self.max_drawing_id() + 1;
+        let mut names: HashSet<String> = self.bookmarks().iter().map(|b| b.name.clone()).collect();
+        let source_names: HashSet<String> =
+            source.bookmarks().iter().map(|b| b.name.clone()).collect();
+        for name in &source_names {
+            if names.contains(name) {
+                let renamed = (1..)
+                    .map(|n| format!("{name}_{n}"))
+                    .find(|c| !names.contains(c) && !source_names.contains(c))
+                    .unwrap_or_default();
+                names.insert(renamed.clone());
+                map.bookmarks.insert(name.clone(), renamed);
+            }
+        }
+        self.remap_content(source, &mut body.content, &mut map);
+        // 内容控件通过数据存储项ID绑定自定义 XML 部件，导入当前文档中没有的部件
+        for part in &source.custom_xml {
+            if part
+                .item_id
+                .as_deref()
+                .is_some_and(|id| self.custom_xml_part(id).is_none())
+            {
+                self.custom_xml.push(part.clone());
+            }
+        }
+
+        // 源文档最后一节的属性只在插入分节符时使用
+        let source_section = match options.section_break {
+            Some(_) => body.section_properties.take().map(|mut section| {
+                self.remap_section(source, &mut section, &mut map);
+                section
+            }),
+            None => None,
+        };
+
+        let current = &mut self.document.body;
+        if let (Some(section_type), false) = (options.section_break, current.content.is_empty()) {
+            // 分节符所在段落的节属性描述它之前的节，因此当前文档最后一节的属性移到分节段落，
+            // 源文档最后一节成为合并后文档的最后一节
+            let closing = current.section_properties.clone().unwrap_or_default();
+            match current.content.last_mut() {
+                Some(BodyContent::Paragraph(paragraph))
+                    if paragraph
+                        .properties
+                        .as_ref()
+                        .is_none_or(|p| p.section_properties.is_none()) =>
+                {
+                    paragraph
+                        .properties
+                        .get_or_insert_with(Default::default)
+                        .section_properties = Some(closing);
+                }
+                _ => current.content.push(BodyContent::Paragraph(Paragraph {
+                    properties: Some(ParagraphProperties {
+                        section_properties: Some(closing),
+                        ..Default::default()
+                    }),
+                    content: Vec::new(),
+                })),
+            }
+            let mut last = source_section
+                .or_else(|| current.section_properties.clone())
+                .unwrap_or_default();
+            last.section_type = Some(Val::new(section_type));
+            current.section_properties = Some(last);
+        }
+        current.content.append(&mut body.content);
+    }
+
+    /// 按冲突策略确定每个用到的样式在当前文档中的ID，返回需要导入的源样式ID及名称后缀
+    fn plan_styles(
+        &self,
+        source: &Docx,
+        style_ids: &BTreeSet<String>,
+        conflict: StyleConflict,
+        map: &mut IdMap,
+    ) -> Vec<(String, Option<u32>)> {
+        let mut imports = Vec::new();
+        for id in style_ids {
+            let Some(style) = source.styles.find_style(id) else {
+                continue;
+            };
+            let decision = match self.styles.find_style(id) {
+                None => Import::SameId,
+                Some(existing) if existing.style_type == style.style_type => match conflict {
+                    StyleConflict::KeepDestination => Import::Skip,
+                    StyleConflict::KeepSource => Import::SameId,
+                    StyleConflict::Rename if existing == style => Import::Skip,
+                    StyleConflict::Rename => Import::Renamed,
+                },
+                // 同ID但类型不同的样式无法共用，只能改名
+                Some(_) => Import::Renamed,
+            };
+            let target = match decision {
+                Import::Skip => {
+                    map.styles.insert(id.clone(), id.clone());
+                    continue;
+                }
+                Import::SameId => (id.clone(), None),
+                Import::Renamed => {
+                    let n = (1..)
+                        .find(|n| {
+                            let candidate = format!("{id}{n}");
+                            self.styles.find_style(&candidate).is_none()
+                                && !map.styles.values().any(|v| *v == candidate)
+                        })
+                        .unwrap_or(1);
+                    (format!("{id}{n}"), Some(n))
+                }
+            };
+            map.styles.insert(id.clone(), target.0);
+            imports.push((id.clone(), target.1));
+        }
+        imports
+    }
+
+    /// 以新ID导入用到的编号实例及其抽象编号
+    fn import_numbering(&mut self, source: &Docx, num_ids: &BTreeSet<i32>, map: &mut IdMap) {
+        let mut abstract_ids = HashMap::new();
+        for &num_id in num_ids {
+            let Some(num) = source.numbering.num(num_id) else {
+                continue;
+            };
+            let source_abstract = num.abstract_num_id.val;
+            let abstract_id = match abstract_ids.get(&source_abstract) {
+                Some(&id) => id,
+                None => {
+                    let Some(mut abstract_num) =
+                        source.numbering.abstract_num(source_abstract).cloned()
+                    else {
+                        continue;
+                    };
+                    // 使用新的 nsid，避免 Word 把两个文档中的列表当作同一个列表
+                    let id = self
+                        .numbering
+                        .abstract_nums
+                        .iter()
+                        .map(|an| an.id)
+                        .max()
+                        .unwrap_or(-1)
+                        + 1;
+                    abstract_num.id = id;
+                    abstract_num.nsid = AbstractNum::new(id, Vec::new()).nsid;
+                    let links = [
+                        &mut abstract_num.style_link,
+                        &mut abstract_num.num_style_link,
+                    ];
+                    let level_styles = abstract_num
+                        .levels
+                        .iter_mut()
+                        .map(|level| &mut level.paragraph_style);
+                    for reference in links.into_iter().chain(level_styles).flatten() {
+                        remap_style(&mut reference.val, map);
+                    }
+                    let id = self.numbering.add_abstract_num(abstract_num);
+                    abstract_ids.insert(source_abstract, id);
+                    id
+                }
+            };
+            let id = self.numbering.add_num(abstract_id);
+            if let Some(imported) = self.numbering.nums.last_mut() {
+                imported.overrides = num.overrides.clone();
+                for reference in imported
+                    .overrides
+                    .iter_mut()
+                    .filter_map(|o| o.definition.as_mut()?.paragraph_style.as_mut())
+                {
+                    remap_style(&mut reference.val, map);
+                }
+            }
+            map.nums.insert(num_id, id);
+        }
+    }
+
+    /// 导入样式并改写其中的样式和编号引用
+    fn import_styles(&mut self, source: &Docx, imports: Vec<(String, Option<u32>)>, map: &IdMap) {
+        for (id, suffix) in imports {
+            let Some(mut style) = source.styles.find_style(&id).cloned() else {
+                continue;
+            };
+            style.style_id = map.styles.get(&id).cloned().unwrap_or(id);
+            if let (Some(n), Some(name)) = (suffix, style.name.as_mut()) {
+                name.val = format!("{} {n}", name.val);
+            }
+            for reference in [&mut style.based_on, &mut style.next, &mut style.link]
+                .into_iter()
+                .flatten()
+            {
+                remap_style(&mut reference.val, map);
+            }
+            if let Some(properties) = &mut style.paragraph_properties {
+                remap_paragraph_properties(properties, map);
+            }
+            // 默认样式保持为当前文档原有的默认样式
+            style.default = self
+                .styles
+                .find_style(&style.style_id)
+                .and_then(|s| s.default);
+            self.styles.set_style(style);
+        }
+    }
+
+    /// 改写复制出的正文中的样式、编号、关系、书签和绘图ID
+    fn remap_content(&mut self, source: &Docx, content: &mut [BodyContent], map: &mut IdMap) {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(paragraph) => self.remap_paragraph(source, paragraph, map),
+                BodyContent::Table(table) => {
+                    if let Some(style) = table.properties.as_mut().and_then(|t| t.style.as_mut()) {
+                        remap_style(&mut style.val, map);
+                    }
+                    for cell in table.rows_mut().flat_map(TableRow::cells_mut) {
+                        self.remap_content(source, &mut cell.content, map);
+                    }
+                }
+                BodyContent::Sdt(sdt) => self.remap_content(source, &mut sdt.content, map),
+            }
+        }
+    }
+
+    fn remap_paragraph(&mut self, source: &Docx, paragraph: &mut Paragraph, map: &mut IdMap) {
+        if let Some(properties) = &mut paragraph.properties {
+            remap_paragraph_properties(properties, map);
+            if let Some(change) = &mut properties.change {
+                remap_paragraph_properties(&mut change.properties, map);
+            }
+            if let Some(section) = &mut properties.section_properties {
+                self.remap_section(source, section, map);
+            }
+        }
+        self.remap_links(source, &mut paragraph.content, map);
+        for run in content_runs_mut(&mut paragraph.content, true) {
+            if let Some(style) = run.properties.as_mut().and_then(|p| p.style.as_mut()) {
+                remap_style(&mut style.val, map);
+            }
+            for mut content in std::mem::take(&mut run.content) {
+                let keep = match &mut content {
+                    RunContent::Drawing(drawing) => {
+                        match self.import_relationship(source, &drawing.embed, map) {
+                            Some(id) => {
+                                drawing.embed = id;
+                                drawing.id = map.next_drawing_id;
+                                map.next_drawing_id += 1;
+                                true
+                            }
+                            None => false,
+                        }
+                    }
+                    RunContent::RawDrawing(drawing) => self.remap_element(source, drawing, map),
+                    RunContent::FootnoteReference(_) | RunContent::EndnoteReference(_) => false,
+                    _ => true,
+                };
+                if keep {
+                    run.content.push(content);
+                }
+            }
+        }
+    }
+
+    /// 改写原样保留的绘图中以 `r:` 前缀属性引用的关系，并为其分配新的绘图对象ID；
+    /// 有关系无法导入时返回 `false`
+    fn remap_element(
+        &mut self,
+        source: &Docx,
+        element: &mut XmlElement,
+        map: &mut IdMap,
+    ) -> bool {
+        if element.name == "wp:docPr" {
+            if let Some((_, id)) = element.attributes.iter_mut().find(|(name, _)| name == "id") {
+                *id = map.next_drawing_id.to_string();
+                map.next_drawing_id += 1;
+            }
+        }
+        for (name, value) in &mut element.attributes {
+            if name.starts_with("r:") {
+                match self.import_relationship(source, value, map) {
+                    Some(id) => *value = id,
+                    None => return false,
+                }
+            }
+        }
+        element
+            .children
+            .iter_mut()
+            .all(|child| self.remap_element(source, child, map))
+    }
+
+    /// 导入节属性引用的页眉和页脚，无法导入的引用被删除
+    fn remap_section(&mut self, source: &Docx, section: &mut SectionProperties, map: &mut IdMap) {
+        for references in [&mut section.headers, &mut section.footers] {
+            for mut reference in std::mem::take(references) {
+                if let Some(id) = self.import_relationship(source, &reference.id, map) {
+                    reference.id = id;
+                    references.push(reference);
+                }
+            }
+        }
+    }
+
+    /// 改写段落内容中超链接的关系和书签，包括内容控件中的内容；关系无法导入的外部链接
+    /// 只保留文字
+    fn remap_links(
+        &mut self,
+        source: &Docx,
+        content: &mut Vec<ParagraphContent>,
+        map: &mut IdMap,
+    ) {
+        for mut item in std::mem::take(content) {
+            match &mut item {
+                ParagraphContent::Hyperlink(hyperlink) => {
+                    if let Some(anchor) = &mut hyperlink.anchor {
+                        if let Some(renamed) = map.bookmarks.get(anchor) {
+                            *anchor = renamed.clone();
+                        }
+                    }
+                    if !hyperlink.r_id.is_empty() {
+                        match self.import_relationship(source, &hyperlink.r_id, map) {
+                            Some(id) => hyperlink.r_id = id,
+                            None if hyperlink.anchor.is_some() => hyperlink.r_id.clear(),
+                            None => {
+                                let runs = std::mem::take(&mut hyperlink.runs);
+                                content.extend(runs.into_iter().map(ParagraphContent::Run));
+                                continue;
+                            }
+                        }
+                    }
+                }
+                ParagraphContent::BookmarkStart(bookmark) => {
+                    bookmark.id += map.bookmark_offset;
+                    if let Some(renamed) = map.bookmarks.get(&bookmark.name) {
+                        bookmark.name = renamed.clone();
+                    }
+                }
+                ParagraphContent::BookmarkEnd(bookmark) => bookmark.id += map.bookmark_offset,
+                ParagraphContent::Sdt(sdt) => self.remap_links(source, &mut sdt.content, map),
+                _ => {}
+            }
+            content.push(item);
+        }
+    }
+
+    /// 把源文档的关系复制到当前文档，内部关系同时复制其指向的部件或媒体文件；返回新的
+    /// 关系ID，关系不存在或指向的文件不在源文档中时返回 `None`
+    fn import_relationship(
+        &mut self,
+        source: &Docx,
+        r_id: &str,
+        map: &mut IdMap,
+    ) -> Option<String> {
+        if let Some(id) = map.relationships.get(r_id) {
+            return Some(id.clone());
+        }
+        let relationship = source.relationships.as_ref()?.get(r_id)?;
+        let target = match relationship.external {
+            true => relationship.target.clone(),
+            false => {
+                let path = self.import_path(source, &part_path(&relationship.target), map)?;
+                relative_target("word/document.xml", &path)
+            }
+        };
+        let id = self.relationships.get_or_insert_with(Default::default).add(
+            &relationship.rel_type,
+            &target,
+            relationship.external,
+        );
+        map.relationships.insert(r_id.to_string(), id.clone());
+        Some(id)
+    }
+
+    /// 复制源文档中的部件或媒体文件，返回其在当前文档中的包内路径
+    fn import_path(&mut self, source: &Docx, path: &str, map: &mut IdMap) -> Option<String> {
+        if let Some(imported) = map.parts.get(path) {
+            return Some(imported.clone());
+        }
+        let imported = match source.media.contains_key(path) {
+            true => self.import_media(source, path)?,
+            false => self.import_part(source, path, map)?,
+        };
+        map.parts.insert(path.to_string(), imported.clone());
+        Some(imported)
+    }
+
+    /// 复制媒体文件并返回新的包内路径，内容相同的已有文件直接复用
+    fn import_media(&mut self, source: &Docx, path: &str) -> Option<String> {
+        let data = source.media.get(path)?;
+        if let Some(existing) = self.media.iter().find(|(_, d)| *d == data) {
+            return Some(existing.0.clone());
+        }
+        let extension = path.rsplit_once('.').map_or("bin", |(_, ext)| ext);
+        let index = (1..)
+            .find(|i| {
+                !self
+                    .media
+                    .contains_key(&format!("word/media/image{i}.{extension}"))
+            })
+            .unwrap_or(1);
+        let path = format!("word/media/image{index}.{extension}");
+        self.media.insert(path.clone(), data.clone());
+        Some(path)
+    }
+
+    /// 复制部件及其关系文件，关系指向的部件和媒体文件一并复制；与当前文档中的部件重名时
+    /// 改用新的序号，如 `word/header1.xml` 导入为 `word/header3.xml`。返回新的包内路径
+    fn import_part(&mut self, source: &Docx, path: &str, map: &mut IdMap) -> Option<String> {
+        let data = source.parts.get(path)?;
+        let imported = match self.parts.contains_key(path) {
+            false => path.to_string(),
+            true => {
+                let (stem, extension) = path.rsplit_once('.').unwrap_or((path, ""));
+                let stem = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+                (1..)
+                    .map(|n| format!("{stem}{n}.{extension}"))
+                    .find(|candidate| !self.parts.contains_key(candidate))
+                    .unwrap_or_default()
+            }
+        };
+        // 先登记路径，部件之间相互引用时不会重复复制
+        self.parts.insert(imported.clone(), data.clone());
+        map.parts.insert(path.to_string(), imported.clone());
+
+        let rels = source
+            .parts
+            .get(&rels_path(path))
+            .and_then(|data| Relationships::from_xml(&String::from_utf8_lossy(data)).ok());
+        if let Some(mut rels) = rels {
+            for relationship in rels.iter_mut().filter(|r| !r.external) {
+                let target = resolve_part_path(path, &relationship.target);
+                if let Some(target) = self.import_path(source, &target, map) {
+                    relationship.target = relative_target(&imported, &target);
+                }
+            }
+            if let Ok(xml) = rels.to_xml() {
+                self.parts.insert(rels_path(&imported), xml.into_bytes());
+            }
+        }
+        Some(imported)
+    }
+
+    /// 文档中最大的绘图对象ID
+    fn max_drawing_id(&self) -> u32 {
+        self.paragraphs()
+            .into_iter()
+            .flat_map(|paragraph| content_runs(&paragraph.content, true))
+            .flat_map(|run| &run.content)
+            .flat_map(|content| match content {
+                RunContent::Drawing(drawing) => vec![drawing.id],
+                RunContent::RawDrawing(drawing) => doc_property_ids(drawing),
+                _ => Vec::new(),
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// 原样保留的绘图中 `wp:docPr` 元素的绘图对象ID
+fn doc_property_ids(element: &XmlElement) -> Vec<u32> {
+    let mut ids: Vec<u32> = element
+        .children
+        .iter()
+        .flat_map(doc_property_ids)
+        .collect();
+    if element.name == "wp:docPr" {
+        ids.extend(element.attribute("id").and_then(|id| id.parse::<u32>().ok()));
+    }
+    ids
+}
+
+/// 收集正文用到的样式ID和编号ID，并沿样式和编号之间的引用补全
+pub(super) fn used_definitions(
+    source: &Docx,
+    content: &[BodyContent],
+) -> (BTreeSet<String>, BTreeSet<i32>) {
+    let mut pending_styles = Vec::new();
+    let mut pending_nums = Vec::new();
+    collect_references(content, &mut pending_styles, &mut pending_nums);
+
+    let mut style_ids = BTreeSet::new();
+    let mut num_ids = BTreeSet::new();
+    while !pending_styles.is_empty() || !pending_nums.is_empty() {
+        while let Some(id) = pending_styles.pop() {
+            if !style_ids.insert(id.clone()) {
+                continue;
+            }
+            let Some(style) = source.styles.find_style(&id) else {
+                continue;
+            };
+            pending_styles.extend(
+                [&style.based_on, &style.next, &style.link]
+                    .into_iter()
+                    .flatten()
+                    .map(|v| v.val.clone()),
+            );
+            pending_nums.extend(
+                style
+                    .paragraph_properties
+                    .as_ref()
+                    .and_then(|p| p.num_pr.as_ref())
+                    .map(|n| n.num_id.val),
+            );
+        }
+        while let Some(id) = pending_nums.pop() {
+            // 编号ID 0 表示“无编号”
+            if id == 0 || !num_ids.insert(id) {
+                continue;
+            }
+            let Some(num) = source.numbering.num(id) else {
+                continue;
+            };
+            let overrides = num
+                .overrides
+                .iter()
+                .filter_map(|o| o.definition.as_ref()?.paragraph_style.as_ref());
+            pending_styles.extend(overrides.map(|v| v.val.clone()));
+            if let Some(abstract_num) = source.numbering.abstract_num(num.abstract_num_id.val) {
+                let links = [&abstract_num.style_link, &abstract_num.num_style_link];
+                let level_styles = abstract_num.levels.iter().map(|l| &l.paragraph_style);
+                pending_styles.extend(
+                    links
+                        .into_iter()
+                        .chain(level_styles)
+                        .flatten()
+                        .map(|v| v.val.clone()),
+                );
+            }
+        }
+    }
+    (style_ids, num_ids)
+}
+
+/// 收集正文中直接引用的样式ID和编号ID
+fn collect_references(content: &[BodyContent], styles: &mut Vec<String>, nums: &mut Vec<i32>) {
+    for item in content {
+        match item {
+            BodyContent::Paragraph(paragraph) => {
+                if let Some(properties) = &paragraph.properties {
+                    styles.extend(properties.style.as_ref().map(|s| s.val.clone()));
+                    styles.extend(
+                        properties
+                            .run_properties
+                            .as_ref()
+                            .and_then(|r| r.style.as_ref())
+                            .map(|s| s.val.clone()),
+                    );
+                    nums.extend(properties.num_pr.as_ref().map(|n| n.num_id.val));
+                }
+                styles.extend(
+                    content_runs(&paragraph.content, true).filter_map(|run| {
+                        Some(run.properties.as_ref()?.style.as_ref()?.val.clone())
+                    }),
+                );
+            }
+            BodyContent::Table(table) => {
+                styles.extend(
+                    table
+                        .properties
+                        .as_ref()
+                        .and_then(|t| t.style.as_ref())
+                        .map(|s| s.val.clone()),
+                );
+                for cell in table.rows().flat_map(TableRow::cells) {
+                    collect_references(&cell.content, styles, nums);
+                }
+            }
+            BodyContent::Sdt(sdt) => collect_references(&sdt.content, styles, nums),
+        }
+    }
+}
+
+/// 改写段落属性中的样式和编号引用
+fn remap_paragraph_properties(properties: &mut ParagraphProperties, map: &IdMap) {
+    if let Some(style) = &mut properties.style {
+        remap_style(&mut style.val, map);
+    }
+    if let Some(style) = properties
+        .run_properties
+        .as_mut()
+        .and_then(|r| r.style.as_mut())
+    {
+        remap_style(&mut style.val, map);
+    }
+    if let Some(num_pr) = &mut properties.num_pr {
+        if let Some(&id) = map.nums.get(&num_pr.num_id.val) {
+            num_pr.num_id.val = id;
+        }
+    }
+}
+
+fn remap_style(style_id: &mut String, map: &IdMap) {
+    if let Some(target) = map.styles.get(style_id.as_str()) {
+        *style_id = target.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::relations::types;
+    use crate::docx::numbering::Numbering;
+    use crate::docx::styles::Styles;
+    use crate::docx::test_utils::{body_xml, docx, p};
+
+    const NUMBERING: &str = "<w:numbering xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+        <w:abstractNum w:abstractNumId=\"0\"><w:lvl w:ilvl=\"0\"><w:start w:val=\"1\"/>\
+        <w:numFmt w:val=\"decimal\"/><w:lvlText w:val=\"%1.\"/></w:lvl></w:abstractNum>\
+        <w:num w:numId=\"1\"><w:abstractNumId w:val=\"0\"/></w:num></w:numbering>";
+
+    /// 包含一个名为 Quote 的段落样式的样式部件，`size` 为其字号
+    fn quote_styles(size: u32) -> Styles {
+        Styles::from_xml(&format!(
+            "<w:styles xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+             <w:style w:type=\"paragraph\" w:styleId=\"Quote\"><w:name w:val=\"Quote\"/>\
+             <w:rPr><w:sz w:val=\"{size}\"/></w:rPr></w:style></w:styles>"
+        ))
+        .unwrap()
+    }
+
+    /// 使用 Quote 样式的段落 XML
+    fn quote(text: &str) -> String {
+        format!(
+            "<w:p><w:pPr><w:pStyle w:val=\"Quote\"/></w:pPr>\
+             <w:r><w:t xml:space=\"preserve\">{text}</w:t></w:r></w:p>"
+        )
+    }
+
+    #[test]
+    fn conflicting_styles_follow_strategy() {
+        let mut source = docx(&quote("source"));
+        source.styles = quote_styles(32);
+        let destination = || {
+            let mut destination = docx(&quote("destination"));
+            destination.styles = quote_styles(20);
+            destination
+        };
+
+        let mut kept = destination();
+        kept.append_document(&source, &MergeOptions::default());
+        assert!(body_xml(&kept).ends_with(&quote("source")));
+        assert_eq!(kept.styles.styles.len(), 1);
+
+        let mut renamed = destination();
+        let options = MergeOptions {
+            style_conflict: StyleConflict::Rename,
+            ..Default::default()
+        };
+        renamed.append_document(&source, &options);
+        assert!(body_xml(&renamed).ends_with(&quote("source").replace("Quote", "Quote1")));
+        let imported = renamed.styles.find_style("Quote1").unwrap();
+        assert_eq!(imported.name.as_ref().unwrap().val, "Quote 1");
+        assert_eq!(imported.run_properties, source.styles.styles[0].run_properties);
+        let original = destination();
+        assert_eq!(renamed.styles.find_style("Quote"), original.styles.find_style("Quote"));
+    }
+
+    #[test]
+    fn lists_bookmarks_and_links_get_new_ids() {
+        let item = "<w:p><w:pPr><w:numPr><w:ilvl w:val=\"0\"/><w:numId w:val=\"1\"/></w:numPr>\
+                    </w:pPr><w:bookmarkStart w:id=\"0\" w:name=\"top\"/>\
+                    <w:r><w:t xml:space=\"preserve\">item</w:t></w:r><w:bookmarkEnd w:id=\"0\"/></w:p>";
+        let link = "<w:p><w:hyperlink w:anchor=\"top\">\
+                    <w:r><w:t xml:space=\"preserve\">back</w:t></w:r></w:hyperlink></w:p>";
+        let mut destination = docx(item);
+        destination.numbering = Numbering::from_xml(NUMBERING).unwrap();
+        let mut source = docx(&format!("{item}{link}"));
+        source.numbering = Numbering::from_xml(NUMBERING).unwrap();
+
+        destination.append_document(&source, &MergeOptions::default());
+        assert_eq!(destination.numbering.nums.len(), 2);
+        assert_eq!(destination.numbering.abstract_nums.len(), 2);
+        let labels: Vec<String> = destination
+            .list_labels()
+            .into_iter()
+            .flatten()
+            .map(|label| label.text)
+            .collect();
+        assert_eq!(labels, ["1.", "1."]);
+
+        let names: Vec<(i32, &str)> = destination
+            .bookmarks()
+            .into_iter()
+            .map(|b| (b.id, b.name.as_str()))
+            .collect();
+        assert_eq!(names, [(0, "top"), (1, "top_1")]);
+        assert!(body_xml(&destination).contains("<w:hyperlink w:anchor=\"top_1\""));
+    }
+
+    #[test]
+    fn section_break_moves_closing_section_properties() {
+        let mut destination = docx(&format!(
+            "{}<w:sectPr><w:pgSz w:w=\"11906\" w:h=\"16838\"/></w:sectPr>",
+            p("first")
+        ));
+        let source = docx(&format!(
+            "{}<w:sectPr><w:pgSz w:w=\"16838\" w:h=\"11906\"/></w:sectPr>",
+            p("second")
+        ));
+        let options = MergeOptions {
+            section_break: Some(SectionType::Continuous),
+            ..Default::default()
+        };
+        destination.append_document(&source, &options);
+
+        let body = &destination.document.body;
+        let BodyContent::Paragraph(first) = &body.content[0] else {
+            panic!("paragraph expected")
+        };
+        let closing = first.properties.as_ref().unwrap().section_properties.as_ref();
+        assert_eq!(closing.unwrap().page_size.as_ref().unwrap().width, 11906);
+        let last = body.section_properties.as_ref().unwrap();
+        assert_eq!(last.page_size.as_ref().unwrap().width, 16838);
+        assert_eq!(last.section_type.as_ref().unwrap().val, SectionType::Continuous);
+        assert_eq!(body.content.len(), 2);
+    }
+
+    #[test]
+    fn parts_are_imported_and_dangling_references_dropped() {
+        let mut destination = docx(&p("first"));
+        destination
+            .parts
+            .insert("word/charts/chart1.xml".to_string(), b"<c:chartSpace/>".to_vec());
+        let mut source = docx(
+            "<w:p><w:r><w:drawing><wp:inline><wp:docPr id=\"4\" name=\"Chart 1\"/>\
+             <a:graphic><a:graphicData><c:chart r:id=\"rId1\"/></a:graphicData></a:graphic>\
+             </wp:inline></w:drawing></w:r></w:p>\
+             <w:p><w:hyperlink r:id=\"rId9\"><w:r><w:t>site</w:t></w:r></w:hyperlink>\
+             <w:r><w:footnoteReference w:id=\"1\"/></w:r></w:p>\
+             <w:sectPr><w:headerReference w:type=\"default\" r:id=\"rId2\"/></w:sectPr>",
+        );
+        // 没有关系信息时解析不保留超链接的关系ID，这里补上一个源文档中不存在的关系
+        if let BodyContent::Paragraph(paragraph) = &mut source.document.body.content[1] {
+            if let ParagraphContent::Hyperlink(hyperlink) = &mut paragraph.content[0] {
+                hyperlink.r_id = "rId9".to_string();
+            }
+        }
+        let mut rels = Relationships::default();
+        let relationships = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+        rels.add(&format!("{relationships}/chart"), "charts/chart1.xml", false);
+        rels.add(types::HEADER, "header1.xml", false);
+        source.relationships = Some(rels);
+        let mut chart_rels = Relationships::default();
+        chart_rels.add(&format!("{relationships}/package"), "../embeddings/book1.xlsx", false);
+        for (path, data) in [
+            ("word/charts/chart1.xml", b"<c:chartSpace/>".to_vec()),
+            ("word/charts/_rels/chart1.xml.rels", chart_rels.to_xml().unwrap().into_bytes()),
+            ("word/embeddings/book1.xlsx", vec![1]),
+            ("word/header1.xml", b"<w:hdr/>".to_vec()),
+        ] {
+            source.parts.insert(path.to_string(), data);
+        }
+
+        let options = MergeOptions {
+            section_break: Some(SectionType::NextPage),
+            ..Default::default()
+        };
+        destination.append_document(&source, &options);
+
+        let paths: Vec<&str> = destination.parts.keys().map(String::as_str).collect();
+        assert_eq!(
+            paths,
+            [
+                "word/charts/_rels/chart2.xml.rels",
+                "word/charts/chart1.xml",
+                "word/charts/chart2.xml",
+                "word/embeddings/book1.xlsx",
+                "word/header1.xml"
+            ]
+        );
+        let chart_rels = &destination.parts["word/charts/_rels/chart2.xml.rels"];
+        let chart_rels = Relationships::from_xml(&String::from_utf8_lossy(chart_rels)).unwrap();
+        assert_eq!(chart_rels.get("rId1").unwrap().target, "../embeddings/book1.xlsx");
+
+        let rels = destination.relationships.as_ref().unwrap();
+        let body = body_xml(&destination);
+        let chart = rels.iter().find(|r| r.target == "charts/chart2.xml").unwrap();
+        assert!(body.contains(&format!("<c:chart r:id=\"{}\"/>", chart.id)));
+        assert!(body.contains("<wp:docPr id=\"1\""));
+        assert!(!body.contains("w:hyperlink"));
+        assert!(body.contains("site"));
+        assert!(!body.contains("footnoteReference"));
+        let section = destination.document.body.section_properties.as_ref().unwrap();
+        assert_eq!(rels.get_target(&section.headers[0].id).unwrap(), "header1.xml");
+    }
+}
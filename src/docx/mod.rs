@@ -1,30 +1,79 @@
 use crate::common::package::open_package;
-use crate::common::relations::Relationships;
-use crate::docprops::{AppProps, CoreProps};
+use crate::common::relations::{types, Relationships};
+use crate::docprops::{AppProps, CoreProps, CustomProps};
 use crate::error::Result;
 use quick_xml::se::to_string;
-use std::io::{Cursor, Write};
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::path::Path;
 use zip::write::{FileOptions, ZipWriter};
 
+/// 文档构建模块
+pub mod builder;
+/// 文档比较模块
+pub mod compare;
+/// 内容控件模块
+pub mod content_controls;
+/// 自定义 XML 部件模块
+pub mod custom_xml;
 /// 文档模块
 pub mod document;
 /// 元素模块
 pub mod elements;
+/// 域模块
+pub mod fields;
+/// HTML 导出模块
+pub mod html;
+/// HTML 导入模块
+pub mod html_import;
+/// 超链接与书签模块
+pub mod links;
+/// 列表编号模块
+pub mod list_numbering;
+/// Markdown 导出模块
+pub mod markdown;
+/// Markdown 导入模块
+pub mod markdown_import;
+/// 文档合并模块
+pub mod merge;
+/// 编号格式化模块
+pub mod number_format;
 /// 编号模块
 pub mod numbering;
 /// 属性模块
 pub mod properties;
+/// 修订跟踪模块
+pub mod revisions;
+/// 查找与替换模块
+pub mod search;
+/// 文档设置模块
+pub mod settings;
+/// 文档拆分模块
+pub mod split;
+/// 样式解析模块
+pub mod style_resolver;
 /// 样式模块
 pub mod styles;
+/// 表格逻辑网格模块
+pub mod table_grid;
+/// 模板填充模块
+pub mod template;
+#[cfg(test)]
+mod test_utils;
+/// 纯文本提取模块
+pub mod text;
+/// 目录模块
+pub mod toc;
 
+use custom_xml::CustomXmlPart;
 use document::Document;
 pub use elements::{BodyContent, Paragraph, ParagraphContent, Run, RunContent};
 use numbering::Numbering;
+use settings::Settings;
 use styles::Styles;
 
 /// DOCX文档结构体，表示整个DOCX文件
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct Docx {
     /// 文档主体内容
     pub document: Document,
@@ -32,17 +81,28 @@ pub struct Docx {
     pub styles: Styles,
     /// 编号定义
     pub numbering: Numbering,
+    /// 文档设置
+    pub settings: Settings,
     /// 文档关系
     pub relationships: Option<Relationships>,
     /// 应用程序属性
     pub app_props: Option<AppProps>,
     /// 核心属性
     pub core_props: Option<CoreProps>,
+    /// 自定义属性
+    pub custom_props: Option<CustomProps>,
+    /// 图片等媒体文件，键为包内路径（如 `word/media/image1.png`）
+    pub media: BTreeMap<String, Vec<u8>>,
+    /// 页眉、页脚、脚注、尾注和图表部件及其关系文件，键为包内路径（如 `word/header1.xml`）；
+    /// 不解析为文档模型，保存时原样写回
+    pub parts: BTreeMap<String, Vec<u8>>,
+    /// 自定义 XML 部件（`customXml/item*.xml`），保存时按顺序重新编号
+    pub custom_xml: Vec<CustomXmlPart>,
 }
 
 impl Docx {
     /// 打开并解析DOCX文件
-    /// 
+    ///
     /// # 参数
     /// * `path` - DOCX文件路径
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
@@ -61,6 +121,12 @@ impl Docx {
             .ok()
             .and_then(|content| CoreProps::from_xml(&content).ok());
 
+        // 读取自定义属性
+        let custom_props = package
+            .read_file_by_path("docProps/custom.xml")
+            .ok()
+            .and_then(|content| CustomProps::from_xml(&content).ok());
+
         // 读取文档关系
         let rels_content = package
             .read_file_by_path("word/_rels/document.xml.rels")
@@ -77,6 +143,33 @@ impl Docx {
             .unwrap_or_default();
         let numbering = Numbering::from_xml(&numbering_content)?;
 
+        // 读取文档设置
+        let settings = match package.read_file_by_path("word/settings.xml") {
+            Ok(content) => Settings::from_xml(&content)?,
+            Err(_) => Settings::default(),
+        };
+
+        // 读取媒体文件
+        let mut media = BTreeMap::new();
+        for name in package.file_names() {
+            if name.starts_with("word/media/") && !name.ends_with('/') {
+                let data = package.read_binary_by_path(&name)?;
+                media.insert(name, data);
+            }
+        }
+
+        // 读取页眉、页脚、脚注、尾注和图表部件
+        let mut parts = BTreeMap::new();
+        for name in package.file_names() {
+            if is_preserved_part(&name) {
+                let data = package.read_binary_by_path(&name)?;
+                parts.insert(name, data);
+            }
+        }
+
+        // 读取自定义 XML 部件
+        let custom_xml = custom_xml::read_custom_xml(&mut package)?;
+
         // 读取主文档内容
         let document_content = package.read_file_by_path("word/document.xml")?;
         let document = Document::from_xml(&document_content, relationships.as_ref())?;
@@ -86,14 +179,19 @@ impl Docx {
             document,
             styles,
             numbering,
+            settings,
             relationships,
             app_props,
             core_props,
+            custom_props,
+            media,
+            parts,
+            custom_xml,
         })
     }
 
     /// 保存DOCX文件
-    /// 
+    ///
     /// # 参数
     /// * `path` - 保存路径
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
@@ -107,34 +205,16 @@ impl Docx {
 
         // 写入[Content_Types].xml文件
         zip.start_file("[Content_Types].xml", options)?;
-        zip.write_all(br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
-    <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
-    <Default Extension="xml" ContentType="application/xml"/>
-    <Override PartName="/_rels/.rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
-    <Override PartName="/word/_rels/document.xml.rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
-    <Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
-    <Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>
-    <Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/>
-    <Override PartName="/docProps/app.xml" ContentType="application/vnd.openxmlformats-officedocument.extended-properties+xml"/>
-    <Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>
-    <Override PartName="/word/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/>
-    <Override PartName="/word/fontTable.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.fontTable+xml"/>
-    <Override PartName="/word/settings.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml"/>
-</Types>"#)?;
+        zip.write_all(self.content_types_xml().as_bytes())?;
 
         // 写入_rels/.rels文件
         zip.add_directory("_rels", options)?;
         zip.start_file("_rels/.rels", options)?;
-        zip.write_all(br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
-    <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
-    <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
-    <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties" Target="docProps/app.xml"/>
-    <Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme" Target="word/theme/theme1.xml"/>
-    <Relationship Id="rId5" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/fontTable" Target="word/fontTable.xml"/>
-    <Relationship Id="rId6" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/settings" Target="word/settings.xml"/>
-</Relationships>"#)?;
+        zip.write_all(PACKAGE_RELS_HEADER.as_bytes())?;
+        if self.custom_props.is_some() {
+            zip.write_all(PACKAGE_RELS_CUSTOM.as_bytes())?;
+        }
+        zip.write_all(b"\n</Relationships>")?;
 
         // 写入docProps/app.xml文件
         if let Some(app_props) = &self.app_props {
@@ -151,6 +231,12 @@ impl Docx {
             zip.write_all(core_props_xml.as_bytes())?;
         }
 
+        // 写入docProps/custom.xml文件
+        if let Some(custom_props) = &self.custom_props {
+            zip.start_file("docProps/custom.xml", options)?;
+            zip.write_all(custom_props.to_xml()?.as_bytes())?;
+        }
+
         // 写入word/document.xml文件
         zip.add_directory("word", options)?;
         zip.start_file("word/document.xml", options)?;
@@ -178,22 +264,262 @@ impl Docx {
 
         // 写入word/settings.xml
         zip.start_file("word/settings.xml", options)?;
-        zip.write_all(DEFAULT_SETTINGS_XML)?;
-
-        // 写入word/_rels/document.xml.rels文件
-        if let Some(rels) = &self.relationships {
-            zip.add_directory("word/_rels", options)?;
-            zip.start_file("word/_rels/document.xml.rels", options)?;
-            let rels_xml = rels.to_xml()?;
-            zip.write_all(rels_xml.as_bytes())?;
+        zip.write_all(self.settings.to_xml().as_bytes())?;
+
+        // 写入word/media目录下的媒体文件
+        for (path, data) in &self.media {
+            zip.start_file(path.as_str(), options)?;
+            zip.write_all(data)?;
+        }
+
+        // 写入页眉、页脚、脚注、尾注和图表部件
+        for (path, data) in &self.parts {
+            zip.start_file(path.as_str(), options)?;
+            zip.write_all(data)?;
+        }
+
+        // 写入自定义 XML 部件及其属性部件
+        for (path, data) in custom_xml::custom_xml_files(&self.custom_xml)? {
+            zip.start_file(path.as_str(), options)?;
+            zip.write_all(&data)?;
         }
 
+        // 写入word/_rels/document.xml.rels文件，并补全固定写出的部件的关系
+        let mut rels = self.relationships.clone().unwrap_or_default();
+        // 自定义 XML 部件保存时重新编号，按写出的部件重建关系
+        let custom_xml_rels: Vec<String> = rels
+            .iter()
+            .filter(|r| r.rel_type == types::CUSTOM_XML)
+            .map(|r| r.id.clone())
+            .collect();
+        for id in custom_xml_rels {
+            rels.remove(&id);
+        }
+        for number in 1..=self.custom_xml.len() {
+            rels.add(
+                types::CUSTOM_XML,
+                &format!("../customXml/item{number}.xml"),
+                false,
+            );
+        }
+        for (rel_type, target) in [
+            (types::STYLES, "styles.xml"),
+            (types::NUMBERING, "numbering.xml"),
+            (types::SETTINGS, "settings.xml"),
+            (types::THEME, "theme/theme1.xml"),
+            (types::FONT_TABLE, "fontTable.xml"),
+        ] {
+            if rels.find_by_type(rel_type).is_none() {
+                rels.add(rel_type, target, false);
+            }
+        }
+        zip.add_directory("word/_rels", options)?;
+        zip.start_file("word/_rels/document.xml.rels", options)?;
+        let rels_xml = rels.to_xml()?;
+        zip.write_all(rels_xml.as_bytes())?;
+
         // 完成ZIP文件写入
         zip.finish()?;
         Ok(())
     }
 }
 
+impl Docx {
+    /// 生成 `[Content_Types].xml`，为媒体文件的扩展名登记内容类型
+    fn content_types_xml(&self) -> String {
+        let mut xml = String::from(CONTENT_TYPES_HEADER);
+        let embeddings = self.parts.keys().filter(|path| path.starts_with("word/embeddings/"));
+        let extensions: std::collections::BTreeSet<String> = self
+            .media
+            .keys()
+            .chain(embeddings)
+            .filter_map(|path| path.rsplit_once('.'))
+            .map(|(_, ext)| ext.to_ascii_lowercase())
+            .collect();
+        for ext in extensions {
+            xml.push_str(&format!(
+                "\n    <Default Extension=\"{ext}\" ContentType=\"{}\"/>",
+                media_content_type(&ext)
+            ));
+        }
+        if self.custom_props.is_some() {
+            xml.push_str(CONTENT_TYPES_CUSTOM);
+        }
+        for path in self.parts.keys() {
+            if let Some(content_type) = part_content_type(path) {
+                xml.push_str(&format!(
+                    "\n    <Override PartName=\"/{path}\" ContentType=\"{content_type}\"/>"
+                ));
+            }
+        }
+        for (number, part) in (1..).zip(&self.custom_xml) {
+            if part.item_id.is_some() {
+                xml.push_str(&format!(
+                    "\n    <Override PartName=\"/customXml/itemProps{number}.xml\" ContentType=\"{CUSTOM_XML_PROPS_CONTENT_TYPE}\"/>"
+                ));
+            }
+        }
+        xml.push_str(CONTENT_TYPES_OVERRIDES);
+        xml
+    }
+}
+
+/// 把 `word/document.xml` 中的关系目标转换为包内路径，如 `media/image1.png` 转换为
+/// `word/media/image1.png`
+pub(crate) fn part_path(target: &str) -> String {
+    match target.strip_prefix('/') {
+        Some(absolute) => absolute.to_string(),
+        None => format!("word/{target}"),
+    }
+}
+
+/// 把部件关系文件中的关系目标转换为包内路径，目标相对于源部件所在的目录，如
+/// `word/charts/chart1.xml` 中的 `../embeddings/book1.xlsx` 转换为 `word/embeddings/book1.xlsx`
+///
+/// # 参数
+/// * `source` - 源部件的包内路径
+/// * `target` - 关系目标
+pub(crate) fn resolve_part_path(source: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+    let mut segments: Vec<&str> = source.split('/').collect();
+    segments.pop();
+    for segment in target.split('/') {
+        match segment {
+            ".." => {
+                segments.pop();
+            }
+            "." | "" => {}
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// 从源部件指向包内路径的相对关系目标，是 [`resolve_part_path`] 的逆运算
+///
+/// # 参数
+/// * `source` - 源部件的包内路径
+/// * `path` - 目标的包内路径
+pub(crate) fn relative_target(source: &str, path: &str) -> String {
+    let mut dir: Vec<&str> = source.split('/').collect();
+    dir.pop();
+    let segments: Vec<&str> = path.split('/').collect();
+    let common = dir
+        .iter()
+        .zip(&segments[..segments.len() - 1])
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut target = "../".repeat(dir.len() - common);
+    target.push_str(&segments[common..].join("/"));
+    target
+}
+
+/// 部件的关系文件路径，如 `word/header1.xml` 的关系文件为 `word/_rels/header1.xml.rels`
+///
+/// # 参数
+/// * `path` - 部件的包内路径
+pub(crate) fn rels_path(path: &str) -> String {
+    match path.rsplit_once('/') {
+        Some((dir, name)) => format!("{dir}/_rels/{name}.rels"),
+        None => format!("_rels/{path}.rels"),
+    }
+}
+
+/// 是否为打开时保留的页眉、页脚、脚注、尾注或图表部件及其关系文件
+fn is_preserved_part(path: &str) -> bool {
+    // 图表及其嵌入的工作簿由原样保留的绘图引用
+    if path.starts_with("word/charts/") || path.starts_with("word/embeddings/") {
+        return !path.ends_with('/');
+    }
+    let name = match path.strip_prefix("word/_rels/") {
+        Some(rels) => match rels.strip_suffix(".rels") {
+            Some(name) => name,
+            None => return false,
+        },
+        None => match path.strip_prefix("word/") {
+            Some(name) if !name.contains('/') => name,
+            _ => return false,
+        },
+    };
+    part_content_type(&format!("word/{name}")).is_some()
+}
+
+/// 页眉、页脚、脚注、尾注和图表部件的内容类型
+fn part_content_type(path: &str) -> Option<&'static str> {
+    let name = path.strip_prefix("word/")?.strip_suffix(".xml")?;
+    let content_type = match name {
+        "footnotes" => {
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.footnotes+xml"
+        }
+        "endnotes" => "application/vnd.openxmlformats-officedocument.wordprocessingml.endnotes+xml",
+        _ if name.starts_with("header") => {
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.header+xml"
+        }
+        _ if name.starts_with("footer") => {
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.footer+xml"
+        }
+        _ if name.starts_with("charts/chart") => {
+            "application/vnd.openxmlformats-officedocument.drawingml.chart+xml"
+        }
+        _ if name.starts_with("charts/colors") => "application/vnd.ms-office.chartcolorstyle+xml",
+        _ if name.starts_with("charts/style") => "application/vnd.ms-office.chartstyle+xml",
+        _ => return None,
+    };
+    Some(content_type)
+}
+
+/// 按扩展名推断媒体文件的内容类型
+pub(crate) fn media_content_type(extension: &str) -> &'static str {
+    match extension {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "tif" | "tiff" => "image/tiff",
+        "svg" => "image/svg+xml",
+        "emf" => "image/x-emf",
+        "wmf" => "image/x-wmf",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        _ => "application/octet-stream",
+    }
+}
+
+const PACKAGE_RELS_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+    <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+    <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
+    <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties" Target="docProps/app.xml"/>
+    <Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme" Target="word/theme/theme1.xml"/>
+    <Relationship Id="rId5" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/fontTable" Target="word/fontTable.xml"/>
+    <Relationship Id="rId6" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/settings" Target="word/settings.xml"/>"#;
+
+const PACKAGE_RELS_CUSTOM: &str = r#"
+    <Relationship Id="rId7" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/custom-properties" Target="docProps/custom.xml"/>"#;
+
+const CONTENT_TYPES_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+    <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+    <Default Extension="xml" ContentType="application/xml"/>"#;
+
+const CUSTOM_XML_PROPS_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.customXmlProperties+xml";
+
+const CONTENT_TYPES_CUSTOM: &str = r#"
+    <Override PartName="/docProps/custom.xml" ContentType="application/vnd.openxmlformats-officedocument.custom-properties+xml"/>"#;
+
+const CONTENT_TYPES_OVERRIDES: &str = r#"
+    <Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+    <Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>
+    <Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/>
+    <Override PartName="/docProps/app.xml" ContentType="application/vnd.openxmlformats-officedocument.extended-properties+xml"/>
+    <Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>
+    <Override PartName="/word/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/>
+    <Override PartName="/word/fontTable.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.fontTable+xml"/>
+    <Override PartName="/word/settings.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml"/>
+</Types>"#;
+
 const DEFAULT_THEME_XML: &[u8] = br#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
 <a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="Office Theme">
   <a:themeElements>
@@ -369,6 +695,75 @@ const DEFAULT_FONT_TABLE_XML: &[u8] = br#"<?xml version="1.0" encoding="UTF-8" s
   </w:font>
 </w:fonts>"#;
 
-const DEFAULT_SETTINGS_XML: &[u8] = br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<w:settings xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
-</w:settings>"#;
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::properties::{HeaderFooterType, SectionProperties};
+    use crate::docx::test_utils::docx;
+
+    fn section(docx: &Docx) -> &SectionProperties {
+        docx.document.body.section_properties.as_ref().unwrap()
+    }
+
+    #[test]
+    fn headers_and_notes_survive_save() {
+        let mut rels = Relationships::default();
+        let header = rels.add(types::HEADER, "header1.xml", false);
+        let first_header = rels.add(types::HEADER, "header2.xml", false);
+        let footer = rels.add(types::FOOTER, "footer1.xml", false);
+        rels.add(types::FOOTNOTES, "footnotes.xml", false);
+        let mut original = docx(&format!(
+            "<w:p><w:r><w:t>text</w:t></w:r><w:r><w:rPr><w:vertAlign w:val=\"superscript\"/></w:rPr>\
+             <w:footnoteReference w:id=\"1\"/></w:r></w:p>\
+             <w:sectPr><w:headerReference w:type=\"default\" r:id=\"{header}\"/>\
+             <w:footerReference w:type=\"first\" r:id=\"{footer}\"/>\
+             <w:headerReference w:type=\"first\" r:id=\"{first_header}\"/>\
+             <w:pgSz w:w=\"11906\" w:h=\"16838\"/><w:pgNumType w:fmt=\"lowerRoman\" w:start=\"3\"/>\
+             <w:titlePg/></w:sectPr>"
+        ));
+        original.relationships = Some(rels);
+        for name in ["header1", "header2", "footer1", "footnotes"] {
+            original
+                .parts
+                .insert(format!("word/{name}.xml"), b"<w:hdr/>".to_vec());
+        }
+
+        let path = std::env::temp_dir().join(format!("office-save-{}.docx", std::process::id()));
+        original.save(&path).unwrap();
+        let reopened = Docx::open(&path);
+        std::fs::remove_file(&path).unwrap();
+        let reopened = reopened.unwrap();
+
+        let sect = section(&reopened);
+        let headers: Vec<_> = sect.headers.iter().map(|h| (h.reference_type, h.id.clone())).collect();
+        assert_eq!(
+            headers,
+            [(HeaderFooterType::Default, header), (HeaderFooterType::First, first_header.clone())]
+        );
+        assert_eq!(sect.footers[0].id, footer);
+        assert_eq!(sect.page_numbering.as_ref().and_then(|n| n.start), Some(3));
+        assert!(sect.unknown.is_empty());
+        assert_eq!(sect, section(&original));
+
+        let BodyContent::Paragraph(paragraph) = &reopened.document.body.content[0] else {
+            panic!("expected a paragraph");
+        };
+        let notes: Vec<_> = paragraph
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                ParagraphContent::Run(run) => Some(&run.content),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|content| match content {
+                RunContent::FootnoteReference(reference) => Some(reference.id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(notes, [1]);
+        assert!(reopened.parts.contains_key("word/footnotes.xml"));
+        let rels = reopened.relationships.as_ref().unwrap();
+        assert_eq!(rels.get_target(&first_header).map(String::as_str), Some("header2.xml"));
+    }
+}
@@ -0,0 +1,627 @@
+//! 编号格式化：按 `ST_NumberFormat` 将编号值渲染为文本
+
+use crate::docx::numbering::NumberFormat;
+
+/// 按编号格式渲染编号值
+///
+/// 字母类格式超出字母表后按重复字母继续（如 `Z` 之后为 `AA`、`BB`），
+/// 有固定范围的格式（带圈数字等）超出范围时退回十进制数字。
+///
+/// # 参数
+/// * `value` - 编号值
+/// * `format` - 编号格式
+pub fn format_number(value: i32, format: NumberFormat) -> String {
+    use NumberFormat::*;
+
+    if value < 0 {
+        return value.to_string();
+    }
+    match format {
+        Decimal | DecimalHalfWidth | Custom => value.to_string(),
+        DecimalZero => format!("{value:02}"),
+        UpperRoman => roman(value),
+        LowerRoman => roman(value).to_lowercase(),
+        UpperLetter => repeated(value, LATIN_UPPER),
+        LowerLetter => repeated(value, LATIN_UPPER).to_lowercase(),
+        Ordinal => ordinal(value),
+        CardinalText => capitalize(&english_cardinal(value)),
+        OrdinalText => capitalize(&english_ordinal(value)),
+        Hex => format!("{value:X}"),
+        Chicago => chicago(value),
+        NumberInDash => format!("- {value} -"),
+        DecimalFullWidth | DecimalFullWidth2 => map_digits(value, '０'),
+        HindiNumbers | HindiCounting => map_digits(value, '०'),
+        ThaiNumbers => map_digits(value, '๐'),
+        DecimalEnclosedCircle | DecimalEnclosedCircleChinese => enclosed_circle(value),
+        DecimalEnclosedFullstop => enclosed(value, 1..=20, '⒈'),
+        DecimalEnclosedParen => enclosed(value, 1..=20, '⑴'),
+        IdeographEnclosedCircle => enclosed(value, 1..=10, '㈠'),
+        IdeographTraditional => cyclic(value, HEAVENLY_STEMS),
+        IdeographZodiac => cyclic(value, EARTHLY_BRANCHES),
+        IdeographZodiacTraditional => sexagenary(value),
+        IdeographDigital | TaiwaneseDigital | JapaneseDigitalTenThousand => {
+            digit_by_digit(value, &CJK_DIGITS)
+        }
+        KoreanDigital2 => digit_by_digit(value, &KOREAN_HANJA_DIGITS),
+        KoreanDigital => digit_by_digit(value, &KOREAN_DIGITS),
+        ChineseCounting => ChineseNumeral::COUNTING.render(value),
+        ChineseCountingThousand => ChineseNumeral::COUNTING_THOUSAND.render(value),
+        TaiwaneseCounting => ChineseNumeral::TAIWANESE.render(value),
+        TaiwaneseCountingThousand => ChineseNumeral::TAIWANESE_THOUSAND.render(value),
+        ChineseLegalSimplified => ChineseNumeral::LEGAL_SIMPLIFIED.render(value),
+        IdeographLegalTraditional => ChineseNumeral::LEGAL_TRADITIONAL.render(value),
+        JapaneseCounting => ChineseNumeral::JAPANESE.render(value),
+        JapaneseLegal => ChineseNumeral::JAPANESE_LEGAL.render(value),
+        KoreanCounting => ChineseNumeral::KOREAN.render(value),
+        KoreanLegal => korean_native(value),
+        Aiueo => cyclic(value, AIUEO_HALF_WIDTH),
+        AiueoFullWidth => cyclic(value, AIUEO_FULL_WIDTH),
+        Iroha => cyclic(value, IROHA_HALF_WIDTH),
+        IrohaFullWidth => cyclic(value, IROHA_FULL_WIDTH),
+        Ganada => cyclic(value, GANADA),
+        Chosung => cyclic(value, CHOSUNG),
+        RussianLower => repeated(value, RUSSIAN_LOWER),
+        RussianUpper => repeated(value, RUSSIAN_LOWER).to_uppercase(),
+        Hebrew1 => hebrew(value),
+        Hebrew2 => repeated(value, HEBREW_LETTERS),
+        ArabicAlpha => cyclic(value, ARABIC_ALPHA),
+        ArabicAbjad => cyclic(value, ARABIC_ABJAD),
+        HindiVowels => cyclic(value, HINDI_VOWELS),
+        HindiConsonants => cyclic(value, HINDI_CONSONANTS),
+        ThaiLetters => cyclic(value, THAI_LETTERS),
+        ThaiCounting => thai_counting(value),
+        BahtText => format!("{}บาทถ้วน", thai_counting(value)),
+        DollarText => format!("{} and 00/100", capitalize(&english_cardinal(value))),
+        VietnameseCounting => vietnamese_counting(value),
+        Bullet | None => String::new(),
+    }
+}
+
+const LATIN_UPPER: &[&str] = &[
+    "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S",
+    "T", "U", "V", "W", "X", "Y", "Z",
+];
+const RUSSIAN_LOWER: &[&str] = &[
+    "а", "б", "в", "г", "д", "е", "ж", "з", "и", "к", "л", "м", "н", "о", "п", "р", "с", "т", "у",
+    "ф", "х", "ц", "ч", "ш", "щ", "э", "ю", "я",
+];
+const HEBREW_LETTERS: &[&str] = &[
+    "א", "ב", "ג", "ד", "ה", "ו", "ז", "ח", "ט", "י", "כ", "ל", "מ", "נ", "ס", "ע", "פ", "צ", "ק",
+    "ר", "ש", "ת",
+];
+const ARABIC_ALPHA: &[&str] = &[
+    "أ", "ب", "ت", "ث", "ج", "ح", "خ", "د", "ذ", "ر", "ز", "س", "ش", "ص", "ض", "ط", "ظ", "ع", "غ",
+    "ف", "ق", "ك", "ل", "م", "ن", "ه", "و", "ي",
+];
+const ARABIC_ABJAD: &[&str] = &[
+    "أ", "ب", "ج", "د", "ه", "و", "ز", "ح", "ط", "ي", "ك", "ل", "م", "ن", "س", "ع", "ف", "ص", "ق",
+    "ر", "ش", "ت", "ث", "خ", "ذ", "ض", "ظ", "غ",
+];
+const HINDI_VOWELS: &[&str] = &[
+    "अ", "आ", "इ", "ई", "उ", "ऊ", "ऋ", "ए", "ऐ", "ओ", "औ", "अं", "अः",
+];
+const HINDI_CONSONANTS: &[&str] = &[
+    "क", "ख", "ग", "घ", "ङ", "च", "छ", "ज", "झ", "ञ", "ट", "ठ", "ड", "ढ", "ण", "त", "थ", "द", "ध",
+    "न", "प", "फ", "ब", "भ", "म", "य", "र", "ल", "व", "श", "ष", "स", "ह",
+];
+const THAI_LETTERS: &[&str] = &[
+    "ก", "ข", "ค", "ง", "จ", "ฉ", "ช", "ซ", "ฌ", "ญ", "ฎ", "ฏ", "ฐ", "ฑ", "ฒ", "ณ", "ด", "ต", "ถ",
+    "ท", "ธ", "น", "บ", "ป", "ผ", "ฝ", "พ", "ฟ", "ภ", "ม", "ย", "ร", "ล", "ว", "ศ", "ษ", "ส", "ห",
+    "ฬ", "อ", "ฮ",
+];
+const HEAVENLY_STEMS: &[&str] = &["甲", "乙", "丙", "丁", "戊", "己", "庚", "辛", "壬", "癸"];
+const EARTHLY_BRANCHES: &[&str] = &[
+    "子", "丑", "寅", "卯", "辰", "巳", "午", "未", "申", "酉", "戌", "亥",
+];
+const AIUEO_FULL_WIDTH: &[&str] = &[
+    "ア", "イ", "ウ", "エ", "オ", "カ", "キ", "ク", "ケ", "コ", "サ", "シ", "ス", "セ", "ソ", "タ",
+    "チ", "ツ", "テ", "ト", "ナ", "ニ", "ヌ", "ネ", "ノ", "ハ", "ヒ", "フ", "ヘ", "ホ", "マ", "ミ",
+    "ム", "メ", "モ", "ヤ", "ユ", "ヨ", "ラ", "リ", "ル", "レ", "ロ", "ワ", "ヲ", "ン",
+];
+const AIUEO_HALF_WIDTH: &[&str] = &[
+    "ｱ", "ｲ", "ｳ", "ｴ", "ｵ", "ｶ", "ｷ", "ｸ", "ｹ", "ｺ", "ｻ", "ｼ", "ｽ", "ｾ", "ｿ", "ﾀ", "ﾁ", "ﾂ", "ﾃ",
+    "ﾄ", "ﾅ", "ﾆ", "ﾇ", "ﾈ", "ﾉ", "ﾊ", "ﾋ", "ﾌ", "ﾍ", "ﾎ", "ﾏ", "ﾐ", "ﾑ", "ﾒ", "ﾓ", "ﾔ", "ﾕ", "ﾖ",
+    "ﾗ", "ﾘ", "ﾙ", "ﾚ", "ﾛ", "ﾜ", "ｦ", "ﾝ",
+];
+const IROHA_FULL_WIDTH: &[&str] = &[
+    "イ", "ロ", "ハ", "ニ", "ホ", "ヘ", "ト", "チ", "リ", "ヌ", "ル", "ヲ", "ワ", "カ", "ヨ", "タ",
+    "レ", "ソ", "ツ", "ネ", "ナ", "ラ", "ム", "ウ", "ヰ", "ノ", "オ", "ク", "ヤ", "マ", "ケ", "フ",
+    "コ", "エ", "テ", "ア", "サ", "キ", "ユ", "メ", "ミ", "シ", "ヱ", "ヒ", "モ", "セ", "ス",
+];
+const IROHA_HALF_WIDTH: &[&str] = &[
+    "ｲ", "ﾛ", "ﾊ", "ﾆ", "ﾎ", "ﾍ", "ﾄ", "ﾁ", "ﾘ", "ﾇ", "ﾙ", "ｦ", "ﾜ", "ｶ", "ﾖ", "ﾀ", "ﾚ", "ｿ", "ﾂ",
+    "ﾈ", "ﾅ", "ﾗ", "ﾑ", "ｳ", "ヰ", "ﾉ", "ｵ", "ｸ", "ﾔ", "ﾏ", "ｹ", "ﾌ", "ｺ", "ｴ", "ﾃ", "ｱ", "ｻ", "ｷ",
+    "ﾕ", "ﾒ", "ﾐ", "ｼ", "ヱ", "ﾋ", "ﾓ", "ｾ", "ｽ",
+];
+const GANADA: &[&str] = &[
+    "가", "나", "다", "라", "마", "바", "사", "아", "자", "차", "카", "타", "파", "하",
+];
+const CHOSUNG: &[&str] = &[
+    "ㄱ", "ㄴ", "ㄷ", "ㄹ", "ㅁ", "ㅂ", "ㅅ", "ㅇ", "ㅈ", "ㅊ", "ㅋ", "ㅌ", "ㅍ", "ㅎ",
+];
+const CJK_DIGITS: [&str; 10] = ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+const KOREAN_HANJA_DIGITS: [&str; 10] =
+    ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+const KOREAN_DIGITS: [&str; 10] = ["영", "일", "이", "삼", "사", "오", "육", "칠", "팔", "구"];
+
+/// 字母表循环：超出字母表后从头开始
+fn cyclic(value: i32, alphabet: &[&str]) -> String {
+    if value < 1 {
+        return value.to_string();
+    }
+    alphabet[(value as usize - 1) % alphabet.len()].to_string()
+}
+
+/// 重复字母：超出字母表后重复同一字母（A…Z, AA…ZZ, AAA…）
+fn repeated(value: i32, alphabet: &[&str]) -> String {
+    if value < 1 {
+        return value.to_string();
+    }
+    let index = value as usize - 1;
+    alphabet[index % alphabet.len()].repeat(index / alphabet.len() + 1)
+}
+
+/// 干支纪年：甲子, 乙丑, …, 癸亥
+fn sexagenary(value: i32) -> String {
+    if value < 1 {
+        return value.to_string();
+    }
+    let index = value as usize - 1;
+    format!(
+        "{}{}",
+        HEAVENLY_STEMS[index % HEAVENLY_STEMS.len()],
+        EARTHLY_BRANCHES[index % EARTHLY_BRANCHES.len()]
+    )
+}
+
+/// 罗马数字，超过 3999 时以重复的 M 表示千位
+fn roman(value: i32) -> String {
+    if value < 1 {
+        return value.to_string();
+    }
+    const NUMERALS: [(i32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut rest = value;
+    let mut result = String::new();
+    for (amount, numeral) in NUMERALS {
+        while rest >= amount {
+            result.push_str(numeral);
+            rest -= amount;
+        }
+    }
+    result
+}
+
+/// 英文序数：1st, 2nd, 3rd, 4th, 11th
+fn ordinal(value: i32) -> String {
+    let suffix = match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{value}{suffix}")
+}
+
+/// 芝加哥手册脚注符号：*, †, ‡, §，之后依次重复为 **, ††, …
+fn chicago(value: i32) -> String {
+    cyclic(value, &["*", "†", "‡", "§"]).repeat((value.max(1) as usize - 1) / 4 + 1)
+}
+
+/// 将每一位阿拉伯数字替换为从 `zero` 开始的连续数字字符
+fn map_digits(value: i32, zero: char) -> String {
+    value
+        .to_string()
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(d) => char::from_u32(zero as u32 + d).unwrap_or(c),
+            None => c,
+        })
+        .collect()
+}
+
+/// 逐位转换数字
+fn digit_by_digit(value: i32, digits: &[&str; 10]) -> String {
+    value
+        .to_string()
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| digits[d as usize])
+        .collect()
+}
+
+/// 连续编码的带圈、带括号等数字字符，超出范围时退回十进制数字
+fn enclosed(value: i32, range: std::ops::RangeInclusive<i32>, first: char) -> String {
+    if !range.contains(&value) {
+        return value.to_string();
+    }
+    char::from_u32(first as u32 + (value - range.start()) as u32)
+        .map_or_else(|| value.to_string(), String::from)
+}
+
+/// 带圈数字：①…⑳ 与 ㉑…㊿ 分属两个字符区段
+fn enclosed_circle(value: i32) -> String {
+    match value {
+        1..=20 => enclosed(value, 1..=20, '①'),
+        21..=35 => enclosed(value, 21..=35, '㉑'),
+        36..=50 => enclosed(value, 36..=50, '㊱'),
+        _ => value.to_string(),
+    }
+}
+
+/// 首字母大写
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+const ENGLISH_ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const ENGLISH_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// 英文基数词（小写）：twenty-one, one hundred five
+fn english_cardinal(value: i32) -> String {
+    fn below_thousand(n: usize) -> String {
+        let mut words = Vec::new();
+        if n >= 100 {
+            words.push(format!("{} hundred", ENGLISH_ONES[n / 100]));
+        }
+        match n % 100 {
+            0 => {}
+            r @ 1..=19 => words.push(ENGLISH_ONES[r].to_string()),
+            r if r % 10 == 0 => words.push(ENGLISH_TENS[r / 10].to_string()),
+            r => words.push(format!("{}-{}", ENGLISH_TENS[r / 10], ENGLISH_ONES[r % 10])),
+        }
+        words.join(" ")
+    }
+
+    if value == 0 {
+        return ENGLISH_ONES[0].to_string();
+    }
+    let mut rest = value as usize;
+    let mut groups = Vec::new();
+    for scale in ["", " thousand", " million", " billion"] {
+        if !rest.is_multiple_of(1000) {
+            groups.push(format!("{}{scale}", below_thousand(rest % 1000)));
+        }
+        rest /= 1000;
+    }
+    groups.reverse();
+    groups.join(" ")
+}
+
+/// 英文序数词（小写）：first, twenty-second, one hundredth
+fn english_ordinal(value: i32) -> String {
+    let cardinal = english_cardinal(value);
+    let split = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+    let (head, last) = cardinal.split_at(split);
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        word if word.ends_with('y') => format!("{}ieth", &word[..word.len() - 1]),
+        word => format!("{word}th"),
+    };
+    format!("{head}{last}")
+}
+
+/// 汉字计数法的书写规则
+struct ChineseNumeral {
+    /// 0–9 的数字
+    digits: [&'static str; 10],
+    /// 十、百、千
+    units: [&'static str; 3],
+    /// 万、亿
+    myriads: [&'static str; 2],
+    /// 中间缺位时插入的零，`None` 表示不写零（日文、韩文写法）
+    zero: Option<&'static str>,
+    /// 十、百、千前的“一”是否省略（日文、韩文写法）
+    omit_one: bool,
+    /// 10–19 开头的“一十”是否简写为“十”
+    short_ten: bool,
+}
+
+impl ChineseNumeral {
+    const COUNTING: ChineseNumeral = ChineseNumeral {
+        digits: CJK_DIGITS,
+        units: ["十", "百", "千"],
+        myriads: ["万", "亿"],
+        zero: Some("〇"),
+        omit_one: false,
+        short_ten: true,
+    };
+    const COUNTING_THOUSAND: ChineseNumeral = ChineseNumeral {
+        zero: Some("零"),
+        ..ChineseNumeral::COUNTING
+    };
+    const TAIWANESE: ChineseNumeral = ChineseNumeral {
+        myriads: ["萬", "億"],
+        ..ChineseNumeral::COUNTING
+    };
+    const TAIWANESE_THOUSAND: ChineseNumeral = ChineseNumeral {
+        zero: Some("零"),
+        ..ChineseNumeral::TAIWANESE
+    };
+    const LEGAL_SIMPLIFIED: ChineseNumeral = ChineseNumeral {
+        digits: ["零", "壹", "贰", "叁", "肆", "伍", "陆", "柒", "捌", "玖"],
+        units: ["拾", "佰", "仟"],
+        myriads: ["万", "亿"],
+        zero: Some("零"),
+        omit_one: false,
+        short_ten: false,
+    };
+    const LEGAL_TRADITIONAL: ChineseNumeral = ChineseNumeral {
+        digits: ["零", "壹", "貳", "參", "肆", "伍", "陸", "柒", "捌", "玖"],
+        myriads: ["萬", "億"],
+        ..ChineseNumeral::LEGAL_SIMPLIFIED
+    };
+    const JAPANESE: ChineseNumeral = ChineseNumeral {
+        digits: CJK_DIGITS,
+        units: ["十", "百", "千"],
+        myriads: ["万", "億"],
+        zero: None,
+        omit_one: true,
+        short_ten: true,
+    };
+    const JAPANESE_LEGAL: ChineseNumeral = ChineseNumeral {
+        digits: ["〇", "壱", "弐", "参", "四", "伍", "六", "七", "八", "九"],
+        units: ["拾", "百", "阡"],
+        myriads: ["萬", "億"],
+        zero: None,
+        omit_one: false,
+        short_ten: false,
+    };
+    const KOREAN: ChineseNumeral = ChineseNumeral {
+        digits: KOREAN_DIGITS,
+        units: ["십", "백", "천"],
+        myriads: ["만", "억"],
+        ..ChineseNumeral::JAPANESE
+    };
+
+    fn render(&self, value: i32) -> String {
+        if value == 0 {
+            return self.digits[0].to_string();
+        }
+
+        // 以万为单位从高到低分组
+        let mut groups = Vec::new();
+        let mut rest = value as u32;
+        while rest > 0 {
+            groups.push(rest % 10_000);
+            rest /= 10_000;
+        }
+
+        let mut result = String::new();
+        let mut pending_zero = false;
+        for (index, &group) in groups.iter().enumerate().rev() {
+            if group == 0 {
+                pending_zero = !result.is_empty();
+                continue;
+            }
+            if !result.is_empty() && group < 1000 {
+                pending_zero = true;
+            }
+            for position in (0..4).rev() {
+                let digit = (group / 10u32.pow(position)) % 10;
+                if digit == 0 {
+                    pending_zero |= !result.is_empty();
+                    continue;
+                }
+                if pending_zero {
+                    result.push_str(self.zero.unwrap_or_default());
+                    pending_zero = false;
+                }
+                let omit = position > 0
+                    && digit == 1
+                    && (self.omit_one || (self.short_ten && position == 1 && result.is_empty()));
+                if !omit {
+                    result.push_str(self.digits[digit as usize]);
+                }
+                if position > 0 {
+                    result.push_str(self.units[position as usize - 1]);
+                }
+            }
+            if index > 0 {
+                result.push_str(self.myriads[(index - 1).min(1)]);
+            }
+            // 组内末尾的零由下一组决定是否写出
+            pending_zero = false;
+        }
+        result
+    }
+}
+
+/// 韩文固有数词（1–99），超出范围时使用汉字词计数
+fn korean_native(value: i32) -> String {
+    const ONES: [&str; 10] = [
+        "", "하나", "둘", "셋", "넷", "다섯", "여섯", "일곱", "여덟", "아홉",
+    ];
+    const TENS: [&str; 10] = [
+        "", "열", "스물", "서른", "마흔", "쉰", "예순", "일흔", "여든", "아흔",
+    ];
+    match value {
+        1..=99 => format!("{}{}", TENS[value as usize / 10], ONES[value as usize % 10]),
+        _ => ChineseNumeral::KOREAN.render(value),
+    }
+}
+
+/// 希伯来数字（字母计数），15 和 16 写作 טו、טז
+fn hebrew(value: i32) -> String {
+    const ONES: [&str; 10] = ["", "א", "ב", "ג", "ד", "ה", "ו", "ז", "ח", "ט"];
+    const TENS: [&str; 10] = ["", "י", "כ", "ל", "מ", "נ", "ס", "ע", "פ", "צ"];
+    const HUNDREDS: [&str; 5] = ["", "ק", "ר", "ש", "ת"];
+    if value < 1 {
+        return value.to_string();
+    }
+
+    let mut result = String::new();
+    let mut rest = value as usize;
+    while rest >= 400 {
+        result.push_str(HUNDREDS[4]);
+        rest -= 400;
+    }
+    result.push_str(HUNDREDS[rest / 100]);
+    rest %= 100;
+    match rest {
+        15 => result.push_str("טו"),
+        16 => result.push_str("טז"),
+        _ => {
+            result.push_str(TENS[rest / 10]);
+            result.push_str(ONES[rest % 10]);
+        }
+    }
+    result
+}
+
+/// 泰文计数：หนึ่ง, สอง, …, สิบเอ็ด, ยี่สิบ
+fn thai_counting(value: i32) -> String {
+    const DIGITS: [&str; 10] = [
+        "ศูนย์",
+        "หนึ่ง",
+        "สอง",
+        "สาม",
+        "สี่",
+        "ห้า",
+        "หก",
+        "เจ็ด",
+        "แปด",
+        "เก้า",
+    ];
+    const UNITS: [&str; 6] = ["", "สิบ", "ร้อย", "พัน", "หมื่น", "แสน"];
+
+    fn below_million(n: u32) -> String {
+        let mut result = String::new();
+        for position in (0..6).rev() {
+            let digit = (n / 10u32.pow(position)) % 10;
+            if digit == 0 {
+                continue;
+            }
+            match (position, digit) {
+                (0, 1) if n >= 10 => result.push_str("เอ็ด"),
+                (1, 1) => {}
+                (1, 2) => result.push_str("ยี่"),
+                _ => result.push_str(DIGITS[digit as usize]),
+            }
+            result.push_str(UNITS[position as usize]);
+        }
+        result
+    }
+
+    if value == 0 {
+        return DIGITS[0].to_string();
+    }
+    let value = value as u32;
+    match value / 1_000_000 {
+        0 => below_million(value),
+        millions => format!(
+            "{}ล้าน{}",
+            below_million(millions),
+            below_million(value % 1_000_000)
+        ),
+    }
+}
+
+/// 越南语计数（1–99），超出范围时退回十进制数字
+fn vietnamese_counting(value: i32) -> String {
+    const ONES: [&str; 10] = [
+        "", "một", "hai", "ba", "bốn", "năm", "sáu", "bảy", "tám", "chín",
+    ];
+    match value {
+        1..=9 => ONES[value as usize].to_string(),
+        10..=99 => {
+            let (tens, ones) = (value as usize / 10, value as usize % 10);
+            let head = match tens {
+                1 => "mười".to_string(),
+                _ => format!("{} mươi", ONES[tens]),
+            };
+            match ones {
+                0 => head,
+                1 if tens > 1 => format!("{head} mốt"),
+                5 => format!("{head} lăm"),
+                _ => format!("{head} {}", ONES[ones]),
+            }
+        }
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(format: NumberFormat, values: &[i32]) -> Vec<String> {
+        values.iter().map(|&v| format_number(v, format)).collect()
+    }
+
+    #[test]
+    fn latin_formats() {
+        let roman = render(NumberFormat::UpperRoman, &[1, 4, 9, 14, 1990]);
+        assert_eq!(roman, ["I", "IV", "IX", "XIV", "MCMXC"]);
+        let letters = render(NumberFormat::LowerLetter, &[1, 26, 27, 53]);
+        assert_eq!(letters, ["a", "z", "aa", "aaa"]);
+        let ordinals = render(NumberFormat::Ordinal, &[1, 2, 3, 11, 22]);
+        assert_eq!(ordinals, ["1st", "2nd", "3rd", "11th", "22nd"]);
+        assert_eq!(render(NumberFormat::CardinalText, &[21]), ["Twenty-one"]);
+        assert_eq!(render(NumberFormat::DecimalZero, &[7, 12]), ["07", "12"]);
+    }
+
+    #[test]
+    fn east_asian_formats() {
+        let counting = render(NumberFormat::ChineseCounting, &[1, 10, 11, 20, 105]);
+        assert_eq!(counting, ["一", "十", "十一", "二十", "一百〇五"]);
+        let thousand = render(NumberFormat::ChineseCountingThousand, &[105, 2010]);
+        assert_eq!(thousand, ["一百零五", "二千零一十"]);
+        assert_eq!(render(NumberFormat::DecimalEnclosedCircle, &[1, 20]), ["①", "⑳"]);
+        assert_eq!(render(NumberFormat::IdeographDigital, &[101]), ["一〇一"]);
+        assert_eq!(render(NumberFormat::DecimalFullWidth, &[12]), ["１２"]);
+    }
+
+    #[test]
+    fn bounded_formats_fall_back_to_decimal() {
+        assert_eq!(format_number(60, NumberFormat::DecimalEnclosedParen), "60");
+        assert_eq!(format_number(3, NumberFormat::Bullet), "");
+    }
+}
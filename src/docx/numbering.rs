@@ -1,99 +1,696 @@
 //! DOCX 编号（列表）格式定义
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
-use crate::docx::properties::Val;
+use crate::docx::number_format::format_number;
+use crate::docx::elements::Paragraph;
+use crate::docx::properties::{
+    Indentation, OnOff, ParagraphProperties, RunFonts, RunProperties, Val,
+};
+use crate::error::{OfficeError, Result};
 
-// --- 用于反序列化的原始结构体 ---
-
-/// 编号格式值枚举
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+/// 编号格式 (`ST_NumberFormat`)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
 #[serde(rename_all = "camelCase")]
-enum NumFmtVal {
-    /// 项目符号
-    Bullet,
-    /// 十进制数字
+pub enum NumberFormat {
+    /// 十进制数字：1, 2, 3
+    #[default]
     Decimal,
-    /// 小写字母
-    LowerLetter,
-    /// 大写字母
-    UpperLetter,
-    /// 小写罗马数字
-    LowerRoman,
-    /// 大写罗马数字
+    /// 大写罗马数字：I, II, III
     UpperRoman,
-    /// 无格式
+    /// 小写罗马数字：i, ii, iii
+    LowerRoman,
+    /// 大写字母：A, B, C, …, AA
+    UpperLetter,
+    /// 小写字母：a, b, c, …, aa
+    LowerLetter,
+    /// 序数：1st, 2nd, 3rd
+    Ordinal,
+    /// 英文基数词：One, Two
+    CardinalText,
+    /// 英文序数词：First, Second
+    OrdinalText,
+    /// 十六进制
+    Hex,
+    /// 芝加哥手册脚注符号：*, †, ‡, §
+    Chicago,
+    /// 汉字逐位数字：一〇一
+    IdeographDigital,
+    /// 日文计数：一, 十一
+    JapaneseCounting,
+    /// 日文五十音：ｱ, ｲ, ｳ
+    Aiueo,
+    /// 日文伊吕波：ｲ, ﾛ, ﾊ
+    Iroha,
+    /// 全角数字：１, ２
+    DecimalFullWidth,
+    /// 半角数字
+    DecimalHalfWidth,
+    /// 日文大写数字：壱, 弐
+    JapaneseLegal,
+    /// 日文逐位数字（万进）
+    JapaneseDigitalTenThousand,
+    /// 带圈数字：①, ②
+    DecimalEnclosedCircle,
+    /// 全角数字（变体）
+    DecimalFullWidth2,
+    /// 全角五十音：ア, イ
+    AiueoFullWidth,
+    /// 全角伊吕波：イ, ロ
+    IrohaFullWidth,
+    /// 补零数字：01, 02
+    DecimalZero,
+    /// 项目符号
+    Bullet,
+    /// 韩文：가, 나, 다
+    Ganada,
+    /// 韩文字母：ㄱ, ㄴ, ㄷ
+    Chosung,
+    /// 带句点数字：⒈, ⒉
+    DecimalEnclosedFullstop,
+    /// 带括号数字：⑴, ⑵
+    DecimalEnclosedParen,
+    /// 带圈数字（中文）：①, ②
+    DecimalEnclosedCircleChinese,
+    /// 带圈汉字：㈠, ㈡
+    IdeographEnclosedCircle,
+    /// 天干：甲, 乙, 丙
+    IdeographTraditional,
+    /// 地支：子, 丑, 寅
+    IdeographZodiac,
+    /// 干支：甲子, 乙丑
+    IdeographZodiacTraditional,
+    /// 台湾计数：一, 十一
+    TaiwaneseCounting,
+    /// 繁体大写数字：壹, 貳
+    IdeographLegalTraditional,
+    /// 台湾计数（千位）：一千零一
+    TaiwaneseCountingThousand,
+    /// 台湾逐位数字：一〇一
+    TaiwaneseDigital,
+    /// 中文计数：一, 十一
+    ChineseCounting,
+    /// 简体大写数字：壹, 贰
+    ChineseLegalSimplified,
+    /// 中文计数（千位）：一千零一
+    ChineseCountingThousand,
+    /// 韩文逐位数字：일, 이
+    KoreanDigital,
+    /// 韩文计数：일, 십일
+    KoreanCounting,
+    /// 韩文固有数词：하나, 둘
+    KoreanLegal,
+    /// 韩文汉字逐位数字
+    KoreanDigital2,
+    /// 越南语计数
+    VietnameseCounting,
+    /// 俄文小写字母
+    RussianLower,
+    /// 俄文大写字母
+    RussianUpper,
+    /// 无编号
     None,
+    /// 带短横线数字：- 1 -
+    NumberInDash,
+    /// 希伯来数字
+    Hebrew1,
+    /// 希伯来字母
+    Hebrew2,
+    /// 阿拉伯字母
+    ArabicAlpha,
+    /// 阿拉伯字母（阿布哲德顺序）
+    ArabicAbjad,
+    /// 印地语元音
+    HindiVowels,
+    /// 印地语辅音
+    HindiConsonants,
+    /// 印地语数字
+    HindiNumbers,
+    /// 印地语计数
+    HindiCounting,
+    /// 泰文字母
+    ThaiLetters,
+    /// 泰文数字
+    ThaiNumbers,
+    /// 泰文计数
+    ThaiCounting,
+    /// 泰铢文本
+    BahtText,
+    /// 美元文本
+    DollarText,
+    /// 自定义格式，由 `w:format` 属性给出
+    Custom,
 }
 
-/// 原始编号格式结构体
-#[derive(Debug, Deserialize, Serialize)]
-struct RawNumFmt {
+/// 编号格式元素 (`w:numFmt`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct NumFmt {
     /// 格式值
-    #[serde(rename = "@w:val")]
-    val: NumFmtVal,
+    #[serde(rename = "@w:val", alias = "@val")]
+    pub val: NumberFormat,
+    /// 自定义格式（如 `001, 002, 003`）
+    #[serde(rename = "@w:format", alias = "@format", skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+/// 编号与文本之间的分隔方式 (`w:suff`)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum LevelSuffix {
+    /// 制表符
+    #[default]
+    Tab,
+    /// 空格
+    Space,
+    /// 无分隔
+    Nothing,
 }
 
-/// 原始级别结构体
-#[derive(Debug, Deserialize, Serialize)]
-struct RawLevel {
+/// 编号级别定义 (`w:lvl`)
+///
+/// 字段顺序与 `CT_Lvl` 的元素顺序一致。
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Level {
     /// 级别索引
-    #[serde(rename = "@w:ilvl")]
-    level: i32,
+    #[serde(rename = "@w:ilvl", alias = "@ilvl")]
+    pub level: i32,
     /// 起始编号
-    #[serde(rename = "w:start", skip_serializing_if = "Option::is_none")]
-    start: Option<Val<i32>>,
+    #[serde(rename = "w:start", alias = "start", skip_serializing_if = "Option::is_none")]
+    pub start: Option<Val<i32>>,
     /// 编号格式
-    #[serde(rename = "w:numFmt", skip_serializing_if = "Option::is_none")]
-    format: Option<RawNumFmt>,
+    #[serde(rename = "w:numFmt", alias = "numFmt", skip_serializing_if = "Option::is_none")]
+    pub format: Option<NumFmt>,
+    /// 在哪一级之后重新开始编号（从 1 开始计），0 表示从不重新开始
+    #[serde(rename = "w:lvlRestart", alias = "lvlRestart", skip_serializing_if = "Option::is_none")]
+    pub restart: Option<Val<i32>>,
+    /// 关联的段落样式
+    #[serde(rename = "w:pStyle", alias = "pStyle", skip_serializing_if = "Option::is_none")]
+    pub paragraph_style: Option<Val<String>>,
+    /// 以阿拉伯数字显示所有级别的编号（法律格式）
+    #[serde(rename = "w:isLgl", alias = "isLgl", skip_serializing_if = "Option::is_none")]
+    pub is_legal: Option<OnOff>,
+    /// 编号之后的分隔方式
+    #[serde(rename = "w:suff", alias = "suff", skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<Val<LevelSuffix>>,
     /// 级别文本
-    #[serde(rename = "w:lvlText", skip_serializing_if = "Option::is_none")]
-    level_text: Option<Val<String>>,
+    #[serde(rename = "w:lvlText", alias = "lvlText", skip_serializing_if = "Option::is_none")]
+    pub level_text: Option<Val<String>>,
+    /// 编号的对齐方式
+    #[serde(rename = "w:lvlJc", alias = "lvlJc", skip_serializing_if = "Option::is_none")]
+    pub justification: Option<Val<String>>,
+    /// 级别的段落属性（通常为缩进和制表位）
+    #[serde(rename = "w:pPr", alias = "pPr", skip_serializing_if = "Option::is_none")]
+    pub paragraph_properties: Option<ParagraphProperties>,
+    /// 编号文本的运行属性
+    #[serde(rename = "w:rPr", alias = "rPr", skip_serializing_if = "Option::is_none")]
+    pub run_properties: Option<RunProperties>,
 }
 
-/// 原始抽象编号结构体
-#[derive(Debug, Deserialize, Serialize)]
-struct RawAbstractNum {
+/// 每一级列表的缩进增量（缇）
+const LEVEL_INDENT: i32 = 720;
+/// 列表编号的悬挂缩进（缇）
+const LEVEL_HANGING: i32 = 360;
+
+impl Level {
+    /// 创建编号级别，从 1 开始计数，左对齐，按级别缩进
+    ///
+    /// # 参数
+    /// * `level` - 级别索引（0–8）
+    /// * `format` - 编号格式
+    /// * `text` - 级别文本，`%1`…`%9` 为各级编号的占位符，如 `%1.%2.`
+    pub fn numbered(level: i32, format: NumberFormat, text: impl Into<String>) -> Self {
+        let mut definition = Level {
+            level,
+            start: Some(Val::new(1)),
+            format: Some(NumFmt {
+                val: format,
+                format: None,
+            }),
+            level_text: Some(Val::new(text.into())),
+            justification: Some(Val::new("left".to_string())),
+            ..Default::default()
+        };
+        definition.set_indentation(LEVEL_INDENT * (level + 1), LEVEL_HANGING);
+        definition
+    }
+
+    /// 创建项目符号级别
+    ///
+    /// # 参数
+    /// * `level` - 级别索引（0–8）
+    /// * `symbol` - 符号字符
+    /// * `font` - 符号字体，如 `Symbol`、`Wingdings`；`None` 时使用段落字体
+    pub fn bullet(level: i32, symbol: impl Into<String>, font: Option<&str>) -> Self {
+        let mut definition = Level::numbered(level, NumberFormat::Bullet, symbol);
+        if let Some(font) = font {
+            definition.set_font(font);
+        }
+        definition
+    }
+
+    /// 起始编号
+    pub fn start(&self) -> i32 {
+        self.start.as_ref().map_or(0, |s| s.val)
+    }
+
+    /// 编号格式
+    pub fn number_format(&self) -> NumberFormat {
+        self.format.as_ref().map_or(NumberFormat::Decimal, |f| f.val)
+    }
+
+    /// 设置起始编号
+    ///
+    /// # 参数
+    /// * `start` - 起始编号
+    pub fn set_start(&mut self, start: i32) {
+        self.start = Some(Val::new(start));
+    }
+
+    /// 设置重新开始编号的规则
+    ///
+    /// # 参数
+    /// * `after_level` - 在第几级（从 1 开始计）或更高级别之后重新开始，0 表示从不重新开始，
+    ///   `None` 表示在任一上级之后重新开始
+    pub fn set_restart(&mut self, after_level: Option<i32>) {
+        self.restart = after_level.map(Val::new);
+    }
+
+    /// 设置编号的缩进：文本左缩进与编号悬挂缩进
+    ///
+    /// # 参数
+    /// * `left` - 左缩进（缇）
+    /// * `hanging` - 悬挂缩进（缇）
+    pub fn set_indentation(&mut self, left: i32, hanging: i32) {
+        let properties = self.paragraph_properties.get_or_insert_with(Default::default);
+        properties.indentation = Some(Indentation {
+            left: Some(left),
+            hanging: Some(hanging),
+            ..Default::default()
+        });
+    }
+
+    /// 设置编号文本的字体，项目符号常用 `Symbol` 或 `Wingdings`
+    ///
+    /// # 参数
+    /// * `font` - 字体名称
+    pub fn set_font(&mut self, font: &str) {
+        let properties = self.run_properties.get_or_insert_with(Default::default);
+        properties.fonts = Some(RunFonts {
+            hint: Some("default".to_string()),
+            ascii: Some(font.to_string()),
+            h_ansi: Some(font.to_string()),
+            ..Default::default()
+        });
+    }
+}
+
+/// 常用列表预设
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListPreset {
+    /// 项目符号列表：与 Word 默认一致，依次使用 Symbol、Courier New、Wingdings 字体中的符号
+    Bullet,
+    /// 编号列表：1.、a.、i. 循环
+    Numbered,
+    /// 多级大纲编号：1.、1.1.、1.1.1.
+    Outline,
+    /// 中文公文编号：一、（一）1.（1）
+    Chinese,
+}
+
+impl ListPreset {
+    /// 生成预设的九个级别定义
+    pub fn levels(self) -> Vec<Level> {
+        (0..9)
+            .map(|level| {
+                let cycle = level as usize % 3;
+                match self {
+                    ListPreset::Bullet => {
+                        let (symbol, font) = [
+                            ("\u{F0B7}", "Symbol"),
+                            ("o", "Courier New"),
+                            ("\u{F0A7}", "Wingdings"),
+                        ][cycle];
+                        Level::bullet(level, symbol, Some(font))
+                    }
+                    ListPreset::Numbered => {
+                        let format = [
+                            NumberFormat::Decimal,
+                            NumberFormat::LowerLetter,
+                            NumberFormat::LowerRoman,
+                        ][cycle];
+                        Level::numbered(level, format, format!("%{}.", level + 1))
+                    }
+                    ListPreset::Outline => {
+                        let text: String = (1..=level + 1).map(|l| format!("%{l}.")).collect();
+                        Level::numbered(level, NumberFormat::Decimal, text)
+                    }
+                    ListPreset::Chinese => {
+                        let (format, text) = match level {
+                            0 => (NumberFormat::ChineseCounting, "%1、".to_string()),
+                            1 => (NumberFormat::ChineseCounting, "（%2）".to_string()),
+                            2 => (NumberFormat::Decimal, "%3.".to_string()),
+                            3 => (NumberFormat::Decimal, "（%4）".to_string()),
+                            l => (NumberFormat::LowerLetter, format!("%{}.", l + 1)),
+                        };
+                        Level::numbered(level, format, text)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// 列表类型 (`w:multiLevelType`)
+    fn multi_level_type(self) -> &'static str {
+        match self {
+            ListPreset::Outline | ListPreset::Chinese => "multilevel",
+            ListPreset::Bullet | ListPreset::Numbered => "hybridMultilevel",
+        }
+    }
+}
+
+impl AbstractNum {
+    /// 创建抽象编号定义
+    ///
+    /// # 参数
+    /// * `id` - 抽象编号ID
+    /// * `levels` - 级别定义
+    pub fn new(id: i32, levels: Vec<Level>) -> Self {
+        let multi_level_type = if levels.len() > 1 {
+            "hybridMultilevel"
+        } else {
+            "singleLevel"
+        };
+        AbstractNum {
+            id,
+            nsid: Some(Val::new(format!("{:08X}", 0x1A2B_0000_u32.wrapping_add(id as u32)))),
+            multi_level_type: Some(Val::new(multi_level_type.to_string())),
+            levels,
+            ..Default::default()
+        }
+    }
+
+    /// 按预设创建抽象编号定义
+    ///
+    /// # 参数
+    /// * `id` - 抽象编号ID
+    /// * `preset` - 列表预设
+    pub fn from_preset(id: i32, preset: ListPreset) -> Self {
+        let mut abstract_num = AbstractNum::new(id, preset.levels());
+        abstract_num.multi_level_type = Some(Val::new(preset.multi_level_type().to_string()));
+        abstract_num
+    }
+
+    /// 查找级别定义
+    ///
+    /// # 参数
+    /// * `level` - 级别索引
+    pub fn level(&self, level: i32) -> Option<&Level> {
+        self.levels.iter().find(|l| l.level == level)
+    }
+
+    /// 查找级别定义的可变引用
+    ///
+    /// # 参数
+    /// * `level` - 级别索引
+    pub fn level_mut(&mut self, level: i32) -> Option<&mut Level> {
+        self.levels.iter_mut().find(|l| l.level == level)
+    }
+}
+
+/// 抽象编号定义 (`w:abstractNum`)，描述列表各级别的格式
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AbstractNum {
     /// 抽象编号ID
-    #[serde(rename = "@w:abstractNumId")]
-    id: i32,
+    #[serde(rename = "@w:abstractNumId", alias = "@abstractNumId")]
+    pub id: i32,
+    /// 唯一标识
+    #[serde(rename = "w:nsid", alias = "nsid", skip_serializing_if = "Option::is_none")]
+    pub nsid: Option<Val<String>>,
+    /// 列表类型（`singleLevel`、`multilevel`、`hybridMultilevel`）
+    #[serde(rename = "w:multiLevelType", alias = "multiLevelType", skip_serializing_if = "Option::is_none")]
+    pub multi_level_type: Option<Val<String>>,
+    /// 模板标识
+    #[serde(rename = "w:tmpl", alias = "tmpl", skip_serializing_if = "Option::is_none")]
+    pub template: Option<Val<String>>,
+    /// 名称
+    #[serde(rename = "w:name", alias = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<Val<String>>,
+    /// 定义此编号的编号样式
+    #[serde(rename = "w:styleLink", alias = "styleLink", skip_serializing_if = "Option::is_none")]
+    pub style_link: Option<Val<String>>,
+    /// 引用的编号样式，实际定义由该样式的编号给出
+    #[serde(rename = "w:numStyleLink", alias = "numStyleLink", skip_serializing_if = "Option::is_none")]
+    pub num_style_link: Option<Val<String>>,
     /// 级别列表
-    #[serde(rename = "w:lvl", default, skip_serializing_if = "Vec::is_empty")]
-    levels: Vec<RawLevel>,
+    #[serde(rename = "w:lvl", alias = "lvl", default, skip_serializing_if = "Vec::is_empty")]
+    pub levels: Vec<Level>,
 }
 
-/// 原始编号结构体
-#[derive(Debug, Deserialize, Serialize)]
-struct RawNum {
+/// 编号级别覆盖 (`w:lvlOverride`)
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LevelOverride {
+    /// 级别索引
+    #[serde(rename = "@w:ilvl", alias = "@ilvl")]
+    pub level: i32,
+    /// 覆盖起始编号
+    #[serde(rename = "w:startOverride", alias = "startOverride", skip_serializing_if = "Option::is_none")]
+    pub start: Option<Val<i32>>,
+    /// 覆盖整个级别定义
+    #[serde(rename = "w:lvl", alias = "lvl", skip_serializing_if = "Option::is_none")]
+    pub definition: Option<Level>,
+}
+
+/// 编号实例 (`w:num`)，段落通过其ID引用抽象编号
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Num {
     /// 编号ID
-    #[serde(rename = "@w:numId")]
-    id: i32,
+    #[serde(rename = "@w:numId", alias = "@numId")]
+    pub id: i32,
     /// 抽象编号ID
-    #[serde(rename = "w:abstractNumId")]
-    abstract_num_id: Val<i32>,
+    #[serde(rename = "w:abstractNumId", alias = "abstractNumId")]
+    pub abstract_num_id: Val<i32>,
+    /// 级别覆盖
+    #[serde(rename = "w:lvlOverride", alias = "lvlOverride", default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<LevelOverride>,
 }
 
 /// 编号结构体，表示文档中的编号定义
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename = "w:numbering")]
 pub struct Numbering {
+    /// WordML命名空间
+    #[serde(rename = "@xmlns:w", skip_deserializing, default = "default_xmlns_w")]
+    pub xmlns_w: String,
     /// 抽象编号列表
-    #[serde(rename = "w:abstractNum", default, skip_serializing_if = "Vec::is_empty")]
-    abstract_nums: Vec<RawAbstractNum>,
+    #[serde(rename = "w:abstractNum", alias = "abstractNum", default, skip_serializing_if = "Vec::is_empty")]
+    pub abstract_nums: Vec<AbstractNum>,
     /// 编号列表
-    #[serde(rename = "w:num", default, skip_serializing_if = "Vec::is_empty")]
-    nums: Vec<RawNum>,
+    #[serde(rename = "w:num", alias = "num", default, skip_serializing_if = "Vec::is_empty")]
+    pub nums: Vec<Num>,
+}
+
+/// 解析后的编号级别定义，已应用 `w:lvlOverride`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedLevel {
+    /// 级别索引
+    pub level: i32,
+    /// 起始编号（已应用 `w:startOverride`）
+    pub start: i32,
+    /// 编号格式
+    pub format: NumberFormat,
+    /// 自定义格式
+    pub custom_format: Option<String>,
+    /// 级别文本，`%1`…`%9` 为各级编号的占位符
+    pub text: String,
+    /// 在哪一级之后重新开始编号（从 1 开始计），`None` 表示任何上级出现后都重新开始，0 表示从不重新开始
+    pub restart: Option<i32>,
+    /// 以阿拉伯数字显示所有级别的编号
+    pub is_legal: bool,
+    /// 编号之后的分隔方式
+    pub suffix: LevelSuffix,
+    /// 级别的段落属性
+    pub paragraph_properties: Option<ParagraphProperties>,
+    /// 编号文本的运行属性
+    pub run_properties: Option<RunProperties>,
+}
+
+impl ResolvedLevel {
+    fn new(raw: &Level, start_override: Option<i32>) -> Self {
+        ResolvedLevel {
+            level: raw.level,
+            start: start_override.unwrap_or_else(|| raw.start()),
+            format: raw.number_format(),
+            custom_format: raw.format.as_ref().and_then(|f| f.format.clone()),
+            text: raw.level_text.as_ref().map(|t| t.val.clone()).unwrap_or_default(),
+            restart: raw.restart.as_ref().map(|r| r.val),
+            is_legal: raw.is_legal.is_some_and(|v| v.is_on()),
+            suffix: raw.suffix.as_ref().map(|s| s.val).unwrap_or_default(),
+            paragraph_properties: raw.paragraph_properties.clone(),
+            run_properties: raw.run_properties.clone(),
+        }
+    }
+
+    /// 按本级格式渲染一个编号值
+    ///
+    /// # 参数
+    /// * `value` - 编号值
+    pub fn format_value(&self, value: i32) -> String {
+        match (self.format, &self.custom_format) {
+            (NumberFormat::Custom, Some(custom)) => format_custom(value, custom),
+            (format, _) => format_number(value, format),
+        }
+    }
+}
+
+/// 按自定义格式渲染编号，支持 `001, 002, 003` 形式的补零格式
+fn format_custom(value: i32, custom: &str) -> String {
+    let first = custom.split(',').next().unwrap_or_default().trim();
+    if !first.is_empty() && first.chars().all(|c| c.is_ascii_digit()) {
+        format!("{value:0width$}", width = first.len())
+    } else {
+        value.to_string()
+    }
 }
 
 // --- 面向公众的结构体和实现 ---
 
+impl Default for Numbering {
+    fn default() -> Self {
+        Numbering {
+            xmlns_w: default_xmlns_w(),
+            abstract_nums: Vec::new(),
+            nums: Vec::new(),
+        }
+    }
+}
+
+fn default_xmlns_w() -> String {
+    "http://schemas.openxmlformats.org/wordprocessingml/2006/main".to_string()
+}
+
 impl Numbering {
+    /// 查找抽象编号定义
+    ///
+    /// # 参数
+    /// * `abstract_num_id` - 抽象编号ID
+    pub fn abstract_num(&self, abstract_num_id: i32) -> Option<&AbstractNum> {
+        self.abstract_nums.iter().find(|an| an.id == abstract_num_id)
+    }
+
+    /// 查找抽象编号定义的可变引用
+    ///
+    /// # 参数
+    /// * `abstract_num_id` - 抽象编号ID
+    pub fn abstract_num_mut(&mut self, abstract_num_id: i32) -> Option<&mut AbstractNum> {
+        self.abstract_nums.iter_mut().find(|an| an.id == abstract_num_id)
+    }
+
+    /// 查找编号实例
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    pub fn num(&self, num_id: i32) -> Option<&Num> {
+        self.nums.iter().find(|n| n.id == num_id)
+    }
+
+    /// 添加抽象编号定义并返回其ID；定义的ID已被占用时改用下一个可用ID
+    ///
+    /// # 参数
+    /// * `abstract_num` - 抽象编号定义
+    pub fn add_abstract_num(&mut self, mut abstract_num: AbstractNum) -> i32 {
+        if self.abstract_num(abstract_num.id).is_some() {
+            let id = self.abstract_nums.iter().map(|an| an.id).max().unwrap_or(-1) + 1;
+            abstract_num.id = id;
+            abstract_num.nsid = AbstractNum::new(id, Vec::new()).nsid;
+        }
+        let id = abstract_num.id;
+        self.abstract_nums.push(abstract_num);
+        id
+    }
+
+    /// 添加引用指定抽象编号的编号实例，返回新的编号ID
+    ///
+    /// # 参数
+    /// * `abstract_num_id` - 抽象编号ID
+    pub fn add_num(&mut self, abstract_num_id: i32) -> i32 {
+        // 编号ID 0 表示“无编号”，新ID从 1 开始
+        let id = self.nums.iter().map(|n| n.id).max().unwrap_or(0) + 1;
+        self.nums.push(Num {
+            id,
+            abstract_num_id: Val::new(abstract_num_id),
+            overrides: Vec::new(),
+        });
+        id
+    }
+
+    /// 按级别定义创建新列表，返回段落可引用的编号ID
+    ///
+    /// # 参数
+    /// * `levels` - 级别定义
+    pub fn add_list(&mut self, levels: Vec<Level>) -> i32 {
+        let next_id = self.abstract_nums.iter().map(|an| an.id).max().unwrap_or(-1) + 1;
+        let abstract_num_id = self.add_abstract_num(AbstractNum::new(next_id, levels));
+        self.add_num(abstract_num_id)
+    }
+
+    /// 按预设创建新列表，返回段落可引用的编号ID
+    ///
+    /// # 参数
+    /// * `preset` - 列表预设
+    pub fn add_preset_list(&mut self, preset: ListPreset) -> i32 {
+        let next_id = self.abstract_nums.iter().map(|an| an.id).max().unwrap_or(-1) + 1;
+        let abstract_num_id = self.add_abstract_num(AbstractNum::from_preset(next_id, preset));
+        self.add_num(abstract_num_id)
+    }
+
+    /// 创建与指定编号格式相同、但从头重新编号的编号实例，返回新的编号ID
+    ///
+    /// # 参数
+    /// * `num_id` - 原编号ID
+    pub fn restart_list(&mut self, num_id: i32) -> Result<i32> {
+        let abstract_num_id = self
+            .abstract_num_id(num_id)
+            .ok_or_else(|| OfficeError::InvalidFormat(format!("numbering `{num_id}` not found")))?;
+        let starts: Vec<(i32, i32)> = self
+            .find_abstract_num(abstract_num_id)
+            .map(|an| an.levels.iter().map(|l| (l.level, l.start())).collect())
+            .unwrap_or_default();
+
+        let id = self.add_num(abstract_num_id);
+        if let Some(num) = self.nums.iter_mut().find(|n| n.id == id) {
+            num.overrides = starts
+                .into_iter()
+                .map(|(level, start)| LevelOverride {
+                    level,
+                    start: Some(Val::new(start)),
+                    definition: None,
+                })
+                .collect();
+        }
+        Ok(id)
+    }
+
+    /// 将段落加入列表的指定级别，编号或级别未定义时返回错误
+    ///
+    /// # 参数
+    /// * `paragraph` - 段落
+    /// * `num_id` - 编号ID
+    /// * `level` - 级别
+    pub fn attach(&self, paragraph: &mut Paragraph, num_id: i32, level: i32) -> Result<()> {
+        if self.resolve_level(num_id, level).is_none() {
+            return Err(OfficeError::InvalidFormat(format!(
+                "numbering `{num_id}` has no level {level}"
+            )));
+        }
+        paragraph.set_list(num_id, level);
+        Ok(())
+    }
+
     /// 从XML内容解析编号定义
-    /// 
+    ///
     /// # 参数
     /// * `xml_content` - XML格式的编号定义内容
-    pub fn from_xml(xml_content: &str) -> crate::error::Result<Self> {
+    pub fn from_xml(xml_content: &str) -> Result<Self> {
         // 如果XML内容为空，返回默认编号定义
         if xml_content.is_empty() {
             return Ok(Numbering::default());
@@ -103,37 +700,222 @@ impl Numbering {
         quick_xml::de::from_str(xml_content).map_err(Into::into)
     }
 
-    /// 获取指定编号和级别的文本
-    /// 
+    /// 查找编号引用的抽象编号ID
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    pub fn abstract_num_id(&self, num_id: i32) -> Option<i32> {
+        self.nums
+            .iter()
+            .find(|n| n.id == num_id)
+            .map(|n| n.abstract_num_id.val)
+    }
+
+    /// 判断编号是否带有级别覆盖（此时它是一个独立计数的列表实例）
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    pub fn has_overrides(&self, num_id: i32) -> bool {
+        self.nums
+            .iter()
+            .find(|n| n.id == num_id)
+            .is_some_and(|n| !n.overrides.is_empty())
+    }
+
+    /// 查找抽象编号；抽象编号引用编号样式 (`w:numStyleLink`) 时，
+    /// 返回定义该编号样式 (`w:styleLink`) 的抽象编号
+    fn find_abstract_num(&self, abstract_num_id: i32) -> Option<&AbstractNum> {
+        let abstract_num = self.abstract_nums.iter().find(|an| an.id == abstract_num_id)?;
+        match &abstract_num.num_style_link {
+            Some(link) => self
+                .abstract_nums
+                .iter()
+                .find(|an| an.style_link.as_ref().is_some_and(|s| s.val == link.val))
+                .or(Some(abstract_num)),
+            None => Some(abstract_num),
+        }
+    }
+
+    /// 获取指定编号和级别的级别定义，已应用编号中的级别覆盖
+    ///
     /// # 参数
     /// * `num_id` - 编号ID
     /// * `level` - 级别
-    /// * `count` - 计数
-    pub fn get_level_text(&self, num_id: i32, level: i32, count: i32) -> Option<String> {
-        // 查找编号定义
+    pub fn resolve_level(&self, num_id: i32, level: i32) -> Option<ResolvedLevel> {
         let num = self.nums.iter().find(|n| n.id == num_id)?;
-        // 查找抽象编号定义
-        let abstract_num = self
-            .abstract_nums
+        let level_override = num.overrides.iter().find(|o| o.level == level);
+        let start_override = level_override.and_then(|o| o.start.as_ref()).map(|s| s.val);
+
+        if let Some(definition) = level_override.and_then(|o| o.definition.as_ref()) {
+            return Some(ResolvedLevel::new(definition, start_override));
+        }
+        let abstract_num = self.find_abstract_num(num.abstract_num_id.val)?;
+        let raw = abstract_num.levels.iter().find(|l| l.level == level)?;
+        Some(ResolvedLevel::new(raw, start_override))
+    }
+
+    /// 查找以 `w:pStyle` 关联指定段落样式的级别
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    /// * `style_id` - 段落样式ID
+    pub fn level_for_style(&self, num_id: i32, style_id: &str) -> Option<i32> {
+        let num = self.nums.iter().find(|n| n.id == num_id)?;
+        let abstract_num = self.find_abstract_num(num.abstract_num_id.val)?;
+        abstract_num
+            .levels
+            .iter()
+            .find(|l| l.paragraph_style.as_ref().is_some_and(|s| s.val == style_id))
+            .map(|l| l.level)
+    }
+
+    /// 获取编号各级起始编号的覆盖值
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    pub fn start_overrides(&self, num_id: i32) -> Vec<(i32, i32)> {
+        self.nums
             .iter()
-            .find(|an| an.id == num.abstract_num_id.val)?;
-
-        // 查找级别定义
-        let level_def = abstract_num.levels.iter().find(|l| l.level == level)?;
-
-        // 获取格式
-        let format = level_def.format.as_ref()?.val;
-        // 根据格式生成文本
-        match format {
-            // 项目符号
-            NumFmtVal::Bullet => level_def.level_text.as_ref().map(|lt| lt.val.clone()),
-            // 十进制数字
-            NumFmtVal::Decimal => {
-                let text_format = level_def.level_text.as_ref()?.val.clone();
-                Some(text_format.replace(&format!("%{}", level + 1), &count.to_string()))
-            }
-            // 其他格式暂未实现
-            _ => None,
+            .find(|n| n.id == num_id)
+            .map(|n| {
+                n.overrides
+                    .iter()
+                    .filter_map(|o| o.start.as_ref().map(|s| (o.level, s.val)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 获取指定编号和级别的段落属性
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    /// * `level` - 级别
+    pub fn level_paragraph_properties(&self, num_id: i32, level: i32) -> Option<ParagraphProperties> {
+        self.resolve_level(num_id, level)?.paragraph_properties
+    }
+
+    /// 获取指定编号和级别的编号文本运行属性
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    /// * `level` - 级别
+    pub fn level_run_properties(&self, num_id: i32, level: i32) -> Option<RunProperties> {
+        self.resolve_level(num_id, level)?.run_properties
+    }
+
+    /// 获取指定编号和级别的文本，上级编号取各自的起始值
+    ///
+    /// 需要按文档顺序连续计数时请使用 [`ListNumberer`](crate::docx::list_numbering::ListNumberer)。
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    /// * `level` - 级别
+    /// * `count` - 计数
+    pub fn get_level_text(&self, num_id: i32, level: i32, count: i32) -> Option<String> {
+        let definition = self.resolve_level(num_id, level)?;
+        let counters: Vec<i32> = (0..9)
+            .map(|l| match l {
+                l if l == level => count,
+                l => self.resolve_level(num_id, l).map_or(1, |d| d.start),
+            })
+            .collect();
+        Some(self.render_label(num_id, &definition, &counters))
+    }
+
+    /// 按级别文本渲染编号，将 `%1`…`%9` 替换为对应级别的编号
+    ///
+    /// # 参数
+    /// * `num_id` - 编号ID
+    /// * `definition` - 当前级别的定义
+    /// * `counters` - 各级当前的编号值
+    pub fn render_label(&self, num_id: i32, definition: &ResolvedLevel, counters: &[i32]) -> String {
+        if definition.format == NumberFormat::Bullet {
+            return definition.text.clone();
+        }
+
+        let mut label = String::new();
+        let mut chars = definition.text.chars().peekable();
+        while let Some(c) = chars.next() {
+            let placeholder = match (c, chars.peek().and_then(|d| d.to_digit(10))) {
+                ('%', Some(digit @ 1..=9)) => digit as usize - 1,
+                _ => {
+                    label.push(c);
+                    continue;
+                }
+            };
+            chars.next();
+            let value = counters.get(placeholder).copied().unwrap_or(1);
+            let rendered = if placeholder as i32 == definition.level {
+                definition.format_value(value)
+            } else {
+                match self.resolve_level(num_id, placeholder as i32) {
+                    // 法律格式下上级编号均以阿拉伯数字显示
+                    Some(_) if definition.is_legal => value.to_string(),
+                    Some(other) => other.format_value(value),
+                    None => value.to_string(),
+                }
+            };
+            label.push_str(&rendered);
         }
+        label
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::test_utils::docx;
+
+    #[test]
+    fn preset_lists_get_separate_definitions() {
+        let mut numbering = Numbering::default();
+        let bullets = numbering.add_preset_list(ListPreset::Bullet);
+        let outline = numbering.add_preset_list(ListPreset::Outline);
+        assert_eq!((bullets, outline), (1, 2));
+        assert_eq!(numbering.abstract_num_id(bullets), Some(0));
+        assert_eq!(numbering.abstract_num_id(outline), Some(1));
+
+        let level = numbering.resolve_level(outline, 2).unwrap();
+        assert_eq!(level.format, NumberFormat::Decimal);
+        assert_eq!(level.text, "%1.%2.%3.");
+        assert_eq!(numbering.resolve_level(bullets, 0).unwrap().format, NumberFormat::Bullet);
+
+        let xml = quick_xml::se::to_string(&numbering).unwrap();
+        assert!(xml.contains("<w:abstractNum w:abstractNumId=\"1\">"), "{xml}");
+        assert!(xml.contains("<w:multiLevelType w:val=\"multilevel\"/>"), "{xml}");
+        let num = "<w:num w:numId=\"2\"><w:abstractNumId w:val=\"1\"/></w:num>";
+        assert!(xml.contains(num), "{xml}");
+    }
+
+    #[test]
+    fn restarted_list_counts_from_start() {
+        let mut docx = docx("<w:p/><w:p/><w:p/><w:p/>");
+        let first = docx.numbering.add_preset_list(ListPreset::Numbered);
+        let second = docx.numbering.restart_list(first).unwrap();
+        assert!(docx.numbering.has_overrides(second));
+
+        let numbering = docx.numbering.clone();
+        let num_ids = [first, first, second, second];
+        for (paragraph, num_id) in docx.paragraphs_mut().into_iter().zip(num_ids) {
+            numbering.attach(paragraph, num_id, 0).unwrap();
+        }
+        let labels = docx.list_labels().into_iter().flatten();
+        let texts: Vec<String> = labels.map(|l| l.text).collect();
+        assert_eq!(texts, ["1.", "2.", "1.", "2."]);
+    }
+
+    #[test]
+    fn attach_rejects_undefined_levels() {
+        let mut numbering = Numbering::default();
+        let num_id = numbering.add_list(vec![Level::numbered(0, NumberFormat::UpperRoman, "%1")]);
+        let mut paragraph = Paragraph::default();
+        assert!(numbering.attach(&mut paragraph, num_id, 1).is_err());
+        assert!(numbering.attach(&mut paragraph, num_id + 1, 0).is_err());
+        assert!(paragraph.properties.is_none());
+
+        numbering.attach(&mut paragraph, num_id, 0).unwrap();
+        let num_pr = paragraph.properties.unwrap().num_pr.unwrap();
+        assert_eq!((num_pr.num_id.val, num_pr.level.val), (num_id, 0));
+    }
+}
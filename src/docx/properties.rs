@@ -1,18 +1,30 @@
 //! DOCX格式属性的定义
 //! Defines structs for properties in DOCX format.
+//!
+//! quick-xml 反序列化时按本地名称匹配元素和属性（即去掉 `w:` 前缀），
+//! 序列化时则需要写出带前缀的名称，因此每个字段都同时声明 `rename` 与 `alias`。
 
 use serde::{Deserialize, Serialize};
 
+use crate::common::xml_element::XmlElement;
+use crate::docx::numbering::NumberFormat;
+
 /// A generic struct for elements that only have a `w:val` attribute.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 pub struct Val<T> {
-    #[serde(rename = "@w:val")]
+    #[serde(rename = "@w:val", alias = "@val")]
     pub val: T,
 }
 
+impl<T> Val<T> {
+    pub fn new(val: T) -> Self {
+        Val { val }
+    }
+}
+
 /// 段落对齐方式
 /// Paragraph alignment
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum JustificationVal {
     #[default]
@@ -21,68 +33,2256 @@ pub enum JustificationVal {
     Right,
     Both,
     Distribute,
+    Start,
+    End,
+    MediumKashida,
+    HighKashida,
+    LowKashida,
+    ThaiDistribute,
+    NumTab,
 }
 
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 #[serde(rename = "w:jc")]
 pub struct Justification {
-    #[serde(rename = "@w:val")]
+    #[serde(rename = "@w:val", alias = "@val")]
     pub val: JustificationVal,
 }
 
 /// 段落样式
 /// Paragraph style
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 pub struct ParagraphStyle {
-    #[serde(rename = "@w:val")]
+    #[serde(rename = "@w:val", alias = "@val")]
     pub val: String,
 }
 
 /// 列表的缩进级别
 /// Indentation level for a list item.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 #[serde(rename = "w:ilvl")]
 pub struct NumLvl {
-    #[serde(rename = "@w:val")]
+    #[serde(rename = "@w:val", alias = "@val")]
     pub val: i32,
 }
 
 /// 列表属性，关联一个段落到一个列表
 /// Numbering properties, associating a paragraph with a list.
-#[derive(Debug, Deserialize, Serialize)]
+///
+/// 样式中的 `w:numPr` 常省略 `w:ilvl`（即第 0 级），编号ID为 0 表示取消编号。
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 #[serde(rename = "w:numPr")]
 pub struct NumPr {
-    #[serde(rename = "w:ilvl")]
+    #[serde(rename = "w:ilvl", alias = "ilvl", default)]
     pub level: NumLvl,
-    #[serde(rename = "w:numId")]
+    #[serde(rename = "w:numId", alias = "numId", default)]
     pub num_id: Val<i32>,
 }
 
+impl NumPr {
+    /// 创建引用指定编号和级别的列表属性
+    /// Creates numbering properties referencing a list and level.
+    pub fn new(num_id: i32, level: i32) -> Self {
+        NumPr {
+            level: NumLvl { val: level },
+            num_id: Val::new(num_id),
+        }
+    }
+
+    /// 取消编号（编号ID为 0），用于覆盖样式中的编号
+    /// Removes numbering inherited from the paragraph style.
+    pub fn none() -> Self {
+        NumPr::new(0, 0)
+    }
+}
+
+/// 取值为 ST_OnOff 的属性（如 `w:beforeAutospacing="1"`）的序列化辅助
+/// Helpers for attributes of type ST_OnOff.
+pub(crate) mod on_off_attr {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Option<bool>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(v) => serializer.serialize_str(if *v { "1" } else { "0" }),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<bool>, D::Error> {
+        let raw = Option::<String>::deserialize(deserializer)?;
+        Ok(raw.map(|v| !matches!(v.as_str(), "0" | "false" | "off")))
+    }
+}
+
+/// 行距规则
+/// Line spacing rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LineSpacingRule {
+    /// `line` 以 1/240 行为单位
+    Auto,
+    /// `line` 为固定值（缇）
+    Exact,
+    /// `line` 为最小值（缇）
+    AtLeast,
+}
+
+/// 段落间距
+/// Paragraph spacing (`w:spacing`); distances in twips, `*_lines` in 1/100 line.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:spacing")]
+pub struct ParagraphSpacing {
+    #[serde(rename = "@w:before", alias = "@before", skip_serializing_if = "Option::is_none")]
+    pub before: Option<u32>,
+    #[serde(rename = "@w:beforeLines", alias = "@beforeLines", skip_serializing_if = "Option::is_none")]
+    pub before_lines: Option<i32>,
+    #[serde(
+        rename = "@w:beforeAutospacing",
+        alias = "@beforeAutospacing",
+        with = "on_off_attr",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub before_autospacing: Option<bool>,
+    #[serde(rename = "@w:after", alias = "@after", skip_serializing_if = "Option::is_none")]
+    pub after: Option<u32>,
+    #[serde(rename = "@w:afterLines", alias = "@afterLines", skip_serializing_if = "Option::is_none")]
+    pub after_lines: Option<i32>,
+    #[serde(
+        rename = "@w:afterAutospacing",
+        alias = "@afterAutospacing",
+        with = "on_off_attr",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub after_autospacing: Option<bool>,
+    #[serde(rename = "@w:line", alias = "@line", skip_serializing_if = "Option::is_none")]
+    pub line: Option<i32>,
+    #[serde(rename = "@w:lineRule", alias = "@lineRule", skip_serializing_if = "Option::is_none")]
+    pub line_rule: Option<LineSpacingRule>,
+}
+
+/// 段落缩进
+/// Paragraph indentation (`w:ind`); distances in twips, `*_chars` in 1/100 character.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:ind")]
+pub struct Indentation {
+    /// 左缩进（`w:left`，也接受 `w:start`）
+    #[serde(rename = "@w:left", alias = "@left", alias = "@start", skip_serializing_if = "Option::is_none")]
+    pub left: Option<i32>,
+    #[serde(rename = "@w:leftChars", alias = "@leftChars", alias = "@startChars", skip_serializing_if = "Option::is_none")]
+    pub left_chars: Option<i32>,
+    /// 右缩进（`w:right`，也接受 `w:end`）
+    #[serde(rename = "@w:right", alias = "@right", alias = "@end", skip_serializing_if = "Option::is_none")]
+    pub right: Option<i32>,
+    #[serde(rename = "@w:rightChars", alias = "@rightChars", alias = "@endChars", skip_serializing_if = "Option::is_none")]
+    pub right_chars: Option<i32>,
+    #[serde(rename = "@w:hanging", alias = "@hanging", skip_serializing_if = "Option::is_none")]
+    pub hanging: Option<i32>,
+    #[serde(rename = "@w:hangingChars", alias = "@hangingChars", skip_serializing_if = "Option::is_none")]
+    pub hanging_chars: Option<i32>,
+    #[serde(rename = "@w:firstLine", alias = "@firstLine", skip_serializing_if = "Option::is_none")]
+    pub first_line: Option<i32>,
+    #[serde(rename = "@w:firstLineChars", alias = "@firstLineChars", skip_serializing_if = "Option::is_none")]
+    pub first_line_chars: Option<i32>,
+}
+
+/// 边框线
+/// A single border (`w:top`, `w:left`, `w:insideH`, ...)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct Border {
+    /// 线型，如 `single`、`double`、`dashed`、`nil`
+    #[serde(rename = "@w:val", alias = "@val")]
+    pub val: String,
+    /// 线宽（1/8 磅）
+    #[serde(rename = "@w:sz", alias = "@sz", skip_serializing_if = "Option::is_none")]
+    pub size: Option<u32>,
+    /// 与文字的间距（磅）
+    #[serde(rename = "@w:space", alias = "@space", skip_serializing_if = "Option::is_none")]
+    pub space: Option<u32>,
+    #[serde(rename = "@w:color", alias = "@color", skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(rename = "@w:themeColor", alias = "@themeColor", skip_serializing_if = "Option::is_none")]
+    pub theme_color: Option<ThemeColor>,
+    #[serde(rename = "@w:shadow", alias = "@shadow", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub shadow: Option<bool>,
+    #[serde(rename = "@w:frame", alias = "@frame", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<bool>,
+}
+
+impl Border {
+    /// 创建指定线型、线宽（1/8 磅）和颜色的边框
+    pub fn new(val: impl Into<String>, size: u32, color: impl Into<String>) -> Self {
+        Border {
+            val: val.into(),
+            size: Some(size),
+            space: Some(0),
+            color: Some(color.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// 段落边框
+/// Paragraph borders (`w:pBdr`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:pBdr")]
+pub struct ParagraphBorders {
+    #[serde(rename = "w:top", alias = "top", skip_serializing_if = "Option::is_none")]
+    pub top: Option<Border>,
+    #[serde(rename = "w:left", alias = "left", skip_serializing_if = "Option::is_none")]
+    pub left: Option<Border>,
+    #[serde(rename = "w:bottom", alias = "bottom", skip_serializing_if = "Option::is_none")]
+    pub bottom: Option<Border>,
+    #[serde(rename = "w:right", alias = "right", skip_serializing_if = "Option::is_none")]
+    pub right: Option<Border>,
+    /// 相同边框设置的段落之间的边框
+    #[serde(rename = "w:between", alias = "between", skip_serializing_if = "Option::is_none")]
+    pub between: Option<Border>,
+    #[serde(rename = "w:bar", alias = "bar", skip_serializing_if = "Option::is_none")]
+    pub bar: Option<Border>,
+}
+
+/// 制表位类型
+/// Tab stop alignment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TabStopType {
+    /// 清除继承的制表位
+    Clear,
+    Start,
+    Left,
+    Center,
+    End,
+    Right,
+    Decimal,
+    Bar,
+    Num,
+}
+
+/// 制表位前导符
+/// Tab leader character
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TabLeader {
+    None,
+    Dot,
+    Hyphen,
+    Underscore,
+    Heavy,
+    MiddleDot,
+}
+
+/// 制表位
+/// Tab stop (`w:tab`)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "w:tab")]
+pub struct TabStop {
+    #[serde(rename = "@w:val", alias = "@val")]
+    pub val: TabStopType,
+    #[serde(rename = "@w:leader", alias = "@leader", skip_serializing_if = "Option::is_none")]
+    pub leader: Option<TabLeader>,
+    /// 位置（缇）
+    #[serde(rename = "@w:pos", alias = "@pos")]
+    pub pos: i32,
+}
+
+/// 制表位列表
+/// Tab stops (`w:tabs`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:tabs")]
+pub struct Tabs {
+    #[serde(rename = "w:tab", alias = "tab", default)]
+    pub tabs: Vec<TabStop>,
+}
+
 /// 段落属性
 /// Paragraph properties
-#[derive(Debug, Default, Deserialize, Serialize)]
+///
+/// 字段顺序与 `CT_PPr` 的元素顺序一致。
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 #[serde(rename = "w:pPr")]
 pub struct ParagraphProperties {
-    #[serde(rename = "w:pStyle", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "w:pStyle", alias = "pStyle", skip_serializing_if = "Option::is_none")]
     pub style: Option<ParagraphStyle>,
-    #[serde(rename = "w:jc", skip_serializing_if = "Option::is_none")]
-    pub justification: Option<Justification>,
-    #[serde(rename = "w:numPr", skip_serializing_if = "Option::is_none")]
+    /// 与下段同页
+    #[serde(rename = "w:keepNext", alias = "keepNext", skip_serializing_if = "Option::is_none")]
+    pub keep_next: Option<OnOff>,
+    /// 段中不分页
+    #[serde(rename = "w:keepLines", alias = "keepLines", skip_serializing_if = "Option::is_none")]
+    pub keep_lines: Option<OnOff>,
+    /// 段前分页
+    #[serde(rename = "w:pageBreakBefore", alias = "pageBreakBefore", skip_serializing_if = "Option::is_none")]
+    pub page_break_before: Option<OnOff>,
+    /// 孤行控制
+    #[serde(rename = "w:widowControl", alias = "widowControl", skip_serializing_if = "Option::is_none")]
+    pub widow_control: Option<OnOff>,
+    #[serde(rename = "w:numPr", alias = "numPr", skip_serializing_if = "Option::is_none")]
     pub num_pr: Option<NumPr>,
+    #[serde(rename = "w:suppressLineNumbers", alias = "suppressLineNumbers", skip_serializing_if = "Option::is_none")]
+    pub suppress_line_numbers: Option<OnOff>,
+    #[serde(rename = "w:pBdr", alias = "pBdr", skip_serializing_if = "Option::is_none")]
+    pub borders: Option<ParagraphBorders>,
+    #[serde(rename = "w:shd", alias = "shd", skip_serializing_if = "Option::is_none")]
+    pub shading: Option<Shading>,
+    #[serde(rename = "w:tabs", alias = "tabs", skip_serializing_if = "Option::is_none")]
+    pub tabs: Option<Tabs>,
+    #[serde(rename = "w:suppressAutoHyphens", alias = "suppressAutoHyphens", skip_serializing_if = "Option::is_none")]
+    pub suppress_auto_hyphens: Option<OnOff>,
+    /// 按中文习惯控制首尾字符
+    #[serde(rename = "w:kinsoku", alias = "kinsoku", skip_serializing_if = "Option::is_none")]
+    pub kinsoku: Option<OnOff>,
+    /// 允许西文在单词中间换行
+    #[serde(rename = "w:wordWrap", alias = "wordWrap", skip_serializing_if = "Option::is_none")]
+    pub word_wrap: Option<OnOff>,
+    /// 允许标点溢出边界
+    #[serde(rename = "w:overflowPunct", alias = "overflowPunct", skip_serializing_if = "Option::is_none")]
+    pub overflow_punct: Option<OnOff>,
+    #[serde(rename = "w:topLinePunct", alias = "topLinePunct", skip_serializing_if = "Option::is_none")]
+    pub top_line_punct: Option<OnOff>,
+    /// 自动调整中文与西文的间距
+    #[serde(rename = "w:autoSpaceDE", alias = "autoSpaceDE", skip_serializing_if = "Option::is_none")]
+    pub auto_space_de: Option<OnOff>,
+    /// 自动调整中文与数字的间距
+    #[serde(rename = "w:autoSpaceDN", alias = "autoSpaceDN", skip_serializing_if = "Option::is_none")]
+    pub auto_space_dn: Option<OnOff>,
+    /// 从右到左段落
+    #[serde(rename = "w:bidi", alias = "bidi", skip_serializing_if = "Option::is_none")]
+    pub bidi: Option<OnOff>,
+    #[serde(rename = "w:adjustRightInd", alias = "adjustRightInd", skip_serializing_if = "Option::is_none")]
+    pub adjust_right_ind: Option<OnOff>,
+    #[serde(rename = "w:snapToGrid", alias = "snapToGrid", skip_serializing_if = "Option::is_none")]
+    pub snap_to_grid: Option<OnOff>,
+    #[serde(rename = "w:spacing", alias = "spacing", skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<ParagraphSpacing>,
+    #[serde(rename = "w:ind", alias = "ind", skip_serializing_if = "Option::is_none")]
+    pub indentation: Option<Indentation>,
+    /// 相同样式的段落间不加空格
+    #[serde(rename = "w:contextualSpacing", alias = "contextualSpacing", skip_serializing_if = "Option::is_none")]
+    pub contextual_spacing: Option<OnOff>,
+    #[serde(rename = "w:mirrorIndents", alias = "mirrorIndents", skip_serializing_if = "Option::is_none")]
+    pub mirror_indents: Option<OnOff>,
+    #[serde(rename = "w:jc", alias = "jc", skip_serializing_if = "Option::is_none")]
+    pub justification: Option<Justification>,
+    #[serde(rename = "w:textDirection", alias = "textDirection", skip_serializing_if = "Option::is_none")]
+    pub text_direction: Option<Val<String>>,
+    /// 行内垂直对齐，如 `auto`、`baseline`、`center`
+    #[serde(rename = "w:textAlignment", alias = "textAlignment", skip_serializing_if = "Option::is_none")]
+    pub text_alignment: Option<Val<String>>,
+    /// 大纲级别（0 为 1 级）
+    #[serde(rename = "w:outlineLvl", alias = "outlineLvl", skip_serializing_if = "Option::is_none")]
+    pub outline_level: Option<Val<u8>>,
+    /// 表格样式条件格式标记
+    #[serde(rename = "w:cnfStyle", alias = "cnfStyle", skip_serializing_if = "Option::is_none")]
+    pub conditional_format: Option<ConditionalFormatStyle>,
+    /// 未建模或无法识别的子元素，保存时原样写在段落标记的运行属性之前
+    #[serde(rename = "$value", skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub unknown: Vec<XmlElement>,
+    /// 段落标记的运行属性
+    #[serde(rename = "w:rPr", alias = "rPr", skip_serializing_if = "Option::is_none")]
+    pub run_properties: Option<RunProperties>,
+    /// 分节符：以本段结束的节的属性
+    /// Section break ending at this paragraph
+    #[serde(rename = "w:sectPr", alias = "sectPr", skip_serializing_if = "Option::is_none")]
+    pub section_properties: Option<SectionProperties>,
+    /// 段落属性修订记录
+    /// Tracked paragraph formatting change
+    #[serde(rename = "w:pPrChange", alias = "pPrChange", skip_serializing_if = "Option::is_none")]
+    pub change: Option<ParagraphPropertiesChange>,
+}
+
+/// 段落属性修订，保存修改前的段落属性
+/// Paragraph formatting change, holding the properties before the revision.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "w:pPrChange")]
+pub struct ParagraphPropertiesChange {
+    #[serde(rename = "@w:id", alias = "@id")]
+    pub id: i32,
+    #[serde(rename = "@w:author", alias = "@author")]
+    pub author: String,
+    #[serde(rename = "@w:date", alias = "@date", skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(rename = "w:pPr", alias = "pPr", default)]
+    pub properties: Box<ParagraphProperties>,
+}
+
+/// 分节符类型，决定节从何处开始
+/// Section start type (`w:type`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SectionType {
+    /// 下一页
+    #[default]
+    NextPage,
+    /// 下一栏
+    NextColumn,
+    /// 连续
+    Continuous,
+    /// 偶数页
+    EvenPage,
+    /// 奇数页
+    OddPage,
+}
+
+/// 页面方向
+/// Page orientation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PageOrientation {
+    /// 纵向
+    Portrait,
+    /// 横向
+    Landscape,
+}
+
+/// 页面大小（缇）
+/// Page size (`w:pgSz`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct PageSize {
+    #[serde(rename = "@w:w", alias = "@w", deserialize_with = "measurement::deserialize", default)]
+    pub width: i32,
+    #[serde(rename = "@w:h", alias = "@h", deserialize_with = "measurement::deserialize", default)]
+    pub height: i32,
+    #[serde(rename = "@w:orient", alias = "@orient", skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<PageOrientation>,
+}
+
+/// 页边距（缇）
+/// Page margins (`w:pgMar`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct PageMargins {
+    #[serde(rename = "@w:top", alias = "@top", deserialize_with = "measurement::deserialize", default)]
+    pub top: i32,
+    #[serde(rename = "@w:right", alias = "@right", deserialize_with = "measurement::deserialize", default)]
+    pub right: i32,
+    #[serde(rename = "@w:bottom", alias = "@bottom", deserialize_with = "measurement::deserialize", default)]
+    pub bottom: i32,
+    #[serde(rename = "@w:left", alias = "@left", deserialize_with = "measurement::deserialize", default)]
+    pub left: i32,
+    #[serde(rename = "@w:header", alias = "@header", deserialize_with = "measurement::deserialize", default)]
+    pub header: i32,
+    #[serde(rename = "@w:footer", alias = "@footer", deserialize_with = "measurement::deserialize", default)]
+    pub footer: i32,
+    #[serde(rename = "@w:gutter", alias = "@gutter", deserialize_with = "measurement::deserialize", default)]
+    pub gutter: i32,
+}
+
+/// 分栏
+/// Columns (`w:cols`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct Columns {
+    /// 栏数
+    #[serde(rename = "@w:num", alias = "@num", skip_serializing_if = "Option::is_none")]
+    pub num: Option<i32>,
+    /// 栏间距（缇）
+    #[serde(rename = "@w:space", alias = "@space", skip_serializing_if = "Option::is_none")]
+    pub space: Option<i32>,
+}
+
+/// 文档网格
+/// Document grid (`w:docGrid`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct DocGrid {
+    #[serde(rename = "@w:type", alias = "@type", skip_serializing_if = "Option::is_none")]
+    pub grid_type: Option<String>,
+    #[serde(rename = "@w:linePitch", alias = "@linePitch", skip_serializing_if = "Option::is_none")]
+    pub line_pitch: Option<i32>,
+    #[serde(rename = "@w:charSpace", alias = "@charSpace", skip_serializing_if = "Option::is_none")]
+    pub char_space: Option<i32>,
+}
+
+/// 页眉页脚的适用页面
+/// Header or footer type (`ST_HdrFtr`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HeaderFooterType {
+    /// 奇数页，或未区分奇偶页时的所有页
+    #[default]
+    Default,
+    /// 偶数页
+    Even,
+    /// 节的首页
+    First,
+}
+
+/// 页眉或页脚引用，通过关系编号指向页眉或页脚部件
+/// Header or footer reference (`w:headerReference`, `w:footerReference`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct HeaderFooterReference {
+    /// 适用页面
+    #[serde(rename = "@w:type", alias = "@type", default)]
+    pub reference_type: HeaderFooterType,
+    /// 页眉或页脚部件的关系编号
+    #[serde(rename = "@r:id", alias = "@id")]
+    pub id: String,
+}
+
+/// 页码设置
+/// Page numbering (`w:pgNumType`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct PageNumberType {
+    /// 页码格式
+    #[serde(rename = "@w:fmt", alias = "@fmt", skip_serializing_if = "Option::is_none")]
+    pub format: Option<NumberFormat>,
+    /// 起始页码，缺省时接续上一节
+    #[serde(rename = "@w:start", alias = "@start", skip_serializing_if = "Option::is_none")]
+    pub start: Option<u32>,
+}
+
+/// 节属性，位于正文末尾或分节段落的段落属性中，描述以该处结束的节
+/// Section properties (`w:sectPr`)
+///
+/// 只建模常用的页面设置；字段顺序与 `CT_SectPr` 的元素顺序一致。
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:sectPr")]
+pub struct SectionProperties {
+    /// 页眉引用
+    #[serde(rename = "w:headerReference", alias = "headerReference", default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<HeaderFooterReference>,
+    /// 页脚引用
+    #[serde(rename = "w:footerReference", alias = "footerReference", default, skip_serializing_if = "Vec::is_empty")]
+    pub footers: Vec<HeaderFooterReference>,
+    /// 节的开始方式，缺省为下一页
+    #[serde(rename = "w:type", alias = "type", skip_serializing_if = "Option::is_none")]
+    pub section_type: Option<Val<SectionType>>,
+    #[serde(rename = "w:pgSz", alias = "pgSz", skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<PageSize>,
+    #[serde(rename = "w:pgMar", alias = "pgMar", skip_serializing_if = "Option::is_none")]
+    pub page_margins: Option<PageMargins>,
+    /// 页码格式和起始页码
+    #[serde(rename = "w:pgNumType", alias = "pgNumType", skip_serializing_if = "Option::is_none")]
+    pub page_numbering: Option<PageNumberType>,
+    #[serde(rename = "w:cols", alias = "cols", skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Columns>,
+    /// 首页使用不同的页眉页脚
+    #[serde(rename = "w:titlePg", alias = "titlePg", skip_serializing_if = "Option::is_none")]
+    pub title_page: Option<OnOff>,
+    #[serde(rename = "w:docGrid", alias = "docGrid", skip_serializing_if = "Option::is_none")]
+    pub doc_grid: Option<DocGrid>,
+    /// 未建模或无法识别的子元素，保存时原样写在末尾
+    #[serde(rename = "$value", skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub unknown: Vec<XmlElement>,
+}
+
+impl SectionProperties {
+    /// 只设置开始方式的节属性
+    ///
+    /// # 参数
+    /// * `section_type` - 节的开始方式
+    pub fn with_type(section_type: SectionType) -> Self {
+        SectionProperties {
+            section_type: Some(Val::new(section_type)),
+            ..Default::default()
+        }
+    }
+}
+
+/// 开关属性，如 `<w:b/>`、`<w:b w:val="false"/>`
+/// On/off property. The element alone means on; `w:val` of `0`, `false` or `off` means off.
+///
+/// 对于粗体、斜体等切换属性 (toggle property)，样式层级中的多次出现会相互抵消，
+/// 而直接格式总是取其本身的值，具体计算见样式解析。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnOff(pub bool);
+
+impl OnOff {
+    /// 开启
+    pub const ON: OnOff = OnOff(true);
+    /// 关闭
+    pub const OFF: OnOff = OnOff(false);
+
+    /// 是否开启
+    pub fn is_on(self) -> bool {
+        self.0
+    }
+}
+
+impl From<bool> for OnOff {
+    fn from(value: bool) -> Self {
+        OnOff(value)
+    }
+}
+
+/// `OnOff` 的XML表示
+#[derive(Deserialize, Serialize)]
+struct RawOnOff {
+    #[serde(rename = "@w:val", alias = "@val", skip_serializing_if = "Option::is_none")]
+    val: Option<String>,
+}
+
+impl Serialize for OnOff {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawOnOff {
+            val: (!self.0).then(|| "0".to_string()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OnOff {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawOnOff::deserialize(deserializer)?;
+        Ok(OnOff(!matches!(
+            raw.val.as_deref(),
+            Some("0") | Some("false") | Some("off")
+        )))
+    }
+}
+
+/// 主题字体
+/// Theme font reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ThemeFont {
+    MajorEastAsia,
+    MajorBidi,
+    MajorAscii,
+    MajorHAnsi,
+    MinorEastAsia,
+    MinorBidi,
+    MinorAscii,
+    MinorHAnsi,
+}
+
+/// 字体设置，分别指定西文、高位ANSI、东亚和复杂文种字体
+/// Run fonts (`w:rFonts`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:rFonts")]
+pub struct RunFonts {
+    #[serde(rename = "@w:hint", alias = "@hint", skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    #[serde(rename = "@w:ascii", alias = "@ascii", skip_serializing_if = "Option::is_none")]
+    pub ascii: Option<String>,
+    #[serde(rename = "@w:hAnsi", alias = "@hAnsi", skip_serializing_if = "Option::is_none")]
+    pub h_ansi: Option<String>,
+    #[serde(rename = "@w:eastAsia", alias = "@eastAsia", skip_serializing_if = "Option::is_none")]
+    pub east_asia: Option<String>,
+    #[serde(rename = "@w:cs", alias = "@cs", skip_serializing_if = "Option::is_none")]
+    pub cs: Option<String>,
+    #[serde(rename = "@w:asciiTheme", alias = "@asciiTheme", skip_serializing_if = "Option::is_none")]
+    pub ascii_theme: Option<ThemeFont>,
+    #[serde(rename = "@w:hAnsiTheme", alias = "@hAnsiTheme", skip_serializing_if = "Option::is_none")]
+    pub h_ansi_theme: Option<ThemeFont>,
+    #[serde(rename = "@w:eastAsiaTheme", alias = "@eastAsiaTheme", skip_serializing_if = "Option::is_none")]
+    pub east_asia_theme: Option<ThemeFont>,
+    #[serde(rename = "@w:cstheme", alias = "@cstheme", skip_serializing_if = "Option::is_none")]
+    pub cs_theme: Option<ThemeFont>,
+}
+
+/// 主题颜色
+/// Theme color reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ThemeColor {
+    Dark1,
+    Light1,
+    Dark2,
+    Light2,
+    Accent1,
+    Accent2,
+    Accent3,
+    Accent4,
+    Accent5,
+    Accent6,
+    Hyperlink,
+    FollowedHyperlink,
+    None,
+    Background1,
+    Text1,
+    Background2,
+    Text2,
+}
+
+/// 文字颜色
+/// Run color (`w:color`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:color")]
+pub struct Color {
+    /// 十六进制RGB值（如 `FF0000`）或 `auto`
+    #[serde(rename = "@w:val", alias = "@val")]
+    pub val: String,
+    #[serde(rename = "@w:themeColor", alias = "@themeColor", skip_serializing_if = "Option::is_none")]
+    pub theme_color: Option<ThemeColor>,
+    #[serde(rename = "@w:themeTint", alias = "@themeTint", skip_serializing_if = "Option::is_none")]
+    pub theme_tint: Option<String>,
+    #[serde(rename = "@w:themeShade", alias = "@themeShade", skip_serializing_if = "Option::is_none")]
+    pub theme_shade: Option<String>,
+}
+
+/// 突出显示颜色
+/// Text highlight color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HighlightColor {
+    Black,
+    Blue,
+    Cyan,
+    Green,
+    Magenta,
+    Red,
+    Yellow,
+    White,
+    DarkBlue,
+    DarkCyan,
+    DarkGreen,
+    DarkMagenta,
+    DarkRed,
+    DarkYellow,
+    DarkGray,
+    LightGray,
+    None,
+}
+
+/// 底纹
+/// Shading (`w:shd`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:shd")]
+pub struct Shading {
+    /// 底纹图案，如 `clear`、`solid`、`pct10`
+    #[serde(rename = "@w:val", alias = "@val")]
+    pub val: String,
+    /// 图案颜色
+    #[serde(rename = "@w:color", alias = "@color", skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(rename = "@w:themeColor", alias = "@themeColor", skip_serializing_if = "Option::is_none")]
+    pub theme_color: Option<ThemeColor>,
+    /// 填充颜色
+    #[serde(rename = "@w:fill", alias = "@fill", skip_serializing_if = "Option::is_none")]
+    pub fill: Option<String>,
+    #[serde(rename = "@w:themeFill", alias = "@themeFill", skip_serializing_if = "Option::is_none")]
+    pub theme_fill: Option<ThemeColor>,
+}
+
+/// 下划线类型
+/// Underline style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnderlineType {
+    #[default]
+    Single,
+    Words,
+    Double,
+    Thick,
+    Dotted,
+    DottedHeavy,
+    Dash,
+    DashedHeavy,
+    DashLong,
+    DashLongHeavy,
+    DotDash,
+    DashDotHeavy,
+    DotDotDash,
+    DashDotDotHeavy,
+    Wave,
+    WavyHeavy,
+    WavyDouble,
+    None,
+}
+
+/// 下划线
+/// Underline (`w:u`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:u")]
+pub struct Underline {
+    #[serde(rename = "@w:val", alias = "@val", skip_serializing_if = "Option::is_none")]
+    pub val: Option<UnderlineType>,
+    #[serde(rename = "@w:color", alias = "@color", skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(rename = "@w:themeColor", alias = "@themeColor", skip_serializing_if = "Option::is_none")]
+    pub theme_color: Option<ThemeColor>,
+}
+
+/// 上下标
+/// Vertical alignment of a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VerticalAlignRun {
+    Baseline,
+    Superscript,
+    Subscript,
+}
+
+/// 语言设置
+/// Languages of a run (`w:lang`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:lang")]
+pub struct Language {
+    #[serde(rename = "@w:val", alias = "@val", skip_serializing_if = "Option::is_none")]
+    pub val: Option<String>,
+    #[serde(rename = "@w:eastAsia", alias = "@eastAsia", skip_serializing_if = "Option::is_none")]
+    pub east_asia: Option<String>,
+    #[serde(rename = "@w:bidi", alias = "@bidi", skip_serializing_if = "Option::is_none")]
+    pub bidi: Option<String>,
 }
 
 /// 运行属性 (文字属性)
 /// Run properties (text properties)
-#[derive(Debug, Default, Deserialize, Serialize)]
+///
+/// 字段顺序与 `CT_RPr` 的元素顺序一致。字号、字距等以半磅为单位，间距以缇 (1/20 磅) 为单位。
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 #[serde(rename = "w:rPr")]
 pub struct RunProperties {
-    #[serde(rename = "w:b", skip_serializing_if = "Option::is_none")]
-    pub bold: Option<()>, // For toggle properties, we just care if the tag exists
-    #[serde(rename = "w:i", skip_serializing_if = "Option::is_none")]
-    pub italic: Option<()>,
-    #[serde(rename = "w:u", skip_serializing_if = "Option::is_none")]
-    pub underline: Option<()>,
-    #[serde(rename = "w:rStyle", skip_serializing_if = "Option::is_none")]
+    /// 段落标记被插入（仅用于段落标记的运行属性）
+    #[serde(rename = "w:ins", alias = "ins", skip_serializing_if = "Option::is_none")]
+    pub inserted: Option<RevisionMark>,
+    /// 段落标记被删除（仅用于段落标记的运行属性）
+    #[serde(rename = "w:del", alias = "del", skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<RevisionMark>,
+    #[serde(rename = "w:rStyle", alias = "rStyle", skip_serializing_if = "Option::is_none")]
     pub style: Option<ParagraphStyle>, // Re-using ParagraphStyle for run style
-                                       // Future properties can be added here, e.g., color, size
+    #[serde(rename = "w:rFonts", alias = "rFonts", skip_serializing_if = "Option::is_none")]
+    pub fonts: Option<RunFonts>,
+    #[serde(rename = "w:b", alias = "b", skip_serializing_if = "Option::is_none")]
+    pub bold: Option<OnOff>,
+    /// 复杂文种粗体
+    #[serde(rename = "w:bCs", alias = "bCs", skip_serializing_if = "Option::is_none")]
+    pub bold_cs: Option<OnOff>,
+    #[serde(rename = "w:i", alias = "i", skip_serializing_if = "Option::is_none")]
+    pub italic: Option<OnOff>,
+    /// 复杂文种斜体
+    #[serde(rename = "w:iCs", alias = "iCs", skip_serializing_if = "Option::is_none")]
+    pub italic_cs: Option<OnOff>,
+    /// 全部大写
+    #[serde(rename = "w:caps", alias = "caps", skip_serializing_if = "Option::is_none")]
+    pub caps: Option<OnOff>,
+    /// 小型大写字母
+    #[serde(rename = "w:smallCaps", alias = "smallCaps", skip_serializing_if = "Option::is_none")]
+    pub small_caps: Option<OnOff>,
+    /// 删除线
+    #[serde(rename = "w:strike", alias = "strike", skip_serializing_if = "Option::is_none")]
+    pub strike: Option<OnOff>,
+    /// 双删除线
+    #[serde(rename = "w:dstrike", alias = "dstrike", skip_serializing_if = "Option::is_none")]
+    pub double_strike: Option<OnOff>,
+    #[serde(rename = "w:outline", alias = "outline", skip_serializing_if = "Option::is_none")]
+    pub outline: Option<OnOff>,
+    #[serde(rename = "w:shadow", alias = "shadow", skip_serializing_if = "Option::is_none")]
+    pub shadow: Option<OnOff>,
+    #[serde(rename = "w:emboss", alias = "emboss", skip_serializing_if = "Option::is_none")]
+    pub emboss: Option<OnOff>,
+    #[serde(rename = "w:imprint", alias = "imprint", skip_serializing_if = "Option::is_none")]
+    pub imprint: Option<OnOff>,
+    /// 不检查拼写和语法
+    #[serde(rename = "w:noProof", alias = "noProof", skip_serializing_if = "Option::is_none")]
+    pub no_proof: Option<OnOff>,
+    /// 隐藏文字
+    #[serde(rename = "w:vanish", alias = "vanish", skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<OnOff>,
+    #[serde(rename = "w:webHidden", alias = "webHidden", skip_serializing_if = "Option::is_none")]
+    pub web_hidden: Option<OnOff>,
+    #[serde(rename = "w:color", alias = "color", skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    /// 字符间距（缇）
+    #[serde(rename = "w:spacing", alias = "spacing", skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<Val<i32>>,
+    /// 字符缩放（百分比）
+    #[serde(rename = "w:w", alias = "w", skip_serializing_if = "Option::is_none")]
+    pub scale: Option<Val<u32>>,
+    /// 字距调整的最小字号（半磅）
+    #[serde(rename = "w:kern", alias = "kern", skip_serializing_if = "Option::is_none")]
+    pub kern: Option<Val<u32>>,
+    /// 基线升降（半磅）
+    #[serde(rename = "w:position", alias = "position", skip_serializing_if = "Option::is_none")]
+    pub position: Option<Val<i32>>,
+    /// 字号（半磅）
+    #[serde(rename = "w:sz", alias = "sz", skip_serializing_if = "Option::is_none")]
+    pub size: Option<Val<u32>>,
+    /// 复杂文种字号（半磅）
+    #[serde(rename = "w:szCs", alias = "szCs", skip_serializing_if = "Option::is_none")]
+    pub size_cs: Option<Val<u32>>,
+    #[serde(rename = "w:highlight", alias = "highlight", skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<Val<HighlightColor>>,
+    #[serde(rename = "w:u", alias = "u", skip_serializing_if = "Option::is_none")]
+    pub underline: Option<Underline>,
+    #[serde(rename = "w:shd", alias = "shd", skip_serializing_if = "Option::is_none")]
+    pub shading: Option<Shading>,
+    #[serde(rename = "w:vertAlign", alias = "vertAlign", skip_serializing_if = "Option::is_none")]
+    pub vertical_align: Option<Val<VerticalAlignRun>>,
+    /// 从右到左文字
+    #[serde(rename = "w:rtl", alias = "rtl", skip_serializing_if = "Option::is_none")]
+    pub rtl: Option<OnOff>,
+    /// 按复杂文种处理
+    #[serde(rename = "w:cs", alias = "cs", skip_serializing_if = "Option::is_none")]
+    pub complex_script: Option<OnOff>,
+    #[serde(rename = "w:lang", alias = "lang", skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+    /// 未建模或无法识别的子元素，保存时原样写在修订记录之前
+    #[serde(rename = "$value", skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub unknown: Vec<XmlElement>,
+    /// 运行属性修订记录
+    /// Tracked run formatting change
+    #[serde(rename = "w:rPrChange", alias = "rPrChange", skip_serializing_if = "Option::is_none")]
+    pub change: Option<RunPropertiesChange>,
+}
+
+/// 修订标记，记录段落标记等的插入或删除
+/// Revision mark (`w:ins`/`w:del` inside paragraph mark properties)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct RevisionMark {
+    #[serde(rename = "@w:id", alias = "@id")]
+    pub id: i32,
+    #[serde(rename = "@w:author", alias = "@author")]
+    pub author: String,
+    #[serde(rename = "@w:date", alias = "@date", skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+/// 运行属性修订，保存修改前的运行属性
+/// Run formatting change, holding the properties before the revision.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "w:rPrChange")]
+pub struct RunPropertiesChange {
+    #[serde(rename = "@w:id", alias = "@id")]
+    pub id: i32,
+    #[serde(rename = "@w:author", alias = "@author")]
+    pub author: String,
+    #[serde(rename = "@w:date", alias = "@date", skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(rename = "w:rPr", alias = "rPr", default)]
+    pub properties: Box<RunProperties>,
+}
+
+/// 宽度或长度值的反序列化辅助，兼容小数与百分比写法（如 `50%` 记为 2500，即 1/50 百分比）
+/// Lenient parsing of ST_MeasurementOrPercent values.
+mod measurement {
+    use serde::{Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        let raw = raw.trim();
+        let value = match raw.strip_suffix('%') {
+            Some(percent) => percent.parse::<f64>().map(|v| v * 50.0),
+            None => raw.parse::<f64>(),
+        };
+        value
+            .map(|v| v.round() as i32)
+            .map_err(|_| serde::de::Error::custom(format!("invalid measurement `{raw}`")))
+    }
+}
+
+/// 宽度单位类型
+/// Width type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WidthType {
+    /// 零宽度
+    Nil,
+    /// 1/50 百分比
+    Pct,
+    /// 缇
+    #[default]
+    Dxa,
+    /// 自动
+    Auto,
+}
+
+/// 表格中的宽度（`w:tblW`、`w:tcW`、`w:tblInd`、单元格边距等）
+/// Table measurement
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct TableWidth {
+    #[serde(rename = "@w:w", alias = "@w", deserialize_with = "measurement::deserialize", default)]
+    pub width: i32,
+    #[serde(rename = "@w:type", alias = "@type", skip_serializing_if = "Option::is_none")]
+    pub width_type: Option<WidthType>,
+}
+
+impl TableWidth {
+    /// 以缇为单位的宽度
+    pub fn dxa(width: i32) -> Self {
+        TableWidth {
+            width,
+            width_type: Some(WidthType::Dxa),
+        }
+    }
+
+    /// 以 1/50 百分比为单位的宽度（5000 表示 100%）
+    pub fn pct(width: i32) -> Self {
+        TableWidth {
+            width,
+            width_type: Some(WidthType::Pct),
+        }
+    }
+
+    /// 自动宽度
+    pub fn auto() -> Self {
+        TableWidth {
+            width: 0,
+            width_type: Some(WidthType::Auto),
+        }
+    }
+}
+
+/// 表格边框
+/// Table borders (`w:tblBorders`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:tblBorders")]
+pub struct TableBorders {
+    #[serde(rename = "w:top", alias = "top", skip_serializing_if = "Option::is_none")]
+    pub top: Option<Border>,
+    #[serde(rename = "w:left", alias = "left", alias = "start", skip_serializing_if = "Option::is_none")]
+    pub left: Option<Border>,
+    #[serde(rename = "w:bottom", alias = "bottom", skip_serializing_if = "Option::is_none")]
+    pub bottom: Option<Border>,
+    #[serde(rename = "w:right", alias = "right", alias = "end", skip_serializing_if = "Option::is_none")]
+    pub right: Option<Border>,
+    /// 内部横向边框
+    #[serde(rename = "w:insideH", alias = "insideH", skip_serializing_if = "Option::is_none")]
+    pub inside_h: Option<Border>,
+    /// 内部纵向边框
+    #[serde(rename = "w:insideV", alias = "insideV", skip_serializing_if = "Option::is_none")]
+    pub inside_v: Option<Border>,
+}
+
+impl TableBorders {
+    /// 所有边框（含内部边框）均使用同一边框线
+    pub fn all(border: Border) -> Self {
+        TableBorders {
+            top: Some(border.clone()),
+            left: Some(border.clone()),
+            bottom: Some(border.clone()),
+            right: Some(border.clone()),
+            inside_h: Some(border.clone()),
+            inside_v: Some(border),
+        }
+    }
+}
+
+/// 单元格边框
+/// Table cell borders (`w:tcBorders`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:tcBorders")]
+pub struct TableCellBorders {
+    #[serde(rename = "w:top", alias = "top", skip_serializing_if = "Option::is_none")]
+    pub top: Option<Border>,
+    #[serde(rename = "w:left", alias = "left", alias = "start", skip_serializing_if = "Option::is_none")]
+    pub left: Option<Border>,
+    #[serde(rename = "w:bottom", alias = "bottom", skip_serializing_if = "Option::is_none")]
+    pub bottom: Option<Border>,
+    #[serde(rename = "w:right", alias = "right", alias = "end", skip_serializing_if = "Option::is_none")]
+    pub right: Option<Border>,
+    #[serde(rename = "w:insideH", alias = "insideH", skip_serializing_if = "Option::is_none")]
+    pub inside_h: Option<Border>,
+    #[serde(rename = "w:insideV", alias = "insideV", skip_serializing_if = "Option::is_none")]
+    pub inside_v: Option<Border>,
+    /// 左上到右下的斜线
+    #[serde(rename = "w:tl2br", alias = "tl2br", skip_serializing_if = "Option::is_none")]
+    pub top_left_to_bottom_right: Option<Border>,
+    /// 右上到左下的斜线
+    #[serde(rename = "w:tr2bl", alias = "tr2bl", skip_serializing_if = "Option::is_none")]
+    pub top_right_to_bottom_left: Option<Border>,
+}
+
+/// 单元格边距
+/// Cell margins (`w:tblCellMar`, `w:tcMar`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct CellMargins {
+    #[serde(rename = "w:top", alias = "top", skip_serializing_if = "Option::is_none")]
+    pub top: Option<TableWidth>,
+    #[serde(rename = "w:left", alias = "left", alias = "start", skip_serializing_if = "Option::is_none")]
+    pub left: Option<TableWidth>,
+    #[serde(rename = "w:bottom", alias = "bottom", skip_serializing_if = "Option::is_none")]
+    pub bottom: Option<TableWidth>,
+    #[serde(rename = "w:right", alias = "right", alias = "end", skip_serializing_if = "Option::is_none")]
+    pub right: Option<TableWidth>,
+}
+
+/// 表格布局方式
+/// Table layout algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TableLayoutType {
+    /// 固定列宽
+    Fixed,
+    /// 根据内容自动调整
+    #[default]
+    Autofit,
+}
+
+/// 表格布局
+/// Table layout (`w:tblLayout`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:tblLayout")]
+pub struct TableLayout {
+    #[serde(rename = "@w:type", alias = "@type")]
+    pub layout_type: TableLayoutType,
+}
+
+/// 表格样式选项，决定表格样式中哪些条件格式生效
+/// Table style options (`w:tblLook`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:tblLook")]
+pub struct TableLook {
+    /// 旧版十六进制位掩码
+    #[serde(rename = "@w:val", alias = "@val", skip_serializing_if = "Option::is_none")]
+    pub val: Option<String>,
+    /// 标题行
+    #[serde(rename = "@w:firstRow", alias = "@firstRow", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub first_row: Option<bool>,
+    /// 汇总行
+    #[serde(rename = "@w:lastRow", alias = "@lastRow", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub last_row: Option<bool>,
+    /// 第一列
+    #[serde(rename = "@w:firstColumn", alias = "@firstColumn", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub first_column: Option<bool>,
+    /// 最后一列
+    #[serde(rename = "@w:lastColumn", alias = "@lastColumn", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub last_column: Option<bool>,
+    /// 不使用镶边行
+    #[serde(rename = "@w:noHBand", alias = "@noHBand", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub no_h_band: Option<bool>,
+    /// 不使用镶边列
+    #[serde(rename = "@w:noVBand", alias = "@noVBand", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub no_v_band: Option<bool>,
+}
+
+impl TableLook {
+    /// 解析旧版位掩码与新版属性，得到标题行是否生效
+    pub fn first_row(&self) -> bool {
+        self.first_row.unwrap_or_else(|| self.mask() & 0x0020 != 0)
+    }
+
+    /// 汇总行是否生效
+    pub fn last_row(&self) -> bool {
+        self.last_row.unwrap_or_else(|| self.mask() & 0x0040 != 0)
+    }
+
+    /// 第一列是否生效
+    pub fn first_column(&self) -> bool {
+        self.first_column.unwrap_or_else(|| self.mask() & 0x0080 != 0)
+    }
+
+    /// 最后一列是否生效
+    pub fn last_column(&self) -> bool {
+        self.last_column.unwrap_or_else(|| self.mask() & 0x0100 != 0)
+    }
+
+    /// 镶边行是否生效
+    pub fn banded_rows(&self) -> bool {
+        !self.no_h_band.unwrap_or_else(|| self.mask() & 0x0200 != 0)
+    }
+
+    /// 镶边列是否生效
+    pub fn banded_columns(&self) -> bool {
+        !self.no_v_band.unwrap_or_else(|| self.mask() & 0x0400 != 0)
+    }
+
+    fn mask(&self) -> u32 {
+        self.val
+            .as_deref()
+            .and_then(|v| u32::from_str_radix(v, 16).ok())
+            .unwrap_or(0)
+    }
+}
+
+/// 条件格式标记，指明行、单元格或段落适用的表格样式条件格式
+/// Conditional formatting flags (`w:cnfStyle`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:cnfStyle")]
+pub struct ConditionalFormatStyle {
+    /// 旧版二进制位串
+    #[serde(rename = "@w:val", alias = "@val", skip_serializing_if = "Option::is_none")]
+    pub val: Option<String>,
+    #[serde(rename = "@w:firstRow", alias = "@firstRow", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub first_row: Option<bool>,
+    #[serde(rename = "@w:lastRow", alias = "@lastRow", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub last_row: Option<bool>,
+    #[serde(rename = "@w:firstColumn", alias = "@firstColumn", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub first_column: Option<bool>,
+    #[serde(rename = "@w:lastColumn", alias = "@lastColumn", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub last_column: Option<bool>,
+    #[serde(rename = "@w:oddVBand", alias = "@oddVBand", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub odd_v_band: Option<bool>,
+    #[serde(rename = "@w:evenVBand", alias = "@evenVBand", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub even_v_band: Option<bool>,
+    #[serde(rename = "@w:oddHBand", alias = "@oddHBand", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub odd_h_band: Option<bool>,
+    #[serde(rename = "@w:evenHBand", alias = "@evenHBand", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub even_h_band: Option<bool>,
+    #[serde(rename = "@w:firstRowFirstColumn", alias = "@firstRowFirstColumn", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub first_row_first_column: Option<bool>,
+    #[serde(rename = "@w:firstRowLastColumn", alias = "@firstRowLastColumn", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub first_row_last_column: Option<bool>,
+    #[serde(rename = "@w:lastRowFirstColumn", alias = "@lastRowFirstColumn", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub last_row_first_column: Option<bool>,
+    #[serde(rename = "@w:lastRowLastColumn", alias = "@lastRowLastColumn", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub last_row_last_column: Option<bool>,
+}
+
+/// 表格属性
+/// Table properties (`w:tblPr`)
+///
+/// 字段顺序与 `CT_TblPr` 的元素顺序一致。
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:tblPr")]
+pub struct TableProperties {
+    /// 表格样式ID
+    #[serde(rename = "w:tblStyle", alias = "tblStyle", skip_serializing_if = "Option::is_none")]
+    pub style: Option<Val<String>>,
+    /// 从右到左表格
+    #[serde(rename = "w:bidiVisual", alias = "bidiVisual", skip_serializing_if = "Option::is_none")]
+    pub bidi_visual: Option<OnOff>,
+    /// 样式中每个镶边行包含的行数
+    #[serde(rename = "w:tblStyleRowBandSize", alias = "tblStyleRowBandSize", skip_serializing_if = "Option::is_none")]
+    pub style_row_band_size: Option<Val<u32>>,
+    /// 样式中每个镶边列包含的列数
+    #[serde(rename = "w:tblStyleColBandSize", alias = "tblStyleColBandSize", skip_serializing_if = "Option::is_none")]
+    pub style_col_band_size: Option<Val<u32>>,
+    #[serde(rename = "w:tblW", alias = "tblW", skip_serializing_if = "Option::is_none")]
+    pub width: Option<TableWidth>,
+    /// 表格在页面中的对齐方式
+    #[serde(rename = "w:jc", alias = "jc", skip_serializing_if = "Option::is_none")]
+    pub justification: Option<Justification>,
+    #[serde(rename = "w:tblCellSpacing", alias = "tblCellSpacing", skip_serializing_if = "Option::is_none")]
+    pub cell_spacing: Option<TableWidth>,
+    /// 表格左缩进
+    #[serde(rename = "w:tblInd", alias = "tblInd", skip_serializing_if = "Option::is_none")]
+    pub indentation: Option<TableWidth>,
+    #[serde(rename = "w:tblBorders", alias = "tblBorders", skip_serializing_if = "Option::is_none")]
+    pub borders: Option<TableBorders>,
+    #[serde(rename = "w:shd", alias = "shd", skip_serializing_if = "Option::is_none")]
+    pub shading: Option<Shading>,
+    #[serde(rename = "w:tblLayout", alias = "tblLayout", skip_serializing_if = "Option::is_none")]
+    pub layout: Option<TableLayout>,
+    /// 默认单元格边距
+    #[serde(rename = "w:tblCellMar", alias = "tblCellMar", skip_serializing_if = "Option::is_none")]
+    pub cell_margins: Option<CellMargins>,
+    #[serde(rename = "w:tblLook", alias = "tblLook", skip_serializing_if = "Option::is_none")]
+    pub look: Option<TableLook>,
+    #[serde(rename = "w:tblCaption", alias = "tblCaption", skip_serializing_if = "Option::is_none")]
+    pub caption: Option<Val<String>>,
+    #[serde(rename = "w:tblDescription", alias = "tblDescription", skip_serializing_if = "Option::is_none")]
+    pub description: Option<Val<String>>,
+    /// 未建模或无法识别的子元素，保存时原样写在末尾
+    #[serde(rename = "$value", skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub unknown: Vec<XmlElement>,
+}
+
+/// 表格网格列
+/// Grid column (`w:gridCol`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:gridCol")]
+pub struct GridColumn {
+    /// 列宽（缇）
+    #[serde(rename = "@w:w", alias = "@w", deserialize_with = "measurement::deserialize", default)]
+    pub width: i32,
+}
+
+/// 表格网格，定义表格的列及列宽
+/// Table grid (`w:tblGrid`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:tblGrid")]
+pub struct TableGrid {
+    #[serde(rename = "w:gridCol", alias = "gridCol", default)]
+    pub columns: Vec<GridColumn>,
+}
+
+impl TableGrid {
+    /// 由列宽（缇）创建表格网格
+    pub fn from_widths(widths: impl IntoIterator<Item = i32>) -> Self {
+        TableGrid {
+            columns: widths.into_iter().map(|width| GridColumn { width }).collect(),
+        }
+    }
+}
+
+/// 行高规则
+/// Row height rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HeightRule {
+    Auto,
+    Exact,
+    AtLeast,
+}
+
+/// 行高
+/// Row height (`w:trHeight`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:trHeight")]
+pub struct RowHeight {
+    /// 高度（缇）
+    #[serde(rename = "@w:val", alias = "@val", deserialize_with = "measurement::deserialize", default)]
+    pub val: i32,
+    #[serde(rename = "@w:hRule", alias = "@hRule", skip_serializing_if = "Option::is_none")]
+    pub rule: Option<HeightRule>,
+}
+
+/// 表格行属性
+/// Table row properties (`w:trPr`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:trPr")]
+pub struct TableRowProperties {
+    #[serde(rename = "w:cnfStyle", alias = "cnfStyle", skip_serializing_if = "Option::is_none")]
+    pub conditional_format: Option<ConditionalFormatStyle>,
+    /// 行首跳过的网格列数
+    #[serde(rename = "w:gridBefore", alias = "gridBefore", skip_serializing_if = "Option::is_none")]
+    pub grid_before: Option<Val<u32>>,
+    /// 行尾跳过的网格列数
+    #[serde(rename = "w:gridAfter", alias = "gridAfter", skip_serializing_if = "Option::is_none")]
+    pub grid_after: Option<Val<u32>>,
+    #[serde(rename = "w:wBefore", alias = "wBefore", skip_serializing_if = "Option::is_none")]
+    pub width_before: Option<TableWidth>,
+    #[serde(rename = "w:wAfter", alias = "wAfter", skip_serializing_if = "Option::is_none")]
+    pub width_after: Option<TableWidth>,
+    /// 不允许跨页断行
+    #[serde(rename = "w:cantSplit", alias = "cantSplit", skip_serializing_if = "Option::is_none")]
+    pub cant_split: Option<OnOff>,
+    #[serde(rename = "w:trHeight", alias = "trHeight", skip_serializing_if = "Option::is_none")]
+    pub height: Option<RowHeight>,
+    /// 在各页顶端重复标题行
+    #[serde(rename = "w:tblHeader", alias = "tblHeader", skip_serializing_if = "Option::is_none")]
+    pub header: Option<OnOff>,
+    #[serde(rename = "w:tblCellSpacing", alias = "tblCellSpacing", skip_serializing_if = "Option::is_none")]
+    pub cell_spacing: Option<TableWidth>,
+    #[serde(rename = "w:jc", alias = "jc", skip_serializing_if = "Option::is_none")]
+    pub justification: Option<Justification>,
+    #[serde(rename = "w:hidden", alias = "hidden", skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<OnOff>,
+    /// 修订：插入的行
+    #[serde(rename = "w:ins", alias = "ins", skip_serializing_if = "Option::is_none")]
+    pub inserted: Option<RevisionMark>,
+    /// 修订：删除的行
+    #[serde(rename = "w:del", alias = "del", skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<RevisionMark>,
+    /// 未建模或无法识别的子元素，保存时原样写在末尾
+    #[serde(rename = "$value", skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub unknown: Vec<XmlElement>,
+}
+
+/// 合并类型
+/// Merge type for `w:vMerge`/`w:hMerge`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeType {
+    /// 开始一个合并区域
+    Restart,
+    /// 延续上方（或左侧）的合并区域
+    Continue,
+}
+
+/// 单元格合并
+/// Cell merge (`w:vMerge`, `w:hMerge`); a missing `w:val` means continue.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct Merge {
+    #[serde(rename = "@w:val", alias = "@val", skip_serializing_if = "Option::is_none")]
+    pub val: Option<MergeType>,
+}
+
+impl Merge {
+    /// 开始合并
+    pub fn restart() -> Self {
+        Merge {
+            val: Some(MergeType::Restart),
+        }
+    }
+
+    /// 延续合并
+    pub fn continuation() -> Self {
+        Merge { val: None }
+    }
+
+    /// 是否开始一个合并区域
+    pub fn is_restart(&self) -> bool {
+        self.val == Some(MergeType::Restart)
+    }
+}
+
+/// 单元格垂直对齐方式
+/// Vertical alignment of cell content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VerticalJc {
+    Top,
+    Center,
+    Both,
+    Bottom,
+}
+
+/// 单元格属性
+/// Table cell properties (`w:tcPr`)
+///
+/// 字段顺序与 `CT_TcPr` 的元素顺序一致。
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:tcPr")]
+pub struct TableCellProperties {
+    #[serde(rename = "w:cnfStyle", alias = "cnfStyle", skip_serializing_if = "Option::is_none")]
+    pub conditional_format: Option<ConditionalFormatStyle>,
+    #[serde(rename = "w:tcW", alias = "tcW", skip_serializing_if = "Option::is_none")]
+    pub width: Option<TableWidth>,
+    /// 横向跨越的网格列数
+    #[serde(rename = "w:gridSpan", alias = "gridSpan", skip_serializing_if = "Option::is_none")]
+    pub grid_span: Option<Val<u32>>,
+    /// 旧式横向合并
+    #[serde(rename = "w:hMerge", alias = "hMerge", skip_serializing_if = "Option::is_none")]
+    pub h_merge: Option<Merge>,
+    /// 纵向合并
+    #[serde(rename = "w:vMerge", alias = "vMerge", skip_serializing_if = "Option::is_none")]
+    pub v_merge: Option<Merge>,
+    #[serde(rename = "w:tcBorders", alias = "tcBorders", skip_serializing_if = "Option::is_none")]
+    pub borders: Option<TableCellBorders>,
+    #[serde(rename = "w:shd", alias = "shd", skip_serializing_if = "Option::is_none")]
+    pub shading: Option<Shading>,
+    #[serde(rename = "w:noWrap", alias = "noWrap", skip_serializing_if = "Option::is_none")]
+    pub no_wrap: Option<OnOff>,
+    #[serde(rename = "w:tcMar", alias = "tcMar", skip_serializing_if = "Option::is_none")]
+    pub margins: Option<CellMargins>,
+    #[serde(rename = "w:textDirection", alias = "textDirection", skip_serializing_if = "Option::is_none")]
+    pub text_direction: Option<Val<String>>,
+    #[serde(rename = "w:tcFitText", alias = "tcFitText", skip_serializing_if = "Option::is_none")]
+    pub fit_text: Option<OnOff>,
+    #[serde(rename = "w:vAlign", alias = "vAlign", skip_serializing_if = "Option::is_none")]
+    pub vertical_align: Option<Val<VerticalJc>>,
+    #[serde(rename = "w:hideMark", alias = "hideMark", skip_serializing_if = "Option::is_none")]
+    pub hide_mark: Option<OnOff>,
+    /// 未建模或无法识别的子元素，保存时原样写在末尾
+    #[serde(rename = "$value", skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub unknown: Vec<XmlElement>,
+}
+
+impl TableCellProperties {
+    /// 单元格横向跨越的网格列数（至少为 1）
+    pub fn span(&self) -> usize {
+        self.grid_span.as_ref().map_or(1, |s| s.val.max(1) as usize)
+    }
+}
+
+/// 用 `$other` 中已设置的字段覆盖 `$target` 中的同名字段
+macro_rules! overlay {
+    ($target:expr, $other:expr; $($field:ident),* $(,)?) => {
+        $(if $other.$field.is_some() {
+            $target.$field = $other.$field.clone();
+        })*
+    };
+}
+
+impl ParagraphSpacing {
+    /// 逐个属性覆盖，`other` 中已设置的属性优先；以行为单位的间距优先于以缇为单位的间距
+    ///
+    /// # 参数
+    /// * `other` - 覆盖的间距
+    pub fn apply(&mut self, other: &ParagraphSpacing) {
+        if other.before.is_some() && other.before_lines.is_none() {
+            self.before_lines = None;
+        }
+        if other.after.is_some() && other.after_lines.is_none() {
+            self.after_lines = None;
+        }
+        overlay!(self, other; before, before_lines, before_autospacing, after, after_lines,
+            after_autospacing, line, line_rule);
+    }
+}
+
+impl Indentation {
+    /// 逐个属性覆盖，`other` 中已设置的属性优先；首行缩进与悬挂缩进互斥
+    ///
+    /// # 参数
+    /// * `other` - 覆盖的缩进
+    pub fn apply(&mut self, other: &Indentation) {
+        if other.left.is_some() && other.left_chars.is_none() {
+            self.left_chars = None;
+        }
+        if other.right.is_some() && other.right_chars.is_none() {
+            self.right_chars = None;
+        }
+        if other.first_line.is_some() || other.first_line_chars.is_some() {
+            self.hanging = None;
+            self.hanging_chars = None;
+        }
+        if other.hanging.is_some() || other.hanging_chars.is_some() {
+            self.first_line = None;
+            self.first_line_chars = None;
+        }
+        overlay!(self, other; left, left_chars, right, right_chars, hanging, hanging_chars,
+            first_line, first_line_chars);
+    }
+}
+
+impl ParagraphBorders {
+    /// 逐条边框覆盖，`other` 中已设置的边框优先
+    ///
+    /// # 参数
+    /// * `other` - 覆盖的边框
+    pub fn apply(&mut self, other: &ParagraphBorders) {
+        overlay!(self, other; top, left, bottom, right, between, bar);
+    }
+}
+
+impl Tabs {
+    /// 合并制表位：相同位置的制表位被替换，`clear` 类型的制表位清除继承的制表位
+    ///
+    /// # 参数
+    /// * `other` - 覆盖的制表位
+    pub fn apply(&mut self, other: &Tabs) {
+        for tab in &other.tabs {
+            self.tabs.retain(|t| t.pos != tab.pos);
+            if tab.val != TabStopType::Clear {
+                self.tabs.push(tab.clone());
+            }
+        }
+        self.tabs.sort_by_key(|t| t.pos);
+    }
+}
+
+/// 合并可逐项覆盖的子属性
+fn apply_nested<T: Clone>(target: &mut Option<T>, other: &Option<T>, apply: impl FnOnce(&mut T, &T)) {
+    match (target.as_mut(), other) {
+        (Some(t), Some(o)) => apply(t, o),
+        (None, Some(o)) => *target = Some(o.clone()),
+        _ => {}
+    }
+}
+
+impl ParagraphProperties {
+    /// 用另一组属性覆盖当前属性，`other` 中已设置的字段优先；
+    /// 间距、缩进、边框和制表位逐项合并。段落标记的运行属性和格式修订不参与合并
+    ///
+    /// # 参数
+    /// * `other` - 覆盖的属性
+    pub fn apply(&mut self, other: &ParagraphProperties) {
+        overlay!(self, other; style, keep_next, keep_lines, page_break_before, widow_control,
+            num_pr, suppress_line_numbers, shading, suppress_auto_hyphens, kinsoku, word_wrap,
+            overflow_punct, top_line_punct, auto_space_de, auto_space_dn, bidi, adjust_right_ind,
+            snap_to_grid, contextual_spacing, mirror_indents, justification, text_direction,
+            text_alignment, outline_level, conditional_format);
+        apply_nested(&mut self.borders, &other.borders, ParagraphBorders::apply);
+        apply_nested(&mut self.tabs, &other.tabs, Tabs::apply);
+        apply_nested(&mut self.spacing, &other.spacing, ParagraphSpacing::apply);
+        apply_nested(&mut self.indentation, &other.indentation, Indentation::apply);
+    }
+}
+
+impl RunFonts {
+    /// 逐个字体槽位覆盖，未指定的槽位保持不变；显式字体会取代同一槽位继承的主题字体
+    ///
+    /// # 参数
+    /// * `other` - 覆盖的字体
+    pub fn apply(&mut self, other: &RunFonts) {
+        if other.ascii.is_some() && other.ascii_theme.is_none() {
+            self.ascii_theme = None;
+        }
+        if other.h_ansi.is_some() && other.h_ansi_theme.is_none() {
+            self.h_ansi_theme = None;
+        }
+        if other.east_asia.is_some() && other.east_asia_theme.is_none() {
+            self.east_asia_theme = None;
+        }
+        if other.cs.is_some() && other.cs_theme.is_none() {
+            self.cs_theme = None;
+        }
+        overlay!(self, other; hint, ascii, h_ansi, east_asia, cs, ascii_theme, h_ansi_theme,
+            east_asia_theme, cs_theme);
+    }
+}
+
+impl RunProperties {
+    /// 用另一组属性覆盖当前属性，`other` 中已设置的字段优先，字体逐个槽位合并。
+    /// 修订标记不参与合并
+    ///
+    /// # 参数
+    /// * `other` - 覆盖的属性
+    pub fn apply(&mut self, other: &RunProperties) {
+        overlay!(self, other; style, bold, bold_cs, italic, italic_cs, caps, small_caps, strike,
+            double_strike, outline, shadow, emboss, imprint, no_proof, hidden, web_hidden, color,
+            spacing, scale, kern, position, size, size_cs, highlight, underline, shading,
+            vertical_align, rtl, complex_script, language);
+        apply_nested(&mut self.fonts, &other.fonts, RunFonts::apply);
+    }
+}
+
+/// 为属性类型实现 `from_element`：逐个子元素反序列化，
+/// 未建模或取值无法识别的子元素保存在 `unknown` 中，不会使整组属性丢失
+macro_rules! lenient_properties {
+    ($($ty:ty => $tag:literal),* $(,)?) => {
+        $(impl $ty {
+            #[doc = concat!("从 `", $tag, "` 元素解析属性，无法识别的子元素保存在 `unknown` 中")]
+            ///
+            /// # 参数
+            /// * `element` - 属性元素
+            pub fn from_element(element: &XmlElement) -> crate::error::Result<Self> {
+                let (mut properties, unknown): (Self, _) = element.deserialize_lenient()?;
+                properties.unknown = unknown;
+                Ok(properties)
+            }
+        })*
+    };
+}
+
+lenient_properties!(
+    RunProperties => "w:rPr",
+    SectionProperties => "w:sectPr",
+    TableProperties => "w:tblPr",
+    TableRowProperties => "w:trPr",
+    TableCellProperties => "w:tcPr",
+);
+
+impl ParagraphProperties {
+    /// 从 `w:pPr` 元素解析段落属性，无法识别的子元素保存在 `unknown` 中；
+    /// 段落标记的运行属性和分节符同样逐个子元素解析
+    ///
+    /// # 参数
+    /// * `element` - 段落属性元素
+    pub fn from_element(element: &XmlElement) -> crate::error::Result<Self> {
+        let (mut properties, unknown): (Self, _) = element.deserialize_lenient()?;
+        properties.unknown = unknown;
+        if properties.run_properties.is_some() {
+            if let Some(r_pr) = element.child("w:rPr") {
+                properties.run_properties = Some(RunProperties::from_element(r_pr)?);
+            }
+        }
+        if properties.section_properties.is_some() {
+            if let Some(sect_pr) = element.child("w:sectPr") {
+                properties.section_properties = Some(SectionProperties::from_element(sect_pr)?);
+            }
+        }
+        Ok(properties)
+    }
+}
+
+/// 没有属性和子元素的标记元素，如 `<w:richText/>`
+/// Marker element without attributes or children
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct Empty {}
+
+/// 内容控件的锁定方式
+/// Content control locking (`w:lock`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SdtLock {
+    /// 不能删除内容控件，可以编辑内容
+    SdtLocked,
+    /// 不能编辑内容，可以删除内容控件
+    ContentLocked,
+    /// 不锁定
+    Unlocked,
+    /// 既不能删除内容控件，也不能编辑内容
+    SdtContentLocked,
+}
+
+/// 内容控件的类型，由内容控件属性中的类型元素决定，未指定时为格式文本
+/// Content control type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdtType {
+    /// 格式文本
+    RichText,
+    /// 纯文本
+    PlainText,
+    /// 复选框
+    Checkbox,
+    /// 组合框：可以选择列表项，也可以输入文本
+    ComboBox,
+    /// 下拉列表
+    DropDownList,
+    /// 日期选取器
+    Date,
+    /// 图片
+    Picture,
+    /// 文档部件库（如封面、目录）
+    DocPart,
+    /// 组
+    Group,
+    /// 重复节，内容为若干重复节项
+    RepeatingSection,
+    /// 重复节项
+    RepeatingSectionItem,
+    /// 公式
+    Equation,
+    /// 引文
+    Citation,
+    /// 书目
+    Bibliography,
+}
+
+/// 内容控件占位符引用的文档部件
+/// Content control placeholder (`w:placeholder`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct SdtPlaceholder {
+    #[serde(rename = "w:docPart", alias = "docPart")]
+    pub doc_part: Val<String>,
+}
+
+/// 内容控件与自定义 XML 部件中节点的绑定
+/// XML data binding (`w:dataBinding`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct DataBinding {
+    /// XPath 中使用的命名空间前缀，如 `xmlns:ns0='http://example.com/ns'`
+    #[serde(
+        rename = "@w:prefixMappings",
+        alias = "@prefixMappings",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub prefix_mappings: Option<String>,
+    /// 绑定节点的 XPath
+    #[serde(rename = "@w:xpath", alias = "@xpath")]
+    pub xpath: String,
+    /// 自定义 XML 部件的项ID
+    #[serde(
+        rename = "@w:storeItemID",
+        alias = "@storeItemID",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub store_item_id: Option<String>,
+}
+
+/// 下拉列表或组合框中的列表项
+/// List item (`w:listItem`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct SdtListItem {
+    /// 显示的文本，未指定时显示值
+    #[serde(
+        rename = "@w:displayText",
+        alias = "@displayText",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub display_text: Option<String>,
+    /// 值
+    #[serde(rename = "@w:value", alias = "@value")]
+    pub value: String,
+}
+
+impl SdtListItem {
+    /// 列表项显示的文本
+    pub fn text(&self) -> &str {
+        self.display_text.as_deref().unwrap_or(&self.value)
+    }
+}
+
+/// 下拉列表或组合框的列表项
+/// Drop-down list or combo box (`w:dropDownList`, `w:comboBox`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct SdtList {
+    /// 最后选择的值
+    #[serde(
+        rename = "@w:lastValue",
+        alias = "@lastValue",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_value: Option<String>,
+    #[serde(
+        rename = "w:listItem",
+        alias = "listItem",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub items: Vec<SdtListItem>,
+}
+
+/// 日期选取器的设置
+/// Date picker (`w:date`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct SdtDate {
+    /// 选择的日期，XML 日期时间格式，如 `2024-05-01T00:00:00Z`
+    #[serde(
+        rename = "@w:fullDate",
+        alias = "@fullDate",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub full_date: Option<String>,
+    /// 显示格式，如 `yyyy-MM-dd`、`yyyy'年'M'月'd'日'`
+    #[serde(
+        rename = "w:dateFormat",
+        alias = "dateFormat",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub format: Option<Val<String>>,
+    /// 显示日期使用的语言，如 `zh-CN`
+    #[serde(
+        rename = "w:lid",
+        alias = "lid",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub language: Option<Val<String>>,
+    /// 绑定到自定义 XML 时日期的存储格式，如 `dateTime`、`date`、`text`
+    #[serde(
+        rename = "w:storeMappedDataAs",
+        alias = "storeMappedDataAs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub store_mapped_data_as: Option<Val<String>>,
+    #[serde(
+        rename = "w:calendar",
+        alias = "calendar",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub calendar: Option<Val<String>>,
+}
+
+/// 纯文本内容控件的设置
+/// Plain text content control (`w:text`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct SdtText {
+    /// 是否允许换行
+    #[serde(
+        rename = "@w:multiLine",
+        alias = "@multiLine",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub multi_line: Option<String>,
+}
+
+/// 文档部件库内容控件的设置
+/// Document part gallery (`w:docPartObj`, `w:docPartList`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct SdtDocPart {
+    /// 部件库，如 `Cover Pages`、`Table of Contents`
+    #[serde(
+        rename = "w:docPartGallery",
+        alias = "docPartGallery",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub gallery: Option<Val<String>>,
+    #[serde(
+        rename = "w:docPartCategory",
+        alias = "docPartCategory",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub category: Option<Val<String>>,
+    #[serde(
+        rename = "w:docPartUnique",
+        alias = "docPartUnique",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub unique: Option<OnOff>,
+}
+
+/// 复选框的状态值，属性位于 `w14` 命名空间
+/// Checkbox state (`w14:checked`, `w14:checkedState`, `w14:uncheckedState`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct CheckboxState {
+    /// 选中状态为 `1` 或 `0`；符号为十六进制字符码，如 `2612`
+    #[serde(rename = "@w14:val", alias = "@val")]
+    pub val: String,
+    /// 符号使用的字体
+    #[serde(
+        rename = "@w14:font",
+        alias = "@font",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub font: Option<String>,
+}
+
+/// 复选框的设置
+/// Checkbox content control (`w14:checkbox`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct SdtCheckbox {
+    #[serde(
+        rename = "w14:checked",
+        alias = "checked",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checked: Option<CheckboxState>,
+    /// 选中时显示的符号，未指定时为 ☒
+    #[serde(
+        rename = "w14:checkedState",
+        alias = "checkedState",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checked_state: Option<CheckboxState>,
+    /// 未选中时显示的符号，未指定时为 ☐
+    #[serde(
+        rename = "w14:uncheckedState",
+        alias = "uncheckedState",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub unchecked_state: Option<CheckboxState>,
+}
+
+impl SdtCheckbox {
+    /// 是否选中
+    pub fn is_checked(&self) -> bool {
+        self.checked
+            .as_ref()
+            .is_some_and(|c| matches!(c.val.as_str(), "1" | "true" | "on"))
+    }
+
+    /// 当前状态显示的符号
+    pub fn symbol(&self) -> char {
+        let (state, default) = match self.is_checked() {
+            true => (&self.checked_state, '\u{2612}'),
+            false => (&self.unchecked_state, '\u{2610}'),
+        };
+        state
+            .as_ref()
+            .and_then(|s| u32::from_str_radix(&s.val, 16).ok())
+            .and_then(char::from_u32)
+            .unwrap_or(default)
+    }
+}
+
+/// `w15` 命名空间中只有 `w15:val` 属性的元素
+/// Element with a single `w15:val` attribute
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct Val15<T> {
+    #[serde(rename = "@w15:val", alias = "@val")]
+    pub val: T,
+}
+
+/// 重复节的设置
+/// Repeating section (`w15:repeatingSection`)
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct SdtRepeatingSection {
+    #[serde(
+        rename = "w15:sectionTitle",
+        alias = "sectionTitle",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub section_title: Option<Val15<String>>,
+    #[serde(
+        rename = "w15:doNotAllowInsertDeleteSection",
+        alias = "doNotAllowInsertDeleteSection",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub do_not_allow_insert_delete: Option<Val15<String>>,
+}
+
+/// 内容控件属性
+/// Structured document tag properties (`w:sdtPr`)
+///
+/// 字段顺序与 `CT_SdtPr` 一致；类型元素中只应设置一个，都未设置时为格式文本。
+/// 复选框和重复节使用 Word 2010、2013 引入的 `w14`、`w15` 扩展元素。
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename = "w:sdtPr")]
+pub struct SdtProperties {
+    /// 内容控件中文本的运行属性
+    #[serde(
+        rename = "w:rPr",
+        alias = "rPr",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub run_properties: Option<RunProperties>,
+    /// 显示的名称（标题）
+    #[serde(
+        rename = "w:alias",
+        alias = "alias",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub alias: Option<Val<String>>,
+    /// 供程序识别的标记
+    #[serde(
+        rename = "w:tag",
+        alias = "tag",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tag: Option<Val<String>>,
+    /// 文档中唯一的ID
+    #[serde(rename = "w:id", alias = "id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<Val<i32>>,
+    #[serde(
+        rename = "w:lock",
+        alias = "lock",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub lock: Option<Val<SdtLock>>,
+    #[serde(
+        rename = "w:placeholder",
+        alias = "placeholder",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub placeholder: Option<SdtPlaceholder>,
+    /// 编辑内容后删除内容控件，只保留内容
+    #[serde(
+        rename = "w:temporary",
+        alias = "temporary",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub temporary: Option<OnOff>,
+    /// 内容当前为占位符文本
+    #[serde(
+        rename = "w:showingPlcHdr",
+        alias = "showingPlcHdr",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub showing_placeholder: Option<OnOff>,
+    #[serde(
+        rename = "w:dataBinding",
+        alias = "dataBinding",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub data_binding: Option<DataBinding>,
+    #[serde(
+        rename = "w:equation",
+        alias = "equation",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub equation: Option<Empty>,
+    #[serde(
+        rename = "w:comboBox",
+        alias = "comboBox",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub combo_box: Option<SdtList>,
+    #[serde(
+        rename = "w:date",
+        alias = "date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub date: Option<SdtDate>,
+    #[serde(
+        rename = "w:docPartObj",
+        alias = "docPartObj",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub doc_part_obj: Option<SdtDocPart>,
+    #[serde(
+        rename = "w:docPartList",
+        alias = "docPartList",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub doc_part_list: Option<SdtDocPart>,
+    #[serde(
+        rename = "w:dropDownList",
+        alias = "dropDownList",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub drop_down_list: Option<SdtList>,
+    #[serde(
+        rename = "w:picture",
+        alias = "picture",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub picture: Option<Empty>,
+    #[serde(
+        rename = "w:richText",
+        alias = "richText",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub rich_text: Option<Empty>,
+    #[serde(
+        rename = "w:text",
+        alias = "text",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub text: Option<SdtText>,
+    #[serde(
+        rename = "w:citation",
+        alias = "citation",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub citation: Option<Empty>,
+    #[serde(
+        rename = "w:group",
+        alias = "group",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub group: Option<Empty>,
+    #[serde(
+        rename = "w:bibliography",
+        alias = "bibliography",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bibliography: Option<Empty>,
+    #[serde(
+        rename = "w14:checkbox",
+        alias = "checkbox",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub checkbox: Option<SdtCheckbox>,
+    #[serde(
+        rename = "w15:repeatingSection",
+        alias = "repeatingSection",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub repeating_section: Option<SdtRepeatingSection>,
+    #[serde(
+        rename = "w15:repeatingSectionItem",
+        alias = "repeatingSectionItem",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub repeating_section_item: Option<Empty>,
+}
+
+impl SdtProperties {
+    /// 创建指定类型和标记的内容控件属性，列表、日期等设置为空
+    ///
+    /// # 参数
+    /// * `sdt_type` - 类型
+    /// * `tag` - 标记
+    pub fn new(sdt_type: SdtType, tag: impl Into<String>) -> Self {
+        let mut properties = SdtProperties {
+            tag: Some(Val::new(tag.into())),
+            ..Default::default()
+        };
+        match sdt_type {
+            SdtType::RichText => properties.rich_text = Some(Empty {}),
+            SdtType::PlainText => properties.text = Some(SdtText::default()),
+            SdtType::Checkbox => {
+                properties.checkbox = Some(SdtCheckbox {
+                    checked: Some(CheckboxState {
+                        val: "0".to_string(),
+                        font: None,
+                    }),
+                    ..Default::default()
+                })
+            }
+            SdtType::ComboBox => properties.combo_box = Some(SdtList::default()),
+            SdtType::DropDownList => properties.drop_down_list = Some(SdtList::default()),
+            SdtType::Date => properties.date = Some(SdtDate::default()),
+            SdtType::Picture => properties.picture = Some(Empty {}),
+            SdtType::DocPart => properties.doc_part_obj = Some(SdtDocPart::default()),
+            SdtType::Group => properties.group = Some(Empty {}),
+            SdtType::RepeatingSection => {
+                properties.repeating_section = Some(SdtRepeatingSection::default())
+            }
+            SdtType::RepeatingSectionItem => properties.repeating_section_item = Some(Empty {}),
+            SdtType::Equation => properties.equation = Some(Empty {}),
+            SdtType::Citation => properties.citation = Some(Empty {}),
+            SdtType::Bibliography => properties.bibliography = Some(Empty {}),
+        }
+        properties
+    }
+
+    /// 内容控件的类型
+    pub fn sdt_type(&self) -> SdtType {
+        if self.text.is_some() {
+            SdtType::PlainText
+        } else if self.checkbox.is_some() {
+            SdtType::Checkbox
+        } else if self.combo_box.is_some() {
+            SdtType::ComboBox
+        } else if self.drop_down_list.is_some() {
+            SdtType::DropDownList
+        } else if self.date.is_some() {
+            SdtType::Date
+        } else if self.picture.is_some() {
+            SdtType::Picture
+        } else if self.doc_part_obj.is_some() || self.doc_part_list.is_some() {
+            SdtType::DocPart
+        } else if self.group.is_some() {
+            SdtType::Group
+        } else if self.repeating_section.is_some() {
+            SdtType::RepeatingSection
+        } else if self.repeating_section_item.is_some() {
+            SdtType::RepeatingSectionItem
+        } else if self.equation.is_some() {
+            SdtType::Equation
+        } else if self.citation.is_some() {
+            SdtType::Citation
+        } else if self.bibliography.is_some() {
+            SdtType::Bibliography
+        } else {
+            SdtType::RichText
+        }
+    }
+
+    /// 标记
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_ref().map(|t| t.val.as_str())
+    }
+
+    /// 显示的名称
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_ref().map(|a| a.val.as_str())
+    }
+
+    /// 内容控件的ID
+    pub fn id(&self) -> Option<i32> {
+        self.id.as_ref().map(|i| i.val)
+    }
+
+    /// 锁定方式，未指定时为不锁定
+    pub fn lock(&self) -> SdtLock {
+        self.lock.as_ref().map_or(SdtLock::Unlocked, |l| l.val)
+    }
+
+    /// 是否不能编辑内容
+    pub fn is_content_locked(&self) -> bool {
+        matches!(
+            self.lock(),
+            SdtLock::ContentLocked | SdtLock::SdtContentLocked
+        )
+    }
+
+    /// 下拉列表或组合框的列表
+    pub fn list(&self) -> Option<&SdtList> {
+        self.drop_down_list.as_ref().or(self.combo_box.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::de::from_str;
+    use quick_xml::se::to_string;
+
+    /// 解析后重新序列化，结果应与输入相同
+    fn round_trip<T: for<'de> Deserialize<'de> + Serialize>(xml: &str) -> T {
+        let value: T = from_str(xml).expect("valid xml");
+        assert_eq!(to_string(&value).expect("serializable"), xml);
+        value
+    }
+
+    #[test]
+    fn run_properties_round_trip() {
+        let xml = "<w:rPr><w:rStyle w:val=\"Emphasis\"/>\
+            <w:rFonts w:ascii=\"Arial\" w:eastAsia=\"SimSun\"/>\
+            <w:b/><w:i w:val=\"0\"/><w:caps/><w:strike/><w:vanish/>\
+            <w:color w:val=\"FF0000\" w:themeColor=\"accent1\"/>\
+            <w:spacing w:val=\"-20\"/><w:w w:val=\"90\"/><w:kern w:val=\"28\"/>\
+            <w:position w:val=\"6\"/><w:sz w:val=\"24\"/><w:szCs w:val=\"24\"/>\
+            <w:highlight w:val=\"yellow\"/><w:u w:val=\"double\" w:color=\"0000FF\"/>\
+            <w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"D9D9D9\"/>\
+            <w:vertAlign w:val=\"superscript\"/><w:lang w:val=\"en-US\" w:eastAsia=\"zh-CN\"/>\
+            </w:rPr>";
+        let properties: RunProperties = round_trip(xml);
+        assert_eq!(properties.bold, Some(OnOff::ON));
+        assert!(properties.italic.is_some_and(|i| !i.is_on()));
+        assert_eq!(properties.size.map(|s| s.val), Some(24));
+        assert_eq!(properties.spacing.map(|s| s.val), Some(-20));
+    }
+
+    #[test]
+    fn paragraph_properties_round_trip() {
+        let xml = "<w:pPr><w:pStyle w:val=\"Heading1\"/><w:keepNext/>\
+            <w:numPr><w:ilvl w:val=\"1\"/><w:numId w:val=\"3\"/></w:numPr>\
+            <w:pBdr><w:bottom w:val=\"single\" w:sz=\"4\" w:space=\"1\" w:color=\"auto\"/></w:pBdr>\
+            <w:tabs><w:tab w:val=\"right\" w:leader=\"dot\" w:pos=\"9350\"/></w:tabs>\
+            <w:spacing w:before=\"240\" w:after=\"120\" w:line=\"360\" w:lineRule=\"auto\"/>\
+            <w:ind w:left=\"720\" w:hanging=\"360\"/><w:contextualSpacing/>\
+            <w:jc w:val=\"center\"/><w:outlineLvl w:val=\"0\"/></w:pPr>";
+        let properties: ParagraphProperties = round_trip(xml);
+        assert_eq!(properties.outline_level.map(|o| o.val), Some(0));
+        let indentation = properties.indentation.unwrap();
+        assert_eq!((indentation.left, indentation.hanging), (Some(720), Some(360)));
+    }
+
+    #[test]
+    fn paragraph_formatting_change_keeps_old_properties() {
+        let xml = "<w:pPr><w:jc w:val=\"center\"/>\
+            <w:pPrChange w:id=\"1\" w:author=\"Ann\"><w:pPr><w:jc w:val=\"left\"/></w:pPr>\
+            </w:pPrChange></w:pPr>";
+        let properties: ParagraphProperties = round_trip(xml);
+        let change = properties.change.unwrap();
+        assert_eq!(change.author, "Ann");
+        assert!(change.properties.justification.is_some());
+    }
+
+    #[test]
+    fn unknown_children_are_kept() {
+        let xml = "<w:pPr><w:pStyle w:val=\"Quote\"/>\
+            <w:framePr w:w=\"2000\" w:wrap=\"around\"/><w:jc w:val=\"center\"/>\
+            <w:rPr><w:b/><w14:ligatures w14:val=\"standard\"/></w:rPr></w:pPr>";
+        let element = XmlElement::parse(xml).unwrap();
+        let properties = ParagraphProperties::from_element(&element).unwrap();
+        assert!(properties.justification.is_some());
+        assert_eq!(properties.unknown[0].name, "w:framePr");
+        let run = properties.run_properties.as_ref().unwrap();
+        assert_eq!(run.bold, Some(OnOff::ON));
+        assert_eq!(run.unknown[0].name, "w14:ligatures");
+
+        let saved = to_string(&properties).unwrap();
+        assert!(saved.contains("<w:framePr w:w=\"2000\" w:wrap=\"around\"/>"));
+        assert!(saved.contains("<w14:ligatures w14:val=\"standard\"/>"));
+        let reparsed = ParagraphProperties::from_element(&XmlElement::parse(&saved).unwrap());
+        assert_eq!(reparsed.unwrap(), properties);
+    }
+
+    #[test]
+    fn invalid_child_keeps_other_fields() {
+        let xml = "<w:pPr><w:pStyle w:val=\"Quote\"/><w:jc w:val=\"unknownValue\"/>\
+            <w:outlineLvl w:val=\"2\"/></w:pPr>";
+        let element = XmlElement::parse(xml).unwrap();
+        let properties = ParagraphProperties::from_element(&element).unwrap();
+        assert_eq!(properties.outline_level.map(|o| o.val), Some(2));
+        assert!(properties.justification.is_none());
+        assert_eq!(properties.unknown[0].attribute("w:val"), Some("unknownValue"));
+    }
 }
@@ -0,0 +1,646 @@
+//! 修订跟踪：列出、接受和拒绝文档中的修订
+//!
+//! 支持运行级的插入、删除和移动，段落标记的插入和删除，表格行的插入和删除，以及段落和
+//! 运行的格式修订。单元格的插入、删除和合并修订（`w:cellIns`、`w:cellDel`、`w:cellMerge`）
+//! 不列出也不处理，原样保留。
+
+use std::ops::Range;
+
+use super::document::Document;
+use super::elements::{
+    BodyContent, Paragraph, ParagraphContent, RevisionKind, Run, RunContent, RunRevision, Table,
+    TableContent, TableItem, TableRow,
+};
+use super::properties::{RevisionMark, RunProperties, TableRowProperties};
+
+/// 修订描述结构体，表示文档中的一处修订
+#[derive(Debug, Clone)]
+pub struct Revision {
+    /// 修订类型
+    pub kind: RevisionKind,
+    /// 修订ID
+    pub id: i32,
+    /// 修订作者
+    pub author: String,
+    /// 修订日期
+    pub date: Option<String>,
+    /// 修订所在的正文块索引（`Body::content` 中的下标）
+    pub block_index: usize,
+}
+
+/// 修订筛选条件，所有条件同时满足时修订才会被处理
+#[derive(Debug, Clone, Default)]
+pub struct RevisionFilter {
+    /// 仅处理该作者的修订
+    pub author: Option<String>,
+    /// 仅处理位于这些正文块中的修订
+    pub blocks: Option<Range<usize>>,
+}
+
+impl RevisionFilter {
+    /// 匹配所有修订的筛选条件
+    pub fn all() -> Self {
+        RevisionFilter::default()
+    }
+
+    /// 按作者筛选修订
+    ///
+    /// # 参数
+    /// * `author` - 修订作者
+    pub fn by_author(author: impl Into<String>) -> Self {
+        RevisionFilter {
+            author: Some(author.into()),
+            blocks: None,
+        }
+    }
+
+    /// 按正文块范围筛选修订
+    ///
+    /// # 参数
+    /// * `blocks` - 正文块索引范围
+    pub fn in_blocks(blocks: Range<usize>) -> Self {
+        RevisionFilter {
+            author: None,
+            blocks: Some(blocks),
+        }
+    }
+
+    /// 判断一处修订是否满足筛选条件
+    fn matches(&self, author: &str, block_index: usize) -> bool {
+        self.author.as_deref().is_none_or(|a| a == author)
+            && self.blocks.as_ref().is_none_or(|r| r.contains(&block_index))
+    }
+}
+
+impl Document {
+    /// 列出文档中的所有修订
+    pub fn revisions(&self) -> Vec<Revision> {
+        let mut revisions = Vec::new();
+        for (index, block) in self.body.content.iter().enumerate() {
+            collect_block(block, index, &mut revisions);
+        }
+        revisions
+    }
+
+    /// 接受文档中的所有修订
+    pub fn accept_all_revisions(&mut self) {
+        self.accept_revisions(&RevisionFilter::all());
+    }
+
+    /// 拒绝文档中的所有修订
+    pub fn reject_all_revisions(&mut self) {
+        self.reject_revisions(&RevisionFilter::all());
+    }
+
+    /// 接受满足筛选条件的修订
+    ///
+    /// # 参数
+    /// * `filter` - 修订筛选条件
+    pub fn accept_revisions(&mut self, filter: &RevisionFilter) {
+        self.resolve_revisions(filter, true);
+    }
+
+    /// 拒绝满足筛选条件的修订
+    ///
+    /// # 参数
+    /// * `filter` - 修订筛选条件
+    pub fn reject_revisions(&mut self, filter: &RevisionFilter) {
+        self.resolve_revisions(filter, false);
+    }
+
+    fn resolve_revisions(&mut self, filter: &RevisionFilter, accept: bool) {
+        let blocks = std::mem::take(&mut self.body.content);
+        let mut merge_next = false;
+        for (index, mut block) in blocks.into_iter().enumerate() {
+            let resolver = Resolver {
+                filter,
+                accept,
+                block_index: index,
+            };
+            let mark_removed = resolver.block(&mut block);
+            if is_empty_table(&block) {
+                continue;
+            }
+            push_block(&mut self.body.content, block, merge_next);
+            merge_next = mark_removed;
+        }
+    }
+}
+
+/// 追加一个正文块；前一段落的段落标记被移除时，将本段落合并到前一段落
+fn push_block(blocks: &mut Vec<BodyContent>, mut block: BodyContent, merge: bool) {
+    if merge {
+        if let (Some(BodyContent::Paragraph(previous)), BodyContent::Paragraph(next)) =
+            (blocks.last_mut(), &mut block)
+        {
+            // 合并后的段落使用后一段落标记的属性
+            previous.content.append(&mut next.content);
+            previous.properties = next.properties.take();
+            return;
+        }
+    }
+    blocks.push(block);
+}
+
+/// 所有行都被删除的表格
+fn is_empty_table(block: &BodyContent) -> bool {
+    matches!(block, BodyContent::Table(table) if table.content.is_empty())
+}
+
+/// 修订处理器，在一个正文块内接受或拒绝修订
+struct Resolver<'a> {
+    filter: &'a RevisionFilter,
+    accept: bool,
+    block_index: usize,
+}
+
+impl Resolver<'_> {
+    fn matches(&self, author: &str) -> bool {
+        self.filter.matches(author, self.block_index)
+    }
+
+    /// 处理一个正文块，返回段落标记是否被移除
+    fn block(&self, block: &mut BodyContent) -> bool {
+        match block {
+            BodyContent::Paragraph(p) => self.paragraph(p),
+            BodyContent::Table(t) => {
+                self.table(t);
+                false
+            }
+            BodyContent::Sdt(sdt) => {
+                self.blocks(&mut sdt.content);
+                false
+            }
+        }
+    }
+
+    /// 处理单元格或内容控件中的正文块
+    fn blocks(&self, content: &mut Vec<BodyContent>) {
+        let blocks = std::mem::take(content);
+        let mut merge_next = false;
+        for mut block in blocks {
+            let mark_removed = self.block(&mut block);
+            if is_empty_table(&block) {
+                continue;
+            }
+            push_block(content, block, merge_next);
+            merge_next = mark_removed;
+        }
+    }
+
+    fn table(&self, table: &mut Table) {
+        self.rows(&mut table.content);
+    }
+
+    /// 处理表格行，包括行级内容控件中的行；接受删除或拒绝插入的行被移除
+    fn rows(&self, content: &mut Vec<TableContent>) {
+        for item in std::mem::take(content) {
+            match item {
+                TableItem::Item(mut row) => {
+                    if self.row(&mut row) {
+                        content.push(TableItem::Item(row));
+                    }
+                }
+                TableItem::Sdt(mut sdt) => {
+                    self.rows(&mut sdt.content);
+                    if !sdt.content.is_empty() {
+                        content.push(TableItem::Sdt(sdt));
+                    }
+                }
+            }
+        }
+    }
+
+    /// 处理表格行中的修订，返回该行是否保留
+    fn row(&self, row: &mut TableRow) -> bool {
+        if let Some(props) = row.properties.as_mut() {
+            if props.inserted.take_if(|m| self.matches(&m.author)).is_some() && !self.accept {
+                return false;
+            }
+            if props.deleted.take_if(|m| self.matches(&m.author)).is_some() && self.accept {
+                return false;
+            }
+            if *props == TableRowProperties::default() {
+                row.properties = None;
+            }
+        }
+        for cell in row.cells_mut() {
+            self.blocks(&mut cell.content);
+        }
+        true
+    }
+
+    /// 处理段落中的修订，返回段落标记是否被移除
+    fn paragraph(&self, paragraph: &mut Paragraph) -> bool {
+        let mut mark_removed = false;
+        if let Some(props) = paragraph.properties.as_mut() {
+            // 段落格式修订，段落标记的运行属性不受影响
+            if let Some(change) = props.change.take_if(|c| self.matches(&c.author)) {
+                if !self.accept {
+                    let mark = props.run_properties.take();
+                    *props = *change.properties;
+                    props.run_properties = mark;
+                }
+            }
+
+            // 段落标记的插入和删除
+            if let Some(mark) = props.run_properties.as_mut() {
+                self.run_properties(mark);
+                if mark.inserted.as_ref().is_some_and(|m| self.matches(&m.author)) {
+                    mark.inserted = None;
+                    mark_removed |= !self.accept;
+                }
+                if mark.deleted.as_ref().is_some_and(|m| self.matches(&m.author)) {
+                    mark.deleted = None;
+                    mark_removed |= self.accept;
+                }
+            }
+            if props.run_properties.as_ref() == Some(&RunProperties::default()) {
+                props.run_properties = None;
+            }
+        }
+
+        self.inline(&mut paragraph.content);
+        mark_removed
+    }
+
+    /// 处理段落内容中的运行级修订
+    fn inline(&self, content: &mut Vec<ParagraphContent>) {
+        for item in std::mem::take(content) {
+            match item {
+                ParagraphContent::Run(mut run) => {
+                    self.run(&mut run);
+                    content.push(ParagraphContent::Run(run));
+                }
+                ParagraphContent::Hyperlink(mut hyperlink) => {
+                    hyperlink.runs.iter_mut().for_each(|r| self.run(r));
+                    content.push(ParagraphContent::Hyperlink(hyperlink));
+                }
+                ParagraphContent::SimpleField(mut field) => {
+                    field.runs.iter_mut().for_each(|r| self.run(r));
+                    content.push(ParagraphContent::SimpleField(field));
+                }
+                ParagraphContent::Sdt(mut sdt) => {
+                    self.inline(&mut sdt.content);
+                    content.push(ParagraphContent::Sdt(sdt));
+                }
+                ParagraphContent::Insertion(revision) => {
+                    self.revision(revision, true, ParagraphContent::Insertion, content)
+                }
+                ParagraphContent::MoveTo(revision) => {
+                    self.revision(revision, true, ParagraphContent::MoveTo, content)
+                }
+                ParagraphContent::Deletion(revision) => {
+                    self.revision(revision, false, ParagraphContent::Deletion, content)
+                }
+                ParagraphContent::MoveFrom(revision) => {
+                    self.revision(revision, false, ParagraphContent::MoveFrom, content)
+                }
+                bookmark @ (ParagraphContent::BookmarkStart(_)
+                | ParagraphContent::BookmarkEnd(_)) => content.push(bookmark),
+            }
+        }
+    }
+
+    /// 处理插入（`inserted` 为真）或删除类的运行级修订
+    fn revision(
+        &self,
+        mut revision: RunRevision,
+        inserted: bool,
+        wrap: fn(RunRevision) -> ParagraphContent,
+        content: &mut Vec<ParagraphContent>,
+    ) {
+        revision.runs.iter_mut().for_each(|r| self.run(r));
+
+        if !self.matches(&revision.author) {
+            content.push(wrap(revision));
+            return;
+        }
+
+        // 接受插入或拒绝删除时保留文本，否则丢弃
+        if inserted == self.accept {
+            for mut run in revision.runs {
+                restore_deleted_text(&mut run);
+                content.push(ParagraphContent::Run(run));
+            }
+        }
+    }
+
+    fn run(&self, run: &mut Run) {
+        if let Some(props) = run.properties.as_mut() {
+            self.run_properties(props);
+        }
+    }
+
+    fn run_properties(&self, props: &mut RunProperties) {
+        if let Some(change) = props.change.take_if(|c| self.matches(&c.author)) {
+            if !self.accept {
+                // 段落标记的插入/删除标记不属于格式修订
+                let (inserted, deleted) = (props.inserted.take(), props.deleted.take());
+                *props = *change.properties;
+                props.inserted = inserted;
+                props.deleted = deleted;
+            }
+        }
+    }
+}
+
+/// 将被删除的文本和域代码恢复为普通文本和域代码
+fn restore_deleted_text(run: &mut Run) {
+    for content in &mut run.content {
+        match content {
+            RunContent::DeletedText(text) => *content = RunContent::Text(std::mem::take(text)),
+            RunContent::DeletedInstrText(text) => {
+                *content = RunContent::InstrText(std::mem::take(text))
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_block(block: &BodyContent, block_index: usize, out: &mut Vec<Revision>) {
+    match block {
+        BodyContent::Paragraph(p) => collect_paragraph(p, block_index, out),
+        BodyContent::Table(t) => {
+            for row in t.rows() {
+                if let Some(properties) = &row.properties {
+                    collect_marks(&properties.inserted, &properties.deleted, block_index, out);
+                }
+                for cell in row.cells() {
+                    for block in &cell.content {
+                        collect_block(block, block_index, out);
+                    }
+                }
+            }
+        }
+        BodyContent::Sdt(sdt) => {
+            for block in &sdt.content {
+                collect_block(block, block_index, out);
+            }
+        }
+    }
+}
+
+fn collect_paragraph(paragraph: &Paragraph, block_index: usize, out: &mut Vec<Revision>) {
+    if let Some(change) = paragraph.properties.as_ref().and_then(|p| p.change.as_ref()) {
+        out.push(Revision {
+            kind: RevisionKind::ParagraphFormatting,
+            id: change.id,
+            author: change.author.clone(),
+            date: change.date.clone(),
+            block_index,
+        });
+    }
+
+    // 段落标记的插入和删除
+    if let Some(mark) = paragraph
+        .properties
+        .as_ref()
+        .and_then(|p| p.run_properties.as_ref())
+    {
+        collect_marks(&mark.inserted, &mark.deleted, block_index, out);
+    }
+
+    collect_inline(&paragraph.content, block_index, out);
+}
+
+/// 收集段落标记或表格行的插入和删除标记
+fn collect_marks(
+    inserted: &Option<RevisionMark>,
+    deleted: &Option<RevisionMark>,
+    block_index: usize,
+    out: &mut Vec<Revision>,
+) {
+    let marks = [
+        (RevisionKind::Insertion, inserted),
+        (RevisionKind::Deletion, deleted),
+    ];
+    for (kind, m) in marks {
+        if let Some(m) = m {
+            out.push(Revision {
+                kind,
+                id: m.id,
+                author: m.author.clone(),
+                date: m.date.clone(),
+                block_index,
+            });
+        }
+    }
+}
+
+fn collect_inline(content: &[ParagraphContent], block_index: usize, out: &mut Vec<Revision>) {
+    for item in content {
+        let (kind, revision) = match item {
+            ParagraphContent::Run(run) => {
+                collect_runs(std::slice::from_ref(run), block_index, out);
+                continue;
+            }
+            ParagraphContent::Hyperlink(hyperlink) => {
+                collect_runs(&hyperlink.runs, block_index, out);
+                continue;
+            }
+            ParagraphContent::SimpleField(field) => {
+                collect_runs(&field.runs, block_index, out);
+                continue;
+            }
+            ParagraphContent::Sdt(sdt) => {
+                collect_inline(&sdt.content, block_index, out);
+                continue;
+            }
+            ParagraphContent::BookmarkStart(_) | ParagraphContent::BookmarkEnd(_) => continue,
+            ParagraphContent::Insertion(r) => (RevisionKind::Insertion, r),
+            ParagraphContent::Deletion(r) => (RevisionKind::Deletion, r),
+            ParagraphContent::MoveFrom(r) => (RevisionKind::MoveFrom, r),
+            ParagraphContent::MoveTo(r) => (RevisionKind::MoveTo, r),
+        };
+        out.push(Revision {
+            kind,
+            id: revision.id,
+            author: revision.author.clone(),
+            date: revision.date.clone(),
+            block_index,
+        });
+        collect_runs(&revision.runs, block_index, out);
+    }
+}
+
+/// 收集文本运行的格式修订
+fn collect_runs(runs: &[Run], block_index: usize, out: &mut Vec<Revision>) {
+    for change in runs
+        .iter()
+        .filter_map(|r| r.properties.as_ref().and_then(|p| p.change.as_ref()))
+    {
+        out.push(Revision {
+            kind: RevisionKind::RunFormatting,
+            id: change.id,
+            author: change.author.clone(),
+            date: change.date.clone(),
+            block_index,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::test_utils::document;
+
+    const EDITED: &str = "<w:p><w:r><w:t>a</w:t></w:r>\
+        <w:ins w:id=\"1\" w:author=\"Ann\"><w:r><w:t>b</w:t></w:r></w:ins>\
+        <w:del w:id=\"2\" w:author=\"Bob\"><w:r><w:delText>c</w:delText></w:r></w:del></w:p>";
+
+    /// 正文中各段落文本运行的文本
+    fn texts(document: &Document) -> Vec<String> {
+        let paragraphs = document.body.content.iter().filter_map(|block| match block {
+            BodyContent::Paragraph(p) => Some(p),
+            _ => None,
+        });
+        paragraphs
+            .map(|p| {
+                let runs = p.content.iter().filter_map(|item| match item {
+                    ParagraphContent::Run(run) => Some(run),
+                    _ => None,
+                });
+                let texts = runs.flat_map(|run| &run.content).filter_map(|c| match c {
+                    RunContent::Text(text) => Some(text.as_str()),
+                    _ => None,
+                });
+                texts.collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lists_run_revisions() {
+        let revisions = document(EDITED).revisions();
+        let found: Vec<_> = revisions
+            .iter()
+            .map(|r| (r.kind, r.id, r.author.as_str(), r.block_index))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (RevisionKind::Insertion, 1, "Ann", 0),
+                (RevisionKind::Deletion, 2, "Bob", 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn accept_and_reject_run_revisions() {
+        let mut accepted = document(EDITED);
+        accepted.accept_all_revisions();
+        assert_eq!(texts(&accepted), ["ab"]);
+        assert!(accepted.revisions().is_empty());
+
+        let mut rejected = document(EDITED);
+        rejected.reject_all_revisions();
+        assert_eq!(texts(&rejected), ["ac"]);
+        assert!(rejected.revisions().is_empty());
+    }
+
+    #[test]
+    fn filter_by_author_keeps_other_revisions() {
+        let mut doc = document(EDITED);
+        doc.accept_revisions(&RevisionFilter::by_author("Ann"));
+        assert_eq!(texts(&doc), ["ab"]);
+        let remaining: Vec<_> = doc.revisions().iter().map(|r| r.author.clone()).collect();
+        assert_eq!(remaining, ["Bob"]);
+    }
+
+    #[test]
+    fn accepting_deleted_paragraph_mark_merges_paragraphs() {
+        let xml = "<w:p><w:pPr><w:rPr><w:del w:id=\"1\" w:author=\"Ann\"/></w:rPr></w:pPr>\
+            <w:r><w:t>one</w:t></w:r></w:p>\
+            <w:p><w:pPr><w:jc w:val=\"center\"/></w:pPr><w:r><w:t>two</w:t></w:r></w:p>";
+
+        let mut accepted = document(xml);
+        accepted.accept_all_revisions();
+        assert_eq!(texts(&accepted), ["onetwo"]);
+        let BodyContent::Paragraph(merged) = &accepted.body.content[0] else {
+            panic!("expected paragraph");
+        };
+        let properties = merged.properties.as_ref().unwrap();
+        assert!(properties.justification.is_some());
+
+        let mut rejected = document(xml);
+        rejected.reject_all_revisions();
+        assert_eq!(texts(&rejected), ["one", "two"]);
+    }
+
+    #[test]
+    fn rejecting_inserted_paragraph_mark_merges_paragraphs() {
+        let xml = "<w:p><w:pPr><w:rPr><w:ins w:id=\"1\" w:author=\"Ann\"/></w:rPr></w:pPr>\
+            <w:r><w:t>one</w:t></w:r></w:p><w:p><w:r><w:t>two</w:t></w:r></w:p>";
+
+        let mut rejected = document(xml);
+        rejected.reject_all_revisions();
+        assert_eq!(texts(&rejected), ["onetwo"]);
+
+        let mut accepted = document(xml);
+        accepted.accept_all_revisions();
+        assert_eq!(texts(&accepted), ["one", "two"]);
+    }
+
+    #[test]
+    fn rejecting_formatting_change_restores_old_properties() {
+        let xml = "<w:p><w:r><w:rPr><w:b/>\
+            <w:rPrChange w:id=\"1\" w:author=\"Ann\"><w:rPr><w:i/></w:rPr></w:rPrChange>\
+            </w:rPr><w:t>x</w:t></w:r></w:p>";
+        let mut doc = document(xml);
+        assert_eq!(doc.revisions()[0].kind, RevisionKind::RunFormatting);
+        doc.reject_all_revisions();
+
+        let BodyContent::Paragraph(paragraph) = &doc.body.content[0] else {
+            panic!("expected paragraph");
+        };
+        let ParagraphContent::Run(run) = &paragraph.content[0] else {
+            panic!("expected run");
+        };
+        let properties = run.properties.as_ref().unwrap();
+        assert!(properties.bold.is_none());
+        assert!(properties.italic.is_some());
+        assert!(properties.change.is_none());
+    }
+
+    #[test]
+    fn inserted_and_deleted_rows_are_resolved() {
+        let row = |mark: &str, text: &str| {
+            format!(
+                "<w:tr><w:trPr>{mark}</w:trPr><w:tc><w:p><w:r><w:t>{text}</w:t></w:r></w:p>\
+                 </w:tc></w:tr>"
+            )
+        };
+        let xml = format!(
+            "<w:tbl>{}{}{}</w:tbl><w:tbl>{}</w:tbl>",
+            row("", "kept"),
+            row("<w:ins w:id=\"1\" w:author=\"Ann\"/>", "new"),
+            row("<w:del w:id=\"2\" w:author=\"Ann\"/>", "old"),
+            row("<w:del w:id=\"3\" w:author=\"Ann\"/>", "gone")
+        );
+        let rows = |document: &Document| -> Vec<String> {
+            let tables = document.body.content.iter().filter_map(|block| match block {
+                BodyContent::Table(table) => Some(table),
+                _ => None,
+            });
+            tables
+                .flat_map(|table| table.rows())
+                .map(|row| {
+                    assert!(row.properties.is_none());
+                    row.cells().map(|cell| cell.text()).collect()
+                })
+                .collect()
+        };
+        assert_eq!(document(&xml).revisions().len(), 3);
+
+        let mut accepted = document(&xml);
+        accepted.accept_all_revisions();
+        assert_eq!(rows(&accepted), ["kept", "new"]);
+        assert!(accepted.revisions().is_empty());
+
+        let mut rejected = document(&xml);
+        rejected.reject_all_revisions();
+        assert_eq!(rows(&rejected), ["kept", "old", "gone"]);
+        assert!(rejected.revisions().is_empty());
+    }
+}
@@ -0,0 +1,511 @@
+//! 查找与替换：在段落文本中查找匹配，替换文本或修改匹配部分的格式
+//!
+//! 查找以段落为单位，在 [`Paragraph::text`] 给出的文本上进行，因此匹配可以跨越多个文本运行，
+//! 制表符和换行符分别对应 `\t` 和 `\n`。替换结果写入匹配起点所在的文本运行，沿用其格式；
+//! 修改格式时在匹配的边界处拆分文本运行，拆出的各部分保留原有的运行属性。
+
+use std::ops::Range;
+
+use regex::Captures;
+pub use regex::Regex;
+
+use super::elements::paragraph::content_runs_mut;
+use super::elements::{Paragraph, ParagraphContent, Run, RunContent};
+use super::properties::{HighlightColor, RunProperties, Val};
+use super::Docx;
+
+/// 一处匹配
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextMatch {
+    /// 段落序号，与 [`Docx::paragraphs`] 的顺序一致
+    pub paragraph: usize,
+    /// 匹配在段落文本中的字节范围
+    pub range: Range<usize>,
+    /// 匹配的文本
+    pub text: String,
+}
+
+impl Docx {
+    /// 查找文本，区分大小写
+    ///
+    /// # 参数
+    /// * `text` - 要查找的文本
+    pub fn find(&self, text: &str) -> Vec<TextMatch> {
+        self.find_regex(&literal(text))
+    }
+
+    /// 按正则表达式查找，空匹配被忽略
+    ///
+    /// # 参数
+    /// * `regex` - 正则表达式
+    pub fn find_regex(&self, regex: &Regex) -> Vec<TextMatch> {
+        self.paragraphs()
+            .iter()
+            .enumerate()
+            .flat_map(|(index, paragraph)| {
+                let text = paragraph.text();
+                regex
+                    .find_iter(&text)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| TextMatch {
+                        paragraph: index,
+                        range: m.range(),
+                        text: m.as_str().to_string(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// 替换文本，返回替换的次数
+    ///
+    /// # 参数
+    /// * `text` - 要查找的文本
+    /// * `replacement` - 替换文本，按原样插入
+    pub fn replace(&mut self, text: &str, replacement: &str) -> usize {
+        self.replace_with(&literal(text), |_| replacement.to_string())
+    }
+
+    /// 按正则表达式替换，返回替换的次数
+    ///
+    /// # 参数
+    /// * `regex` - 正则表达式
+    /// * `replacement` - 替换文本，可用 `$1`、`${name}` 引用捕获组
+    pub fn replace_regex(&mut self, regex: &Regex, replacement: &str) -> usize {
+        self.replace_with(regex, |captures| {
+            let mut text = String::new();
+            captures.expand(replacement, &mut text);
+            text
+        })
+    }
+
+    /// 按正则表达式替换，替换文本由闭包根据捕获组生成；返回替换的次数
+    ///
+    /// # 参数
+    /// * `regex` - 正则表达式
+    /// * `replacement` - 生成替换文本的闭包，文本中的 `\n` 转换为换行符
+    pub fn replace_with(
+        &mut self,
+        regex: &Regex,
+        mut replacement: impl FnMut(&Captures) -> String,
+    ) -> usize {
+        let mut count = 0;
+        for paragraph in self.paragraphs_mut() {
+            let text = paragraph.text();
+            let edits: Vec<_> = regex
+                .captures_iter(&text)
+                .filter_map(|captures| {
+                    let m = captures.get(0).filter(|m| !m.is_empty())?;
+                    Some((m.range(), replacement(&captures)))
+                })
+                .collect();
+            count += edits.len();
+            replace_ranges(paragraph, edits);
+        }
+        count
+    }
+
+    /// 修改匹配部分的运行属性，返回匹配的次数
+    ///
+    /// # 参数
+    /// * `regex` - 正则表达式
+    /// * `format` - 修改运行属性的闭包，对匹配范围内的每个文本运行调用
+    pub fn format_matches(
+        &mut self,
+        regex: &Regex,
+        mut format: impl FnMut(&mut RunProperties),
+    ) -> usize {
+        let mut count = 0;
+        for paragraph in self.paragraphs_mut() {
+            let text = paragraph.text();
+            let ranges: Vec<_> = regex
+                .find_iter(&text)
+                .filter(|m| !m.is_empty())
+                .map(|m| m.range())
+                .collect();
+            count += ranges.len();
+            for range in ranges {
+                format_range(paragraph, &range, &mut format);
+            }
+        }
+        count
+    }
+
+    /// 突出显示匹配的文本，返回匹配的次数
+    ///
+    /// # 参数
+    /// * `regex` - 正则表达式
+    /// * `color` - 突出显示颜色
+    pub fn highlight_matches(&mut self, regex: &Regex, color: HighlightColor) -> usize {
+        self.format_matches(regex, |properties| {
+            properties.highlight = Some(Val::new(color))
+        })
+    }
+}
+
+/// 按原样匹配文本的正则表达式
+fn literal(text: &str) -> Regex {
+    Regex::new(&regex::escape(text)).expect("escaped text is a valid regex")
+}
+
+/// 运行内容在段落文本中的长度，与 [`Run::text`] 一致
+fn content_len(content: &RunContent) -> usize {
+    match content {
+        RunContent::Text(text) => text.len(),
+        RunContent::Break | RunContent::PageBreak | RunContent::Tab => 1,
+        RunContent::DeletedText(_)
+        | RunContent::Drawing(_)
+        | RunContent::RawDrawing(_)
+        | RunContent::FieldChar(_)
+        | RunContent::InstrText(_)
+        | RunContent::DeletedInstrText(_)
+        | RunContent::FootnoteReference(_)
+        | RunContent::EndnoteReference(_) => 0,
+    }
+}
+
+/// 替换段落文本中的若干字节范围，范围互不重叠
+///
+/// 替换文本写入范围起点所在的文本运行；范围内的其他文本、制表符和换行符被删除，图片等不占
+/// 文本长度的内容保留。替换文本中的 `\n` 转换为换行符。
+///
+/// # 参数
+/// * `paragraph` - 段落
+/// * `edits` - 段落文本中的字节范围及其替换文本
+pub(crate) fn replace_ranges(paragraph: &mut Paragraph, mut edits: Vec<(Range<usize>, String)>) {
+    if edits.is_empty() {
+        return;
+    }
+    // 从后向前替换，前面的偏移不受影响
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    for (range, replacement) in edits {
+        let mut pending = Some(replacement);
+        let mut offset = 0;
+        for run in content_runs_mut(&mut paragraph.content, false) {
+            let len: usize = run.content.iter().map(content_len).sum();
+            if offset < range.end && range.start < offset + len {
+                replace_in_run(run, offset, &range, &mut pending);
+            }
+            offset += len;
+            if offset >= range.end {
+                break;
+            }
+        }
+    }
+
+    // 整理替换后的文本：删除空文本，将 `\n` 转换为换行符
+    for run in content_runs_mut(&mut paragraph.content, false) {
+        if !run
+            .content
+            .iter()
+            .any(|c| matches!(c, RunContent::Text(t) if t.is_empty() || t.contains('\n')))
+        {
+            continue;
+        }
+        let mut content = Vec::with_capacity(run.content.len());
+        for item in run.content.drain(..) {
+            let RunContent::Text(text) = item else {
+                content.push(item);
+                continue;
+            };
+            for (i, line) in text.split('\n').enumerate() {
+                if i > 0 {
+                    content.push(RunContent::Break);
+                }
+                if !line.is_empty() {
+                    content.push(RunContent::Text(line.to_string()));
+                }
+            }
+        }
+        run.content = content;
+    }
+
+    remove_empty_runs(&mut paragraph.content);
+}
+
+/// 删除被替换清空的文本运行，包括插入修订和运行级内容控件中的运行；
+/// 运行全部被删除的插入修订随之删除
+fn remove_empty_runs(content: &mut Vec<ParagraphContent>) {
+    let non_empty = |run: &Run| !run.content.is_empty();
+    content.retain_mut(|item| match item {
+        ParagraphContent::Run(run) => non_empty(run),
+        ParagraphContent::Hyperlink(hyperlink) => {
+            hyperlink.runs.retain(non_empty);
+            true
+        }
+        ParagraphContent::SimpleField(field) => {
+            field.runs.retain(non_empty);
+            true
+        }
+        ParagraphContent::Insertion(revision) | ParagraphContent::MoveTo(revision) => {
+            let had_runs = !revision.runs.is_empty();
+            revision.runs.retain(non_empty);
+            !had_runs || !revision.runs.is_empty()
+        }
+        ParagraphContent::Sdt(sdt) => {
+            remove_empty_runs(&mut sdt.content);
+            true
+        }
+        ParagraphContent::Deletion(_)
+        | ParagraphContent::MoveFrom(_)
+        | ParagraphContent::BookmarkStart(_)
+        | ParagraphContent::BookmarkEnd(_) => true,
+    });
+}
+
+/// 替换运行中与范围重叠的部分，替换文本在范围起点处插入一次
+fn replace_in_run(run: &mut Run, start: usize, range: &Range<usize>, pending: &mut Option<String>) {
+    let mut position = start;
+    let mut content = Vec::with_capacity(run.content.len());
+    for item in run.content.drain(..) {
+        let item_start = position;
+        position += content_len(&item);
+        let lo = range.start.max(item_start);
+        let hi = range.end.min(position);
+        if lo >= hi {
+            content.push(item);
+            continue;
+        }
+        let insert = if lo == range.start {
+            pending.take()
+        } else {
+            None
+        };
+        match item {
+            RunContent::Text(text) => {
+                let mut replaced = text[..lo - item_start].to_string();
+                replaced.push_str(insert.as_deref().unwrap_or_default());
+                replaced.push_str(&text[hi - item_start..]);
+                content.push(RunContent::Text(replaced));
+            }
+            // 制表符、换行符被替换
+            _ => content.extend(insert.map(RunContent::Text)),
+        }
+    }
+    run.content = content;
+}
+
+/// 修改段落文本中指定范围的运行属性，在范围边界处拆分文本运行
+///
+/// # 参数
+/// * `paragraph` - 段落
+/// * `range` - 段落文本中的字节范围
+/// * `format` - 修改运行属性的闭包
+pub(crate) fn format_range(
+    paragraph: &mut Paragraph,
+    range: &Range<usize>,
+    format: &mut dyn FnMut(&mut RunProperties),
+) {
+    let content = std::mem::take(&mut paragraph.content);
+    paragraph.content = format_content(content, &mut 0, range, format);
+}
+
+/// 拆分并修改段落内容中与范围重叠的运行，包括内容控件中的运行
+fn format_content(
+    content: Vec<ParagraphContent>,
+    offset: &mut usize,
+    range: &Range<usize>,
+    format: &mut dyn FnMut(&mut RunProperties),
+) -> Vec<ParagraphContent> {
+    let mut output = Vec::with_capacity(content.len());
+    for item in content {
+        match item {
+            ParagraphContent::Run(run) => {
+                let runs = split_runs(vec![run], offset, range, format);
+                output.extend(runs.into_iter().map(ParagraphContent::Run));
+            }
+            ParagraphContent::Hyperlink(mut hyperlink) => {
+                let runs = std::mem::take(&mut hyperlink.runs);
+                hyperlink.runs = split_runs(runs, offset, range, format);
+                output.push(ParagraphContent::Hyperlink(hyperlink));
+            }
+            ParagraphContent::SimpleField(mut field) => {
+                let runs = std::mem::take(&mut field.runs);
+                field.runs = split_runs(runs, offset, range, format);
+                output.push(ParagraphContent::SimpleField(field));
+            }
+            ParagraphContent::Insertion(mut revision) => {
+                let runs = std::mem::take(&mut revision.runs);
+                revision.runs = split_runs(runs, offset, range, format);
+                output.push(ParagraphContent::Insertion(revision));
+            }
+            ParagraphContent::MoveTo(mut revision) => {
+                let runs = std::mem::take(&mut revision.runs);
+                revision.runs = split_runs(runs, offset, range, format);
+                output.push(ParagraphContent::MoveTo(revision));
+            }
+            ParagraphContent::Sdt(mut sdt) => {
+                let inner = std::mem::take(&mut sdt.content);
+                sdt.content = format_content(inner, offset, range, format);
+                output.push(ParagraphContent::Sdt(sdt));
+            }
+            other => output.push(other),
+        }
+    }
+    output
+}
+
+fn split_runs(
+    runs: Vec<Run>,
+    offset: &mut usize,
+    range: &Range<usize>,
+    format: &mut dyn FnMut(&mut RunProperties),
+) -> Vec<Run> {
+    runs.into_iter()
+        .flat_map(|run| split_run(run, offset, range, format))
+        .collect()
+}
+
+/// 将运行拆分为范围前、范围内、范围后三部分，只修改范围内部分的属性
+fn split_run(
+    run: Run,
+    offset: &mut usize,
+    range: &Range<usize>,
+    format: &mut dyn FnMut(&mut RunProperties),
+) -> Vec<Run> {
+    let start = *offset;
+    let len: usize = run.content.iter().map(content_len).sum();
+    *offset += len;
+    if len == 0 || range.end <= start || start + len <= range.start {
+        return vec![run];
+    }
+    // 范围在运行内的相对位置
+    let a = range.start.saturating_sub(start);
+    let b = range.end.min(start + len) - start;
+
+    let mut parts: [Vec<RunContent>; 3] = Default::default();
+    let mut position = 0;
+    for item in run.content {
+        let item_len = content_len(&item);
+        match item {
+            RunContent::Text(text) => {
+                for (part, (lo, hi)) in [(0, a), (a, b), (b, len)].into_iter().enumerate() {
+                    let lo = lo.max(position);
+                    let hi = hi.min(position + item_len);
+                    if lo < hi {
+                        parts[part].push(RunContent::Text(
+                            text[lo - position..hi - position].to_string(),
+                        ));
+                    }
+                }
+            }
+            // 不占长度的内容归入其前面的部分
+            item if item_len == 0 => {
+                let part = usize::from(position > a) + usize::from(position >= b);
+                parts[part].push(item);
+            }
+            item => {
+                let part = usize::from(position >= a) + usize::from(position >= b);
+                parts[part].push(item);
+            }
+        }
+        position += item_len;
+    }
+
+    parts
+        .into_iter()
+        .enumerate()
+        .filter(|(_, content)| !content.is_empty())
+        .map(|(part, content)| {
+            let mut properties = run.properties.clone();
+            if part == 1 {
+                format(properties.get_or_insert_with(Default::default));
+            }
+            Run {
+                properties,
+                content,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::test_utils::{body_xml, docx};
+
+    #[test]
+    fn replacement_removes_emptied_runs_inside_revisions_and_content_controls() {
+        let mut doc = docx(
+            "<w:p><w:r><w:t>keep </w:t></w:r><w:ins w:id=\"1\" w:author=\"a\">\
+             <w:r><w:t>gone</w:t></w:r></w:ins><w:sdt><w:sdtPr/><w:sdtContent>\
+             <w:r><w:t>x</w:t></w:r><w:r><w:t>gone</w:t></w:r></w:sdtContent></w:sdt></w:p>",
+        );
+        assert_eq!(doc.replace("gone", ""), 2);
+
+        let xml = body_xml(&doc);
+        assert!(!xml.contains("<w:ins"));
+        assert!(!xml.contains("<w:r/>"));
+        assert!(xml.contains(
+            "<w:sdtContent><w:r><w:t xml:space=\"preserve\">x</w:t></w:r></w:sdtContent>"
+        ));
+        assert_eq!(doc.paragraphs()[0].text(), "keep x");
+    }
+
+    #[test]
+    fn replacement_spans_runs_and_uses_format_of_match_start() {
+        let mut doc = docx(
+            "<w:p><w:r><w:rPr><w:b/></w:rPr><w:t>Hel</w:t></w:r><w:r><w:t>lo wor</w:t></w:r>\
+             <w:r><w:t>ld!</w:t></w:r></w:p>",
+        );
+        assert_eq!(doc.replace("llo wor", "y\nW"), 1);
+
+        let xml = body_xml(&doc);
+        assert!(xml.contains(
+            "<w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">Hey</w:t><w:br/>\
+             <w:t xml:space=\"preserve\">W</w:t></w:r><w:r><w:t xml:space=\"preserve\">ld!</w:t></w:r>"
+        ));
+        assert_eq!(doc.paragraphs()[0].text(), "Hey\nWld!");
+    }
+
+    #[test]
+    fn regex_replacement_expands_captures_across_runs() {
+        let mut doc = docx("<w:p><w:r><w:t>2024-</w:t></w:r><w:r><w:t>05-01</w:t></w:r></w:p>");
+        let regex = Regex::new(r"(\d+)-(\d+)-(\d+)").unwrap();
+        assert_eq!(doc.replace_regex(&regex, "$3/$2/$1"), 1);
+        assert_eq!(doc.paragraphs()[0].text(), "01/05/2024");
+        assert_eq!(
+            doc.find("05/2"),
+            [TextMatch {
+                paragraph: 0,
+                range: 3..7,
+                text: "05/2".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn highlight_after_inline_content_control_uses_paragraph_offsets() {
+        let mut doc = docx(
+            "<w:p><w:sdt><w:sdtPr><w:tag w:val=\"t\"/></w:sdtPr><w:sdtContent>\
+             <w:r><w:t>AAAA</w:t></w:r></w:sdtContent></w:sdt>\
+             <w:r><w:t xml:space=\"preserve\"> hello world</w:t></w:r></w:p>",
+        );
+        let regex = Regex::new("world").unwrap();
+        assert_eq!(doc.highlight_matches(&regex, HighlightColor::Yellow), 1);
+
+        let xml = body_xml(&doc);
+        assert!(xml.contains(">AAAA</w:t></w:r></w:sdtContent>"));
+        assert!(xml.contains(
+            "<w:r><w:rPr><w:highlight w:val=\"yellow\"/></w:rPr>\
+             <w:t xml:space=\"preserve\">world</w:t></w:r>"
+        ));
+        assert!(xml.contains("> hello </w:t>"));
+    }
+
+    #[test]
+    fn highlight_inside_inline_content_control() {
+        let mut doc = docx(
+            "<w:p><w:r><w:t>ab</w:t></w:r><w:sdt><w:sdtPr/><w:sdtContent>\
+             <w:r><w:t>cd</w:t></w:r></w:sdtContent></w:sdt></w:p>",
+        );
+        let regex = Regex::new("bc").unwrap();
+        assert_eq!(doc.highlight_matches(&regex, HighlightColor::Green), 1);
+        let xml = body_xml(&doc);
+        assert_eq!(xml.matches("<w:highlight w:val=\"green\"/>").count(), 2);
+        assert!(xml.contains(
+            "<w:sdtContent><w:r><w:rPr><w:highlight w:val=\"green\"/></w:rPr>\
+             <w:t xml:space=\"preserve\">c</w:t></w:r>"
+        ));
+    }
+}
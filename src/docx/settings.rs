@@ -0,0 +1,301 @@
+//! 文档设置 (`word/settings.xml`)
+//!
+//! 设置部件包含上百种元素，这里只按原样保存每个子元素的XML，并提供按名称读写的接口；
+//! 新增元素时按 `CT_Settings` 规定的顺序插入，未知元素保持原位。
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::error::{OfficeError, Result};
+
+/// 默认的设置根元素
+const DEFAULT_ROOT: &str =
+    r#"<w:settings xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#;
+
+/// `CT_Settings` 中子元素的顺序
+const ELEMENT_ORDER: &[&str] = &[
+    "w:writeProtection",
+    "w:view",
+    "w:zoom",
+    "w:removePersonalInformation",
+    "w:removeDateAndTime",
+    "w:doNotDisplayPageBoundaries",
+    "w:displayBackgroundShape",
+    "w:printPostScriptOverText",
+    "w:printFractionalCharacterWidth",
+    "w:printFormsData",
+    "w:embedTrueTypeFonts",
+    "w:embedSystemFonts",
+    "w:saveSubsetFonts",
+    "w:saveFormsData",
+    "w:mirrorMargins",
+    "w:alignBordersAndEdges",
+    "w:bordersDoNotSurroundHeader",
+    "w:bordersDoNotSurroundFooter",
+    "w:gutterAtTop",
+    "w:hideSpellingErrors",
+    "w:hideGrammaticalErrors",
+    "w:activeWritingStyle",
+    "w:proofState",
+    "w:formsDesign",
+    "w:attachedTemplate",
+    "w:linkStyles",
+    "w:stylePaneFormatFilter",
+    "w:stylePaneSortMethod",
+    "w:documentType",
+    "w:mailMerge",
+    "w:revisionView",
+    "w:trackRevisions",
+    "w:doNotTrackMoves",
+    "w:doNotTrackFormatting",
+    "w:documentProtection",
+    "w:autoFormatOverride",
+    "w:styleLockTheme",
+    "w:styleLockQFSet",
+    "w:defaultTabStop",
+    "w:autoHyphenation",
+    "w:consecutiveHyphenLimit",
+    "w:hyphenationZone",
+    "w:doNotHyphenateCaps",
+    "w:showEnvelope",
+    "w:summaryLength",
+    "w:clickAndTypeStyle",
+    "w:defaultTableStyle",
+    "w:evenAndOddHeaders",
+    "w:bookFoldRevPrinting",
+    "w:bookFoldPrinting",
+    "w:bookFoldPrintingSheets",
+    "w:drawingGridHorizontalSpacing",
+    "w:drawingGridVerticalSpacing",
+    "w:displayHorizontalDrawingGridEvery",
+    "w:displayVerticalDrawingGridEvery",
+    "w:doNotUseMarginsForDrawingGridOrigin",
+    "w:drawingGridHorizontalOrigin",
+    "w:drawingGridVerticalOrigin",
+    "w:doNotShadeFormData",
+    "w:noPunctuationKerning",
+    "w:characterSpacingControl",
+    "w:printTwoOnOne",
+    "w:strictFirstAndLastChars",
+    "w:noLineBreaksAfter",
+    "w:noLineBreaksBefore",
+    "w:savePreviewPicture",
+    "w:doNotValidateAgainstSchema",
+    "w:saveInvalidXml",
+    "w:ignoreMixedContent",
+    "w:alwaysShowPlaceholderText",
+    "w:doNotDemarcateInvalidXml",
+    "w:saveXmlDataOnly",
+    "w:useXSLTWhenSaving",
+    "w:saveThroughXslt",
+    "w:showXMLTags",
+    "w:alwaysMergeEmptyNamespace",
+    "w:updateFields",
+    "w:hdrShapeDefaults",
+    "w:footnotePr",
+    "w:endnotePr",
+    "w:compat",
+    "w:docVars",
+    "w:rsids",
+    "m:mathPr",
+    "w:attachedSchema",
+    "w:themeFontLang",
+    "w:clrSchemeMapping",
+    "w:doNotIncludeSubdocsInStats",
+    "w:doNotAutoCompressPictures",
+    "w:forceUpgrade",
+    "w:captions",
+    "w:readModeInkLockDown",
+    "w:smartTagType",
+    "sl:schemaLibrary",
+    "w:shapeDefaults",
+    "w:doNotEmbedSmartTags",
+    "w:decimalSymbol",
+    "w:listSeparator",
+];
+
+/// 设置中的一个子元素
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsElement {
+    /// 元素的限定名，如 `w:updateFields`
+    pub name: String,
+    /// 元素的完整XML
+    pub xml: String,
+}
+
+/// 文档设置
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// 根元素的起始标签，保留命名空间声明
+    root: String,
+    /// 按文档顺序排列的子元素
+    pub elements: Vec<SettingsElement>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            root: DEFAULT_ROOT.to_string(),
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl Settings {
+    /// 从XML字符串解析设置
+    ///
+    /// # 参数
+    /// * `xml_content` - `word/settings.xml` 的内容
+    pub fn from_xml(xml_content: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(xml_content);
+        let mut settings = Settings::default();
+        let mut in_root = false;
+        loop {
+            let start = reader.buffer_position() as usize;
+            match reader.read_event()? {
+                Event::Start(_) if !in_root => {
+                    let end = reader.buffer_position() as usize;
+                    settings.root = xml_content[start..end].to_string();
+                    in_root = true;
+                }
+                Event::Start(e) => {
+                    let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                    reader.read_to_end(e.name())?;
+                    let end = reader.buffer_position() as usize;
+                    settings.elements.push(SettingsElement {
+                        name,
+                        xml: xml_content[start..end].to_string(),
+                    });
+                }
+                Event::Empty(e) if in_root => {
+                    let end = reader.buffer_position() as usize;
+                    settings.elements.push(SettingsElement {
+                        name: String::from_utf8_lossy(e.name().as_ref()).into_owned(),
+                        xml: xml_content[start..end].to_string(),
+                    });
+                }
+                Event::End(_) | Event::Eof => break,
+                _ => {}
+            }
+        }
+        if !in_root {
+            return Err(OfficeError::InvalidFormat(
+                "settings root element not found".to_string(),
+            ));
+        }
+        Ok(settings)
+    }
+
+    /// 序列化为 `word/settings.xml` 的内容
+    pub fn to_xml(&self) -> String {
+        let root_name = self
+            .root
+            .trim_start_matches('<')
+            .split(|c: char| c.is_whitespace() || c == '>')
+            .next()
+            .unwrap_or("w:settings");
+        let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#);
+        xml.push('\n');
+        xml.push_str(&self.root);
+        for element in &self.elements {
+            xml.push_str(&element.xml);
+        }
+        xml.push_str(&format!("</{root_name}>"));
+        xml
+    }
+
+    /// 获取指定名称的子元素的XML
+    ///
+    /// # 参数
+    /// * `name` - 元素的限定名，如 `w:evenAndOddHeaders`
+    pub fn element(&self, name: &str) -> Option<&str> {
+        self.elements
+            .iter()
+            .find(|e| e.name == name)
+            .map(|e| e.xml.as_str())
+    }
+
+    /// 设置子元素，替换同名元素；新元素按 `CT_Settings` 的顺序插入
+    ///
+    /// # 参数
+    /// * `name` - 元素的限定名
+    /// * `xml` - 元素的完整XML，使用根元素已声明的命名空间前缀
+    pub fn set_element(&mut self, name: &str, xml: impl Into<String>) {
+        let element = SettingsElement {
+            name: name.to_string(),
+            xml: xml.into(),
+        };
+        if let Some(existing) = self.elements.iter_mut().find(|e| e.name == name) {
+            *existing = element;
+            return;
+        }
+        let index = match ELEMENT_ORDER.iter().position(|n| *n == name) {
+            Some(order) => self
+                .elements
+                .iter()
+                .position(|e| {
+                    ELEMENT_ORDER
+                        .iter()
+                        .position(|n| *n == e.name)
+                        .is_some_and(|o| o > order)
+                })
+                .unwrap_or(self.elements.len()),
+            None => self.elements.len(),
+        };
+        self.elements.insert(index, element);
+    }
+
+    /// 删除指定名称的子元素，元素不存在时返回 `false`
+    ///
+    /// # 参数
+    /// * `name` - 元素的限定名
+    pub fn remove_element(&mut self, name: &str) -> bool {
+        let len = self.elements.len();
+        self.elements.retain(|e| e.name != name);
+        self.elements.len() != len
+    }
+
+    /// 读取开关型设置，元素不存在时返回 `None`
+    ///
+    /// # 参数
+    /// * `name` - 元素的限定名
+    pub fn on_off(&self, name: &str) -> Option<bool> {
+        let xml = self.element(name)?;
+        let mut reader = Reader::from_str(xml);
+        let value = match reader.read_event() {
+            Ok(Event::Start(e) | Event::Empty(e)) => e
+                .try_get_attribute("w:val")
+                .ok()
+                .flatten()
+                .map(|attr| attr.value.into_owned()),
+            _ => None,
+        };
+        Some(!matches!(value.as_deref(), Some(b"false" | b"0" | b"off")))
+    }
+
+    /// 设置开关型设置，为 `false` 时删除该元素
+    ///
+    /// # 参数
+    /// * `name` - 元素的限定名
+    /// * `value` - 是否开启
+    pub fn set_on_off(&mut self, name: &str, value: bool) {
+        if value {
+            self.set_element(name, format!(r#"<{name} w:val="true"/>"#));
+        } else {
+            self.remove_element(name);
+        }
+    }
+
+    /// 打开文档时是否提示更新域
+    pub fn update_fields(&self) -> bool {
+        self.on_off("w:updateFields").unwrap_or(false)
+    }
+
+    /// 设置打开文档时是否提示更新域，目录页码等依赖排版的域需要 Word 更新
+    ///
+    /// # 参数
+    /// * `value` - 是否更新
+    pub fn set_update_fields(&mut self, value: bool) {
+        self.set_on_off("w:updateFields", value);
+    }
+}
@@ -0,0 +1,555 @@
+//! 文档拆分：按标题、分节符或分页符把文档拆分为多个独立的文档
+//!
+//! 每一部分都是完整的文档：保留原文档的设置、文档属性和文档默认格式，样式、编号定义、
+//! 关系、页眉页脚等部件和媒体文件只保留该部分实际用到的，ID 与原文档相同。每一部分的
+//! 最后一节沿用原文档中对应位置的节属性。
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::common::relations::{types, Relationships};
+use crate::common::xml_element::XmlElement;
+use crate::error::{OfficeError, Result};
+
+use super::document::{Body, Document};
+use super::elements::paragraph::content_runs;
+use super::elements::{BodyContent, Paragraph, ParagraphContent, Run, RunContent, TableRow};
+use super::merge::used_definitions;
+use super::properties::SectionProperties;
+use super::{part_path, rels_path, resolve_part_path, Docx};
+
+/// 文档的拆分位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitAt {
+    /// 在大纲级别不低于指定级别（1–9）的标题之前拆分，如 `Heading(2)` 在标题 1 和标题 2 处拆分
+    Heading(u8),
+    /// 在每个分节符之后拆分
+    SectionBreak,
+    /// 在分页符处以及设置了段前分页的段落之前拆分，分页符本身被删除
+    PageBreak,
+}
+
+impl Docx {
+    /// 拆分文档，返回按原顺序排列的各部分；没有内容的部分被忽略
+    ///
+    /// 只拆分正文顶层的段落，表格中的标题和分页符不作为拆分位置。标题级别不在 1–9 之间时
+    /// 返回错误。
+    ///
+    /// # 参数
+    /// * `at` - 拆分位置
+    pub fn split(&self, at: SplitAt) -> Result<Vec<Docx>> {
+        if let SplitAt::Heading(level) = at {
+            if !(1..=9).contains(&level) {
+                return Err(OfficeError::InvalidFormat(format!(
+                    "heading level {level} is out of range 1-9"
+                )));
+            }
+        }
+        let mut pieces = self.split_content(at);
+        let mut documents = Vec::new();
+        for index in 0..pieces.len() {
+            // 每部分最后一节的属性位于其末尾之后的第一个分节段落，或正文末尾
+            let section = pieces[index]
+                .last()
+                .into_iter()
+                .chain(pieces[index + 1..].iter().flatten())
+                .find_map(|item| match item {
+                    BodyContent::Paragraph(p) => p.properties.as_ref()?.section_properties.clone(),
+                    BodyContent::Table(_) | BodyContent::Sdt(_) => None,
+                })
+                .or_else(|| self.document.body.section_properties.clone());
+
+            let mut content = std::mem::take(&mut pieces[index]);
+            if let Some(BodyContent::Paragraph(last)) = content.last_mut() {
+                if let Some(properties) = &mut last.properties {
+                    // 节属性移到正文末尾，只承载分节符的空段落一并删除
+                    if properties.section_properties.take().is_some() && last.content.is_empty() {
+                        content.pop();
+                    }
+                }
+            }
+            if !content.is_empty() {
+                documents.push(self.extract(content, section)?);
+            }
+        }
+        Ok(documents)
+    }
+
+    /// 拆分文档并把各部分保存到指定目录，文件名为 `{prefix}01.docx`、`{prefix}02.docx` 等；
+    /// 返回保存的文件路径
+    ///
+    /// # 参数
+    /// * `at` - 拆分位置
+    /// * `dir` - 保存目录，须已存在
+    /// * `prefix` - 文件名前缀
+    pub fn split_to_files<P: AsRef<Path>>(
+        &self,
+        at: SplitAt,
+        dir: P,
+        prefix: &str,
+    ) -> Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        for (index, part) in self.split(at)?.iter().enumerate() {
+            let path = dir.as_ref().join(format!("{prefix}{:02}.docx", index + 1));
+            part.save(&path)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// 按拆分位置把正文顶层内容分组
+    fn split_content(&self, at: SplitAt) -> Vec<Vec<BodyContent>> {
+        let resolver = self.style_resolver();
+        let mut pieces = Vec::new();
+        let mut piece = Vec::new();
+        for item in &self.document.body.content {
+            let BodyContent::Paragraph(paragraph) = item else {
+                piece.push(item.clone());
+                continue;
+            };
+            match at {
+                SplitAt::Heading(level) => {
+                    // 大纲级别从 0 开始，对应标题 1
+                    let heading = resolver
+                        .paragraph_properties(paragraph, None)
+                        .outline_level
+                        .is_some_and(|outline| outline.val < level);
+                    if heading {
+                        start_piece(&mut pieces, &mut piece);
+                    }
+                    piece.push(item.clone());
+                }
+                SplitAt::SectionBreak => {
+                    piece.push(item.clone());
+                    let section_end = paragraph
+                        .properties
+                        .as_ref()
+                        .is_some_and(|p| p.section_properties.is_some());
+                    if section_end {
+                        start_piece(&mut pieces, &mut piece);
+                    }
+                }
+                SplitAt::PageBreak => {
+                    let break_before = resolver
+                        .paragraph_properties(paragraph, None)
+                        .page_break_before
+                        .is_some_and(|v| v.is_on());
+                    if break_before {
+                        start_piece(&mut pieces, &mut piece);
+                    }
+                    for (index, part) in split_at_page_breaks(paragraph).into_iter().enumerate() {
+                        if index > 0 {
+                            start_piece(&mut pieces, &mut piece);
+                        }
+                        let section_end = part
+                            .properties
+                            .as_ref()
+                            .is_some_and(|p| p.section_properties.is_some());
+                        if !part.content.is_empty() || section_end {
+                            piece.push(BodyContent::Paragraph(part));
+                        }
+                    }
+                }
+            }
+        }
+        start_piece(&mut pieces, &mut piece);
+        pieces
+    }
+
+    /// 用部分正文构建完整的文档，只保留其用到的样式、编号、关系、部件和媒体文件
+    fn extract(
+        &self,
+        content: Vec<BodyContent>,
+        section: Option<SectionProperties>,
+    ) -> Result<Docx> {
+        let (mut style_ids, num_ids) = used_definitions(self, &content);
+        // 默认样式不需要显式引用
+        style_ids.extend(
+            self.styles
+                .styles
+                .iter()
+                .filter(|s| s.default == Some(true))
+                .map(|s| s.style_id.clone()),
+        );
+        let mut styles = self.styles.clone();
+        styles.styles.retain(|s| style_ids.contains(&s.style_id));
+
+        let mut numbering = self.numbering.clone();
+        numbering.nums.retain(|n| num_ids.contains(&n.id));
+        let abstract_ids: HashSet<i32> = numbering
+            .nums
+            .iter()
+            .map(|n| n.abstract_num_id.val)
+            .collect();
+        numbering
+            .abstract_nums
+            .retain(|an| abstract_ids.contains(&an.id));
+
+        // 超链接以及指向页眉页脚、脚注、图表、图片等部件的关系只保留正文引用的；
+        // 指向样式、设置等部件的关系与正文内容无关，全部保留
+        let mut references = References::default();
+        references.collect(&content);
+        if let Some(section) = &section {
+            references.section(section);
+        }
+        let relationships = self.relationships.clone().map(|mut rels| {
+            let unused: Vec<String> = rels
+                .iter()
+                .filter(|r| {
+                    let path = part_path(&r.target);
+                    let content_target = r.rel_type == types::HYPERLINK
+                        || (!r.external
+                            && (self.parts.contains_key(&path) || self.media.contains_key(&path)));
+                    let used = references.r_ids.contains(r.id.as_str())
+                        || (r.rel_type == types::FOOTNOTES && references.footnotes)
+                        || (r.rel_type == types::ENDNOTES && references.endnotes);
+                    content_target && !used
+                })
+                .map(|r| r.id.clone())
+                .collect();
+            for id in unused {
+                rels.remove(&id);
+            }
+            rels
+        });
+
+        // 保留的关系指向的部件和媒体文件，连同这些部件自身的关系及其指向的部件
+        let mut parts = BTreeMap::new();
+        let mut media = BTreeMap::new();
+        let mut pending: Vec<String> = relationships
+            .iter()
+            .flat_map(|rels| rels.iter())
+            .filter(|r| !r.external)
+            .map(|r| part_path(&r.target))
+            .collect();
+        while let Some(path) = pending.pop() {
+            if let Some(data) = self.media.get(&path) {
+                media.insert(path, data.clone());
+                continue;
+            }
+            let Some(data) = self.parts.get(&path) else {
+                continue;
+            };
+            if parts.insert(path.clone(), data.clone()).is_some() {
+                continue;
+            }
+            let rels_path = rels_path(&path);
+            if let Some(data) = self.parts.get(&rels_path) {
+                let rels = Relationships::from_xml(&String::from_utf8_lossy(data))?;
+                pending.extend(
+                    rels.iter()
+                        .filter(|r| !r.external)
+                        .map(|r| resolve_part_path(&path, &r.target)),
+                );
+                parts.insert(rels_path, data.clone());
+            }
+        }
+
+        Ok(Docx {
+            document: Document {
+                body: Body {
+                    content,
+                    section_properties: section,
+                },
+                xmlns_w: self.document.xmlns_w.clone(),
+                xmlns_r: self.document.xmlns_r.clone(),
+                xmlns_mc: self.document.xmlns_mc.clone(),
+                xmlns_w14: self.document.xmlns_w14.clone(),
+                xmlns_w15: self.document.xmlns_w15.clone(),
+                other_namespaces: self.document.other_namespaces.clone(),
+                mc_ignorable: self.document.mc_ignorable.clone(),
+            },
+            styles,
+            numbering,
+            settings: self.settings.clone(),
+            relationships,
+            app_props: self.app_props.clone(),
+            core_props: self.core_props.clone(),
+            custom_props: self.custom_props.clone(),
+            media,
+            parts,
+            custom_xml: self.custom_xml.clone(),
+        })
+    }
+}
+
+/// 结束当前部分，当前部分为空时继续使用
+fn start_piece(pieces: &mut Vec<Vec<BodyContent>>, piece: &mut Vec<BodyContent>) {
+    if !piece.is_empty() {
+        pieces.push(std::mem::take(piece));
+    }
+}
+
+/// 在段落的分页符处拆分段落并删除分页符；节属性只保留在最后一部分
+fn split_at_page_breaks(paragraph: &Paragraph) -> Vec<Paragraph> {
+    let mut parts = Vec::new();
+    let mut current = Vec::new();
+    for item in &paragraph.content {
+        let ParagraphContent::Run(run) = item else {
+            current.push(item.clone());
+            continue;
+        };
+        let mut content = Vec::new();
+        for run_content in &run.content {
+            if !matches!(run_content, RunContent::PageBreak) {
+                content.push(run_content.clone());
+                continue;
+            }
+            if !content.is_empty() {
+                current.push(ParagraphContent::Run(Run {
+                    properties: run.properties.clone(),
+                    content: std::mem::take(&mut content),
+                }));
+            }
+            parts.push(std::mem::take(&mut current));
+        }
+        if !content.is_empty() {
+            current.push(ParagraphContent::Run(Run {
+                properties: run.properties.clone(),
+                content,
+            }));
+        }
+    }
+    parts.push(current);
+
+    let last = parts.len() - 1;
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(index, content)| {
+            let mut properties = paragraph.properties.clone();
+            if let (true, Some(properties)) = (index != last, properties.as_mut()) {
+                properties.section_properties = None;
+            }
+            Paragraph {
+                properties,
+                content,
+            }
+        })
+        .collect()
+}
+
+/// 正文引用的关系和注释
+#[derive(Default)]
+struct References<'a> {
+    /// 超链接、图片、图表和页眉页脚引用的关系ID
+    r_ids: HashSet<&'a str>,
+    /// 是否引用了脚注
+    footnotes: bool,
+    /// 是否引用了尾注
+    endnotes: bool,
+}
+
+impl<'a> References<'a> {
+    /// 收集正文中的引用，包括表格和内容控件中的内容
+    ///
+    /// # 参数
+    /// * `content` - 正文内容
+    fn collect(&mut self, content: &'a [BodyContent]) {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(paragraph) => {
+                    self.r_ids
+                        .extend(paragraph.hyperlinks().map(|h| h.r_id.as_str()));
+                    if let Some(section) = paragraph
+                        .properties
+                        .as_ref()
+                        .and_then(|p| p.section_properties.as_ref())
+                    {
+                        self.section(section);
+                    }
+                    let runs = content_runs(&paragraph.content, true);
+                    for run_content in runs.flat_map(|run| &run.content) {
+                        match run_content {
+                            RunContent::Drawing(drawing) => {
+                                self.r_ids.insert(drawing.embed.as_str());
+                            }
+                            RunContent::RawDrawing(drawing) => self.element(drawing),
+                            RunContent::FootnoteReference(_) => self.footnotes = true,
+                            RunContent::EndnoteReference(_) => self.endnotes = true,
+                            _ => {}
+                        }
+                    }
+                }
+                BodyContent::Table(table) => {
+                    for cell in table.rows().flat_map(TableRow::cells) {
+                        self.collect(&cell.content);
+                    }
+                }
+                BodyContent::Sdt(sdt) => self.collect(&sdt.content),
+            }
+        }
+    }
+
+    /// 收集节属性引用的页眉和页脚
+    ///
+    /// # 参数
+    /// * `section` - 节属性
+    fn section(&mut self, section: &'a SectionProperties) {
+        let references = section.headers.iter().chain(&section.footers);
+        self.r_ids.extend(references.map(|r| r.id.as_str()));
+    }
+
+    /// 收集原样保留的元素中以 `r:` 前缀属性引用的关系，如图表的 `r:id`
+    ///
+    /// # 参数
+    /// * `element` - 元素
+    fn element(&mut self, element: &'a XmlElement) {
+        let ids = element
+            .attributes
+            .iter()
+            .filter(|(name, _)| name.starts_with("r:"))
+            .map(|(_, value)| value.as_str());
+        self.r_ids.extend(ids);
+        for child in &element.children {
+            self.element(child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::styles::Styles;
+    use crate::docx::test_utils::{docx, p};
+
+    /// 各部分中段落的文本
+    fn texts(parts: &[Docx]) -> Vec<Vec<String>> {
+        parts
+            .iter()
+            .map(|part| part.paragraphs().iter().map(|p| p.text()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn headings_start_parts_with_used_styles() {
+        let heading = |text: &str| {
+            format!(
+                "<w:p><w:pPr><w:pStyle w:val=\"Heading1\"/></w:pPr>\
+                 <w:r><w:t xml:space=\"preserve\">{text}</w:t></w:r></w:p>"
+            )
+        };
+        let mut docx = docx(&format!(
+            "{}{}{}{}{}",
+            p("intro"),
+            heading("A"),
+            p("a"),
+            heading("B"),
+            p("b")
+        ));
+        docx.styles = Styles::from_xml(
+            "<w:styles xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+             <w:style w:type=\"paragraph\" w:default=\"1\" w:styleId=\"Normal\">\
+             <w:name w:val=\"Normal\"/></w:style>\
+             <w:style w:type=\"paragraph\" w:styleId=\"Heading1\"><w:name w:val=\"heading 1\"/>\
+             <w:basedOn w:val=\"Normal\"/><w:pPr><w:outlineLvl w:val=\"0\"/></w:pPr></w:style>\
+             <w:style w:type=\"character\" w:styleId=\"Unused\"><w:name w:val=\"x\"/></w:style>\
+             </w:styles>",
+        )
+        .unwrap();
+
+        let parts = docx.split(SplitAt::Heading(1)).unwrap();
+        assert_eq!(texts(&parts), [vec!["intro"], vec!["A", "a"], vec!["B", "b"]]);
+        let ids = |part: &Docx| -> Vec<String> {
+            part.styles.styles.iter().map(|s| s.style_id.clone()).collect()
+        };
+        assert_eq!(ids(&parts[0]), ["Normal"]);
+        assert_eq!(ids(&parts[1]), ["Normal", "Heading1"]);
+    }
+
+    #[test]
+    fn section_breaks_keep_section_properties() {
+        let docx = docx(&format!(
+            "<w:p><w:pPr><w:sectPr><w:pgSz w:w=\"16838\" w:h=\"11906\"/></w:sectPr></w:pPr>\
+             <w:r><w:t xml:space=\"preserve\">wide</w:t></w:r></w:p>\
+             <w:p><w:pPr><w:sectPr><w:pgSz w:w=\"12240\" w:h=\"15840\"/></w:sectPr></w:pPr></w:p>\
+             {}<w:sectPr><w:pgSz w:w=\"11906\" w:h=\"16838\"/></w:sectPr>",
+            p("tall")
+        ));
+
+        let parts = docx.split(SplitAt::SectionBreak).unwrap();
+        assert_eq!(texts(&parts), [vec!["wide"], vec!["tall"]]);
+        let widths: Vec<i32> = parts
+            .iter()
+            .map(|part| {
+                let section = part.document.body.section_properties.as_ref().unwrap();
+                section.page_size.as_ref().unwrap().width
+            })
+            .collect();
+        assert_eq!(widths, [16838, 11906]);
+        let BodyContent::Paragraph(first) = &parts[0].document.body.content[0] else {
+            panic!("paragraph expected")
+        };
+        assert!(first.properties.as_ref().unwrap().section_properties.is_none());
+    }
+
+    #[test]
+    fn page_breaks_are_removed() {
+        let docx = docx(&format!(
+            "<w:p><w:r><w:t xml:space=\"preserve\">one</w:t><w:br w:type=\"page\"/>\
+             <w:t xml:space=\"preserve\">two</w:t></w:r></w:p>\
+             <w:p><w:pPr><w:pageBreakBefore/></w:pPr>\
+             <w:r><w:t xml:space=\"preserve\">three</w:t></w:r></w:p>{}",
+            p("four")
+        ));
+
+        let parts = docx.split(SplitAt::PageBreak).unwrap();
+        assert_eq!(texts(&parts), [vec!["one"], vec!["two"], vec!["three", "four"]]);
+    }
+
+    #[test]
+    fn heading_level_zero_is_rejected() {
+        let docx = docx(&p("text"));
+        assert!(docx.split(SplitAt::Heading(0)).is_err());
+    }
+
+    #[test]
+    fn pieces_keep_only_referenced_parts() {
+        let section = |header: &str, width: i32| {
+            format!(
+                "<w:sectPr><w:headerReference w:type=\"default\" r:id=\"{header}\"/>\
+                 <w:pgSz w:w=\"{width}\" w:h=\"11906\"/></w:sectPr>"
+            )
+        };
+        let mut docx = docx(&format!(
+            "<w:p><w:pPr>{}</w:pPr><w:r><w:t>one</w:t></w:r></w:p>{}{}",
+            section("rId1", 16838),
+            p("two"),
+            section("rId2", 11906)
+        ));
+        let mut rels = Relationships::default();
+        rels.add(types::HEADER, "header1.xml", false);
+        rels.add(types::HEADER, "header2.xml", false);
+        rels.add(types::STYLES, "styles.xml", false);
+        docx.relationships = Some(rels);
+        let mut header_rels = Relationships::default();
+        header_rels.add(types::IMAGE, "media/image1.png", false);
+        for (path, data) in [
+            ("word/header1.xml", b"<w:hdr/>".to_vec()),
+            ("word/header2.xml", b"<w:hdr/>".to_vec()),
+            ("word/_rels/header2.xml.rels", header_rels.to_xml().unwrap().into_bytes()),
+        ] {
+            docx.parts.insert(path.to_string(), data);
+        }
+        docx.media.insert("word/media/image1.png".to_string(), vec![1]);
+        docx.media.insert("word/media/image2.png".to_string(), vec![2]);
+
+        let parts = docx.split(SplitAt::SectionBreak).unwrap();
+        let keys = |map: &BTreeMap<String, Vec<u8>>| -> Vec<String> {
+            map.keys().cloned().collect()
+        };
+        let targets = |part: &Docx| -> Vec<String> {
+            let rels = part.relationships.as_ref().unwrap();
+            rels.iter().map(|r| r.target.clone()).collect()
+        };
+        assert_eq!(keys(&parts[0].parts), ["word/header1.xml"]);
+        assert!(parts[0].media.is_empty());
+        assert_eq!(targets(&parts[0]), ["header1.xml", "styles.xml"]);
+        assert_eq!(
+            keys(&parts[1].parts),
+            ["word/_rels/header2.xml.rels", "word/header2.xml"]
+        );
+        assert_eq!(keys(&parts[1].media), ["word/media/image1.png"]);
+        assert_eq!(targets(&parts[1]), ["header2.xml", "styles.xml"]);
+    }
+}
@@ -0,0 +1,531 @@
+//! 样式解析：按样式层级计算段落和文本运行的最终格式
+//!
+//! 层级顺序（后者覆盖前者）：文档默认格式 → 表格样式（含条件格式）→ 段落样式链 →
+//! 编号级别 → 字符样式链 → 直接格式。
+
+use std::collections::HashSet;
+
+use super::elements::{Paragraph, Run, Table};
+use super::numbering::Numbering;
+use super::properties::{OnOff, ParagraphProperties, RunProperties, TableLook, TableProperties};
+use super::styles::{Style, StyleType, Styles, TableStyleOverrideType};
+use super::Docx;
+
+/// 样式链的最大深度，防止循环引用
+const MAX_CHAIN_DEPTH: usize = 32;
+
+/// 段落所在的表格单元格，用于应用表格样式及其条件格式
+#[derive(Debug, Clone, Copy)]
+pub struct CellContext<'a> {
+    /// 所在表格
+    pub table: &'a Table,
+    /// 行号
+    pub row: usize,
+    /// 网格列号
+    pub col: usize,
+}
+
+impl<'a> CellContext<'a> {
+    /// 创建单元格上下文
+    ///
+    /// # 参数
+    /// * `table` - 所在表格
+    /// * `row` - 行号
+    /// * `col` - 网格列号
+    pub fn new(table: &'a Table, row: usize, col: usize) -> Self {
+        CellContext { table, row, col }
+    }
+}
+
+/// 样式解析器
+#[derive(Debug, Clone, Copy)]
+pub struct StyleResolver<'a> {
+    styles: &'a Styles,
+    numbering: &'a Numbering,
+}
+
+impl Docx {
+    /// 获取文档的样式解析器
+    pub fn style_resolver(&self) -> StyleResolver<'_> {
+        StyleResolver::new(&self.styles, &self.numbering)
+    }
+}
+
+impl<'a> StyleResolver<'a> {
+    /// 创建样式解析器
+    ///
+    /// # 参数
+    /// * `styles` - 样式集合
+    /// * `numbering` - 编号定义
+    pub fn new(styles: &'a Styles, numbering: &'a Numbering) -> Self {
+        StyleResolver { styles, numbering }
+    }
+
+    /// 计算段落的最终段落属性
+    ///
+    /// # 参数
+    /// * `paragraph` - 段落
+    /// * `cell` - 段落所在的表格单元格，不在表格中时为 `None`
+    pub fn paragraph_properties(
+        &self,
+        paragraph: &Paragraph,
+        cell: Option<&CellContext>,
+    ) -> ParagraphProperties {
+        let mut resolved = self
+            .styles
+            .doc_defaults
+            .as_ref()
+            .and_then(|d| d.paragraph_properties.as_ref())
+            .and_then(|d| d.paragraph_properties.clone())
+            .unwrap_or_default();
+
+        if let Some(cell) = cell {
+            for layer in self.table_layers(cell) {
+                if let Some(props) = &layer.0 {
+                    resolved.apply(props);
+                }
+            }
+        }
+        for style in self.paragraph_style_chain(paragraph) {
+            if let Some(props) = &style.paragraph_properties {
+                resolved.apply(props);
+            }
+        }
+
+        let direct = paragraph.properties.as_ref();
+        if let Some(props) = self
+            .numbering_level(paragraph, &resolved)
+            .and_then(|(num_id, level)| self.numbering.level_paragraph_properties(num_id, level))
+        {
+            resolved.apply(&props);
+        }
+
+        if let Some(direct) = direct {
+            resolved.apply(direct);
+        }
+        resolved
+    }
+
+    /// 获取段落的编号ID和级别，段落不属于列表时返回 `None`
+    ///
+    /// # 参数
+    /// * `paragraph` - 段落
+    /// * `cell` - 段落所在的表格单元格，不在表格中时为 `None`
+    pub fn list_level(&self, paragraph: &Paragraph, cell: Option<&CellContext>) -> Option<(i32, i32)> {
+        self.numbering_level(paragraph, &self.paragraph_properties(paragraph, cell))
+    }
+
+    /// 由直接格式或样式中的 `w:numPr` 确定编号ID和级别。
+    /// 编号来自段落样式时，优先使用以 `w:pStyle` 关联该样式的级别
+    fn numbering_level(
+        &self,
+        paragraph: &Paragraph,
+        resolved: &ParagraphProperties,
+    ) -> Option<(i32, i32)> {
+        let direct = paragraph.properties.as_ref();
+        if let Some(num_pr) = direct.and_then(|p| p.num_pr.as_ref()) {
+            return (num_pr.num_id.val != 0).then_some((num_pr.num_id.val, num_pr.level.val));
+        }
+
+        let num_pr = resolved.num_pr.as_ref().filter(|n| n.num_id.val != 0)?;
+        let num_id = num_pr.num_id.val;
+        let level = self
+            .paragraph_style_chain(paragraph)
+            .iter()
+            .rev()
+            .find_map(|style| self.numbering.level_for_style(num_id, &style.style_id))
+            .unwrap_or(num_pr.level.val);
+        Some((num_id, level))
+    }
+
+    /// 计算段落中一个文本运行的最终运行属性
+    ///
+    /// # 参数
+    /// * `paragraph` - 文本运行所在的段落
+    /// * `run` - 文本运行
+    /// * `cell` - 段落所在的表格单元格，不在表格中时为 `None`
+    pub fn run_properties(
+        &self,
+        paragraph: &Paragraph,
+        run: &Run,
+        cell: Option<&CellContext>,
+    ) -> RunProperties {
+        let mut resolved = self.base_run_properties(paragraph, cell);
+
+        let style_id = run
+            .properties
+            .as_ref()
+            .and_then(|p| p.style.as_ref())
+            .map(|s| s.val.as_str());
+        let character_layer =
+            chain_run_properties(&self.style_chain(style_id, StyleType::Character));
+        apply_style_layer(&mut resolved, &character_layer);
+
+        if let Some(direct) = &run.properties {
+            resolved.apply(direct);
+        }
+        resolved
+    }
+
+    /// 计算段落标记的最终运行属性
+    ///
+    /// # 参数
+    /// * `paragraph` - 段落
+    /// * `cell` - 段落所在的表格单元格，不在表格中时为 `None`
+    pub fn paragraph_mark_properties(
+        &self,
+        paragraph: &Paragraph,
+        cell: Option<&CellContext>,
+    ) -> RunProperties {
+        let mut resolved = self.base_run_properties(paragraph, cell);
+        if let Some(mark) = paragraph
+            .properties
+            .as_ref()
+            .and_then(|p| p.run_properties.as_ref())
+        {
+            let style_id = mark.style.as_ref().map(|s| s.val.as_str());
+            let character_layer =
+                chain_run_properties(&self.style_chain(style_id, StyleType::Character));
+            apply_style_layer(&mut resolved, &character_layer);
+            resolved.apply(mark);
+        }
+        resolved
+    }
+
+    /// 文档默认格式、表格样式和段落样式链共同决定的运行属性
+    fn base_run_properties(
+        &self,
+        paragraph: &Paragraph,
+        cell: Option<&CellContext>,
+    ) -> RunProperties {
+        let mut resolved = self
+            .styles
+            .doc_defaults
+            .as_ref()
+            .and_then(|d| d.run_properties.as_ref())
+            .and_then(|d| d.run_properties.clone())
+            .unwrap_or_default();
+
+        if let Some(cell) = cell {
+            // 表格样式的各条件格式依次覆盖，它们共同构成样式层级中的一层
+            let mut table_layer = RunProperties::default();
+            for layer in self.table_layers(cell) {
+                if let Some(props) = &layer.1 {
+                    table_layer.apply(props);
+                }
+            }
+            apply_style_layer(&mut resolved, &table_layer);
+        }
+
+        let paragraph_layer = chain_run_properties(&self.paragraph_style_chain(paragraph));
+        apply_style_layer(&mut resolved, &paragraph_layer);
+        resolved
+    }
+
+    /// 段落样式链，未指定样式时使用默认段落样式
+    fn paragraph_style_chain(&self, paragraph: &Paragraph) -> Vec<&'a Style> {
+        let style_id = paragraph
+            .properties
+            .as_ref()
+            .and_then(|p| p.style.as_ref())
+            .map(|s| s.val.as_str());
+        self.style_chain(style_id, StyleType::Paragraph)
+    }
+
+    /// 获取样式及其 `w:basedOn` 祖先，按从根到自身的顺序排列。
+    /// 样式类型不符时沿 `w:link` 查找链接的样式；未指定样式时使用该类型的默认样式
+    fn style_chain(&self, style_id: Option<&str>, style_type: StyleType) -> Vec<&'a Style> {
+        let styles = self.styles;
+        let start = match style_id {
+            Some(id) => styles.find_style(id).and_then(|style| {
+                if style.style_type == style_type {
+                    Some(style)
+                } else {
+                    style
+                        .link
+                        .as_ref()
+                        .and_then(|l| styles.find_style(&l.val))
+                        .filter(|s| s.style_type == style_type)
+                }
+            }),
+            None => styles.default_style(style_type),
+        };
+
+        let mut chain = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = start;
+        while let Some(style) = current {
+            if !visited.insert(style.style_id.as_str()) || chain.len() >= MAX_CHAIN_DEPTH {
+                break;
+            }
+            chain.push(style);
+            current = style
+                .based_on
+                .as_ref()
+                .and_then(|b| styles.find_style(&b.val))
+                .filter(|s| s.style_type == style_type);
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// 表格样式对指定单元格生效的段落和运行属性层，按应用顺序排列
+    fn table_layers(
+        &self,
+        cell: &CellContext,
+    ) -> Vec<(Option<ParagraphProperties>, Option<RunProperties>)> {
+        let table_props = cell.table.properties.as_ref();
+        let chain = self.style_chain(
+            table_props
+                .and_then(|p| p.style.as_ref())
+                .map(|s| s.val.as_str()),
+            StyleType::Table,
+        );
+        if chain.is_empty() {
+            return Vec::new();
+        }
+
+        let mut layers: Vec<_> = chain
+            .iter()
+            .map(|s| (s.paragraph_properties.clone(), s.run_properties.clone()))
+            .collect();
+
+        let band_size = |pick: fn(&TableProperties) -> Option<u32>| {
+            table_props
+                .and_then(pick)
+                .or_else(|| {
+                    chain
+                        .iter()
+                        .rev()
+                        .find_map(|s| s.table_properties.as_ref().and_then(pick))
+                })
+                .unwrap_or(1)
+                .max(1) as usize
+        };
+        let row_band = band_size(|p| p.style_row_band_size.as_ref().map(|v| v.val));
+        let col_band = band_size(|p| p.style_col_band_size.as_ref().map(|v| v.val));
+        let look = table_props.and_then(|p| p.look.clone()).unwrap_or_default();
+
+        for kind in conditional_types(cell, &look, row_band, col_band) {
+            for style in &chain {
+                for format in style
+                    .conditional_formats
+                    .iter()
+                    .filter(|f| f.override_type == kind)
+                {
+                    layers.push((
+                        format.paragraph_properties.clone(),
+                        format.run_properties.clone(),
+                    ));
+                }
+            }
+        }
+        layers
+    }
+}
+
+/// 合并样式链中的运行属性，派生样式覆盖其基础样式
+fn chain_run_properties(chain: &[&Style]) -> RunProperties {
+    let mut merged = RunProperties::default();
+    for props in chain.iter().filter_map(|s| s.run_properties.as_ref()) {
+        merged.apply(props);
+    }
+    merged
+}
+
+/// 计算单元格适用的表格样式条件格式，按应用顺序排列
+fn conditional_types(
+    cell: &CellContext,
+    look: &TableLook,
+    row_band: usize,
+    col_band: usize,
+) -> Vec<TableStyleOverrideType> {
+    use TableStyleOverrideType::*;
+
+    let view = cell.table.grid_view();
+    let (rows, cols) = (view.rows(), view.columns());
+    let (row, col, last_row_covered, last_col_covered) = match view.cell_at(cell.row, cell.col) {
+        Some(c) => (
+            c.row,
+            c.col,
+            c.row + c.row_span >= rows,
+            c.col + c.col_span >= cols,
+        ),
+        None => (
+            cell.row,
+            cell.col,
+            cell.row + 1 >= rows,
+            cell.col + 1 >= cols,
+        ),
+    };
+
+    let first_row = look.first_row() && row == 0;
+    let last_row = look.last_row() && last_row_covered;
+    let first_col = look.first_column() && col == 0;
+    let last_col = look.last_column() && last_col_covered;
+
+    let mut types = vec![WholeTable];
+    if look.banded_columns() && !first_col && !last_col {
+        let index = col - look.first_column() as usize;
+        types.push(if (index / col_band).is_multiple_of(2) {
+            Band1Vert
+        } else {
+            Band2Vert
+        });
+    }
+    if look.banded_rows() && !first_row && !last_row {
+        let index = row - look.first_row() as usize;
+        types.push(if (index / row_band).is_multiple_of(2) {
+            Band1Horz
+        } else {
+            Band2Horz
+        });
+    }
+    let flags = [
+        (first_col, FirstCol),
+        (last_col, LastCol),
+        (first_row, FirstRow),
+        (last_row, LastRow),
+        (first_row && last_col, NeCell),
+        (first_row && first_col, NwCell),
+        (last_row && last_col, SeCell),
+        (last_row && first_col, SwCell),
+    ];
+    types.extend(
+        flags
+            .into_iter()
+            .filter(|(on, _)| *on)
+            .map(|(_, kind)| kind),
+    );
+    types
+}
+
+/// 应用样式层级中的一层：开关属性（粗体、斜体等）设为开时切换当前状态，
+/// 设为关时保持不变；其他属性直接覆盖
+fn apply_style_layer(target: &mut RunProperties, layer: &RunProperties) {
+    macro_rules! toggles {
+        ($($field:ident),* $(,)?) => {{
+            let toggled = ($(
+                match layer.$field {
+                    Some(value) if value.is_on() => {
+                        Some(OnOff(!target.$field.is_some_and(|v| v.is_on())))
+                    }
+                    _ => target.$field,
+                },
+            )*);
+            target.apply(layer);
+            let ($($field,)*) = toggled;
+            $(target.$field = $field;)*
+        }};
+    }
+    toggles!(
+        bold, bold_cs, italic, italic_cs, caps, small_caps, strike, outline, shadow, emboss,
+        imprint, hidden,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::elements::{BodyContent, ParagraphContent};
+    use crate::docx::test_utils::docx;
+
+    const STYLES: &str = "<w:styles xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+        <w:docDefaults><w:rPrDefault><w:rPr><w:sz w:val=\"20\"/></w:rPr></w:rPrDefault>\
+        <w:pPrDefault><w:pPr><w:spacing w:after=\"160\"/></w:pPr></w:pPrDefault></w:docDefaults>\
+        <w:style w:type=\"paragraph\" w:default=\"1\" w:styleId=\"Normal\"><w:name w:val=\"Normal\"/>\
+        <w:pPr><w:jc w:val=\"both\"/></w:pPr><w:rPr><w:color w:val=\"333333\"/></w:rPr></w:style>\
+        <w:style w:type=\"paragraph\" w:styleId=\"Heading1\"><w:name w:val=\"heading 1\"/>\
+        <w:basedOn w:val=\"Normal\"/><w:pPr><w:spacing w:after=\"0\"/></w:pPr>\
+        <w:rPr><w:b/><w:sz w:val=\"32\"/></w:rPr></w:style>\
+        <w:style w:type=\"character\" w:styleId=\"Strong\"><w:name w:val=\"Strong\"/>\
+        <w:rPr><w:b/><w:i/></w:rPr></w:style>\
+        <w:style w:type=\"table\" w:styleId=\"Grid\"><w:name w:val=\"Grid\"/>\
+        <w:tblStylePr w:type=\"firstRow\"><w:rPr><w:b/></w:rPr></w:tblStylePr></w:style>\
+        </w:styles>";
+
+    fn document(body: &str) -> Docx {
+        let mut docx = docx(body);
+        docx.styles = Styles::from_xml(STYLES).unwrap();
+        docx
+    }
+
+    fn paragraph(block: &BodyContent) -> &Paragraph {
+        match block {
+            BodyContent::Paragraph(p) => p,
+            _ => panic!("paragraph expected"),
+        }
+    }
+
+    fn first_run(paragraph: &Paragraph) -> &Run {
+        match &paragraph.content[0] {
+            ParagraphContent::Run(run) => run,
+            _ => panic!("run expected"),
+        }
+    }
+
+    #[test]
+    fn paragraph_style_chain_overrides_defaults() {
+        let docx = document(
+            "<w:p><w:pPr><w:pStyle w:val=\"Heading1\"/></w:pPr><w:r><w:t>h</w:t></w:r></w:p>\
+             <w:p><w:r><w:t>n</w:t></w:r></w:p>",
+        );
+        let resolver = docx.style_resolver();
+        let heading = paragraph(&docx.document.body.content[0]);
+        let normal = paragraph(&docx.document.body.content[1]);
+
+        let after = |p: &Paragraph| {
+            let props = resolver.paragraph_properties(p, None);
+            props.spacing.and_then(|s| s.after)
+        };
+        assert_eq!(after(heading), Some(0));
+        assert_eq!(after(normal), Some(160));
+        assert!(resolver.paragraph_properties(heading, None).justification.is_some());
+
+        let run = resolver.run_properties(heading, first_run(heading), None);
+        assert_eq!(run.size.map(|s| s.val), Some(32));
+        assert_eq!(run.color.map(|c| c.val), Some("333333".to_string()));
+        let run = resolver.run_properties(normal, first_run(normal), None);
+        assert_eq!(run.size.map(|s| s.val), Some(20));
+        assert!(run.bold.is_none_or(|b| !b.is_on()));
+    }
+
+    #[test]
+    fn toggle_properties_cancel_across_style_layers() {
+        let docx = document(
+            "<w:p><w:pPr><w:pStyle w:val=\"Heading1\"/></w:pPr>\
+             <w:r><w:rPr><w:rStyle w:val=\"Strong\"/></w:rPr><w:t>x</w:t></w:r></w:p>",
+        );
+        let resolver = docx.style_resolver();
+        let heading = paragraph(&docx.document.body.content[0]);
+
+        let run = resolver.run_properties(heading, first_run(heading), None);
+        assert!(run.bold.is_some_and(|b| !b.is_on()));
+        assert!(run.italic.is_some_and(|i| i.is_on()));
+    }
+
+    #[test]
+    fn table_style_conditional_formatting_applies_to_first_row() {
+        let docx = document(
+            "<w:tbl><w:tblPr><w:tblStyle w:val=\"Grid\"/><w:tblLook w:firstRow=\"1\"/></w:tblPr>\
+             <w:tr><w:tc><w:p><w:r><w:t>a</w:t></w:r></w:p></w:tc></w:tr>\
+             <w:tr><w:tc><w:p><w:r><w:t>b</w:t></w:r></w:p></w:tc></w:tr></w:tbl>",
+        );
+        let BodyContent::Table(table) = &docx.document.body.content[0] else {
+            panic!("table expected");
+        };
+        let resolver = docx.style_resolver();
+
+        let bold: Vec<bool> = table
+            .rows()
+            .enumerate()
+            .map(|(row, r)| {
+                let cell = CellContext::new(table, row, 0);
+                let p = paragraph(&r.cells().next().unwrap().content[0]);
+                let props = resolver.run_properties(p, first_run(p), Some(&cell));
+                props.bold.is_some_and(|b| b.is_on())
+            })
+            .collect();
+        assert_eq!(bold, [true, false]);
+    }
+}
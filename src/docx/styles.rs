@@ -1,7 +1,12 @@
-use crate::error::Result;
-use crate::docx::properties::{ParagraphProperties, RunProperties};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::common::xml_element::XmlElement;
+use crate::error::{OfficeError, Result};
+use crate::docx::properties::{
+    on_off_attr, Border, CellMargins, Indentation, LineSpacingRule, OnOff, ParagraphProperties,
+    ParagraphSpacing, RunFonts, RunProperties, TableBorders, TableCellProperties, TableProperties,
+    TableRowProperties, TableWidth, Val,
+};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
 
 /// 样式类型枚举
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
@@ -18,42 +23,702 @@ pub enum StyleType {
 }
 
 /// 样式结构体，表示文档中的一个样式定义
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// 字段顺序与 `CT_Style` 的元素顺序一致。
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "w:style")]
 pub struct Style {
     /// 样式类型
-    #[serde(rename = "@w:type")]
+    #[serde(rename = "@w:type", alias = "@type")]
     pub style_type: StyleType,
     /// 样式ID
-    #[serde(rename = "@w:styleId")]
+    #[serde(rename = "@w:styleId", alias = "@styleId")]
     pub style_id: String,
+    /// 是否为该类型的默认样式
+    #[serde(rename = "@w:default", alias = "@default", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<bool>,
+    /// 是否为用户自定义样式
+    #[serde(rename = "@w:customStyle", alias = "@customStyle", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub custom_style: Option<bool>,
+    /// 样式名称
+    #[serde(rename = "w:name", alias = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<Val<String>>,
+    /// 样式别名，以逗号分隔
+    #[serde(rename = "w:aliases", alias = "aliases", skip_serializing_if = "Option::is_none")]
+    pub aliases: Option<Val<String>>,
+    /// 基于的父样式ID
+    #[serde(rename = "w:basedOn", alias = "basedOn", skip_serializing_if = "Option::is_none")]
+    pub based_on: Option<Val<String>>,
+    /// 后续段落样式ID
+    #[serde(rename = "w:next", alias = "next", skip_serializing_if = "Option::is_none")]
+    pub next: Option<Val<String>>,
+    /// 链接的样式ID（段落样式与字符样式互相链接）
+    #[serde(rename = "w:link", alias = "link", skip_serializing_if = "Option::is_none")]
+    pub link: Option<Val<String>>,
+    /// 自动更新样式
+    #[serde(rename = "w:autoRedefine", alias = "autoRedefine", skip_serializing_if = "Option::is_none")]
+    pub auto_redefine: Option<OnOff>,
+    /// 在界面中隐藏
+    #[serde(rename = "w:hidden", alias = "hidden", skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<OnOff>,
+    /// 界面中的排序优先级
+    #[serde(rename = "w:uiPriority", alias = "uiPriority", skip_serializing_if = "Option::is_none")]
+    pub ui_priority: Option<Val<i32>>,
+    /// 在推荐列表中隐藏
+    #[serde(rename = "w:semiHidden", alias = "semiHidden", skip_serializing_if = "Option::is_none")]
+    pub semi_hidden: Option<OnOff>,
+    /// 使用时取消隐藏
+    #[serde(rename = "w:unhideWhenUsed", alias = "unhideWhenUsed", skip_serializing_if = "Option::is_none")]
+    pub unhide_when_used: Option<OnOff>,
+    /// 显示在快速样式库中
+    #[serde(rename = "w:qFormat", alias = "qFormat", skip_serializing_if = "Option::is_none")]
+    pub q_format: Option<OnOff>,
+    /// 样式被锁定
+    #[serde(rename = "w:locked", alias = "locked", skip_serializing_if = "Option::is_none")]
+    pub locked: Option<OnOff>,
+    /// 电子邮件正文样式
+    #[serde(rename = "w:personal", alias = "personal", skip_serializing_if = "Option::is_none")]
+    pub personal: Option<OnOff>,
+    /// 撰写电子邮件时使用的样式
+    #[serde(rename = "w:personalCompose", alias = "personalCompose", skip_serializing_if = "Option::is_none")]
+    pub personal_compose: Option<OnOff>,
+    /// 回复电子邮件时使用的样式
+    #[serde(rename = "w:personalReply", alias = "personalReply", skip_serializing_if = "Option::is_none")]
+    pub personal_reply: Option<OnOff>,
+    /// 修订标识
+    #[serde(rename = "w:rsid", alias = "rsid", skip_serializing_if = "Option::is_none")]
+    pub rsid: Option<Val<String>>,
     /// 段落属性
-    #[serde(rename = "w:pPr", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "w:pPr", alias = "pPr", skip_serializing_if = "Option::is_none")]
     pub paragraph_properties: Option<ParagraphProperties>,
     /// 文本运行属性
-    #[serde(rename = "w:rPr", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "w:rPr", alias = "rPr", skip_serializing_if = "Option::is_none")]
+    pub run_properties: Option<RunProperties>,
+    /// 表格属性（表格样式）
+    #[serde(rename = "w:tblPr", alias = "tblPr", skip_serializing_if = "Option::is_none")]
+    pub table_properties: Option<TableProperties>,
+    /// 表格行属性（表格样式）
+    #[serde(rename = "w:trPr", alias = "trPr", skip_serializing_if = "Option::is_none")]
+    pub table_row_properties: Option<TableRowProperties>,
+    /// 单元格属性（表格样式）
+    #[serde(rename = "w:tcPr", alias = "tcPr", skip_serializing_if = "Option::is_none")]
+    pub table_cell_properties: Option<TableCellProperties>,
+    /// 表格样式的条件格式
+    #[serde(rename = "w:tblStylePr", alias = "tblStylePr", default, skip_serializing_if = "Vec::is_empty")]
+    pub conditional_formats: Vec<TableStyleConditionalFormat>,
+    /// 未建模或无法识别的子元素，保存时原样写在末尾
+    #[serde(rename = "$value", skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub unknown: Vec<XmlElement>,
+}
+
+impl Style {
+    /// 创建指定类型、ID和名称的空样式
+    ///
+    /// # 参数
+    /// * `style_type` - 样式类型
+    /// * `style_id` - 样式ID
+    /// * `name` - 样式名称
+    pub fn new(style_type: StyleType, style_id: impl Into<String>, name: impl Into<String>) -> Self {
+        Style {
+            style_type,
+            style_id: style_id.into(),
+            default: None,
+            custom_style: None,
+            name: Some(Val::new(name.into())),
+            aliases: None,
+            based_on: None,
+            next: None,
+            link: None,
+            auto_redefine: None,
+            hidden: None,
+            ui_priority: None,
+            semi_hidden: None,
+            unhide_when_used: None,
+            q_format: None,
+            locked: None,
+            personal: None,
+            personal_compose: None,
+            personal_reply: None,
+            rsid: None,
+            paragraph_properties: None,
+            run_properties: None,
+            table_properties: None,
+            table_row_properties: None,
+            table_cell_properties: None,
+            conditional_formats: Vec::new(),
+            unknown: Vec::new(),
+        }
+    }
+
+    /// 从 `w:style` 元素解析样式，无法识别的子元素保存在 `unknown` 中；
+    /// 其中的格式属性同样逐个子元素解析
+    ///
+    /// # 参数
+    /// * `element` - 样式元素
+    pub fn from_element(element: &XmlElement) -> Result<Self> {
+        let (mut style, unknown): (Self, _) = element.deserialize_lenient()?;
+        style.unknown = unknown;
+        reparse(&mut style.paragraph_properties, element, "w:pPr", ParagraphProperties::from_element)?;
+        reparse(&mut style.run_properties, element, "w:rPr", RunProperties::from_element)?;
+        reparse(&mut style.table_properties, element, "w:tblPr", TableProperties::from_element)?;
+        reparse(&mut style.table_row_properties, element, "w:trPr", TableRowProperties::from_element)?;
+        reparse(&mut style.table_cell_properties, element, "w:tcPr", TableCellProperties::from_element)?;
+        // 无法解析的条件格式已原样保存，不参与对应
+        let conditions = element
+            .children
+            .iter()
+            .filter(|c| c.name == "w:tblStylePr" && !style.unknown.contains(c));
+        for (format, element) in style.conditional_formats.iter_mut().zip(conditions) {
+            reparse(&mut format.paragraph_properties, element, "w:pPr", ParagraphProperties::from_element)?;
+            reparse(&mut format.run_properties, element, "w:rPr", RunProperties::from_element)?;
+            reparse(&mut format.table_properties, element, "w:tblPr", TableProperties::from_element)?;
+            reparse(&mut format.table_row_properties, element, "w:trPr", TableRowProperties::from_element)?;
+            reparse(&mut format.table_cell_properties, element, "w:tcPr", TableCellProperties::from_element)?;
+        }
+        Ok(style)
+    }
+
+    /// 判断样式名称或别名是否与给定名称相同（不区分大小写）
+    ///
+    /// # 参数
+    /// * `name` - 样式名称
+    pub fn matches_name(&self, name: &str) -> bool {
+        let same = |candidate: &str| candidate.trim().eq_ignore_ascii_case(name.trim());
+        self.name.as_ref().is_some_and(|n| same(&n.val))
+            || self
+                .aliases
+                .as_ref()
+                .is_some_and(|a| a.val.split(',').any(same))
+    }
+}
+
+/// 内置样式模板
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinStyle {
+    /// 正文（默认段落样式）
+    Normal,
+    /// 标题 1–9
+    Heading(u8),
+    /// 标题（文档标题）
+    Title,
+    /// 列表段落
+    ListParagraph,
+    /// 默认段落字体（默认字符样式）
+    DefaultParagraphFont,
+    /// 普通表格（默认表格样式）
+    TableNormal,
+    /// 网格型表格
+    TableGrid,
+    /// 目录 1–9
+    Toc(u8),
+    /// 目录标题
+    TocHeading,
+    /// 文本块（引用段落）
+    BlockText,
+    /// HTML 预设格式（等宽代码段落）
+    HtmlPreformatted,
+    /// HTML 代码（等宽代码字符样式）
+    HtmlCode,
+}
+
+impl BuiltinStyle {
+    /// 样式所基于的内置样式
+    pub fn base(self) -> Option<BuiltinStyle> {
+        match self {
+            BuiltinStyle::Heading(_)
+            | BuiltinStyle::Title
+            | BuiltinStyle::ListParagraph
+            | BuiltinStyle::Toc(_)
+            | BuiltinStyle::BlockText
+            | BuiltinStyle::HtmlPreformatted => Some(BuiltinStyle::Normal),
+            BuiltinStyle::TableGrid => Some(BuiltinStyle::TableNormal),
+            BuiltinStyle::HtmlCode => Some(BuiltinStyle::DefaultParagraphFont),
+            BuiltinStyle::TocHeading => Some(BuiltinStyle::Heading(1)),
+            _ => None,
+        }
+    }
+
+    /// 根据内置样式ID查找内置样式
+    ///
+    /// # 参数
+    /// * `style_id` - 内置样式ID，如 `Heading1`
+    fn from_id(style_id: &str) -> Option<BuiltinStyle> {
+        use BuiltinStyle::*;
+        [
+            Normal,
+            Title,
+            ListParagraph,
+            DefaultParagraphFont,
+            TableNormal,
+            TableGrid,
+            TocHeading,
+            BlockText,
+            HtmlPreformatted,
+            HtmlCode,
+        ]
+        .into_iter()
+        .chain((1..=9).flat_map(|level| [Heading(level), Toc(level)]))
+        .find(|builtin| builtin.style().style_id == style_id)
+    }
+
+    /// 生成内置样式的定义，与 Word 默认模板中的同名样式一致
+    pub fn style(self) -> Style {
+        let on = || Some(OnOff::ON);
+        match self {
+            BuiltinStyle::Normal => Style {
+                default: Some(true),
+                q_format: on(),
+                ..Style::new(StyleType::Paragraph, "Normal", "Normal")
+            },
+            BuiltinStyle::Heading(level) => {
+                let level = level.clamp(1, 9);
+                // 字号以半磅为单位
+                let size = [32, 26, 24, 22, 22, 21, 21, 21, 21][level as usize - 1];
+                Style {
+                    based_on: Some(Val::new("Normal".to_string())),
+                    next: Some(Val::new("Normal".to_string())),
+                    ui_priority: Some(Val::new(9)),
+                    unhide_when_used: (level > 1).then_some(OnOff::ON),
+                    q_format: on(),
+                    paragraph_properties: Some(ParagraphProperties {
+                        keep_next: on(),
+                        keep_lines: on(),
+                        spacing: Some(ParagraphSpacing {
+                            before: Some(if level == 1 { 240 } else { 40 }),
+                            after: Some(0),
+                            ..Default::default()
+                        }),
+                        outline_level: Some(Val::new(level - 1)),
+                        ..Default::default()
+                    }),
+                    run_properties: Some(RunProperties {
+                        bold: on(),
+                        size: Some(Val::new(size)),
+                        size_cs: Some(Val::new(size)),
+                        ..Default::default()
+                    }),
+                    ..Style::new(
+                        StyleType::Paragraph,
+                        format!("Heading{level}"),
+                        format!("heading {level}"),
+                    )
+                }
+            }
+            BuiltinStyle::Title => Style {
+                based_on: Some(Val::new("Normal".to_string())),
+                next: Some(Val::new("Normal".to_string())),
+                ui_priority: Some(Val::new(10)),
+                q_format: on(),
+                paragraph_properties: Some(ParagraphProperties {
+                    spacing: Some(ParagraphSpacing {
+                        after: Some(0),
+                        line: Some(240),
+                        line_rule: Some(LineSpacingRule::Auto),
+                        ..Default::default()
+                    }),
+                    contextual_spacing: on(),
+                    ..Default::default()
+                }),
+                run_properties: Some(RunProperties {
+                    kern: Some(Val::new(28)),
+                    size: Some(Val::new(56)),
+                    size_cs: Some(Val::new(56)),
+                    ..Default::default()
+                }),
+                ..Style::new(StyleType::Paragraph, "Title", "Title")
+            },
+            BuiltinStyle::ListParagraph => Style {
+                based_on: Some(Val::new("Normal".to_string())),
+                ui_priority: Some(Val::new(34)),
+                q_format: on(),
+                paragraph_properties: Some(ParagraphProperties {
+                    indentation: Some(Indentation {
+                        left: Some(720),
+                        ..Default::default()
+                    }),
+                    contextual_spacing: on(),
+                    ..Default::default()
+                }),
+                ..Style::new(StyleType::Paragraph, "ListParagraph", "List Paragraph")
+            },
+            BuiltinStyle::DefaultParagraphFont => Style {
+                default: Some(true),
+                ui_priority: Some(Val::new(1)),
+                semi_hidden: on(),
+                unhide_when_used: on(),
+                ..Style::new(
+                    StyleType::Character,
+                    "DefaultParagraphFont",
+                    "Default Paragraph Font",
+                )
+            },
+            BuiltinStyle::TableNormal => Style {
+                default: Some(true),
+                ui_priority: Some(Val::new(99)),
+                semi_hidden: on(),
+                unhide_when_used: on(),
+                table_properties: Some(TableProperties {
+                    indentation: Some(TableWidth::dxa(0)),
+                    cell_margins: Some(CellMargins {
+                        top: Some(TableWidth::dxa(0)),
+                        left: Some(TableWidth::dxa(108)),
+                        bottom: Some(TableWidth::dxa(0)),
+                        right: Some(TableWidth::dxa(108)),
+                    }),
+                    ..Default::default()
+                }),
+                ..Style::new(StyleType::Table, "TableNormal", "Normal Table")
+            },
+            BuiltinStyle::TableGrid => Style {
+                based_on: Some(Val::new("TableNormal".to_string())),
+                ui_priority: Some(Val::new(39)),
+                paragraph_properties: Some(ParagraphProperties {
+                    spacing: Some(ParagraphSpacing {
+                        after: Some(0),
+                        line: Some(240),
+                        line_rule: Some(LineSpacingRule::Auto),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                table_properties: Some(TableProperties {
+                    borders: Some(TableBorders::all(Border::new("single", 4, "auto"))),
+                    ..Default::default()
+                }),
+                ..Style::new(StyleType::Table, "TableGrid", "Table Grid")
+            },
+            BuiltinStyle::Toc(level) => {
+                let level = level.clamp(1, 9);
+                Style {
+                    based_on: Some(Val::new("Normal".to_string())),
+                    next: Some(Val::new("Normal".to_string())),
+                    auto_redefine: on(),
+                    ui_priority: Some(Val::new(39)),
+                    unhide_when_used: on(),
+                    paragraph_properties: Some(ParagraphProperties {
+                        spacing: Some(ParagraphSpacing {
+                            after: Some(100),
+                            ..Default::default()
+                        }),
+                        // 每级缩进 220 缇
+                        indentation: (level > 1).then(|| Indentation {
+                            left: Some(220 * (level as i32 - 1)),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Style::new(
+                        StyleType::Paragraph,
+                        format!("TOC{level}"),
+                        format!("toc {level}"),
+                    )
+                }
+            }
+            BuiltinStyle::TocHeading => Style {
+                based_on: Some(Val::new("Heading1".to_string())),
+                next: Some(Val::new("Normal".to_string())),
+                ui_priority: Some(Val::new(39)),
+                unhide_when_used: on(),
+                q_format: on(),
+                paragraph_properties: Some(ParagraphProperties {
+                    // 目录标题本身不进入目录
+                    outline_level: Some(Val::new(9)),
+                    ..Default::default()
+                }),
+                ..Style::new(StyleType::Paragraph, "TOCHeading", "TOC Heading")
+            },
+            BuiltinStyle::BlockText => Style {
+                based_on: Some(Val::new("Normal".to_string())),
+                ui_priority: Some(Val::new(99)),
+                unhide_when_used: on(),
+                paragraph_properties: Some(ParagraphProperties {
+                    indentation: Some(Indentation {
+                        left: Some(1152),
+                        right: Some(1152),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                run_properties: Some(RunProperties {
+                    italic: on(),
+                    italic_cs: on(),
+                    ..Default::default()
+                }),
+                ..Style::new(StyleType::Paragraph, "BlockText", "Block Text")
+            },
+            BuiltinStyle::HtmlPreformatted => Style {
+                based_on: Some(Val::new("Normal".to_string())),
+                ui_priority: Some(Val::new(99)),
+                unhide_when_used: on(),
+                paragraph_properties: Some(ParagraphProperties {
+                    spacing: Some(ParagraphSpacing {
+                        after: Some(0),
+                        line: Some(240),
+                        line_rule: Some(LineSpacingRule::Auto),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                run_properties: Some(monospace_font()),
+                ..Style::new(StyleType::Paragraph, "HTMLPreformatted", "HTML Preformatted")
+            },
+            BuiltinStyle::HtmlCode => Style {
+                based_on: Some(Val::new("DefaultParagraphFont".to_string())),
+                ui_priority: Some(Val::new(99)),
+                unhide_when_used: on(),
+                run_properties: Some(monospace_font()),
+                ..Style::new(StyleType::Character, "HTMLCode", "HTML Code")
+            },
+        }
+    }
+}
+
+/// 已读出嵌套的格式属性时，改为逐个子元素重新解析，保留其中未建模的子元素
+///
+/// # 参数
+/// * `slot` - 已读出的属性
+/// * `parent` - 父元素
+/// * `name` - 属性元素名称
+/// * `parse` - 属性的解析函数
+fn reparse<T>(
+    slot: &mut Option<T>,
+    parent: &XmlElement,
+    name: &str,
+    parse: fn(&XmlElement) -> Result<T>,
+) -> Result<()> {
+    if slot.is_some() {
+        if let Some(element) = parent.child(name) {
+            *slot = Some(parse(element)?);
+        }
+    }
+    Ok(())
+}
+
+/// 代码样式使用的等宽字体，字号 10 磅
+fn monospace_font() -> RunProperties {
+    let font = || Some("Courier New".to_string());
+    RunProperties {
+        fonts: Some(RunFonts {
+            ascii: font(),
+            h_ansi: font(),
+            cs: font(),
+            ..Default::default()
+        }),
+        size: Some(Val::new(20)),
+        size_cs: Some(Val::new(20)),
+        ..Default::default()
+    }
+}
+
+/// 表格样式条件格式的适用区域
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum TableStyleOverrideType {
+    /// 整个表格
+    WholeTable,
+    /// 奇数镶边列
+    Band1Vert,
+    /// 偶数镶边列
+    Band2Vert,
+    /// 奇数镶边行
+    Band1Horz,
+    /// 偶数镶边行
+    Band2Horz,
+    /// 第一列
+    FirstCol,
+    /// 最后一列
+    LastCol,
+    /// 标题行
+    FirstRow,
+    /// 汇总行
+    LastRow,
+    /// 右上角单元格
+    NeCell,
+    /// 左上角单元格
+    NwCell,
+    /// 右下角单元格
+    SeCell,
+    /// 左下角单元格
+    SwCell,
+}
+
+/// 表格样式的条件格式 (`w:tblStylePr`)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TableStyleConditionalFormat {
+    /// 适用区域
+    #[serde(rename = "@w:type", alias = "@type")]
+    pub override_type: TableStyleOverrideType,
+    #[serde(rename = "w:pPr", alias = "pPr", skip_serializing_if = "Option::is_none")]
+    pub paragraph_properties: Option<ParagraphProperties>,
+    #[serde(rename = "w:rPr", alias = "rPr", skip_serializing_if = "Option::is_none")]
+    pub run_properties: Option<RunProperties>,
+    #[serde(rename = "w:tblPr", alias = "tblPr", skip_serializing_if = "Option::is_none")]
+    pub table_properties: Option<TableProperties>,
+    #[serde(rename = "w:trPr", alias = "trPr", skip_serializing_if = "Option::is_none")]
+    pub table_row_properties: Option<TableRowProperties>,
+    #[serde(rename = "w:tcPr", alias = "tcPr", skip_serializing_if = "Option::is_none")]
+    pub table_cell_properties: Option<TableCellProperties>,
+}
+
+/// 默认运行属性 (`w:rPrDefault`)
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RunPropertiesDefault {
+    #[serde(rename = "w:rPr", alias = "rPr", skip_serializing_if = "Option::is_none")]
     pub run_properties: Option<RunProperties>,
-    // 其他字段如名称、基于等可以在这里添加
+}
+
+/// 默认段落属性 (`w:pPrDefault`)
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ParagraphPropertiesDefault {
+    #[serde(rename = "w:pPr", alias = "pPr", skip_serializing_if = "Option::is_none")]
+    pub paragraph_properties: Option<ParagraphProperties>,
+}
+
+/// 文档默认格式 (`w:docDefaults`)，位于样式层级的最底层
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DocDefaults {
+    #[serde(rename = "w:rPrDefault", alias = "rPrDefault", skip_serializing_if = "Option::is_none")]
+    pub run_properties: Option<RunPropertiesDefault>,
+    #[serde(rename = "w:pPrDefault", alias = "pPrDefault", skip_serializing_if = "Option::is_none")]
+    pub paragraph_properties: Option<ParagraphPropertiesDefault>,
+}
+
+impl DocDefaults {
+    /// 从 `w:docDefaults` 元素解析文档默认格式，其中的格式属性逐个子元素解析
+    ///
+    /// # 参数
+    /// * `element` - 文档默认格式元素
+    pub fn from_element(element: &XmlElement) -> Result<Self> {
+        let run_properties = element
+            .child("w:rPrDefault")
+            .map(|default| -> Result<_> {
+                Ok(RunPropertiesDefault {
+                    run_properties: default
+                        .child("w:rPr")
+                        .map(RunProperties::from_element)
+                        .transpose()?,
+                })
+            })
+            .transpose()?;
+        let paragraph_properties = element
+            .child("w:pPrDefault")
+            .map(|default| -> Result<_> {
+                Ok(ParagraphPropertiesDefault {
+                    paragraph_properties: default
+                        .child("w:pPr")
+                        .map(ParagraphProperties::from_element)
+                        .transpose()?,
+                })
+            })
+            .transpose()?;
+        Ok(DocDefaults {
+            run_properties,
+            paragraph_properties,
+        })
+    }
+}
+
+/// 隐含样式的例外设置 (`w:lsdException`)
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LatentStyleException {
+    /// 样式名称
+    #[serde(rename = "@w:name", alias = "@name")]
+    pub name: String,
+    #[serde(rename = "@w:locked", alias = "@locked", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+    #[serde(rename = "@w:uiPriority", alias = "@uiPriority", skip_serializing_if = "Option::is_none")]
+    pub ui_priority: Option<i32>,
+    #[serde(rename = "@w:semiHidden", alias = "@semiHidden", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub semi_hidden: Option<bool>,
+    #[serde(rename = "@w:unhideWhenUsed", alias = "@unhideWhenUsed", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub unhide_when_used: Option<bool>,
+    #[serde(rename = "@w:qFormat", alias = "@qFormat", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub q_format: Option<bool>,
+}
+
+/// 隐含样式 (`w:latentStyles`)，即未写入样式表的内置样式的默认设置
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LatentStyles {
+    #[serde(rename = "@w:defLockedState", alias = "@defLockedState", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub default_locked_state: Option<bool>,
+    #[serde(rename = "@w:defUIPriority", alias = "@defUIPriority", skip_serializing_if = "Option::is_none")]
+    pub default_ui_priority: Option<i32>,
+    #[serde(rename = "@w:defSemiHidden", alias = "@defSemiHidden", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub default_semi_hidden: Option<bool>,
+    #[serde(rename = "@w:defUnhideWhenUsed", alias = "@defUnhideWhenUsed", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub default_unhide_when_used: Option<bool>,
+    #[serde(rename = "@w:defQFormat", alias = "@defQFormat", with = "on_off_attr", default, skip_serializing_if = "Option::is_none")]
+    pub default_q_format: Option<bool>,
+    /// 隐含样式数量
+    #[serde(rename = "@w:count", alias = "@count", skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    #[serde(rename = "w:lsdException", alias = "lsdException", default, skip_serializing_if = "Vec::is_empty")]
+    pub exceptions: Vec<LatentStyleException>,
 }
 
 /// 样式集合结构体，包含所有样式定义
-#[derive(Debug, Default, Serialize)]
-#[serde(rename = "w:styles")]
+#[derive(Debug, Clone)]
 pub struct Styles {
+    /// WordML命名空间
+    pub xmlns_w: String,
+    /// 原样式表根元素上的其他属性，如其他命名空间声明和 `mc:Ignorable`；
+    /// 保存时原样写回，使保留下来的未知元素中的前缀仍有定义
+    pub other_attributes: Vec<(String, String)>,
+    /// 文档默认格式
+    pub doc_defaults: Option<DocDefaults>,
+    /// 隐含样式
+    pub latent_styles: Option<LatentStyles>,
     /// 样式列表
-    #[serde(rename = "w:style")]
-    styles: Vec<Style>,
+    pub styles: Vec<Style>,
 }
 
-/// 样式根结构体，用于反序列化
-#[derive(Debug, Deserialize)]
-struct StylesRoot {
-    /// 样式列表
-    #[serde(rename = "style", default)]
-    styles: Vec<Style>,
+impl Serialize for Styles {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        // 根元素上的属性不固定，以映射的形式写出
+        serializer.serialize_newtype_variant("Styles", 0, "w:styles", &StylesContent(self))
+    }
+}
+
+/// 样式表根元素的属性和内容
+struct StylesContent<'a>(&'a Styles);
+
+impl Serialize for StylesContent<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let styles = self.0;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("@xmlns:w", &styles.xmlns_w)?;
+        for (name, value) in &styles.other_attributes {
+            map.serialize_entry(&format!("@{name}"), value)?;
+        }
+        if let Some(doc_defaults) = &styles.doc_defaults {
+            map.serialize_entry("w:docDefaults", doc_defaults)?;
+        }
+        if let Some(latent_styles) = &styles.latent_styles {
+            map.serialize_entry("w:latentStyles", latent_styles)?;
+        }
+        map.serialize_entry("w:style", &styles.styles)?;
+        map.end()
+    }
+}
+
+impl Default for Styles {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Styles {
+    /// 创建空的样式集合
+    pub fn new() -> Self {
+        Styles {
+            xmlns_w: "http://schemas.openxmlformats.org/wordprocessingml/2006/main".to_string(),
+            other_attributes: Vec::new(),
+            doc_defaults: None,
+            latent_styles: None,
+            styles: Vec::new(),
+        }
+    }
+
     /// 从XML内容解析样式
     /// 
     /// # 参数
@@ -64,15 +729,24 @@ impl Styles {
             return Ok(Styles::default());
         }
 
-        // 反序列化XML内容
-        let root: StylesRoot = quick_xml::de::from_str(xml_content)?;
-        let styles = root
-            .styles
-            .into_iter()
-            // 可以在这里映射为HashMap等其他数据结构
-            .collect();
-
-        Ok(Styles { styles })
+        // 逐个样式解析，保留根元素的属性和未建模的子元素
+        let root = XmlElement::parse(xml_content)?;
+        let mut styles = Styles::new();
+        for (name, value) in &root.attributes {
+            match name.as_str() {
+                "xmlns:w" => styles.xmlns_w = value.clone(),
+                _ => styles.other_attributes.push((name.clone(), value.clone())),
+            }
+        }
+        for child in &root.children {
+            match child.name.as_str() {
+                "w:docDefaults" => styles.doc_defaults = Some(DocDefaults::from_element(child)?),
+                "w:latentStyles" => styles.latent_styles = Some(quick_xml::de::from_str(&child.to_xml()?)?),
+                "w:style" => styles.styles.push(Style::from_element(child)?),
+                _ => {}
+            }
+        }
+        Ok(styles)
     }
 
     /// 根据样式ID查找样式
@@ -82,4 +756,229 @@ impl Styles {
     pub fn find_style(&self, style_id: &str) -> Option<&Style> {
         self.styles.iter().find(|s| s.style_id == style_id)
     }
-}
\ No newline at end of file
+
+    /// 根据样式ID查找样式的可变引用
+    ///
+    /// # 参数
+    /// * `style_id` - 要查找的样式ID
+    pub fn find_style_mut(&mut self, style_id: &str) -> Option<&mut Style> {
+        self.styles.iter_mut().find(|s| s.style_id == style_id)
+    }
+
+    /// 根据样式名称查找样式，名称不区分大小写，也匹配样式别名
+    ///
+    /// # 参数
+    /// * `name` - 样式名称，如 `heading 1`
+    pub fn find_style_by_name(&self, name: &str) -> Option<&Style> {
+        self.styles.iter().find(|s| s.matches_name(name))
+    }
+
+    /// 添加样式，样式ID已存在时返回错误
+    ///
+    /// # 参数
+    /// * `style` - 要添加的样式
+    pub fn add_style(&mut self, style: Style) -> Result<()> {
+        if self.find_style(&style.style_id).is_some() {
+            return Err(OfficeError::InvalidFormat(format!(
+                "style `{}` already exists",
+                style.style_id
+            )));
+        }
+        self.set_style(style);
+        Ok(())
+    }
+
+    /// 添加或替换样式，返回被替换的同ID样式。
+    /// 新样式为默认样式时，同类型的其他样式不再作为默认样式
+    ///
+    /// # 参数
+    /// * `style` - 样式
+    pub fn set_style(&mut self, style: Style) -> Option<Style> {
+        if style.default == Some(true) {
+            for other in self
+                .styles
+                .iter_mut()
+                .filter(|s| s.style_type == style.style_type && s.style_id != style.style_id)
+            {
+                other.default = None;
+            }
+        }
+        match self.find_style_mut(&style.style_id) {
+            Some(existing) => Some(std::mem::replace(existing, style)),
+            None => {
+                self.styles.push(style);
+                None
+            }
+        }
+    }
+
+    /// 删除样式并返回被删除的样式
+    ///
+    /// # 参数
+    /// * `style_id` - 样式ID
+    pub fn remove_style(&mut self, style_id: &str) -> Option<Style> {
+        let index = self.styles.iter().position(|s| s.style_id == style_id)?;
+        Some(self.styles.remove(index))
+    }
+
+    /// 确保内置样式存在，缺失时连同其基础样式一起添加，返回该样式。
+    /// 已有同名样式（如样式ID为 `1` 的 `Normal`）时直接使用已有样式
+    ///
+    /// # 参数
+    /// * `builtin` - 内置样式
+    pub fn ensure_builtin(&mut self, builtin: BuiltinStyle) -> &mut Style {
+        let mut style = builtin.style();
+        let existing = self.styles.iter().position(|s| {
+            s.style_type == style.style_type
+                && (s.style_id == style.style_id
+                    || style.name.as_ref().is_some_and(|n| s.matches_name(&n.val)))
+        });
+        let index = match existing {
+            Some(index) => index,
+            None => {
+                // 引用的内置样式在文档中的实际ID可能与内置ID不同（如本地化的样式ID）
+                if let Some(base) = builtin.base() {
+                    style.based_on = Some(Val::new(self.ensure_builtin(base).style_id.clone()));
+                }
+                let references = [&mut style.next, &mut style.link];
+                for reference in references.into_iter().flatten() {
+                    if let Some(target) = BuiltinStyle::from_id(&reference.val) {
+                        if target != builtin {
+                            reference.val = self.ensure_builtin(target).style_id.clone();
+                        }
+                    }
+                }
+                // 已有其他默认样式时不抢占默认地位
+                if style.default == Some(true) && self.default_style(style.style_type).is_some() {
+                    style.default = None;
+                }
+                self.styles.push(style);
+                self.styles.len() - 1
+            }
+        };
+        &mut self.styles[index]
+    }
+
+    /// 获取指定类型的默认样式
+    ///
+    /// # 参数
+    /// * `style_type` - 样式类型
+    pub fn default_style(&self, style_type: StyleType) -> Option<&Style> {
+        self.styles
+            .iter()
+            .find(|s| s.style_type == style_type && s.default == Some(true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_update_and_remove_styles() {
+        let mut styles = Styles::new();
+        let mut normal = Style::new(StyleType::Paragraph, "Normal", "Normal");
+        normal.default = Some(true);
+        styles.add_style(normal).unwrap();
+        assert!(styles.add_style(Style::new(StyleType::Paragraph, "Normal", "Normal")).is_err());
+
+        let mut body = Style::new(StyleType::Paragraph, "Body", "Body");
+        body.default = Some(true);
+        assert!(styles.set_style(body).is_none());
+        assert_eq!(styles.default_style(StyleType::Paragraph).unwrap().style_id, "Body");
+        assert_eq!(styles.find_style("Normal").unwrap().default, None);
+
+        let replaced = styles.set_style(Style::new(StyleType::Paragraph, "Normal", "Plain"));
+        assert_eq!(replaced.unwrap().name.unwrap().val, "Normal");
+        assert_eq!(styles.styles.len(), 2);
+
+        assert!(styles.remove_style("Body").is_some());
+        assert!(styles.remove_style("Body").is_none());
+        assert!(styles.find_style("Body").is_none());
+    }
+
+    #[test]
+    fn find_style_by_name_ignores_case_and_checks_aliases() {
+        let mut styles = Styles::new();
+        let mut quote = Style::new(StyleType::Paragraph, "a1", "Quote");
+        quote.aliases = Some(Val { val: "引用,Citation".to_string() });
+        styles.add_style(quote).unwrap();
+
+        assert_eq!(styles.find_style_by_name("quote").unwrap().style_id, "a1");
+        assert_eq!(styles.find_style_by_name("citation").unwrap().style_id, "a1");
+        assert_eq!(styles.find_style_by_name("引用").unwrap().style_id, "a1");
+        assert!(styles.find_style_by_name("Title").is_none());
+    }
+
+    #[test]
+    fn ensure_builtin_reuses_localized_base_style() {
+        let mut styles = Styles::new();
+        styles.add_style(Style::new(StyleType::Paragraph, "a", "Normal")).unwrap();
+
+        let heading = styles.ensure_builtin(BuiltinStyle::Heading(1)).clone();
+        assert_eq!(heading.based_on.map(|v| v.val).as_deref(), Some("a"));
+        assert_eq!(styles.styles.len(), 2);
+
+        styles.ensure_builtin(BuiltinStyle::Heading(1));
+        assert_eq!(styles.styles.len(), 2);
+    }
+
+    #[test]
+    fn builtin_references_use_existing_localized_ids() {
+        let mut styles = Styles::new();
+        styles.add_style(Style::new(StyleType::Paragraph, "a", "Normal")).unwrap();
+        styles.add_style(Style::new(StyleType::Paragraph, "1", "heading 1")).unwrap();
+
+        let heading = styles.ensure_builtin(BuiltinStyle::TocHeading).clone();
+        assert_eq!(heading.style_id, "TOCHeading");
+        assert_eq!(heading.based_on.map(|v| v.val).as_deref(), Some("1"));
+        assert_eq!(heading.next.map(|v| v.val).as_deref(), Some("a"));
+        assert_eq!(styles.styles.len(), 3);
+    }
+
+    #[test]
+    fn missing_builtin_references_are_added() {
+        let mut styles = Styles::new();
+        styles.ensure_builtin(BuiltinStyle::TocHeading);
+
+        let ids: Vec<&str> = styles.styles.iter().map(|s| s.style_id.as_str()).collect();
+        assert_eq!(ids, ["Normal", "Heading1", "TOCHeading"]);
+    }
+
+    #[test]
+    fn styles_xml_round_trips_unknown_content() {
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+            <w:styles xmlns:mc=\"http://schemas.openxmlformats.org/markup-compatibility/2006\" \
+            xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\" \
+            xmlns:w14=\"http://schemas.microsoft.com/office/word/2010/wordml\" mc:Ignorable=\"w14\">\
+            <w:docDefaults><w:rPrDefault><w:rPr><w:sz w:val=\"22\"/>\
+            <w14:ligatures w14:val=\"standard\"/></w:rPr></w:rPrDefault></w:docDefaults>\
+            <w:latentStyles w:count=\"376\"><w:lsdException w:name=\"Normal\" w:qFormat=\"1\"/>\
+            </w:latentStyles>\
+            <w:style w:type=\"paragraph\" w:styleId=\"Quote\"><w:name w:val=\"Quote\"/>\
+            <w:personal/><w:pPr><w:framePr w:w=\"2000\"/><w:jc w:val=\"center\"/></w:pPr>\
+            <w14:extra w14:val=\"1\"/></w:style>\
+            <w:style w:type=\"table\" w:styleId=\"Grid\"><w:name w:val=\"Grid\"/>\
+            <w:tblStylePr w:type=\"firstRow\"><w:rPr><w:b/><w14:shadow/></w:rPr></w:tblStylePr>\
+            </w:style></w:styles>";
+        let styles = Styles::from_xml(xml).unwrap();
+        let quote = styles.find_style("Quote").unwrap();
+        assert_eq!(quote.personal, Some(OnOff::ON));
+        assert_eq!(quote.unknown[0].name, "w14:extra");
+        let p_pr = quote.paragraph_properties.as_ref().unwrap();
+        assert!(p_pr.justification.is_some());
+        assert_eq!(p_pr.unknown[0].name, "w:framePr");
+        let grid = styles.find_style("Grid").unwrap();
+        let first_row = grid.conditional_formats[0].run_properties.as_ref().unwrap();
+        assert_eq!(first_row.unknown[0].name, "w14:shadow");
+
+        let saved = quick_xml::se::to_string(&styles).unwrap();
+        assert!(saved.contains("mc:Ignorable=\"w14\""));
+        assert!(saved.contains("xmlns:w14=\"http://schemas.microsoft.com/office/word/2010/wordml\""));
+        assert!(saved.contains("<w14:ligatures w14:val=\"standard\"/>"));
+        let reparsed = Styles::from_xml(&saved).unwrap();
+        assert_eq!(reparsed.styles, styles.styles);
+        assert_eq!(reparsed.other_attributes, styles.other_attributes);
+        assert_eq!(quick_xml::se::to_string(&reparsed).unwrap(), saved);
+    }
+}
@@ -0,0 +1,1046 @@
+//! 表格逻辑网格：解析横向跨列 (`w:gridSpan`) 与纵向合并 (`w:vMerge`)，
+//! 按可视行列访问和编辑表格
+
+use std::ops::Range;
+
+use super::elements::{BodyContent, Table, TableCell, TableContent, TableItem, TableRow};
+use super::properties::{
+    GridColumn, Merge, TableCellProperties, TableGrid, TableRowProperties, TableWidth, Val,
+    WidthType,
+};
+use crate::error::{OfficeError, Result};
+
+/// 网格中的逻辑单元格，即一个（可能经过合并的）单元格区域
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridCell {
+    /// 区域左上角所在的行
+    pub row: usize,
+    /// 区域左上角所在的网格列
+    pub col: usize,
+    /// 纵向跨越的行数
+    pub row_span: usize,
+    /// 横向跨越的网格列数
+    pub col_span: usize,
+    /// 区域起始单元格在所在行的单元格（[`TableRow::cells`]）中的下标
+    pub cell_index: usize,
+    /// 纵向合并的延续单元格在其所在行中的下标
+    continuations: Vec<usize>,
+}
+
+impl GridCell {
+    /// 判断区域是否覆盖指定位置
+    ///
+    /// # 参数
+    /// * `row` - 行号
+    /// * `col` - 网格列号
+    pub fn covers(&self, row: usize, col: usize) -> bool {
+        (self.row..self.row + self.row_span).contains(&row)
+            && (self.col..self.col + self.col_span).contains(&col)
+    }
+
+    /// 是否为合并单元格
+    pub fn is_merged(&self) -> bool {
+        self.row_span > 1 || self.col_span > 1
+    }
+}
+
+/// 表格的逻辑网格视图
+#[derive(Debug, Clone, Default)]
+pub struct GridView {
+    columns: usize,
+    cells: Vec<GridCell>,
+    /// 每个网格位置所属的逻辑单元格下标
+    slots: Vec<Vec<Option<usize>>>,
+}
+
+impl GridView {
+    /// 由表格构建网格视图
+    ///
+    /// # 参数
+    /// * `table` - 表格
+    pub fn new(table: &Table) -> Self {
+        let mut cells: Vec<GridCell> = Vec::new();
+        let mut slots: Vec<Vec<Option<usize>>> = Vec::new();
+
+        for (r, row) in table.rows().enumerate() {
+            let mut line: Vec<Option<usize>> = Vec::new();
+            let mut col = row
+                .properties
+                .as_ref()
+                .and_then(|p| p.grid_before.as_ref())
+                .map_or(0, |v| v.val as usize);
+            line.resize(col, None);
+
+            for (cell_index, cell) in row.cells().enumerate() {
+                let props = cell.properties.as_ref();
+                let span = props.map_or(1, |p| p.span());
+                let continues = |merge: Option<&Merge>| merge.is_some_and(|m| !m.is_restart());
+
+                // 旧式横向合并：并入同一行左侧的单元格
+                let left = col.checked_sub(1).and_then(|c| line[c]);
+                if let Some(index) =
+                    left.filter(|_| continues(props.and_then(|p| p.h_merge.as_ref())))
+                {
+                    cells[index].col_span += span;
+                    line.resize(col + span, Some(index));
+                    col += span;
+                    continue;
+                }
+
+                // 纵向合并：延续上一行同一起始列的区域
+                let above = r
+                    .checked_sub(1)
+                    .and_then(|p| slots[p].get(col).copied().flatten())
+                    .filter(|&index| cells[index].col == col);
+                if let Some(index) =
+                    above.filter(|_| continues(props.and_then(|p| p.v_merge.as_ref())))
+                {
+                    cells[index].row_span = r - cells[index].row + 1;
+                    cells[index].continuations.push(cell_index);
+                    line.resize(col + span, Some(index));
+                    col += span;
+                    continue;
+                }
+
+                cells.push(GridCell {
+                    row: r,
+                    col,
+                    row_span: 1,
+                    col_span: span,
+                    cell_index,
+                    continuations: Vec::new(),
+                });
+                line.resize(col + span, Some(cells.len() - 1));
+                col += span;
+            }
+            slots.push(line);
+        }
+
+        let grid_columns = table.grid.as_ref().map_or(0, |g| g.columns.len());
+        let columns = slots
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+            .max(grid_columns);
+        for line in &mut slots {
+            line.resize(columns, None);
+        }
+
+        GridView {
+            columns,
+            cells,
+            slots,
+        }
+    }
+
+    /// 行数
+    pub fn rows(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// 网格列数
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// 所有逻辑单元格，按起始位置的行优先顺序排列
+    pub fn cells(&self) -> &[GridCell] {
+        &self.cells
+    }
+
+    /// 获取覆盖指定位置的逻辑单元格
+    ///
+    /// # 参数
+    /// * `row` - 行号
+    /// * `col` - 网格列号
+    pub fn cell_at(&self, row: usize, col: usize) -> Option<&GridCell> {
+        let index = self.slots.get(row)?.get(col).copied().flatten()?;
+        self.cells.get(index)
+    }
+}
+
+impl Table {
+    /// 获取表格的逻辑网格视图
+    pub fn grid_view(&self) -> GridView {
+        GridView::new(self)
+    }
+
+    /// 获取覆盖指定位置的单元格（合并区域的起始单元格）
+    ///
+    /// # 参数
+    /// * `row` - 行号
+    /// * `col` - 网格列号
+    pub fn cell_at(&self, row: usize, col: usize) -> Option<&TableCell> {
+        let cell = self.grid_view().cell_at(row, col)?.clone();
+        self.rows().nth(cell.row)?.cells().nth(cell.cell_index)
+    }
+
+    /// 获取覆盖指定位置的单元格的可变引用
+    ///
+    /// # 参数
+    /// * `row` - 行号
+    /// * `col` - 网格列号
+    pub fn cell_at_mut(&mut self, row: usize, col: usize) -> Option<&mut TableCell> {
+        let cell = self.grid_view().cell_at(row, col)?.clone();
+        self.rows_mut().nth(cell.row)?.cells_mut().nth(cell.cell_index)
+    }
+
+    /// 将表格转换为矩形的文本网格，合并区域的文本只出现在其左上角位置
+    pub fn to_string_grid(&self) -> Vec<Vec<String>> {
+        let view = self.grid_view();
+        let rows: Vec<&TableRow> = self.rows().collect();
+        let mut grid = vec![vec![String::new(); view.columns()]; view.rows()];
+        for cell in view.cells() {
+            if let Some(table_cell) = rows[cell.row].cells().nth(cell.cell_index) {
+                grid[cell.row][cell.col] = table_cell.text();
+            }
+        }
+        grid
+    }
+
+    /// 合并一个矩形区域内的单元格，各单元格中的非空段落依次移入合并后的单元格
+    ///
+    /// # 参数
+    /// * `rows` - 行范围
+    /// * `cols` - 网格列范围
+    pub fn merge_cells(&mut self, rows: Range<usize>, cols: Range<usize>) -> Result<()> {
+        let mut layout = Layout::take(self);
+        let result = layout.merge(rows, cols);
+        layout.write(self);
+        result
+    }
+
+    /// 拆分覆盖指定位置的合并单元格，拆出的单元格为空
+    ///
+    /// # 参数
+    /// * `row` - 行号
+    /// * `col` - 网格列号
+    pub fn unmerge_cell(&mut self, row: usize, col: usize) -> Result<()> {
+        let mut layout = Layout::take(self);
+        let result = layout.unmerge(row, col);
+        layout.write(self);
+        result
+    }
+
+    /// 在指定位置插入一行，新行沿用相邻行的单元格结构和格式；
+    /// 跨越插入位置的纵向合并区域随之延伸
+    ///
+    /// # 参数
+    /// * `index` - 新行的行号
+    pub fn insert_row(&mut self, index: usize) -> Result<()> {
+        let mut layout = Layout::take(self);
+        let result = layout.insert_row(index);
+        layout.write(self);
+        result
+    }
+
+    /// 删除一行，跨越该行的合并区域随之缩短
+    ///
+    /// # 参数
+    /// * `index` - 行号
+    pub fn delete_row(&mut self, index: usize) -> Result<()> {
+        let mut layout = Layout::take(self);
+        let result = layout.delete_row(index);
+        layout.write(self);
+        result
+    }
+
+    /// 在指定位置插入一列，新列沿用相邻列的宽度和格式；
+    /// 跨越插入位置的横向合并区域随之加宽
+    ///
+    /// # 参数
+    /// * `index` - 新列的网格列号
+    pub fn insert_column(&mut self, index: usize) -> Result<()> {
+        let mut layout = Layout::take(self);
+        let result = layout.insert_column(index);
+        layout.write(self);
+        result
+    }
+
+    /// 删除一列，跨越该列的合并区域随之变窄
+    ///
+    /// # 参数
+    /// * `index` - 网格列号
+    pub fn delete_column(&mut self, index: usize) -> Result<()> {
+        let mut layout = Layout::take(self);
+        let result = layout.delete_column(index);
+        layout.write(self);
+        result
+    }
+}
+
+/// 编辑用的区域，持有起始单元格及延续单元格的属性
+struct Region {
+    row: usize,
+    col: usize,
+    row_span: usize,
+    col_span: usize,
+    cell: TableCell,
+    /// 延续单元格的属性，长度为 `row_span - 1`
+    continuations: Vec<Option<TableCellProperties>>,
+    /// 各行单元格在该行内容中所占的位置（见 [`restore_items`]），长度为 `row_span`
+    slots: Vec<Option<usize>>,
+    /// 跨越的列发生变化，写回时需按表格网格重新计算宽度
+    resized: bool,
+}
+
+impl Region {
+    fn rows(&self) -> Range<usize> {
+        self.row..self.row + self.row_span
+    }
+
+    fn cols(&self) -> Range<usize> {
+        self.col..self.col + self.col_span
+    }
+}
+
+/// 表格的可编辑网格布局，编辑完成后重新写回表格
+struct Layout {
+    /// 取出各行后的表格内容，保留行级内容控件的结构
+    skeleton: Vec<TableContent>,
+    /// 各行的属性和取出单元格后的行内容，单元格保存在区域中
+    rows: Vec<TableRow>,
+    /// 各行在表格内容中所占的位置
+    row_slots: Vec<Option<usize>>,
+    columns: usize,
+    grid: Option<TableGrid>,
+    regions: Vec<Region>,
+}
+
+impl Layout {
+    /// 从表格中取出所有行和单元格，构建可编辑布局
+    fn take(table: &mut Table) -> Self {
+        let view = table.grid_view();
+        let mut skeleton = std::mem::take(&mut table.content);
+        let mut rows = take_items(&mut skeleton);
+        let mut cells: Vec<Vec<Option<TableCell>>> = rows
+            .iter_mut()
+            .map(|row| take_items(&mut row.content).into_iter().map(Some).collect())
+            .collect();
+
+        let regions = view
+            .cells
+            .iter()
+            .map(|c| Region {
+                row: c.row,
+                col: c.col,
+                row_span: c.row_span,
+                col_span: c.col_span,
+                cell: cells[c.row][c.cell_index].take().unwrap_or_default(),
+                resized: false,
+                slots: std::iter::once(c.cell_index)
+                    .chain(c.continuations.iter().copied())
+                    .map(Some)
+                    .collect(),
+                continuations: c
+                    .continuations
+                    .iter()
+                    .enumerate()
+                    .map(|(offset, &index)| {
+                        cells[c.row + offset + 1][index]
+                            .take()
+                            .and_then(|cell| cell.properties)
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Layout {
+            skeleton,
+            row_slots: (0..rows.len()).map(Some).collect(),
+            rows,
+            columns: view.columns,
+            grid: table.grid.take(),
+            regions,
+        }
+    }
+
+    /// 每个网格位置所属的区域下标
+    fn occupancy(&self) -> Vec<Vec<Option<usize>>> {
+        let mut slots = vec![vec![None; self.columns]; self.rows.len()];
+        for (index, region) in self.regions.iter().enumerate() {
+            for r in region.rows() {
+                for c in region.cols() {
+                    if let Some(slot) = slots.get_mut(r).and_then(|l| l.get_mut(c)) {
+                        *slot = Some(index);
+                    }
+                }
+            }
+        }
+        slots
+    }
+
+    fn check_row(&self, row: usize) -> Result<()> {
+        if row < self.rows.len() {
+            Ok(())
+        } else {
+            Err(OfficeError::InvalidFormat(format!(
+                "row {row} out of range (table has {} rows)",
+                self.rows.len()
+            )))
+        }
+    }
+
+    fn check_column(&self, col: usize) -> Result<()> {
+        if col < self.columns {
+            Ok(())
+        } else {
+            Err(OfficeError::InvalidFormat(format!(
+                "column {col} out of range (table has {} columns)",
+                self.columns
+            )))
+        }
+    }
+
+    fn merge(&mut self, rows: Range<usize>, cols: Range<usize>) -> Result<()> {
+        if rows.is_empty() || cols.is_empty() {
+            return Err(OfficeError::InvalidFormat("empty merge range".to_string()));
+        }
+        self.check_row(rows.end - 1)?;
+        self.check_column(cols.end - 1)?;
+
+        // 只能合并完整的区域，不能切开已有的合并单元格
+        let inside = |region: &Region| {
+            rows.start <= region.row
+                && region.row + region.row_span <= rows.end
+                && cols.start <= region.col
+                && region.col + region.col_span <= cols.end
+        };
+        let overlaps = |region: &Region| {
+            region.row < rows.end
+                && rows.start < region.row + region.row_span
+                && region.col < cols.end
+                && cols.start < region.col + region.col_span
+        };
+        if self.regions.iter().any(|r| overlaps(r) && !inside(r)) {
+            return Err(OfficeError::InvalidFormat(
+                "merge range partially overlaps a merged cell".to_string(),
+            ));
+        }
+
+        let (mut merged, rest): (Vec<Region>, Vec<Region>) = std::mem::take(&mut self.regions)
+            .into_iter()
+            .partition(|r| inside(r));
+        self.regions = rest;
+        merged.sort_by_key(|r| (r.row, r.col));
+
+        // 合并后的宽度为首行各单元格宽度之和（均以缇为单位时）
+        let first_row: Vec<&Region> = merged.iter().filter(|r| r.row == rows.start).collect();
+        let width = first_row
+            .iter()
+            .map(|r| {
+                r.cell
+                    .properties
+                    .as_ref()
+                    .and_then(|p| p.width.as_ref())
+                    .filter(|w| w.width_type == Some(WidthType::Dxa))
+                    .map(|w| w.width)
+            })
+            .sum::<Option<i32>>();
+
+        // 合并后各行的单元格放在该行最左侧单元格的位置
+        let slots = rows
+            .clone()
+            .map(|r| {
+                merged
+                    .iter()
+                    .filter(|region| region.rows().contains(&r))
+                    .min_by_key(|region| region.col)
+                    .and_then(|region| region.slots[r - region.row])
+            })
+            .collect();
+        let mut target = match merged.first() {
+            Some(first) if first.row == rows.start && first.col == cols.start => TableCell {
+                properties: first.cell.properties.clone(),
+                content: Vec::new(),
+            },
+            _ => TableCell::default(),
+        };
+        let mut continuation = None;
+        for region in merged {
+            if region.row > rows.start
+                && region.col == cols.start
+                && region.row + region.row_span == rows.end
+            {
+                // 保留最后一行单元格的属性（如底部边框）
+                continuation = Some(region.cell.properties.clone());
+            }
+            let blocks = region.cell.content.into_iter().filter(
+                |block| !matches!(block, BodyContent::Paragraph(p) if p.content.is_empty()),
+            );
+            target.content.extend(blocks);
+        }
+        if target.content.is_empty() {
+            target = TableCell::empty(target.properties);
+        }
+        if let (Some(width), Some(props)) = (width, target.properties.as_mut()) {
+            props.width = Some(TableWidth::dxa(width));
+        }
+
+        let row_span = rows.len();
+        let mut continuations = vec![None; row_span - 1];
+        if let (Some(last), Some(props)) = (continuations.last_mut(), continuation) {
+            *last = props;
+        }
+        self.regions.push(Region {
+            row: rows.start,
+            col: cols.start,
+            row_span,
+            col_span: cols.len(),
+            cell: target,
+            continuations,
+            slots,
+            resized: true,
+        });
+        Ok(())
+    }
+
+    fn unmerge(&mut self, row: usize, col: usize) -> Result<()> {
+        self.check_row(row)?;
+        self.check_column(col)?;
+        let Some(position) = self
+            .regions
+            .iter()
+            .position(|r| r.rows().contains(&row) && r.cols().contains(&col))
+        else {
+            return Ok(());
+        };
+
+        let mut region = self.regions.swap_remove(position);
+        let template = region.cell.properties.clone();
+        let continuations = std::mem::take(&mut region.continuations);
+        let slots = std::mem::take(&mut region.slots);
+        for r in region.rows() {
+            for c in region.cols() {
+                if r == region.row && c == region.col {
+                    continue;
+                }
+                let properties = if c == region.col && r > region.row {
+                    continuations[r - region.row - 1]
+                        .clone()
+                        .or_else(|| template.clone())
+                } else {
+                    template.clone()
+                };
+                self.regions.push(Region {
+                    row: r,
+                    col: c,
+                    row_span: 1,
+                    col_span: 1,
+                    cell: TableCell::empty(properties),
+                    continuations: Vec::new(),
+                    slots: vec![slots[r - region.row]],
+                    resized: true,
+                });
+            }
+        }
+        region.slots = vec![slots[0]];
+        region.row_span = 1;
+        region.col_span = 1;
+        region.resized = true;
+        self.regions.push(region);
+        Ok(())
+    }
+
+    fn insert_row(&mut self, index: usize) -> Result<()> {
+        if index > self.rows.len() {
+            return Err(OfficeError::InvalidFormat(format!(
+                "row {index} out of range (table has {} rows)",
+                self.rows.len()
+            )));
+        }
+        if self.rows.is_empty() {
+            self.rows.push(TableRow::default());
+            self.row_slots.push(None);
+            for col in 0..self.columns {
+                self.regions.push(Region {
+                    row: 0,
+                    col,
+                    row_span: 1,
+                    col_span: 1,
+                    cell: TableCell::empty(None),
+                    continuations: Vec::new(),
+                    slots: vec![None],
+                    resized: true,
+                });
+            }
+            return Ok(());
+        }
+
+        // 以上方一行（插入到首行时为原首行）为模板
+        let reference = index.saturating_sub(1);
+        let mut new_regions = Vec::new();
+        for region in &mut self.regions {
+            if region.row < index && index < region.row + region.row_span {
+                // 跨越插入位置的纵向合并区域
+                region.continuations.insert(index - region.row - 1, None);
+                let slot = region.slots[index - region.row - 1];
+                region.slots.insert(index - region.row, slot);
+                region.row_span += 1;
+            } else if region.rows().contains(&reference) {
+                let mut properties = region.cell.properties.clone();
+                if let Some(props) = properties.as_mut() {
+                    props.v_merge = None;
+                }
+                new_regions.push(Region {
+                    row: index,
+                    col: region.col,
+                    row_span: 1,
+                    col_span: region.col_span,
+                    cell: TableCell::empty(properties),
+                    continuations: Vec::new(),
+                    slots: vec![region.slots[reference - region.row]],
+                    resized: true,
+                });
+                if region.row >= index {
+                    region.row += 1;
+                }
+            } else if region.row >= index {
+                region.row += 1;
+            }
+        }
+        self.regions.extend(new_regions);
+        let row = self.rows[reference].clone();
+        self.rows.insert(index, row);
+        self.row_slots.insert(index, self.row_slots[reference]);
+        Ok(())
+    }
+
+    fn delete_row(&mut self, index: usize) -> Result<()> {
+        self.check_row(index)?;
+        let mut regions = Vec::with_capacity(self.regions.len());
+        for mut region in std::mem::take(&mut self.regions) {
+            if region.rows().contains(&index) {
+                if region.row_span == 1 {
+                    continue;
+                }
+                region.slots.remove(index - region.row);
+                if region.row == index {
+                    // 删除起始行时，第一个延续单元格接替为起始单元格，内容保留
+                    if let Some(props) = region.continuations.remove(0) {
+                        region.cell.properties = Some(props);
+                    }
+                } else {
+                    region.continuations.remove(index - region.row - 1);
+                }
+                region.row_span -= 1;
+            } else if region.row > index {
+                region.row -= 1;
+            }
+            regions.push(region);
+        }
+        self.regions = regions;
+        self.rows.remove(index);
+        self.row_slots.remove(index);
+        Ok(())
+    }
+
+    fn insert_column(&mut self, index: usize) -> Result<()> {
+        if index > self.columns {
+            return Err(OfficeError::InvalidFormat(format!(
+                "column {index} out of range (table has {} columns)",
+                self.columns
+            )));
+        }
+
+        // 以左侧一列（插入到首列时为原首列）为模板
+        let reference = index.saturating_sub(1);
+        let mut new_regions = Vec::new();
+        for region in &mut self.regions {
+            if region.col < index && index < region.col + region.col_span {
+                region.col_span += 1;
+                region.resized = true;
+            } else if region.cols().contains(&reference) {
+                let mut properties = region.cell.properties.clone();
+                if let Some(props) = properties.as_mut() {
+                    props.grid_span = None;
+                    props.width = props.width.take().map(|w| {
+                        if w.width_type == Some(WidthType::Dxa) {
+                            TableWidth::dxa(w.width / region.col_span as i32)
+                        } else {
+                            w
+                        }
+                    });
+                }
+                new_regions.push(Region {
+                    row: region.row,
+                    col: index,
+                    row_span: region.row_span,
+                    col_span: 1,
+                    cell: TableCell::empty(properties.clone()),
+                    continuations: vec![properties; region.row_span - 1],
+                    slots: region.slots.clone(),
+                    resized: true,
+                });
+                if region.col >= index {
+                    region.col += 1;
+                }
+            } else if region.col >= index {
+                region.col += 1;
+            }
+        }
+        if self.columns == 0 {
+            for (row, _) in self.rows.iter().enumerate() {
+                new_regions.push(Region {
+                    row,
+                    col: 0,
+                    row_span: 1,
+                    col_span: 1,
+                    cell: TableCell::empty(None),
+                    continuations: Vec::new(),
+                    slots: vec![None],
+                    resized: true,
+                });
+            }
+        }
+        self.regions.extend(new_regions);
+        self.columns += 1;
+        if let Some(grid) = self.grid.as_mut() {
+            let width = grid.columns.get(reference).map_or(0, |c| c.width);
+            let index = index.min(grid.columns.len());
+            grid.columns.insert(index, GridColumn { width });
+        }
+        Ok(())
+    }
+
+    fn delete_column(&mut self, index: usize) -> Result<()> {
+        self.check_column(index)?;
+        let mut regions = Vec::with_capacity(self.regions.len());
+        for mut region in std::mem::take(&mut self.regions) {
+            if region.cols().contains(&index) {
+                if region.col_span == 1 {
+                    continue;
+                }
+                region.col_span -= 1;
+                region.resized = true;
+            } else if region.col > index {
+                region.col -= 1;
+            }
+            regions.push(region);
+        }
+        self.regions = regions;
+        self.columns -= 1;
+        if let Some(grid) = self.grid.as_mut().filter(|g| index < g.columns.len()) {
+            grid.columns.remove(index);
+        }
+        Ok(())
+    }
+
+    /// 将布局写回表格
+    fn write(mut self, table: &mut Table) {
+        self.regions.sort_by_key(|r| (r.row, r.col));
+        let slots = self.occupancy();
+        let templates: Vec<Option<TableCellProperties>> = self
+            .regions
+            .iter()
+            .map(|r| r.cell.properties.clone())
+            .collect();
+
+        let mut rows = Vec::with_capacity(self.rows.len());
+        for (r, (mut row, row_slot)) in self.rows.into_iter().zip(self.row_slots).enumerate() {
+            let line = &slots[r];
+            let before = line.iter().take_while(|s| s.is_none()).count();
+            let after = if before == line.len() {
+                0
+            } else {
+                line.iter().rev().take_while(|s| s.is_none()).count()
+            };
+
+            row.properties = with_grid_skips(row.properties.take(), before, after);
+            let mut cells = Vec::new();
+            let mut c = before;
+            while c < line.len() - after {
+                let Some(index) = line[c] else {
+                    cells.push((TableCell::empty(None), None));
+                    c += 1;
+                    continue;
+                };
+                let region = &mut self.regions[index];
+                if region.col != c {
+                    c += 1;
+                    continue;
+                }
+                let vertical = region.row_span > 1;
+                let width = if region.resized {
+                    grid_width(self.grid.as_ref(), region.cols())
+                } else {
+                    None
+                };
+                let cell = if region.row == r {
+                    let mut cell = std::mem::take(&mut region.cell);
+                    set_merge(
+                        &mut cell.properties,
+                        region.col_span,
+                        vertical.then(Merge::restart),
+                    );
+                    fit_width(&mut cell.properties, width);
+                    cell
+                } else {
+                    let mut properties = region.continuations[r - region.row - 1]
+                        .take()
+                        .or_else(|| templates[index].clone());
+                    set_merge(
+                        &mut properties,
+                        region.col_span,
+                        Some(Merge::continuation()),
+                    );
+                    fit_width(&mut properties, width);
+                    TableCell::empty(properties)
+                };
+                cells.push((cell, region.slots[r - region.row]));
+                c += region.col_span;
+            }
+            row.content = restore_items(std::mem::take(&mut row.content), cells);
+            rows.push((row, row_slot));
+        }
+        table.content = restore_items(self.skeleton, rows);
+
+        table.grid = self.grid.map(|mut grid| {
+            resize_grid(&mut grid, self.columns);
+            grid
+        });
+    }
+}
+
+/// 取出内容中的全部表格行或单元格，原处留下空的占位，返回按文档顺序排列的元素
+fn take_items<T: Default>(content: &mut [TableItem<T>]) -> Vec<T> {
+    content
+        .iter_mut()
+        .flat_map(TableItem::items_mut)
+        .map(std::mem::take)
+        .collect()
+}
+
+/// 把元素放回 [`take_items`] 留下的占位：每个元素附带其占位的序号，同一占位可以放入
+/// 零个或多个元素。元素保持给出的顺序，没有占位的元素跟在前一个元素之后；
+/// 内容中没有占位时追加到末尾。不再包含任何元素的内容控件被删除。
+fn restore_items<T>(
+    content: Vec<TableItem<T>>,
+    items: Vec<(T, Option<usize>)>,
+) -> Vec<TableItem<T>> {
+    let count = content.iter().map(|item| item.items().count()).sum();
+    let mut groups: Vec<Vec<T>> = (0..count).map(|_| Vec::new()).collect();
+    let first = items.iter().find_map(|(_, slot)| *slot);
+    let mut last = None;
+    let mut rest = Vec::new();
+    for (item, slot) in items {
+        // 占位序号不得回退，否则元素的顺序会改变
+        let slot = match (slot, last) {
+            (Some(slot), Some(last)) => Some(slot.max(last)),
+            (slot, last) => slot.or(last).or(first),
+        };
+        match slot.filter(|&slot| slot < count) {
+            Some(slot) => {
+                groups[slot].push(item);
+                last = Some(slot);
+            }
+            None => rest.push(item),
+        }
+    }
+
+    let mut output = fill_slots(content, &mut groups.into_iter());
+    output.extend(rest.into_iter().map(TableItem::Item));
+    output
+}
+
+fn fill_slots<T>(
+    content: Vec<TableItem<T>>,
+    groups: &mut std::vec::IntoIter<Vec<T>>,
+) -> Vec<TableItem<T>> {
+    let mut output = Vec::with_capacity(content.len());
+    for item in content {
+        match item {
+            TableItem::Item(_) => {
+                let group = groups.next().unwrap_or_default();
+                output.extend(group.into_iter().map(TableItem::Item));
+            }
+            TableItem::Sdt(mut sdt) => {
+                sdt.content = fill_slots(sdt.content, groups);
+                if !sdt.content.is_empty() {
+                    output.push(TableItem::Sdt(sdt));
+                }
+            }
+        }
+    }
+    output
+}
+
+/// 设置单元格的合并属性，属性为空时清除
+fn set_merge(
+    properties: &mut Option<TableCellProperties>,
+    col_span: usize,
+    v_merge: Option<Merge>,
+) {
+    let props = properties.get_or_insert_with(TableCellProperties::default);
+    props.grid_span = (col_span > 1).then(|| Val::new(col_span as u32));
+    props.h_merge = None;
+    props.v_merge = v_merge;
+    if *props == TableCellProperties::default() {
+        *properties = None;
+    }
+}
+
+/// 设置行首、行尾跳过的网格列数，属性为空时清除
+fn with_grid_skips(
+    properties: Option<TableRowProperties>,
+    before: usize,
+    after: usize,
+) -> Option<TableRowProperties> {
+    let mut props = properties.unwrap_or_default();
+    props.grid_before = (before > 0).then(|| Val::new(before as u32));
+    props.grid_after = (after > 0).then(|| Val::new(after as u32));
+    (props != TableRowProperties::default()).then_some(props)
+}
+
+/// 表格网格中若干列的总宽度（缇）
+fn grid_width(grid: Option<&TableGrid>, cols: Range<usize>) -> Option<i32> {
+    let columns = grid?.columns.get(cols)?;
+    Some(columns.iter().map(|c| c.width).sum())
+}
+
+/// 以缇为单位的单元格宽度按网格宽度更新，其他单位的宽度保持不变
+fn fit_width(properties: &mut Option<TableCellProperties>, width: Option<i32>) {
+    let (Some(props), Some(width)) = (properties.as_mut(), width) else {
+        return;
+    };
+    if let Some(w) = props.width.as_mut() {
+        if w.width_type.unwrap_or_default() == WidthType::Dxa {
+            w.width = width;
+        }
+    }
+}
+
+/// 调整表格网格的列数，新增的列沿用最后一列的宽度
+fn resize_grid(grid: &mut TableGrid, columns: usize) {
+    let width = grid.columns.last().map_or(0, |c| c.width);
+    grid.columns.resize(columns, GridColumn { width });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::test_utils::{document, p};
+
+    fn table(xml: &str) -> Table {
+        match document(xml).body.content.into_iter().next() {
+            Some(BodyContent::Table(table)) => table,
+            _ => panic!("table expected"),
+        }
+    }
+
+    fn row(cells: &[&str]) -> String {
+        let cells: String = cells.iter().map(|c| format!("<w:tc>{}</w:tc>", p(c))).collect();
+        format!("<w:tr>{cells}</w:tr>")
+    }
+
+    fn xml(table: &Table) -> String {
+        quick_xml::se::to_string(table).unwrap()
+    }
+
+    #[test]
+    fn row_edits_keep_row_content_controls() {
+        let mut table = table(&format!(
+            "<w:tbl>{}<w:sdt><w:sdtPr><w:tag w:val=\"rows\"/></w:sdtPr><w:sdtContent>{}{}\
+             </w:sdtContent></w:sdt></w:tbl>",
+            row(&["a", "b"]),
+            row(&["c", "d"]),
+            row(&["e", "f"])
+        ));
+
+        table.insert_row(2).unwrap();
+        let TableItem::Sdt(sdt) = &table.content[1] else {
+            panic!("row content control expected");
+        };
+        assert_eq!(sdt.items().count(), 3);
+        assert_eq!(table.to_string_grid()[3], ["e", "f"]);
+
+        table.delete_row(1).unwrap();
+        table.delete_row(1).unwrap();
+        table.delete_row(1).unwrap();
+        assert_eq!(table.content.len(), 1);
+        assert!(!xml(&table).contains("<w:sdt>"));
+    }
+
+    #[test]
+    fn merge_across_cell_content_control_keeps_leftmost_position() {
+        let mut table = table(&format!(
+            "<w:tbl><w:tr><w:tc>{}</w:tc><w:sdt><w:sdtPr><w:tag w:val=\"c\"/></w:sdtPr>\
+             <w:sdtContent><w:tc>{}</w:tc><w:tc>{}</w:tc></w:sdtContent></w:sdt></w:tr></w:tbl>",
+            p("a"),
+            p("b"),
+            p("c")
+        ));
+
+        table.merge_cells(0..1, 1..3).unwrap();
+        let merged = xml(&table);
+        assert_eq!(merged.matches("<w:tc>").count(), 2);
+        assert!(merged.contains("<w:sdtContent><w:tc><w:tcPr><w:gridSpan w:val=\"2\"/>"));
+        assert_eq!(table.to_string_grid()[0], ["a", "b\nc", ""]);
+
+        table.merge_cells(0..1, 0..3).unwrap();
+        assert!(!xml(&table).contains("<w:sdt>"));
+    }
+
+    #[test]
+    fn grid_view_resolves_spans_and_vertical_merges() {
+        let table = table(
+            "<w:tbl><w:tblGrid><w:gridCol w:w=\"1000\"/><w:gridCol w:w=\"1000\"/>\
+             <w:gridCol w:w=\"1000\"/></w:tblGrid>\
+             <w:tr><w:tc><w:tcPr><w:gridSpan w:val=\"2\"/></w:tcPr><w:p/></w:tc>\
+             <w:tc><w:tcPr><w:vMerge w:val=\"restart\"/></w:tcPr><w:p/></w:tc></w:tr>\
+             <w:tr><w:tc><w:p/></w:tc><w:tc><w:p/></w:tc>\
+             <w:tc><w:tcPr><w:vMerge/></w:tcPr><w:p/></w:tc></w:tr></w:tbl>",
+        );
+
+        let view = table.grid_view();
+        assert_eq!((view.rows(), view.columns()), (2, 3));
+        assert_eq!(view.cells().len(), 4);
+        let wide = view.cell_at(0, 1).unwrap();
+        assert_eq!((wide.row, wide.col, wide.col_span), (0, 0, 2));
+        let tall = view.cell_at(1, 2).unwrap();
+        assert_eq!((tall.row, tall.row_span, tall.cell_index), (0, 2, 1));
+        assert!(tall.is_merged() && tall.covers(1, 2));
+        assert!(!view.cell_at(1, 0).unwrap().is_merged());
+    }
+
+    #[test]
+    fn merge_and_unmerge_cells() {
+        let mut table = table(&format!("<w:tbl>{}{}</w:tbl>", row(&["a", "b"]), row(&["c", "d"])));
+
+        table.merge_cells(0..2, 0..1).unwrap();
+        let merged = xml(&table);
+        assert!(merged.contains("<w:vMerge w:val=\"restart\"/>"), "{merged}");
+        assert!(merged.contains("<w:vMerge/>"), "{merged}");
+        assert_eq!(table.to_string_grid(), [["a\nc", "b"], ["", "d"]]);
+
+        assert!(table.merge_cells(1..2, 0..2).is_err());
+
+        table.unmerge_cell(1, 0).unwrap();
+        assert!(!xml(&table).contains("w:vMerge"));
+        assert_eq!(table.to_string_grid(), [["a\nc", "b"], ["", "d"]]);
+        assert!(!table.grid_view().cell_at(0, 0).unwrap().is_merged());
+    }
+
+    #[test]
+    fn column_edits_adjust_spans_and_grid() {
+        let mut table = table(&format!(
+            "<w:tbl><w:tblGrid><w:gridCol w:w=\"1000\"/><w:gridCol w:w=\"2000\"/></w:tblGrid>\
+             <w:tr><w:tc><w:tcPr><w:gridSpan w:val=\"2\"/></w:tcPr>{}</w:tc></w:tr>{}</w:tbl>",
+            p("top"),
+            row(&["a", "b"])
+        ));
+
+        table.insert_column(1).unwrap();
+        assert_eq!(table.grid_view().cell_at(0, 0).unwrap().col_span, 3);
+        assert_eq!(table.to_string_grid()[1], ["a", "", "b"]);
+        let grid = table.grid.as_ref().unwrap();
+        let widths: Vec<i32> = grid.columns.iter().map(|c| c.width).collect();
+        assert_eq!(widths, [1000, 1000, 2000]);
+
+        table.delete_column(0).unwrap();
+        table.delete_column(0).unwrap();
+        assert_eq!(table.to_string_grid(), [["top"], ["b"]]);
+        assert!(!xml(&table).contains("w:gridSpan"));
+    }
+}
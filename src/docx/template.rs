@@ -0,0 +1,694 @@
+//! 模板填充：用数据模型替换文档中的 `{{占位符}}`
+//!
+//! Word 经常把一个占位符拆到多个格式不同的文本运行中，因此这里先拼接段落中全部文本运行的
+//! 文本再查找标签，替换时把结果写入标签起始处所在的文本运行，沿用该运行的格式，并清除标签
+//! 在后续运行中的部分。
+//!
+//! 支持的标签：
+//! * `{{name}}`、`{{customer.address.city}}` - 插入值，路径先在当前循环项中查找，再逐层向外
+//! * `{{this}}`、`{{@index}}`、`{{@number}}` - 当前循环项、从 0 和从 1 开始的序号
+//! * `{{#if path}}…{{/if}}`、`{{#unless path}}…{{/unless}}` - 条件块
+//! * `{{#each path}}…{{/each}}` - 对数组中的每一项重复
+//!
+//! 块标签的作用范围由其位置决定：开始和结束标签在同一段落中时作用于段落内的文本；各自独占
+//! 一个段落时作用于两者之间的段落和表格；位于不同单元格或不同表格行时作用于其间的表格行。
+
+use std::ops::Range;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::{OfficeError, Result};
+
+use super::elements::{BodyContent, Paragraph, TableContent, TableItem, TableRow};
+use super::search::replace_ranges;
+use super::Docx;
+
+/// 块标签的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    Each,
+    If,
+    Unless,
+}
+
+impl BlockKind {
+    fn name(self) -> &'static str {
+        match self {
+            BlockKind::Each => "each",
+            BlockKind::If => "if",
+            BlockKind::Unless => "unless",
+        }
+    }
+}
+
+/// 模板标签
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Tag {
+    /// 插入值
+    Value(String),
+    /// 块开始
+    Open(BlockKind, String),
+    /// 块结束
+    Close(BlockKind),
+}
+
+/// 文本中的一个标签及其字节范围
+#[derive(Debug, Clone)]
+struct TagMatch {
+    range: Range<usize>,
+    tag: Tag,
+}
+
+/// 段落内的模板节点，标签之间的文本保持原样
+enum Node {
+    Value(Range<usize>, String),
+    Block {
+        kind: BlockKind,
+        path: String,
+        open: Range<usize>,
+        close: Range<usize>,
+        children: Vec<Node>,
+    },
+}
+
+impl Node {
+    fn range(&self) -> Range<usize> {
+        match self {
+            Node::Value(range, _) => range.clone(),
+            Node::Block { open, close, .. } => open.start..close.end,
+        }
+    }
+}
+
+/// 查找作用域，内层为循环项
+#[derive(Clone)]
+struct Scope<'a> {
+    frames: Vec<(&'a Value, Option<usize>)>,
+}
+
+impl<'a> Scope<'a> {
+    fn new(root: &'a Value) -> Self {
+        Scope {
+            frames: vec![(root, None)],
+        }
+    }
+
+    /// 进入循环项
+    fn with_item(&self, item: &'a Value, index: usize) -> Self {
+        let mut scope = self.clone();
+        scope.frames.push((item, Some(index)));
+        scope
+    }
+
+    /// 按路径查找值，路径的第一段在哪一层找到就在哪一层继续
+    fn lookup(&self, path: &str) -> Option<Value> {
+        let index = || self.frames.iter().rev().find_map(|(_, index)| *index);
+        match path {
+            "this" | "." => return self.frames.last().map(|(value, _)| (*value).clone()),
+            "@index" => return index().map(Value::from),
+            "@number" => return index().map(|i| Value::from(i + 1)),
+            _ => {}
+        }
+        let (frames, path) = match path.strip_prefix("this.") {
+            Some(rest) => (&self.frames[self.frames.len() - 1..], rest),
+            None => (&self.frames[..], path),
+        };
+        let mut keys = path.split('.');
+        let first = keys.next()?;
+        let value = frames
+            .iter()
+            .rev()
+            .find_map(|(value, _)| child(value, first))?;
+        keys.try_fold(value, child).cloned()
+    }
+
+    /// 条件是否成立，不存在、`null`、`false`、`0`、空字符串和空数组为假
+    fn truthy(&self, path: &str) -> bool {
+        match self.lookup(path) {
+            None | Some(Value::Null) | Some(Value::Bool(false)) => false,
+            Some(Value::Number(n)) => n.as_f64() != Some(0.0),
+            Some(Value::String(s)) => !s.is_empty(),
+            Some(Value::Array(a)) => !a.is_empty(),
+            Some(Value::Object(_)) | Some(Value::Bool(true)) => true,
+        }
+    }
+
+    /// 循环的各项：数组的元素；非数组的真值视为单个项
+    fn items(&self, path: &str) -> Vec<Value> {
+        match self.lookup(path) {
+            Some(Value::Array(items)) => items,
+            Some(value) if self.truthy(path) => vec![value],
+            _ => Vec::new(),
+        }
+    }
+
+    /// 块的条件是否成立
+    fn condition(&self, kind: BlockKind, path: &str) -> bool {
+        self.truthy(path) == (kind == BlockKind::If)
+    }
+
+    /// 插入的文本，不存在的值为空字符串
+    fn display(&self, path: &str) -> String {
+        match self.lookup(path) {
+            None | Some(Value::Null) | Some(Value::Object(_)) => String::new(),
+            Some(Value::String(s)) => s,
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|item| match item {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            Some(other) => other.to_string(),
+        }
+    }
+}
+
+/// 按键或数组下标获取子值
+fn child<'v>(value: &'v Value, key: &str) -> Option<&'v Value> {
+    match value {
+        Value::Object(map) => map.get(key),
+        Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => None,
+    }
+}
+
+impl Docx {
+    /// 用数据模型填充模板，数据可以是任意可序列化的类型
+    ///
+    /// 填充失败时文档保持不变。
+    ///
+    /// # 参数
+    /// * `data` - 数据模型，序列化后按字段名查找
+    pub fn render_template<T: Serialize + ?Sized>(&mut self, data: &T) -> Result<()> {
+        let data = serde_json::to_value(data)
+            .map_err(|e| OfficeError::InvalidFormat(format!("invalid template data: {e}")))?;
+        let content = render_body(self.document.body.content.clone(), &Scope::new(&data))?;
+        self.document.body.content = content;
+        Ok(())
+    }
+
+    /// 按文档顺序列出模板中引用的数据路径，不重复，不含 `this` 和序号等特殊值
+    pub fn template_placeholders(&self) -> Vec<String> {
+        let mut paths: Vec<String> = Vec::new();
+        for paragraph in self.paragraphs() {
+            for found in scan_tags(&paragraph.text()) {
+                let path = match found.tag {
+                    Tag::Value(path) | Tag::Open(_, path) => path,
+                    Tag::Close(_) => continue,
+                };
+                let special = path == "this" || path == "." || path.starts_with('@');
+                if !special && !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+}
+
+/// 解析标签内的文本
+fn parse_tag(inner: &str) -> Result<Tag> {
+    let inner = inner.trim();
+    let block = |name: &str| match name {
+        "each" => Ok(BlockKind::Each),
+        "if" => Ok(BlockKind::If),
+        "unless" => Ok(BlockKind::Unless),
+        _ => Err(OfficeError::InvalidFormat(format!(
+            "unknown template block `{name}`"
+        ))),
+    };
+    if let Some(rest) = inner.strip_prefix('#') {
+        let (name, path) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let path = path.trim();
+        if path.is_empty() {
+            return Err(OfficeError::InvalidFormat(format!(
+                "template block `{{{{{inner}}}}}` has no path"
+            )));
+        }
+        Ok(Tag::Open(block(name)?, path.to_string()))
+    } else if let Some(name) = inner.strip_prefix('/') {
+        Ok(Tag::Close(block(name.trim())?))
+    } else {
+        Ok(Tag::Value(inner.to_string()))
+    }
+}
+
+/// 查找文本中的全部标签，无法解析的标签被忽略
+fn scan_tags(text: &str) -> Vec<TagMatch> {
+    try_scan_tags(text)
+        .into_iter()
+        .filter_map(|(range, tag)| {
+            Some(TagMatch {
+                range,
+                tag: tag.ok()?,
+            })
+        })
+        .collect()
+}
+
+/// 查找文本中的全部标签
+fn try_scan_tags(text: &str) -> Vec<(Range<usize>, Result<Tag>)> {
+    let mut tags = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = text[pos..].find("{{").map(|i| pos + i) {
+        let Some(end) = text[start + 2..].find("}}").map(|i| start + 2 + i + 2) else {
+            break;
+        };
+        tags.push((start..end, parse_tag(&text[start + 2..end - 2])));
+        pos = end;
+    }
+    tags
+}
+
+/// 查找文本中的全部标签，遇到无法解析的标签时报错
+fn tags_in(text: &str) -> Result<Vec<TagMatch>> {
+    try_scan_tags(text)
+        .into_iter()
+        .map(|(range, tag)| Ok(TagMatch { range, tag: tag? }))
+        .collect()
+}
+
+/// 将标签序列组织为节点树
+fn build_nodes(tags: Vec<TagMatch>) -> Result<Vec<Node>> {
+    // 栈中保存未闭合的块及其已收集的子节点
+    let mut stack: Vec<(BlockKind, String, Range<usize>, Vec<Node>)> = Vec::new();
+    let mut nodes = Vec::new();
+    for TagMatch { range, tag } in tags {
+        match tag {
+            Tag::Value(path) => {
+                let node = Node::Value(range, path);
+                match stack.last_mut() {
+                    Some((.., children)) => children.push(node),
+                    None => nodes.push(node),
+                }
+            }
+            Tag::Open(kind, path) => stack.push((kind, path, range, Vec::new())),
+            Tag::Close(kind) => {
+                let Some((open_kind, path, open, children)) = stack.pop() else {
+                    return Err(unexpected_close(kind));
+                };
+                if open_kind != kind {
+                    return Err(unexpected_close(kind));
+                }
+                let node = Node::Block {
+                    kind,
+                    path,
+                    open,
+                    close: range,
+                    children,
+                };
+                match stack.last_mut() {
+                    Some((.., children)) => children.push(node),
+                    None => nodes.push(node),
+                }
+            }
+        }
+    }
+    match stack.pop() {
+        Some((kind, path, ..)) => Err(unclosed(kind, &path)),
+        None => Ok(nodes),
+    }
+}
+
+fn unexpected_close(kind: BlockKind) -> OfficeError {
+    OfficeError::InvalidFormat(format!(
+        "unexpected template tag `{{{{/{}}}}}`",
+        kind.name()
+    ))
+}
+
+fn unclosed(kind: BlockKind, path: &str) -> OfficeError {
+    OfficeError::InvalidFormat(format!(
+        "template block `{{{{#{} {path}}}}}` is not closed",
+        kind.name()
+    ))
+}
+
+/// 生成段落内节点的替换操作
+fn collect_edits(
+    nodes: &[Node],
+    text: &str,
+    scope: &Scope,
+    edits: &mut Vec<(Range<usize>, String)>,
+) {
+    for node in nodes {
+        match node {
+            Node::Value(range, path) => edits.push((range.clone(), scope.display(path))),
+            Node::Block {
+                kind: BlockKind::Each,
+                ..
+            } => edits.push((node.range(), render_text(node, text, scope))),
+            Node::Block {
+                kind,
+                path,
+                open,
+                close,
+                children,
+            } => {
+                if scope.condition(*kind, path) {
+                    edits.push((open.clone(), String::new()));
+                    collect_edits(children, text, scope, edits);
+                    edits.push((close.clone(), String::new()));
+                } else {
+                    edits.push((node.range(), String::new()));
+                }
+            }
+        }
+    }
+}
+
+/// 将节点渲染为纯文本，用于段落内的循环
+fn render_text(node: &Node, text: &str, scope: &Scope) -> String {
+    let Node::Block {
+        kind,
+        path,
+        open,
+        close,
+        children,
+    } = node
+    else {
+        return String::new();
+    };
+    let render = |scope: &Scope| {
+        let mut output = String::new();
+        let mut pos = open.end;
+        for child in children {
+            output.push_str(&text[pos..child.range().start]);
+            match child {
+                Node::Value(_, path) => output.push_str(&scope.display(path)),
+                block => output.push_str(&render_text(block, text, scope)),
+            }
+            pos = child.range().end;
+        }
+        output.push_str(&text[pos..close.start]);
+        output
+    };
+    match kind {
+        BlockKind::Each => scope
+            .items(path)
+            .iter()
+            .enumerate()
+            .map(|(index, item)| render(&scope.with_item(item, index)))
+            .collect(),
+        _ if scope.condition(*kind, path) => render(scope),
+        _ => String::new(),
+    }
+}
+
+/// 填充段落内的标签
+fn render_paragraph(paragraph: &mut Paragraph, scope: &Scope) -> Result<()> {
+    let text = paragraph.text();
+    let tags = tags_in(&text)?;
+    if tags.is_empty() {
+        return Ok(());
+    }
+    let nodes = build_nodes(tags)?;
+    let mut edits = Vec::new();
+    collect_edits(&nodes, &text, scope, &mut edits);
+    replace_ranges(paragraph, edits);
+    Ok(())
+}
+
+/// 独占一个段落的块标签
+fn block_tag(content: &BodyContent) -> Option<Tag> {
+    let BodyContent::Paragraph(paragraph) = content else {
+        return None;
+    };
+    let text = paragraph.text();
+    match scan_tags(&text).as_slice() {
+        [found] if text.trim() == &text[found.range.clone()] => match &found.tag {
+            Tag::Value(_) => None,
+            tag => Some(tag.clone()),
+        },
+        _ => None,
+    }
+}
+
+/// 填充正文或单元格中的内容
+fn render_body(content: Vec<BodyContent>, scope: &Scope) -> Result<Vec<BodyContent>> {
+    let mut output = Vec::with_capacity(content.len());
+    let mut i = 0;
+    while i < content.len() {
+        match block_tag(&content[i]) {
+            Some(Tag::Open(kind, path)) => {
+                // 查找同一层级的结束标签
+                let mut depth = 0;
+                let end = (i + 1..content.len())
+                    .find(|&j| match block_tag(&content[j]) {
+                        Some(Tag::Open(..)) => {
+                            depth += 1;
+                            false
+                        }
+                        Some(Tag::Close(_)) if depth > 0 => {
+                            depth -= 1;
+                            false
+                        }
+                        Some(Tag::Close(_)) => true,
+                        _ => false,
+                    })
+                    .ok_or_else(|| unclosed(kind, &path))?;
+                if block_tag(&content[end]) != Some(Tag::Close(kind)) {
+                    return Err(unclosed(kind, &path));
+                }
+                let inner = &content[i + 1..end];
+                if kind == BlockKind::Each {
+                    for (index, item) in scope.items(&path).iter().enumerate() {
+                        output.extend(render_body(inner.to_vec(), &scope.with_item(item, index))?);
+                    }
+                } else if scope.condition(kind, &path) {
+                    output.extend(render_body(inner.to_vec(), scope)?);
+                }
+                i = end + 1;
+            }
+            Some(Tag::Close(kind)) => return Err(unexpected_close(kind)),
+            _ => {
+                let mut item = content[i].clone();
+                match &mut item {
+                    BodyContent::Paragraph(paragraph) => render_paragraph(paragraph, scope)?,
+                    BodyContent::Table(table) => {
+                        table.content = render_table(std::mem::take(&mut table.content), scope)?;
+                    }
+                    BodyContent::Sdt(sdt) => {
+                        sdt.content = render_body(std::mem::take(&mut sdt.content), scope)?;
+                    }
+                }
+                output.push(item);
+                i += 1;
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// 行中跨段落未配对的块标签，及其所在的单元格、段落序号
+fn row_block_tags(row: &TableRow) -> Result<Vec<(usize, usize, TagMatch)>> {
+    let mut tags = Vec::new();
+    for (c, cell) in row.cells().enumerate() {
+        // 单元格内配对的块标签由单元格内容自行处理
+        let mut pending: Vec<(usize, usize, TagMatch)> = Vec::new();
+        for (p, content) in cell.content.iter().enumerate() {
+            let BodyContent::Paragraph(paragraph) = content else {
+                continue;
+            };
+            let mut local: Vec<TagMatch> = Vec::new();
+            for found in tags_in(&paragraph.text())? {
+                match &found.tag {
+                    Tag::Value(_) => {}
+                    Tag::Open(..) => local.push(found),
+                    Tag::Close(_) => {
+                        if local.pop().is_none() {
+                            match pending.last() {
+                                Some((
+                                    ..,
+                                    TagMatch {
+                                        tag: Tag::Open(..), ..
+                                    },
+                                )) => {
+                                    pending.pop();
+                                }
+                                _ => pending.push((c, p, found)),
+                            }
+                        }
+                    }
+                }
+            }
+            pending.extend(local.into_iter().map(|found| (c, p, found)));
+        }
+        tags.extend(pending);
+    }
+    Ok(tags)
+}
+
+/// 删除行中指定段落里的标签文本
+fn remove_row_tag(row: &mut TableRow, (cell, paragraph, found): &(usize, usize, TagMatch)) {
+    let cell = row.cells_mut().nth(*cell);
+    if let Some(BodyContent::Paragraph(paragraph)) = cell.and_then(|c| c.content.get_mut(*paragraph)) {
+        replace_ranges(paragraph, vec![(found.range.clone(), String::new())]);
+    }
+}
+
+/// 填充表格内容：连续的表格行按 [`render_rows`] 处理，行级内容控件中的行在内容控件内处理
+fn render_table(content: Vec<TableContent>, scope: &Scope) -> Result<Vec<TableContent>> {
+    let mut output = Vec::with_capacity(content.len());
+    let mut rows = Vec::new();
+    for item in content {
+        match item {
+            TableItem::Item(row) => rows.push(row),
+            TableItem::Sdt(mut sdt) => {
+                let rendered = render_rows(std::mem::take(&mut rows), scope)?;
+                output.extend(rendered.into_iter().map(TableItem::Item));
+                sdt.content = render_table(sdt.content, scope)?;
+                output.push(TableItem::Sdt(sdt));
+            }
+        }
+    }
+    output.extend(render_rows(rows, scope)?.into_iter().map(TableItem::Item));
+    Ok(output)
+}
+
+/// 填充表格行，处理跨单元格或跨行的块标签
+fn render_rows(rows: Vec<TableRow>, scope: &Scope) -> Result<Vec<TableRow>> {
+    let mut output = Vec::with_capacity(rows.len());
+    let mut i = 0;
+    while i < rows.len() {
+        let tags = row_block_tags(&rows[i])?;
+        let Some(open) = tags.first().cloned() else {
+            let mut row = rows[i].clone();
+            for cell in row.cells_mut() {
+                cell.content = render_body(std::mem::take(&mut cell.content), scope)?;
+                if !cell
+                    .content
+                    .iter()
+                    .any(|c| matches!(c, BodyContent::Paragraph(_)))
+                {
+                    cell.content
+                        .push(BodyContent::Paragraph(Paragraph::default()));
+                }
+            }
+            output.push(row);
+            i += 1;
+            continue;
+        };
+        let (kind, path) = match &open.2.tag {
+            Tag::Open(kind, path) => (*kind, path.clone()),
+            Tag::Close(kind) => return Err(unexpected_close(*kind)),
+            Tag::Value(_) => unreachable!("only block tags are collected"),
+        };
+
+        // 从开始标签之后查找配对的结束标签
+        let mut depth = 0;
+        let mut close = None;
+        'rows: for (j, row) in rows.iter().enumerate().skip(i) {
+            let row_tags = if j == i {
+                tags[1..].to_vec()
+            } else {
+                row_block_tags(row)?
+            };
+            for found in row_tags {
+                match found.2.tag {
+                    Tag::Open(..) => depth += 1,
+                    Tag::Close(_) if depth > 0 => depth -= 1,
+                    Tag::Close(close_kind) if close_kind == kind => {
+                        close = Some((j, found));
+                        break 'rows;
+                    }
+                    Tag::Close(close_kind) => return Err(unexpected_close(close_kind)),
+                    Tag::Value(_) => {}
+                }
+            }
+        }
+        let (end, close) = close.ok_or_else(|| unclosed(kind, &path))?;
+
+        let mut block = rows[i..=end].to_vec();
+        let last = block.len() - 1;
+        remove_row_tag(&mut block[last], &close);
+        remove_row_tag(&mut block[0], &open);
+        if kind == BlockKind::Each {
+            for (index, item) in scope.items(&path).iter().enumerate() {
+                output.extend(render_rows(block.clone(), &scope.with_item(item, index))?);
+            }
+        } else if scope.condition(kind, &path) {
+            output.extend(render_rows(block, scope)?);
+        }
+        i = end + 1;
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::docx::elements::BodyContent;
+    use crate::docx::test_utils::{body_xml, docx, p};
+    use crate::docx::Docx;
+
+    fn texts(docx: &Docx) -> Vec<String> {
+        docx.paragraphs().iter().map(|p| p.text()).collect()
+    }
+
+    #[test]
+    fn placeholders_split_across_runs_keep_first_run_format() {
+        let mut docx = docx(
+            "<w:p><w:r><w:t xml:space=\"preserve\">Hello </w:t></w:r>\
+             <w:r><w:rPr><w:b/></w:rPr><w:t>{{cust</w:t></w:r>\
+             <w:r><w:rPr><w:i/></w:rPr><w:t>omer.name}}</w:t></w:r>\
+             <w:r><w:t>!</w:t></w:r></w:p>",
+        );
+        assert_eq!(docx.template_placeholders(), ["customer.name"]);
+
+        docx.render_template(&json!({ "customer": { "name": "Ann" } })).unwrap();
+        assert_eq!(texts(&docx), ["Hello Ann!"]);
+        let xml = body_xml(&docx);
+        let bold = "<w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">Ann</w:t>";
+        assert!(xml.contains(bold), "{xml}");
+        assert!(!xml.contains("<w:i/>"), "{xml}");
+    }
+
+    #[test]
+    fn blocks_repeat_paragraphs_and_inline_text() {
+        let mut docx = docx(&format!(
+            "{}{}{}{}",
+            p("{{#each items}}"),
+            p("{{@number}}. {{name}}{{#if sale}} (sale){{/if}}"),
+            p("{{/each}}"),
+            p("{{#unless items}}none{{/unless}}end")
+        ));
+        let data = json!({ "items": [{ "name": "a", "sale": true }, { "name": "b" }] });
+        docx.render_template(&data).unwrap();
+        assert_eq!(texts(&docx), ["1. a (sale)", "2. b", "end"]);
+    }
+
+    #[test]
+    fn blocks_across_cells_repeat_table_rows() {
+        let cell = |text: &str| format!("<w:tc>{}</w:tc>", p(text));
+        let mut docx = docx(&format!(
+            "<w:tbl><w:tr>{}{}</w:tr><w:tr>{}{}</w:tr></w:tbl>",
+            cell("Item"),
+            cell("Price"),
+            cell("{{#each rows}}{{item}}"),
+            cell("{{price}}{{/each}}")
+        ));
+        let data = json!({
+            "rows": [{ "item": "pen", "price": 2 }, { "item": "ink", "price": 5 }]
+        });
+        docx.render_template(&data).unwrap();
+
+        let BodyContent::Table(table) = &docx.document.body.content[0] else {
+            panic!("table expected");
+        };
+        let grid = table.to_string_grid();
+        assert_eq!(grid, [["Item", "Price"], ["pen", "2"], ["ink", "5"]]);
+    }
+
+    #[test]
+    fn unbalanced_blocks_leave_document_unchanged() {
+        let mut docx = docx(&format!("{}{}", p("{{#each items}}"), p("{{name}}")));
+        let before = body_xml(&docx);
+        assert!(docx.render_template(&json!({ "items": [] })).is_err());
+        assert_eq!(body_xml(&docx), before);
+    }
+}
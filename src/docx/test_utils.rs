@@ -0,0 +1,50 @@
+//! 单元测试使用的辅助函数
+
+use quick_xml::se::to_string;
+
+use super::document::Document;
+use super::Docx;
+
+/// 创建正文为指定 XML 的文档
+///
+/// # 参数
+/// * `body` - `w:body` 中的 XML
+pub(crate) fn docx(body: &str) -> Docx {
+    Docx {
+        document: document(body),
+        ..Default::default()
+    }
+}
+
+/// 解析正文为指定 XML 的文档部件
+///
+/// # 参数
+/// * `body` - `w:body` 中的 XML
+pub(crate) fn document(body: &str) -> Document {
+    let xml = format!(
+        "<w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\" \
+         xmlns:w14=\"http://schemas.microsoft.com/office/word/2010/wordml\" \
+         xmlns:w15=\"http://schemas.microsoft.com/office/word/2012/wordml\">\
+         <w:body>{body}</w:body></w:document>"
+    );
+    Document::from_xml(&xml, None).expect("valid document xml")
+}
+
+/// 序列化后的 `w:body` 内容
+///
+/// # 参数
+/// * `docx` - 文档
+pub(crate) fn body_xml(docx: &Docx) -> String {
+    let xml = to_string(&docx.document).expect("serializable document");
+    let start = xml.find("<w:body>").map_or(0, |i| i + "<w:body>".len());
+    let end = xml.rfind("</w:body>").unwrap_or(xml.len());
+    xml[start..end].to_string()
+}
+
+/// 包含一个文本运行的段落 XML
+///
+/// # 参数
+/// * `text` - 文本
+pub(crate) fn p(text: &str) -> String {
+    format!("<w:p><w:r><w:t xml:space=\"preserve\">{text}</w:t></w:r></w:p>")
+}
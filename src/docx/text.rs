@@ -0,0 +1,409 @@
+//! 纯文本提取：按文档顺序输出可见文本，供全文检索等场景使用
+//!
+//! 每个段落占一行，表格的每一行占一行，单元格之间以分隔符连接。制表符和换行符原样输出，
+//! 复杂域只输出域结果而不输出域代码。列表编号、页眉页脚、脚注尾注、隐藏文字、被删除的
+//! 修订文字和链接地址可以通过选项控制是否输出。
+
+use std::borrow::Cow;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::common::relations::Relationships;
+use crate::error::Result;
+
+use super::document::Body;
+use super::elements::{BodyContent, HyperlinkTarget, Paragraph, ParagraphContent, Run, RunContent};
+use super::list_numbering::ListNumberer;
+use super::numbering::LevelSuffix;
+use super::style_resolver::{CellContext, StyleResolver};
+use super::{rels_path, Docx};
+
+/// 纯文本提取选项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextOptions {
+    /// 在列表段落前输出编号文本，编号之后按级别的设置使用制表符或空格分隔
+    pub list_labels: bool,
+    /// 同一行单元格之间的分隔符；单元格中的多个段落以空格连接
+    pub cell_separator: String,
+    /// 在正文之前输出页眉，在正文之后输出页脚
+    pub headers_footers: bool,
+    /// 在正文之后输出脚注和尾注，每条以 `[编号]` 开头
+    pub footnotes: bool,
+    /// 输出隐藏文字（包括样式中设置的隐藏）
+    pub hidden_text: bool,
+    /// 输出修订中被删除的文字
+    pub deleted_text: bool,
+    /// 在外部链接的文本之后以 `<地址>` 的形式输出链接地址
+    pub hyperlink_urls: bool,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        TextOptions {
+            list_labels: true,
+            cell_separator: "\t".to_string(),
+            headers_footers: false,
+            footnotes: false,
+            hidden_text: false,
+            deleted_text: false,
+            hyperlink_urls: false,
+        }
+    }
+}
+
+impl Docx {
+    /// 按默认选项提取纯文本：包含列表编号，单元格以制表符分隔，不包含页眉页脚和脚注
+    pub fn to_text(&self) -> String {
+        self.export_text(&TextOptions::default())
+    }
+
+    /// 按选项提取纯文本，各行以换行符分隔；无法解析的页眉、页脚和脚注部件被忽略
+    ///
+    /// # 参数
+    /// * `options` - 提取选项
+    pub fn export_text(&self, options: &TextOptions) -> String {
+        let mut lines = Vec::new();
+        if options.headers_footers {
+            for (content, rels) in self.header_footer_parts("header") {
+                let mut writer = TextWriter::new(self, options, rels.as_ref(), false);
+                writer.blocks(&content, None, &mut lines);
+            }
+        }
+
+        let mut writer = TextWriter::new(self, options, self.relationships.as_ref(), true);
+        writer.blocks(&self.document.body.content, None, &mut lines);
+
+        if options.headers_footers {
+            for (content, rels) in self.header_footer_parts("footer") {
+                let mut writer = TextWriter::new(self, options, rels.as_ref(), false);
+                writer.blocks(&content, None, &mut lines);
+            }
+        }
+        if options.footnotes {
+            for path in ["word/footnotes.xml", "word/endnotes.xml"] {
+                let Some((xml, rels)) = self.part_xml(path) else {
+                    continue;
+                };
+                let Ok(notes) = parse_notes(&xml, rels.as_ref()) else {
+                    continue;
+                };
+                let mut writer = TextWriter::new(self, options, rels.as_ref(), false);
+                for (id, content) in notes {
+                    let mut note = Vec::new();
+                    writer.blocks(&content, None, &mut note);
+                    lines.push(format!("[{id}] {}", note.join(" ")));
+                }
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// 按编号顺序解析页眉或页脚部件，返回内容和部件的关系
+    ///
+    /// # 参数
+    /// * `kind` - `header` 或 `footer`
+    fn header_footer_parts(&self, kind: &str) -> Vec<(Vec<BodyContent>, Option<Relationships>)> {
+        let mut parts: Vec<(u32, &str)> = self
+            .parts
+            .keys()
+            .filter_map(|path| {
+                let number = path.strip_prefix("word/")?.strip_prefix(kind)?;
+                let number = number.strip_suffix(".xml")?;
+                Some((number.parse().unwrap_or(0), path.as_str()))
+            })
+            .collect();
+        parts.sort();
+        parts
+            .into_iter()
+            .filter_map(|(_, path)| {
+                let (xml, rels) = self.part_xml(path)?;
+                let content = parse_part(&xml, rels.as_ref()).ok()?;
+                Some((content, rels))
+            })
+            .collect()
+    }
+
+    /// 读取保留部件的 XML 及其关系
+    fn part_xml(&self, path: &str) -> Option<(Cow<'_, str>, Option<Relationships>)> {
+        let xml = String::from_utf8_lossy(self.parts.get(path)?);
+        let rels = self
+            .parts
+            .get(&rels_path(path))
+            .and_then(|data| Relationships::from_xml(&String::from_utf8_lossy(data)).ok());
+        Some((xml, rels))
+    }
+}
+
+/// 按顺序输出段落和表格的文本
+struct TextWriter<'a> {
+    options: &'a TextOptions,
+    resolver: StyleResolver<'a>,
+    /// 只为正文计算列表编号
+    numberer: Option<ListNumberer<'a>>,
+    rels: Option<&'a Relationships>,
+}
+
+impl<'a> TextWriter<'a> {
+    fn new(
+        docx: &'a Docx,
+        options: &'a TextOptions,
+        rels: Option<&'a Relationships>,
+        body: bool,
+    ) -> Self {
+        TextWriter {
+            options,
+            resolver: docx.style_resolver(),
+            numberer: (body && options.list_labels).then(|| docx.list_numberer()),
+            rels,
+        }
+    }
+
+    fn blocks(
+        &mut self,
+        content: &[BodyContent],
+        cell: Option<&CellContext>,
+        lines: &mut Vec<String>,
+    ) {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(p) => lines.push(self.paragraph(p, cell)),
+                BodyContent::Table(table) => {
+                    for (row_index, row) in table.rows().enumerate() {
+                        let mut col = row
+                            .properties
+                            .as_ref()
+                            .and_then(|p| p.grid_before.as_ref())
+                            .map_or(0, |g| g.val as usize);
+                        let mut cells = Vec::new();
+                        for table_cell in row.cells() {
+                            let context = CellContext::new(table, row_index, col);
+                            let mut cell_lines = Vec::new();
+                            self.blocks(&table_cell.content, Some(&context), &mut cell_lines);
+                            cell_lines.retain(|line| !line.is_empty());
+                            cells.push(cell_lines.join(" "));
+                            col += table_cell.properties.as_ref().map_or(1, |p| p.span());
+                        }
+                        lines.push(cells.join(&self.options.cell_separator));
+                    }
+                }
+                BodyContent::Sdt(sdt) => self.blocks(&sdt.content, cell, lines),
+            }
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: &Paragraph, cell: Option<&CellContext>) -> String {
+        let mut text = String::new();
+        if let Some(numberer) = &mut self.numberer {
+            if let Some(label) = numberer.next_label(paragraph, cell) {
+                text.push_str(&label.text);
+                match label.suffix {
+                    LevelSuffix::Tab => text.push('\t'),
+                    LevelSuffix::Space => text.push(' '),
+                    LevelSuffix::Nothing => {}
+                }
+            }
+        }
+        self.inline(paragraph, &paragraph.content, cell, &mut text);
+        text
+    }
+
+    fn inline(
+        &mut self,
+        paragraph: &Paragraph,
+        content: &[ParagraphContent],
+        cell: Option<&CellContext>,
+        text: &mut String,
+    ) {
+        for item in content {
+            match item {
+                ParagraphContent::Run(run) => self.run(paragraph, run, cell, text),
+                ParagraphContent::Hyperlink(link) => {
+                    let start = text.len();
+                    for run in &link.runs {
+                        self.run(paragraph, run, cell, text);
+                    }
+                    if !self.options.hyperlink_urls {
+                        continue;
+                    }
+                    if let Some(HyperlinkTarget::Url(url)) = link.target(self.rels) {
+                        if text[start..] != url {
+                            text.push_str(&format!(" <{url}>"));
+                        }
+                    }
+                }
+                ParagraphContent::SimpleField(field) => {
+                    for run in &field.runs {
+                        self.run(paragraph, run, cell, text);
+                    }
+                }
+                ParagraphContent::Insertion(revision) | ParagraphContent::MoveTo(revision) => {
+                    for run in &revision.runs {
+                        self.run(paragraph, run, cell, text);
+                    }
+                }
+                ParagraphContent::Deletion(revision) | ParagraphContent::MoveFrom(revision) => {
+                    if self.options.deleted_text {
+                        for run in &revision.runs {
+                            self.run(paragraph, run, cell, text);
+                        }
+                    }
+                }
+                ParagraphContent::Sdt(sdt) => self.inline(paragraph, &sdt.content, cell, text),
+                ParagraphContent::BookmarkStart(_) | ParagraphContent::BookmarkEnd(_) => {}
+            }
+        }
+    }
+
+    fn run(&self, paragraph: &Paragraph, run: &Run, cell: Option<&CellContext>, text: &mut String) {
+        if !self.options.hidden_text
+            && self
+                .resolver
+                .run_properties(paragraph, run, cell)
+                .hidden
+                .is_some_and(|h| h.is_on())
+        {
+            return;
+        }
+        for content in &run.content {
+            match content {
+                RunContent::Text(t) => text.push_str(t),
+                RunContent::DeletedText(t) if self.options.deleted_text => text.push_str(t),
+                RunContent::Break | RunContent::PageBreak => text.push('\n'),
+                RunContent::Tab => text.push('\t'),
+                RunContent::DeletedText(_)
+                | RunContent::Drawing(_)
+                | RunContent::RawDrawing(_)
+                | RunContent::FieldChar(_)
+                | RunContent::InstrText(_)
+                | RunContent::DeletedInstrText(_)
+                | RunContent::FootnoteReference(_)
+                | RunContent::EndnoteReference(_) => {}
+            }
+        }
+    }
+}
+
+/// 解析页眉、页脚部件根元素中的段落和表格
+fn parse_part(xml: &str, rels: Option<&Relationships>) -> Result<Vec<BodyContent>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(false);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => {
+                return Ok(Body::from_body_reader(&mut reader, e.name(), rels)?.content);
+            }
+            Event::Eof => return Ok(Vec::new()),
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// 解析脚注或尾注部件，返回各条注释的编号和内容；跳过分隔线等特殊注释
+fn parse_notes(xml: &str, rels: Option<&Relationships>) -> Result<Vec<(String, Vec<BodyContent>)>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(false);
+    let decoder = reader.decoder();
+    let mut buf = Vec::new();
+    let mut notes = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) if matches!(e.name().as_ref(), b"w:footnote" | b"w:endnote") => {
+                let mut id = String::new();
+                let mut special = false;
+                for attr in e.attributes() {
+                    let attr = attr?;
+                    let value = attr.decode_and_unescape_value(decoder)?;
+                    match attr.key.as_ref() {
+                        b"w:id" => id = value.into_owned(),
+                        b"w:type" => special = value != "normal",
+                        _ => {}
+                    }
+                }
+                if special {
+                    reader.read_to_end_into(e.name(), &mut Vec::new())?;
+                } else {
+                    let body = Body::from_body_reader(&mut reader, e.name(), rels)?;
+                    notes.push((id, body.content));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::builder::ParagraphBuilder;
+    use crate::docx::test_utils::{document, p};
+
+    const NS: &str = "xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\"";
+
+    /// 包含列表、链接、域、表格、修订和隐藏文字，以及页眉、页脚和脚注部件的文档
+    fn sample() -> Docx {
+        let mut docx = Docx::builder()
+            .numbered_list(["one"])
+            .paragraph(
+                ParagraphBuilder::new()
+                    .text("see ")
+                    .hyperlink("site", "https://example.com")
+                    .text(" page ")
+                    .field("PAGE", "3"),
+            )
+            .table_with_header([["a", "b"]])
+            .build();
+        let edited = document(
+            "<w:p><w:r><w:t xml:space=\"preserve\">kept</w:t></w:r>\
+             <w:del w:id=\"1\" w:author=\"a\"><w:r><w:delText>gone</w:delText></w:r></w:del>\
+             <w:r><w:rPr><w:vanish/></w:rPr><w:t>hidden</w:t></w:r></w:p>",
+        );
+        docx.document.body.content.extend(edited.body.content);
+
+        let parts = [
+            ("word/header1.xml", format!("<w:hdr {NS}>{}</w:hdr>", p("head"))),
+            ("word/footer1.xml", format!("<w:ftr {NS}>{}</w:ftr>", p("foot"))),
+            (
+                "word/footnotes.xml",
+                format!(
+                    "<w:footnotes {NS}><w:footnote w:type=\"separator\" w:id=\"-1\">{}\
+                     </w:footnote><w:footnote w:id=\"1\">{}</w:footnote></w:footnotes>",
+                    p("-"),
+                    p("note")
+                ),
+            ),
+        ];
+        for (path, xml) in parts {
+            docx.parts.insert(path.to_string(), xml.into_bytes());
+        }
+        docx
+    }
+
+    #[test]
+    fn default_text_is_visible_body_only() {
+        assert_eq!(sample().to_text(), "1.\tone\nsee site page 3\na\tb\nkept");
+    }
+
+    #[test]
+    fn options_add_hidden_parts_and_urls() {
+        let options = TextOptions {
+            list_labels: false,
+            cell_separator: " | ".to_string(),
+            headers_footers: true,
+            footnotes: true,
+            hidden_text: true,
+            deleted_text: true,
+            hyperlink_urls: true,
+        };
+        assert_eq!(
+            sample().export_text(&options),
+            "head\none\nsee site <https://example.com> page 3\na | b\n\
+             keptgonehidden\nfoot\n[1] note"
+        );
+    }
+}
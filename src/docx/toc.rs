@@ -0,0 +1,357 @@
+//! 目录：根据标题段落生成目录域
+//!
+//! 目录是一个 `TOC` 复杂域，域开始字符位于第一个目录项段落，域结束字符位于最后一个
+//! 目录项段落。每个目录项链接到标题上的 `_Toc` 书签，页码为 `PAGEREF` 域。页码依赖
+//! 排版，无法在此计算，因此生成目录时会设置 `w:updateFields`，由 Word 在打开文档时更新。
+
+use crate::error::{OfficeError, Result};
+
+use super::elements::{
+    BodyContent, FieldChar, FieldCharType, Hyperlink, Paragraph, ParagraphContent, Run, RunContent,
+};
+use super::fields::field_runs;
+use super::properties::{
+    ParagraphProperties, ParagraphStyle, TabLeader, TabStop, TabStopType, Tabs,
+};
+use super::styles::BuiltinStyle;
+use super::Docx;
+
+/// 目录页码制表位的默认位置（缇），与构建器中表格的默认宽度一致
+const DEFAULT_TAB_POSITION: i32 = 9026;
+
+/// 没有目录项时 Word 显示的文本
+const NO_ENTRIES_TEXT: &str = "No table of contents entries found.";
+
+/// 目录生成选项
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocOptions {
+    /// 目录标题，使用“目录标题”样式；为 `None` 时不添加标题
+    pub title: Option<String>,
+    /// 收录的最高标题级别（1–9）
+    pub min_level: u8,
+    /// 收录的最低标题级别（1–9）
+    pub max_level: u8,
+    /// 目录项是否链接到标题（`\h`）
+    pub hyperlinks: bool,
+    /// 是否显示页码，不显示时使用 `\n` 开关
+    pub page_numbers: bool,
+    /// 页码右对齐制表位的位置（缇）
+    pub tab_position: i32,
+    /// 页码制表位的前导符
+    pub tab_leader: TabLeader,
+}
+
+impl Default for TocOptions {
+    fn default() -> Self {
+        TocOptions {
+            title: None,
+            min_level: 1,
+            max_level: 3,
+            hyperlinks: true,
+            page_numbers: true,
+            tab_position: DEFAULT_TAB_POSITION,
+            tab_leader: TabLeader::Dot,
+        }
+    }
+}
+
+impl TocOptions {
+    /// 生成目录域代码，如 `TOC \o "1-3" \h \z \u`
+    pub fn instruction(&self) -> String {
+        let (min, max) = self.levels();
+        let mut instruction = format!("TOC \\o \"{min}-{max}\"");
+        if self.hyperlinks {
+            instruction.push_str(" \\h");
+        }
+        if !self.page_numbers {
+            instruction.push_str(" \\n");
+        }
+        instruction.push_str(" \\z \\u");
+        instruction
+    }
+
+    /// 规范化后的级别范围
+    fn levels(&self) -> (u8, u8) {
+        let min = self.min_level.clamp(1, 9);
+        (min, self.max_level.clamp(min, 9))
+    }
+}
+
+/// 目录项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    /// 标题级别（1–9）
+    pub level: u8,
+    /// 标题文本
+    pub text: String,
+    /// 标题段落序号，与 [`Docx::paragraphs`] 的顺序一致
+    pub paragraph: usize,
+    /// 标题上的 `_Toc` 书签名称，尚未添加书签时为 `None`
+    pub bookmark: Option<String>,
+}
+
+impl Docx {
+    /// 按文档顺序收集可进入目录的标题，标题级别由段落的大纲级别决定
+    ///
+    /// # 参数
+    /// * `options` - 目录选项，只收集其级别范围内的非空标题
+    pub fn toc_entries(&self, options: &TocOptions) -> Vec<TocEntry> {
+        let (min, max) = options.levels();
+        let resolver = self.style_resolver();
+        self.paragraphs()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, paragraph)| {
+                let level = resolver
+                    .paragraph_properties(paragraph, None)
+                    .outline_level?
+                    .val
+                    .saturating_add(1);
+                let text = paragraph.text().trim().to_string();
+                if !(min..=max).contains(&level) || text.is_empty() {
+                    return None;
+                }
+                Some(TocEntry {
+                    level,
+                    text,
+                    paragraph: index,
+                    bookmark: paragraph
+                        .bookmarks()
+                        .find(|b| b.name.starts_with("_Toc"))
+                        .map(|b| b.name.clone()),
+                })
+            })
+            .collect()
+    }
+
+    /// 在文档正文的指定位置插入目录，返回目录项数
+    ///
+    /// 标题上会添加 `_Toc` 书签，并登记目录样式，设置打开文档时更新域。
+    ///
+    /// # 参数
+    /// * `index` - 正文内容的插入位置
+    /// * `options` - 目录选项
+    pub fn insert_table_of_contents(
+        &mut self,
+        index: usize,
+        options: &TocOptions,
+    ) -> Result<usize> {
+        if index > self.document.body.content.len() {
+            return Err(OfficeError::InvalidFormat(format!(
+                "body index {index} out of range"
+            )));
+        }
+        let (content, count) = self.create_table_of_contents(options);
+        self.document.body.content.splice(
+            index..index,
+            content.into_iter().map(BodyContent::Paragraph),
+        );
+        Ok(count)
+    }
+
+    /// 在文档末尾添加目录，返回目录项数
+    ///
+    /// # 参数
+    /// * `options` - 目录选项
+    pub fn add_table_of_contents(&mut self, options: &TocOptions) -> usize {
+        let (content, count) = self.create_table_of_contents(options);
+        self.document
+            .body
+            .content
+            .extend(content.into_iter().map(BodyContent::Paragraph));
+        count
+    }
+
+    /// 为标题添加书签并生成目录段落，但不插入文档；返回段落和目录项数
+    ///
+    /// # 参数
+    /// * `options` - 目录选项
+    pub fn create_table_of_contents(&mut self, options: &TocOptions) -> (Vec<Paragraph>, usize) {
+        let mut entries = self.toc_entries(options);
+        for entry in &mut entries {
+            if entry.bookmark.is_none() {
+                entry.bookmark = Some(self.add_toc_bookmark(entry.paragraph));
+            }
+        }
+        self.settings.set_update_fields(true);
+
+        let mut paragraphs = Vec::new();
+        if let Some(title) = &options.title {
+            let style_id = self
+                .styles
+                .ensure_builtin(BuiltinStyle::TocHeading)
+                .style_id
+                .clone();
+            paragraphs.push(Paragraph {
+                properties: Some(paragraph_style(style_id)),
+                content: vec![ParagraphContent::Run(Run {
+                    properties: None,
+                    content: vec![RunContent::Text(title.clone())],
+                })],
+            });
+        }
+
+        let field_char = |char_type| Run {
+            properties: None,
+            content: vec![RunContent::FieldChar(FieldChar::new(char_type))],
+        };
+        let field_start = vec![
+            field_char(FieldCharType::Begin),
+            Run {
+                properties: None,
+                content: vec![RunContent::InstrText(format!(
+                    " {} ",
+                    options.instruction()
+                ))],
+            },
+            field_char(FieldCharType::Separate),
+        ];
+
+        let first = paragraphs.len();
+        for entry in &entries {
+            let style_id = self
+                .styles
+                .ensure_builtin(BuiltinStyle::Toc(entry.level))
+                .style_id
+                .clone();
+            let bookmark = entry.bookmark.clone().unwrap_or_default();
+            let mut runs = vec![Run {
+                properties: None,
+                content: vec![RunContent::Text(entry.text.clone())],
+            }];
+            let mut properties = paragraph_style(style_id);
+            if options.page_numbers {
+                runs.push(Run {
+                    properties: None,
+                    content: vec![RunContent::Tab],
+                });
+                runs.extend(field_runs(&format!("PAGEREF {bookmark} \\h"), ""));
+                properties.tabs = Some(Tabs {
+                    tabs: vec![TabStop {
+                        val: TabStopType::Right,
+                        leader: Some(options.tab_leader),
+                        pos: options.tab_position,
+                    }],
+                });
+            }
+            let content = if options.hyperlinks {
+                vec![ParagraphContent::Hyperlink(Hyperlink::internal(
+                    bookmark, runs,
+                ))]
+            } else {
+                runs.into_iter().map(ParagraphContent::Run).collect()
+            };
+            paragraphs.push(Paragraph {
+                properties: Some(properties),
+                content,
+            });
+        }
+        if entries.is_empty() {
+            paragraphs.push(Paragraph {
+                properties: None,
+                content: vec![ParagraphContent::Run(Run {
+                    properties: None,
+                    content: vec![RunContent::Text(NO_ENTRIES_TEXT.to_string())],
+                })],
+            });
+        }
+
+        // 域开始放在第一个目录项之前，域结束放在最后一个目录项之后
+        let items = field_start.into_iter().map(ParagraphContent::Run);
+        paragraphs[first].content.splice(0..0, items);
+        if let Some(last) = paragraphs.last_mut() {
+            last.content
+                .push(ParagraphContent::Run(field_char(FieldCharType::End)));
+        }
+        (paragraphs, entries.len())
+    }
+
+    /// 为标题段落添加未使用的 `_Toc` 书签，返回书签名称
+    fn add_toc_bookmark(&mut self, paragraph_index: usize) -> String {
+        let id = self.next_bookmark_id();
+        let name = (id..)
+            .map(|n| format!("_Toc{n:09}"))
+            .find(|name| self.find_bookmark(name).is_none())
+            .unwrap_or_default();
+        if let Some(paragraph) = self.paragraphs_mut().into_iter().nth(paragraph_index) {
+            paragraph.wrap_bookmark(id, name.clone());
+        }
+        name
+    }
+}
+
+/// 只设置段落样式的段落属性
+fn paragraph_style(style_id: String) -> ParagraphProperties {
+    ParagraphProperties {
+        style: Some(ParagraphStyle { val: style_id }),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::builder::ParagraphBuilder;
+    use crate::docx::test_utils::body_xml;
+
+    fn document() -> Docx {
+        let mut docx = Docx::builder().build();
+        docx.add_heading("Intro", 1);
+        docx.add_paragraph("body");
+        docx.add_heading("Detail", 2);
+        docx.add_heading("Deep", 4);
+        docx.add_paragraph(ParagraphBuilder::new().style("Heading2"));
+        docx
+    }
+
+    #[test]
+    fn entries_follow_outline_levels() {
+        let docx = document();
+        let entries: Vec<(u8, String, usize)> = docx
+            .toc_entries(&TocOptions::default())
+            .into_iter()
+            .map(|e| (e.level, e.text, e.paragraph))
+            .collect();
+        assert_eq!(entries, [(1, "Intro".to_string(), 0), (2, "Detail".to_string(), 2)]);
+
+        let options = TocOptions {
+            min_level: 2,
+            max_level: 4,
+            ..Default::default()
+        };
+        assert_eq!(options.instruction(), "TOC \\o \"2-4\" \\h \\z \\u");
+        assert_eq!(docx.toc_entries(&options).len(), 2);
+    }
+
+    #[test]
+    fn table_of_contents_links_to_heading_bookmarks() {
+        let mut docx = document();
+        let options = TocOptions {
+            title: Some("Contents".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(docx.insert_table_of_contents(0, &options).unwrap(), 2);
+        assert!(docx.insert_table_of_contents(99, &options).is_err());
+
+        let entries = docx.toc_entries(&TocOptions::default());
+        let bookmarks: Vec<_> = entries.iter().filter_map(|e| e.bookmark.clone()).collect();
+        assert_eq!(bookmarks.len(), 2);
+        assert!(bookmarks.iter().all(|b| b.starts_with("_Toc")));
+
+        let xml = body_xml(&docx);
+        assert!(xml.starts_with("<w:p><w:pPr><w:pStyle w:val=\"TOCHeading\"/>"), "{xml}");
+        assert!(xml.contains(&format!("<w:hyperlink w:anchor=\"{}\"", bookmarks[0])), "{xml}");
+        assert!(xml.contains(&format!("PAGEREF {} \\h", bookmarks[1])), "{xml}");
+        assert!(xml.contains("<w:pStyle w:val=\"TOC2\"/>"), "{xml}");
+        assert!(docx.styles.find_style("TOC1").is_some());
+        assert!(docx.settings.update_fields());
+        assert_eq!(docx.fields()[0].field_type(), "TOC");
+    }
+
+    #[test]
+    fn empty_table_of_contents_shows_placeholder() {
+        let mut docx = Docx::builder().paragraph("text").build();
+        assert_eq!(docx.add_table_of_contents(&TocOptions::default()), 0);
+        assert_eq!(docx.paragraphs().last().unwrap().text(), NO_ENTRIES_TEXT);
+    }
+}